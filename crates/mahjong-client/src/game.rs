@@ -33,8 +33,8 @@ pub struct WinResult {
     pub yaku: Vec<(String, u32)>,
     /// 翻数
     pub han: u32,
-    /// 符
-    pub fu: u32,
+    /// 符（役満は `None`）
+    pub fu: Option<u32>,
     /// 和了点
     pub score_points: i32,
     /// 点数等級名（満貫・跳満など。通常は空）
@@ -101,10 +101,13 @@ impl Translator {
     }
 
     /// 翻符のまとめ表記（例: 日「{han}飜 {fu}符」/ 英「{han} han {fu} fu」）。
-    pub fn han_fu(&self, han: u32, fu: u32) -> String {
-        match self.lang {
-            Lang::Ja => format!("{han}飜 {fu}符"),
-            Lang::En => format!("{han} han {fu} fu"),
+    /// 符がない場合（役満）は翻数のみ。
+    pub fn han_fu(&self, han: u32, fu: Option<u32>) -> String {
+        match (self.lang, fu) {
+            (Lang::Ja, Some(fu)) => format!("{han}飜 {fu}符"),
+            (Lang::Ja, None) => format!("{han}飜"),
+            (Lang::En, Some(fu)) => format!("{han} han {fu} fu"),
+            (Lang::En, None) => format!("{han} han"),
         }
     }
 
@@ -183,6 +186,7 @@ impl Translator {
                 DrawReason::NineTerminals => "九種九牌",
                 DrawReason::FourKans => "四槓散了",
                 DrawReason::TripleRon => "三家和",
+                DrawReason::DoubleRon => "ダブロン流局",
             },
             Lang::En => match reason {
                 DrawReason::Exhaustive => "Exhaustive draw",
@@ -191,6 +195,7 @@ impl Translator {
                 DrawReason::NineTerminals => "Nine terminals",
                 DrawReason::FourKans => "Four quads",
                 DrawReason::TripleRon => "Triple ron",
+                DrawReason::DoubleRon => "Double ron",
             },
         }
     }
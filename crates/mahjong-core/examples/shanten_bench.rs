@@ -0,0 +1,62 @@
+//! 向聴数計算の速度を比較する
+//!
+//! 使い方:
+//! ```sh
+//! cargo run -p mahjong-core --release --example shanten_bench -- [hands]
+//! ```
+//!
+//! デフォルトは 100000 手。無作為な手牌と、1色に偏った手牌（再帰探索が遅くなりやすい）の
+//! それぞれについて、分解を行う `calc_shanten_number` と分解表を用いる
+//! `shanten_fast::shanten` の所要時間を表示する。
+
+use std::time::{Duration, Instant};
+
+use mahjong_core::hand::Hand;
+use mahjong_core::hand_info::hand_analyzer::calc_shanten_number;
+use mahjong_core::hand_info::shanten_fast;
+use mahjong_core::tile::Tile;
+use rand::seq::SliceRandom;
+use rand::{SeedableRng, rngs::SmallRng};
+
+/// 牌山から無作為に14枚を取った手牌を作る
+fn random_hands(rng: &mut SmallRng, tiles: &[Tile], count: usize) -> Vec<Hand> {
+    let mut wall = tiles.to_vec();
+    (0..count)
+        .map(|_| {
+            wall.shuffle(rng);
+            Hand::new(wall[..13].to_vec(), Some(wall[13]))
+        })
+        .collect()
+}
+
+fn measure(hands: &[Hand], f: impl Fn(&Hand) -> i32) -> (Duration, i64) {
+    let start = Instant::now();
+    let checksum = hands.iter().map(|hand| f(hand) as i64).sum();
+    (start.elapsed(), checksum)
+}
+
+fn main() {
+    let count = std::env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(100_000);
+    let mut rng = SmallRng::seed_from_u64(42);
+
+    let all: Vec<Tile> = (0..Tile::LEN as u32)
+        .flat_map(|t| std::iter::repeat_n(Tile::new(t), 4))
+        .collect();
+    let one_suit: Vec<Tile> = (Tile::M1..=Tile::M9)
+        .flat_map(|t| std::iter::repeat_n(Tile::new(t), 4))
+        .collect();
+
+    for (label, tiles) in [("random", &all), ("one suit", &one_suit)] {
+        let hands = random_hands(&mut rng, tiles, count);
+        let (recursive, a) = measure(&hands, |hand| calc_shanten_number(hand).as_i32());
+        let (table, b) = measure(&hands, |hand| shanten_fast::shanten(hand).as_i32());
+        assert_eq!(a, b, "results differ");
+        println!(
+            "{label:>8}: recursive {recursive:>10.2?}, table {table:>10.2?} (x{:.1})",
+            recursive.as_secs_f64() / table.as_secs_f64()
+        );
+    }
+}
@@ -1 +1,85 @@
+//! 卓の座席と局の進行
+//!
+//! 局のインデックス（0 = 東1局、4 = 南1局、…）と起家の座席から、
+//! 親・場風・各座席の自風を求める純粋な関数群。
+//! ゲームエンジンと、牌譜から局の状況を復元する処理の双方で使う。
+//!
+//! 牌山・手牌・河・手番・鳴きの優先順位（ロン > ポン・カン > チー）・局の進行を扱う
+//! 状態機械はこのクレートには持たず、`mahjong-server` の `round::Round`（1局）と
+//! `table::Table`（半荘）が担う。進行はいずれも `ServerEvent` として通知される。
+//! ある座席がいま取れる行動（打牌・リーチ・カン・鳴きなど）の列挙も `Round::legal_actions` が行う。
 
+use crate::tile::Wind;
+
+/// 手番で取れる行動の可否判定
+pub mod actions;
+
+/// 途中流局の判定
+pub mod abortive_draw;
+
+/// 荒牌流局の判定と精算
+pub mod draw_outcome;
+
+/// 局の座席構成
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeatAssignment {
+    /// 親の座席（0-3）
+    pub dealer: usize,
+    /// 場風
+    pub round_wind: Wind,
+    /// 各座席の自風
+    pub seat_winds: [Wind; 4],
+}
+
+/// 局のインデックスと起家の座席から親の座席を返す
+pub fn dealer_seat(kyoku: usize, first_dealer: usize) -> usize {
+    (first_dealer + kyoku) % 4
+}
+
+/// 局のインデックスから場風を返す（4局ごとに東→南→西→北）
+pub fn round_wind(kyoku: usize) -> Wind {
+    Wind::from_index(kyoku / 4)
+}
+
+/// 局のインデックスから局数（1-4）を返す（例: 南3局なら3）
+pub fn round_in_wind(kyoku: usize) -> usize {
+    kyoku % 4 + 1
+}
+
+/// 親の座席から各座席の自風を返す
+pub fn seat_winds(dealer: usize) -> [Wind; 4] {
+    std::array::from_fn(|seat| Wind::from_seat(dealer, seat))
+}
+
+/// 局のインデックスと起家の座席から座席構成を返す
+pub fn seat_assignment(kyoku: usize, first_dealer: usize) -> SeatAssignment {
+    let dealer = dealer_seat(kyoku, first_dealer);
+    SeatAssignment {
+        dealer,
+        round_wind: round_wind(kyoku),
+        seat_winds: seat_winds(dealer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seat_assignment_test() {
+        let east1 = seat_assignment(0, 0);
+        assert_eq!(east1.dealer, 0);
+        assert_eq!(east1.round_wind, Wind::East);
+        assert_eq!(east1.seat_winds, Wind::ALL);
+
+        // 起家が座席2の南2局: 親は座席3
+        let south2 = seat_assignment(5, 2);
+        assert_eq!(south2.dealer, 3);
+        assert_eq!(south2.round_wind, Wind::South);
+        assert_eq!(
+            south2.seat_winds,
+            [Wind::South, Wind::West, Wind::North, Wind::East]
+        );
+        assert_eq!(round_in_wind(5), 2);
+    }
+}
@@ -0,0 +1,134 @@
+//! 途中流局（九種九牌・四風連打・四槓散了・四家立直・三家和）の判定
+//!
+//! いずれも対応する `Settings` のフラグが無効なら成立しない。
+
+use std::collections::HashSet;
+
+use crate::hand::Hand;
+use crate::settings::{MultipleRon, Settings};
+use crate::tile::Tile;
+
+/// 手牌（ツモ牌を含む）に含まれる么九牌の種類数を返す
+pub fn count_terminal_and_honour_kinds(hand: &Hand) -> usize {
+    hand.tiles()
+        .iter()
+        .chain(hand.drawn().iter())
+        .filter(|t| t.is_1_9_honour())
+        .map(|t| t.get())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// 九種九牌を宣言できるかを返す
+///
+/// - `hand`: ツモ直後の手牌（14枚）
+/// - `is_first_turn`: 自分の第一ツモで、それまでに鳴きが入っていないか
+pub fn can_declare_nine_terminals(hand: &Hand, is_first_turn: bool, settings: &Settings) -> bool {
+    settings.nine_terminals_draw && is_first_turn && count_terminal_and_honour_kinds(hand) >= 9
+}
+
+/// 四風連打が成立するかを返す
+///
+/// - `discards`: 各座席の河（捨てた順）
+/// - `any_call`: 局の開始から鳴きが入ったか
+///
+/// 全員がちょうど1枚ずつ捨てており、それが全て同じ風牌の場合に成立する。
+pub fn is_four_winds_draw(discards: [&[Tile]; 4], any_call: bool, settings: &Settings) -> bool {
+    if !settings.four_winds_draw || any_call || discards.iter().any(|d| d.len() != 1) {
+        return false;
+    }
+    let first = discards[0][0];
+    first.is_wind() && discards.iter().all(|d| d[0].get() == first.get())
+}
+
+/// 四槓散了が成立するかを返す
+///
+/// - `kan_counts`: 各座席のカンの回数
+///
+/// 場全体で4回カンが成立し、かつ2人以上がカンしている場合に成立する
+/// （1人が4回カンした場合は四槓子の可能性があるため続行する）。
+pub fn is_four_kans_draw(kan_counts: [usize; 4], settings: &Settings) -> bool {
+    settings.four_kans_draw
+        && kan_counts.iter().sum::<usize>() >= 4
+        && kan_counts.iter().filter(|&&n| n > 0).count() >= 2
+}
+
+/// 四家立直が成立するかを返す
+///
+/// - `riichi`: 各座席が立直を宣言済みか
+pub fn is_four_riichi_draw(riichi: [bool; 4], settings: &Settings) -> bool {
+    settings.four_riichi_draw && riichi.iter().all(|&r| r)
+}
+
+/// 三家和が成立するかを返す
+///
+/// - `ron_count`: 同じ打牌に対してロンを宣言した人数
+pub fn is_triple_ron_draw(ron_count: usize, settings: &Settings) -> bool {
+    settings.triple_ron_draw && ron_count >= 3
+}
+
+/// 複数同時ロンによる流局が成立するかを返す
+///
+/// 三家和（`triple_ron_draw`）に加え、`multiple_ron` が流局の場合は2人以上のロンで流局とする。
+pub fn is_multiple_ron_draw(ron_count: usize, settings: &Settings) -> bool {
+    is_triple_ron_draw(ron_count, settings)
+        || (settings.multiple_ron == MultipleRon::AbortiveDraw && ron_count >= 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nine_terminals() {
+        let settings = Settings::new();
+        let hand = Hand::from("1289m1469p1s1234z 5z");
+        assert_eq!(count_terminal_and_honour_kinds(&hand), 10);
+        assert!(can_declare_nine_terminals(&hand, true, &settings));
+        assert!(!can_declare_nine_terminals(&hand, false, &settings));
+
+        let hand = Hand::from("12259m1469p1s123z 2m");
+        assert_eq!(count_terminal_and_honour_kinds(&hand), 8);
+        assert!(!can_declare_nine_terminals(&hand, true, &settings));
+    }
+
+    #[test]
+    fn test_four_winds_draw() {
+        let mut settings = Settings::new();
+        let east = [Tile::new(Tile::Z1)];
+        let south = [Tile::new(Tile::Z2)];
+        assert!(is_four_winds_draw([&east; 4], false, &settings));
+        assert!(!is_four_winds_draw([&east; 4], true, &settings));
+        assert!(!is_four_winds_draw(
+            [&east, &east, &east, &south],
+            false,
+            &settings
+        ));
+        let white = [Tile::new(Tile::Z5)];
+        assert!(!is_four_winds_draw([&white; 4], false, &settings));
+
+        settings.four_winds_draw = false;
+        assert!(!is_four_winds_draw([&east; 4], false, &settings));
+    }
+
+    #[test]
+    fn test_four_kans_four_riichi_triple_ron() {
+        let mut settings = Settings::new();
+        assert!(is_four_kans_draw([2, 1, 1, 0], &settings));
+        assert!(!is_four_kans_draw([4, 0, 0, 0], &settings));
+        assert!(!is_four_kans_draw([1, 1, 1, 0], &settings));
+
+        settings.four_riichi_draw = true;
+        assert!(is_four_riichi_draw([true; 4], &settings));
+        assert!(!is_four_riichi_draw([true, true, true, false], &settings));
+
+        assert!(!is_triple_ron_draw(3, &settings));
+        settings.triple_ron_draw = true;
+        assert!(is_triple_ron_draw(3, &settings));
+        assert!(!is_triple_ron_draw(2, &settings));
+        assert!(!is_multiple_ron_draw(2, &settings));
+        settings.multiple_ron = MultipleRon::AbortiveDraw;
+        assert!(is_multiple_ron_draw(2, &settings));
+        assert!(!is_multiple_ron_draw(1, &settings));
+    }
+}
@@ -0,0 +1,58 @@
+//! 手番で取れる行動の可否判定
+
+use crate::hand::Hand;
+use crate::tile::TileType;
+
+/// リーチ後に暗カンできるかを返す
+///
+/// - `hand`: ツモ直後の手牌（14枚）
+/// - `waits`: リーチ時点（カン前）の和了牌の牌種
+/// - `tile`: カンする牌種
+///
+/// 以下を全て満たす場合に限り暗カンできる。
+/// - ツモ牌がカンする牌種である（手牌の3枚とツモ牌で揃える。送りカンは不可）
+/// - 暗カンの前後で和了牌が変わらない
+///
+/// 例: 3334m で 3m をツモった場合、カン前は 2m・4m・5m 待ちだがカン後は 4m 単騎になるため不可。
+pub fn can_ankan_after_riichi(hand: &Hand, waits: &[TileType], tile: TileType) -> bool {
+    let Some(drawn) = hand.drawn() else {
+        return false;
+    };
+    if drawn.get() != tile || waits.is_empty() {
+        return false;
+    }
+
+    let mut after = hand.clone();
+    if after.apply_ankan(tile).is_err() {
+        return false;
+    }
+    after.winning_tile_types() == waits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Tile;
+
+    #[rstest::rstest]
+    // 刻子をそのままカンしても待ちが変わらない
+    #[case("111m456p789s2233z 1m", Tile::M1, true)]
+    #[case("111234m456p789s5z 1m", Tile::M1, true)]
+    // 待ちが変わる（2-4-5m待ちが4m単騎に）
+    #[case("3334m456p789s111z 3m", Tile::M3, false)]
+    // 九蓮宝燈の形を崩す
+    #[case("1112345678999m 1m", Tile::M1, false)]
+    // 送りカン（ツモ牌以外の4枚でのカン）
+    #[case("1111m456p789s223z 3z", Tile::M1, false)]
+    // ツモ牌が別の牌種
+    #[case("111m456p789s2233z 9s", Tile::M1, false)]
+    fn can_ankan_after_riichi_test(
+        #[case] hand: &str,
+        #[case] tile: TileType,
+        #[case] expected: bool,
+    ) {
+        let hand = Hand::from(hand);
+        let waits = hand.winning_tile_types();
+        assert_eq!(can_ankan_after_riichi(&hand, &waits, tile), expected);
+    }
+}
@@ -0,0 +1,241 @@
+//! 荒牌流局（牌山が尽きたときの流局）の判定と精算
+//!
+//! 聴牌の判定・ノーテン罰符・流し満貫の成立判定・親の連荘をまとめて扱う。
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::calc_shanten_number;
+use crate::scoring::payment::tenpai_payment_deltas;
+use crate::settings::{Renchan, Settings};
+use crate::tile::Tile;
+use crate::winning_hand::checker::YakuResult;
+use crate::winning_hand::name::Kind;
+
+/// 流局時の座席の状態
+#[derive(Debug, Clone, Copy)]
+pub struct DrawSeat<'a> {
+    /// 手牌（13枚）
+    pub hand: &'a Hand,
+    /// 河（鳴かれた牌も含む、捨てた順）
+    pub discards: &'a [Tile],
+    /// 捨て牌を他家に鳴かれたか
+    pub discard_called: bool,
+}
+
+/// 荒牌流局の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DrawOutcome {
+    /// 聴牌している座席（昇順）
+    pub tenpai: Vec<usize>,
+    /// 流し満貫が成立した座席（昇順）
+    pub nagashi: Vec<usize>,
+    /// 各座席の点数変動（正=増加、負=減少）。合計は必ず0。
+    pub deltas: [i32; 4],
+    /// 親が連荘するか
+    pub dealer_keeps_deal: bool,
+}
+
+/// 13枚の手牌が聴牌しているかを返す
+pub fn is_tenpai(hand: &Hand) -> bool {
+    calc_shanten_number(hand).is_ready()
+}
+
+/// 河が流し満貫の条件を満たしているかを返す
+///
+/// 捨て牌がすべて么九牌で、かつ1枚も鳴かれていない場合に成立する。
+pub fn qualifies_for_nagashi(discards: &[Tile], discard_called: bool) -> bool {
+    !discard_called && !discards.is_empty() && discards.iter().all(|t| t.is_1_9_honour())
+}
+
+/// 流し満貫の役としての判定結果を返す（表示用）
+///
+/// 流し満貫は和了形を必要とせず、荒牌流局時に河の条件だけで成立する。
+/// 役の一覧に表示するため、満貫相当の5翻で成立した結果を返す。
+pub fn nagashi_mangan_yaku(settings: &Settings) -> YakuResult {
+    YakuResult::new(Kind::NagashiMangan, false, settings.display_lang).established(5)
+}
+
+/// 流し満貫の点数移動を求める
+///
+/// 達成者それぞれが満貫のツモ和了と同じ額（親は4000点オール、子は親4000点・子2000点）を受け取る。
+/// `settings.nagashi_mangan_honba` が有効なら各支払いに本場（1本場につき100点）を加算し、
+/// `settings.nagashi_mangan_tenpai_payments` が有効ならノーテン罰符も合わせて精算する。
+pub fn nagashi_mangan_deltas(
+    nagashi: &[usize],
+    dealer: usize,
+    honba: u32,
+    tenpai: &[usize],
+    settings: &Settings,
+) -> [i32; 4] {
+    let mut deltas = [0i32; 4];
+    let honba_bonus = if settings.nagashi_mangan_honba {
+        honba as i32 * 100
+    } else {
+        0
+    };
+
+    for &winner in nagashi {
+        for payer in (0..4).filter(|&i| i != winner) {
+            let base = if winner == dealer || payer == dealer {
+                4000
+            } else {
+                2000
+            };
+            let pay = base + honba_bonus;
+            deltas[payer] -= pay;
+            deltas[winner] += pay;
+        }
+    }
+
+    if settings.nagashi_mangan_tenpai_payments {
+        for (delta, payment) in deltas.iter_mut().zip(tenpai_payment_deltas(tenpai)) {
+            *delta += payment;
+        }
+    }
+    deltas
+}
+
+/// 荒牌流局の結果を求める
+///
+/// - `seats`: 各座席の状態
+/// - `dealer`: 親の座席 (0-3)
+/// - `honba`: 本場数（流し満貫の支払いにのみ使う）
+/// - `settings`: ルール設定（連荘の条件・流し満貫の精算方法）
+///
+/// 流し満貫が成立した場合は流し満貫の精算を、そうでなければノーテン罰符の精算を行う。
+/// 親の連荘はいずれの場合も親の聴牌で判定する。
+pub fn draw_outcome(
+    seats: &[DrawSeat; 4],
+    dealer: usize,
+    honba: u32,
+    settings: &Settings,
+) -> DrawOutcome {
+    let tenpai: Vec<usize> = (0..4).filter(|&i| is_tenpai(seats[i].hand)).collect();
+    let nagashi: Vec<usize> = (0..4)
+        .filter(|&i| qualifies_for_nagashi(seats[i].discards, seats[i].discard_called))
+        .collect();
+
+    let deltas = if nagashi.is_empty() {
+        tenpai_payment_deltas(&tenpai)
+    } else {
+        nagashi_mangan_deltas(&nagashi, dealer, honba, &tenpai, settings)
+    };
+    let dealer_keeps_deal = tenpai.contains(&dealer) && settings.renchan == Renchan::WinOrTenpai;
+
+    DrawOutcome {
+        tenpai,
+        nagashi,
+        deltas,
+        dealer_keeps_deal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        Hand::from(s).tiles().to_vec()
+    }
+
+    #[test]
+    fn test_qualifies_for_nagashi() {
+        assert!(qualifies_for_nagashi(&tiles("19m19p19s1234567z"), false));
+        assert!(!qualifies_for_nagashi(&tiles("19m19p19s1234567z"), true));
+        assert!(!qualifies_for_nagashi(&tiles("19m28p19s"), false));
+        assert!(!qualifies_for_nagashi(&[], false));
+    }
+
+    #[test]
+    fn test_nagashi_mangan_yaku() {
+        let yaku = nagashi_mangan_yaku(&Settings::new());
+        assert_eq!(yaku.kind, Kind::NagashiMangan);
+        assert!(yaku.is_established);
+        assert_eq!(yaku.han, 5);
+        assert!(!yaku.is_yakuman);
+    }
+
+    #[test]
+    fn test_nagashi_mangan_deltas() {
+        let settings = Settings::new();
+        // 子の流し満貫（1本場）: 親4100・子2100ずつ
+        let deltas = nagashi_mangan_deltas(&[2], 0, 1, &[1], &settings);
+        assert_eq!(deltas, [-4100, -2100, 8300, -2100]);
+
+        // 親の流し満貫: 4000点オール
+        let deltas = nagashi_mangan_deltas(&[0], 0, 0, &[], &settings);
+        assert_eq!(deltas, [12000, -4000, -4000, -4000]);
+    }
+
+    #[test]
+    fn test_nagashi_mangan_deltas_with_settings() {
+        let mut settings = Settings::new();
+        settings.nagashi_mangan_honba = false;
+        settings.nagashi_mangan_tenpai_payments = true;
+        // 子の流し満貫（本場なし）+ 座席1のみ聴牌のノーテン罰符
+        let deltas = nagashi_mangan_deltas(&[2], 0, 3, &[1], &settings);
+        assert_eq!(deltas, [-5000, 1000, 7000, -3000]);
+        assert_eq!(deltas.iter().sum::<i32>(), 0);
+    }
+
+    #[test]
+    fn test_draw_outcome_tenpai_payments() {
+        let tenpai = Hand::from("123m456p789s1122z");
+        let noten = Hand::from("147m258p369s1234z");
+        let discards = tiles("5m5p");
+        let seat = |hand| DrawSeat {
+            hand,
+            discards: &discards,
+            discard_called: false,
+        };
+        let seats = [seat(&tenpai), seat(&noten), seat(&noten), seat(&noten)];
+
+        let outcome = draw_outcome(&seats, 0, 1, &Settings::new());
+        assert_eq!(outcome.tenpai, vec![0]);
+        assert!(outcome.nagashi.is_empty());
+        assert_eq!(outcome.deltas, [3000, -1000, -1000, -1000]);
+        assert!(outcome.dealer_keeps_deal);
+
+        let outcome = draw_outcome(&seats, 1, 1, &Settings::new());
+        assert!(!outcome.dealer_keeps_deal);
+    }
+
+    #[test]
+    fn test_draw_outcome_nagashi_mangan() {
+        let tenpai = Hand::from("123m456p789s1122z");
+        let noten = Hand::from("147m258p369s1234z");
+        let terminals = tiles("19m19p1z");
+        let discards = tiles("5m5p");
+        let seats = [
+            DrawSeat {
+                hand: &noten,
+                discards: &discards,
+                discard_called: false,
+            },
+            DrawSeat {
+                hand: &tenpai,
+                discards: &discards,
+                discard_called: false,
+            },
+            DrawSeat {
+                hand: &noten,
+                discards: &terminals,
+                discard_called: false,
+            },
+            DrawSeat {
+                hand: &noten,
+                discards: &terminals,
+                discard_called: true,
+            },
+        ];
+
+        let outcome = draw_outcome(&seats, 0, 1, &Settings::new());
+        assert_eq!(outcome.tenpai, vec![1]);
+        assert_eq!(outcome.nagashi, vec![2]);
+        assert_eq!(outcome.deltas, [-4100, -2100, 8300, -2100]);
+        assert!(!outcome.dealer_keeps_deal);
+    }
+}
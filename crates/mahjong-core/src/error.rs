@@ -0,0 +1,66 @@
+//! このクレートのエラー型
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::tile::TileType;
+
+/// このクレートの関数が返すエラー
+#[derive(Debug, Error)]
+pub enum MahjongError {
+    /// 存在しない牌種
+    #[error("invalid tile: {0}")]
+    InvalidTile(TileType),
+    /// 面子・搭子を構成できない牌の組み合わせ
+    #[error("{0}")]
+    InvalidBlock(String),
+    /// 手牌が処理の前提（枚数・ツモ牌の有無・門前かどうかなど）を満たしていない
+    #[error("{0}")]
+    InvalidHand(String),
+    /// 局の状態の組み合わせが矛盾している（ロンでの海底摸月など）
+    #[error("{0}")]
+    InvalidStatus(String),
+    /// ルール設定で使用できない牌が含まれている
+    #[error("{0}")]
+    UnsupportedTile(String),
+    /// 手牌文字列の解釈に失敗した
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// 牌譜の解釈に失敗した
+    #[error("{0}")]
+    InvalidLog(String),
+    /// JSON の解釈に失敗した
+    #[cfg(feature = "tenhou")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// `MahjongError` を返す `Result`
+pub type Result<T> = std::result::Result<T, MahjongError>;
+
+/// 手牌文字列の解釈エラー
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// エラー位置（入力文字列のバイトオフセット）
+    pub offset: usize,
+    /// エラーの内容
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(offset: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            offset,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -1,10 +1,32 @@
+use crate::error::{MahjongError, Result};
+use crate::hand_info::block::{Same3, Sequential3};
 use crate::hand_info::meld::*;
+use crate::hand_info::shanten_fast;
 use crate::tile::*;
-use std::collections::VecDeque;
+use crate::tile_counts::TileCounts;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Write};
+use std::str::FromStr;
+
+mod parser;
+
+pub use crate::error::ParseError;
+use parser::GroupKind;
+
+/// 手牌文字列の解釈モード
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ParseMode {
+    /// 不正な表記をエラーとして扱う
+    Strict,
+    /// 他のツールの表記を受け付け、不正な表記を読み飛ばす（`Hand::from`と同じ挙動）
+    #[default]
+    Lenient,
+}
 
 /// 手牌
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Hand {
     /// 現在の手牌（副露がなければ13枚）
     tiles: Vec<Tile>,
@@ -12,6 +34,9 @@ pub struct Hand {
     melds: Vec<Meld>,
     /// ツモってきた牌
     drawn: Option<Tile>,
+    /// 抜きドラとして抜き出した北（三人麻雀のみ）
+    #[cfg_attr(feature = "serde", serde(default))]
+    nuki: Vec<Tile>,
 }
 impl Hand {
     /// 手牌の参照を返す
@@ -34,6 +59,18 @@ impl Hand {
         self.melds.push(open);
     }
 
+    /// 抜き出した北を返す
+    pub fn nuki(&self) -> &[Tile] {
+        &self.nuki
+    }
+
+    /// 北を抜きドラとして追加する
+    ///
+    /// 抜いた北は手牌の解析（向聴数・役判定）の対象外になる。
+    pub fn add_nuki(&mut self, tile: Tile) {
+        self.nuki.push(tile);
+    }
+
     /// 指定インデックスの牌を手牌から除去する
     pub fn remove_tiles_by_indices(&mut self, indices: &mut [usize]) {
         indices.sort_unstable_by(|a, b| b.cmp(a));
@@ -52,6 +89,7 @@ impl Hand {
             tiles,
             drawn,
             melds,
+            nuki: Vec::new(),
         }
     }
 
@@ -60,11 +98,47 @@ impl Hand {
         self.drawn
     }
 
-    /// 副露を返す
+    /// 副露（暗カンを含む）を返す
+    ///
+    /// 以前の `opened` フィールドの読み出しにはこれを使う（`opened()` という別名は設けない）。
     pub fn melds(&self) -> &[Meld] {
         &self.melds
     }
 
+    /// 門前（暗カン以外の副露がない）かどうかを返す
+    ///
+    /// 役の門前条件（立直・門前清自摸和など）に用いる。暗カンも許さない判定は
+    /// `has_no_melds` を使う。
+    pub fn is_concealed(&self) -> bool {
+        self.melds.iter().all(|m| m.is_concealed())
+    }
+
+    /// 副露が1つもない（暗カンもしていない）かどうかを返す
+    ///
+    /// 七対子・国士無双のように暗カンも許されない形の判定に用いる。
+    /// 門前かどうか（暗カンを許す）は `is_concealed` を使う。
+    pub fn has_no_melds(&self) -> bool {
+        self.melds.is_empty()
+    }
+
+    /// 副露を除いた手牌の枚数（ツモ牌を含む）を返す
+    pub fn concealed_count(&self) -> usize {
+        self.tiles.len() + usize::from(self.drawn.is_some())
+    }
+
+    /// ツモ牌を除いた手牌に`tile`を加えると和了形になるかを返す
+    ///
+    /// ロンの判定のように、他家の捨て牌ごとに和了できるかを調べる用途を想定する。
+    /// 役の有無やフリテンは考慮しない。
+    pub fn wins_with(&self, tile: Tile) -> bool {
+        shanten_fast::wins_with(self, tile.get())
+    }
+
+    /// ツモ牌を除いた手牌の和了牌を全て返す（牌種の昇順）
+    pub fn winning_tile_types(&self) -> Vec<TileType> {
+        shanten_fast::winning_tile_types(self)
+    }
+
     /// 副露の可変参照を返す
     pub fn melds_mut(&mut self) -> &mut Vec<Meld> {
         &mut self.melds
@@ -74,34 +148,286 @@ impl Hand {
     pub fn sort(&mut self) {
         self.tiles.sort();
     }
-    /// 種類別に各牌の数をカウントする
-    pub fn summarize_tiles(&self) -> TileSummarize {
-        let mut result: TileSummarize = [0; Tile::LEN];
 
-        // 通常の手牌をカウント
-        for i in 0..self.tiles.len() {
-            result[self.tiles[i].get() as usize] += 1;
+    /// 牌をツモる
+    ///
+    /// 既にツモ牌がある場合は、それを手牌に加えてから新しいツモ牌をセットする。
+    pub fn draw(&mut self, tile: Tile) {
+        self.settle_drawn();
+        self.drawn = Some(tile);
+    }
+
+    /// 牌を1枚捨てる
+    ///
+    /// ツモ牌と同じ牌ならツモ切りし、そうでなければ手牌から捨ててツモ牌を手牌に加える。
+    /// 赤ドラと通常の5は区別する。手牌にもツモ牌にもない牌はエラーとする。
+    pub fn discard(&mut self, tile: Tile) -> Result<Tile> {
+        if self.drawn == Some(tile) {
+            self.drawn = None;
+            return Ok(tile);
         }
+        let idx = self
+            .tiles
+            .iter()
+            .position(|t| *t == tile)
+            .ok_or_else(|| MahjongError::InvalidHand(format!("{} is not in the hand", tile)))?;
+        let discarded = self.tiles.remove(idx);
+        self.settle_drawn();
+        Ok(discarded)
+    }
+
+    /// チーを適用する
+    ///
+    /// 手牌から`hand_tiles`の2枚を取り除き、鳴いた牌と合わせて上家からの副露に加える。
+    /// 順子にならない組み合わせや、手牌にない牌、ツモ牌がある状態での副露はエラーとする。
+    pub fn apply_chi(&mut self, called_tile: Tile, hand_tiles: [Tile; 2]) -> Result<()> {
+        self.ensure_no_drawn("chi")?;
+        let mut tiles = [hand_tiles[0], hand_tiles[1], called_tile];
+        tiles.sort();
+        Sequential3::new(tiles[0].get(), tiles[1].get(), tiles[2].get())?;
+        self.take_tiles(&hand_tiles)?;
+        self.melds.push(Meld {
+            tiles: tiles.to_vec(),
+            category: MeldType::Chi,
+            from: MeldFrom::Previous,
+            called_tile: Some(called_tile),
+        });
+        Ok(())
+    }
 
-        // 鳴いている牌があればカウント
-        //
+    /// ポンを適用する
+    ///
+    /// 手牌から`hand_tiles`の2枚を取り除き、鳴いた牌と合わせて副露に加える。
+    /// 刻子にならない組み合わせや、手牌にない牌、ツモ牌がある状態での副露はエラーとする。
+    pub fn apply_pon(
+        &mut self,
+        called_tile: Tile,
+        hand_tiles: [Tile; 2],
+        from: MeldFrom,
+    ) -> Result<()> {
+        self.ensure_no_drawn("pon")?;
+        Same3::new(hand_tiles[0].get(), hand_tiles[1].get(), called_tile.get())?;
+        self.take_tiles(&hand_tiles)?;
+        self.melds.push(Meld {
+            tiles: vec![hand_tiles[0], hand_tiles[1], called_tile],
+            category: MeldType::Pon,
+            from,
+            called_tile: Some(called_tile),
+        });
+        Ok(())
+    }
+
+    /// 大明カンを適用する
+    ///
+    /// 手牌から鳴いた牌と同じ種類の3枚を取り除いて副露に加える。
+    pub fn apply_daiminkan(&mut self, called_tile: Tile, from: MeldFrom) -> Result<()> {
+        self.ensure_no_drawn("daiminkan")?;
+        let held: Vec<Tile> = self
+            .tiles
+            .iter()
+            .copied()
+            .filter(|t| t.get() == called_tile.get())
+            .take(3)
+            .collect();
+        if held.len() < 3 {
+            return Err(MahjongError::InvalidHand(format!(
+                "daiminkan of {} needs three tiles in the hand",
+                called_tile
+            )));
+        }
+        self.take_tiles(&held)?;
+        self.melds.push(Meld {
+            tiles: held,
+            category: MeldType::Kan,
+            from,
+            called_tile: Some(called_tile),
+        });
+        Ok(())
+    }
+
+    /// 暗カンを適用する
+    ///
+    /// 手牌とツモ牌から指定の牌種4枚を取り除いて副露に加える。
+    /// ツモ牌がカンする牌でなければ手牌に加え、暗カン後はツモ牌なし（嶺上牌を待つ状態）になる。
+    pub fn apply_ankan(&mut self, tile_type: TileType) -> Result<()> {
+        let mut kan_tiles: Vec<Tile> = self
+            .tiles
+            .iter()
+            .chain(self.drawn.iter())
+            .copied()
+            .filter(|t| t.get() == tile_type)
+            .collect();
+        if kan_tiles.len() != 4 {
+            return Err(MahjongError::InvalidHand(format!(
+                "ankan of {} needs four tiles in the hand",
+                Tile::new(tile_type)
+            )));
+        }
+        self.tiles.retain(|t| t.get() != tile_type);
+        if self.drawn.is_some_and(|t| t.get() == tile_type) {
+            self.drawn = None;
+        }
+        self.settle_drawn();
+
+        // 解析用に3枚だけ保持する。赤ドラは4枚目として補えないので必ず残す
+        kan_tiles.sort_by_key(|t| !t.is_red_dora());
+        kan_tiles.truncate(3);
+        self.melds.push(Meld {
+            tiles: kan_tiles,
+            category: MeldType::Kan,
+            from: MeldFrom::Myself,
+            called_tile: None,
+        });
+        Ok(())
+    }
+
+    /// 加カンを適用し、ポンに加えた牌を返す
+    ///
+    /// ツモ牌が同じ種類ならツモ牌を、そうでなければ手牌の牌を加える（後者ではツモ牌を手牌に加える）。
+    /// 加カン後はツモ牌なし（嶺上牌を待つ状態）になる。
+    pub fn apply_kakan(&mut self, tile_type: TileType) -> Result<Tile> {
+        let Some(meld_idx) = self
+            .melds
+            .iter()
+            .position(|m| m.category == MeldType::Pon && m.tiles[0].get() == tile_type)
+        else {
+            return Err(MahjongError::InvalidHand(format!(
+                "no pon of {} to extend",
+                Tile::new(tile_type)
+            )));
+        };
+        let added = match self.drawn {
+            Some(drawn) if drawn.get() == tile_type => {
+                self.drawn = None;
+                drawn
+            }
+            _ => {
+                let idx = self
+                    .tiles
+                    .iter()
+                    .position(|t| t.get() == tile_type)
+                    .ok_or_else(|| {
+                        MahjongError::InvalidHand(format!(
+                            "kakan of {} needs the fourth tile in the hand",
+                            Tile::new(tile_type)
+                        ))
+                    })?;
+                let tile = self.tiles.remove(idx);
+                self.settle_drawn();
+                tile
+            }
+        };
+        let meld = &mut self.melds[meld_idx];
+        meld.category = MeldType::Kakan;
+        meld.called_tile = Some(added);
+        Ok(added)
+    }
+
+    /// ツモ牌を手牌に加えてソートする
+    fn settle_drawn(&mut self) {
+        if let Some(tile) = self.drawn.take() {
+            self.tiles.push(tile);
+            self.tiles.sort();
+        }
+    }
+
+    /// 他家の捨て牌を鳴く前提（ツモ牌がない）を満たしているか
+    fn ensure_no_drawn(&self, call: &str) -> Result<()> {
+        if self.drawn.is_some() {
+            return Err(MahjongError::InvalidHand(format!(
+                "cannot {} while holding a drawn tile",
+                call
+            )));
+        }
+        Ok(())
+    }
+
+    /// 手牌から指定の牌を1枚ずつ取り除く（足りなければ手牌を変更せずにエラーとする）
+    fn take_tiles(&mut self, targets: &[Tile]) -> Result<()> {
+        let mut indices: Vec<usize> = Vec::with_capacity(targets.len());
+        for target in targets {
+            let idx = self
+                .tiles
+                .iter()
+                .enumerate()
+                .position(|(i, t)| t == target && !indices.contains(&i))
+                .ok_or_else(|| {
+                    MahjongError::InvalidHand(format!("{} is not in the hand", target))
+                })?;
+            indices.push(idx);
+        }
+        self.remove_tiles_by_indices(&mut indices);
+        Ok(())
+    }
+
+    /// 種類別に各牌の数をカウントする
+    ///
+    /// 戻り値は以前の`TileSummarize`（`[u32; 34]`）から[`TileCounts`]に変わった。
+    /// 配列として参照する場合は`Deref`、所有する配列が必要な場合は`into_inner()`を使う。
+    pub fn summarize_tiles(&self) -> TileCounts {
         // 解析用途では副露は常に1面子として扱う。槓子の4枚目まで数えると
         // 「4面子1雀頭に加えて孤立牌が1枚ある」手を和了形と誤認しうる。
-        for i in 0..self.melds.len() {
-            for j in 0..self.melds[i].tiles.len() {
-                result[self.melds[i].tiles[j].get() as usize] += 1;
-            }
+        self.tiles
+            .iter()
+            .chain(self.melds.iter().flat_map(|m| m.tiles.iter()))
+            .chain(self.drawn.iter())
+            .collect()
+    }
+
+    /// 手牌として成り立つ枚数かを検証する
+    ///
+    /// 以下のいずれかに該当する場合はエラーを返す。
+    /// - 副露が5つ以上ある、または3枚・4枚以外の副露がある
+    /// - 手牌・副露（槓子は1面子3枚として数える）・ツモ牌の合計が13枚・14枚のいずれでもない
+    /// - 同じ牌が手牌・副露（槓子は4枚）・ツモ牌・抜きドラを通じて5枚以上ある
+    pub fn validate(&self) -> Result<()> {
+        if self.melds.len() > 4 {
+            return Err(MahjongError::InvalidHand(format!(
+                "too many melds: {}",
+                self.melds.len()
+            )));
+        }
+        if let Some(meld) = self
+            .melds
+            .iter()
+            .find(|m| !(3..=4).contains(&m.tiles.len()))
+        {
+            return Err(MahjongError::InvalidHand(format!(
+                "meld has {} tiles (expected 3 or 4)",
+                meld.tiles.len()
+            )));
         }
 
-        // ツモった牌があればカウント
-        if let Some(t) = self.drawn {
-            result[t.get() as usize] += 1;
+        let total = self.concealed_count() + 3 * self.melds.len();
+        if total != 13 && total != 14 {
+            return Err(MahjongError::InvalidHand(format!(
+                "hand has {total} tiles counting each meld as 3 (expected 13 or 14)"
+            )));
         }
 
-        result
+        let mut counts = [0u32; Tile::LEN];
+        let all_tiles = self
+            .tiles
+            .iter()
+            .copied()
+            .chain(self.melds.iter().flat_map(|m| m.expanded_tiles()))
+            .chain(self.drawn)
+            .chain(self.nuki.iter().copied());
+        for tile in all_tiles {
+            counts[tile.get() as usize] += 1;
+        }
+        if let Some((tile, &count)) = counts.iter().enumerate().find(|&(_, &c)| c > 4) {
+            return Err(MahjongError::InvalidHand(format!(
+                "{} appears {count} times",
+                Tile::new(tile as TileType).to_char()
+            )));
+        }
+        Ok(())
     }
 
     /// 絵文字として出力する
+    ///
+    /// 副露は`Meld`の表示と同じ順に並べ、鳴いた牌（横向きにする牌）の直後に`'`を付ける。
     pub fn to_emoji(&self) -> String {
         let mut result = String::new();
         for tile in &self.tiles {
@@ -110,7 +436,18 @@ impl Hand {
 
         for meld in &self.melds {
             result.push(' ');
-            for tile in meld.expanded_tiles() {
+            let (tiles, called) = meld.display_tiles();
+            for (i, tile) in tiles.iter().enumerate() {
+                result.push(tile.to_char());
+                if called == Some(i) {
+                    result.push('\'');
+                }
+            }
+        }
+
+        if !self.nuki.is_empty() {
+            result.push_str(" n");
+            for tile in &self.nuki {
                 result.push(tile.to_char());
             }
         }
@@ -121,120 +458,178 @@ impl Hand {
         result
     }
 
-    /// `Vec<Tile>`から連続した牌の種類を圧縮した文字列を返す
-    fn make_short_str(mut tiles: Vec<Tile>) -> String {
-        if tiles.is_empty() {
-            return String::new();
-        } else if tiles.len() == 1 {
-            return tiles[0].to_string();
+    /// 枠付きのアスキーアートとして出力する
+    ///
+    /// 絵文字を表示できない端末向けに、各牌を`|1m|`のように囲んで並べる。
+    /// 手牌・副露・抜きドラ・ツモ牌の各まとまりは2つの空白で区切る。
+    pub fn to_ascii_art(&self) -> String {
+        fn boxed(tiles: &[Tile]) -> String {
+            tiles.iter().map(|tile| format!("|{tile}|")).collect()
         }
-        tiles.sort();
-        let mut result = String::new();
-        let mut current_suit = None;
-        for tile in tiles {
-            let (rank, suit) = Self::short_tile_parts(tile);
-            if let Some(prev_suit) = current_suit
-                && suit != prev_suit
-            {
-                result.push(prev_suit);
-            }
-            result.push(rank);
-            current_suit = Some(suit);
+
+        let mut groups = vec![boxed(&self.tiles)];
+        for meld in &self.melds {
+            groups.push(boxed(&meld.expanded_tiles()));
         }
-        if let Some(suit) = current_suit {
-            result.push(suit);
+        if !self.nuki.is_empty() {
+            groups.push(boxed(&self.nuki));
         }
-        result
+        if let Some(tsumo) = self.drawn {
+            groups.push(boxed(&[tsumo]));
+        }
+        groups.join("  ")
     }
 
-    fn short_tile_parts(tile: Tile) -> (char, char) {
-        match tile.get() {
-            Tile::M1..=Tile::M9 => (Self::rank_char(tile.get() - Tile::M1), 'm'),
-            Tile::P1..=Tile::P9 => (Self::rank_char(tile.get() - Tile::P1), 'p'),
-            Tile::S1..=Tile::S9 => (Self::rank_char(tile.get() - Tile::S1), 's'),
-            Tile::Z1..=Tile::Z7 => (Self::rank_char(tile.get() - Tile::Z1), 'z'),
-            _ => ('?', '?'),
+    /// `Vec<Tile>`から連続した牌の種類を圧縮した文字列を返す
+    ///
+    /// 牌を並べ替えてから同じスートの数字をまとめ、スートを1回だけ付ける（例: `123m406p`）。
+    /// 赤5は`0`で表す。
+    pub(crate) fn make_short_str(mut tiles: Vec<Tile>) -> String {
+        tiles.sort();
+        let mut result = String::new();
+        for group in
+            tiles.chunk_by(|a, b| Self::short_tile_parts(*a).1 == Self::short_tile_parts(*b).1)
+        {
+            result.extend(group.iter().map(|&tile| Self::short_tile_parts(tile).0));
+            result.push(Self::short_tile_parts(group[0]).1);
         }
+        result
     }
 
-    fn rank_char(zero_based_rank: TileType) -> char {
-        char::from(b'1' + zero_based_rank as u8)
+    /// 牌を数字（赤5は`0`）とスートの文字に分ける
+    pub(crate) fn short_tile_parts(tile: Tile) -> (char, char) {
+        let (base, suit) = match tile.get() {
+            Tile::M1..=Tile::M9 => (Tile::M1, 'm'),
+            Tile::P1..=Tile::P9 => (Tile::P1, 'p'),
+            Tile::S1..=Tile::S9 => (Tile::S1, 's'),
+            Tile::Z1..=Tile::Z7 => (Tile::Z1, 'z'),
+            _ => return ('?', '?'),
+        };
+        if tile.is_red_dora() {
+            return ('0', suit);
+        }
+        (char::from(b'1' + (tile.get() - base) as u8), suit)
     }
 
     /// 文字列として出力する
     ///
     /// `to_string`と違い、こちらは連続した牌の種類は省略して`123m123p...`と出力する。
+    /// 副露は鳴いた牌の印と暗カンの`a`を付けて出力し（`Meld`の表示を参照）、赤5は`0`で表すため、
+    /// `Hand::from`で同じ手牌に読み戻せる。
     pub fn to_short_string(&self) -> String {
-        let tiles = self.tiles.clone();
-        let mut result = Hand::make_short_str(tiles);
+        let mut result = Hand::make_short_str(self.tiles.clone());
 
         for meld in &self.melds {
-            let _ = write!(result, " {}", Hand::make_short_str(meld.expanded_tiles()));
+            let _ = write!(result, " {meld}");
+        }
+
+        if !self.nuki.is_empty() {
+            let _ = write!(result, " n{}", Hand::make_short_str(self.nuki.clone()));
         }
 
         if let Some(tsumo) = self.drawn {
-            let _ = write!(result, " {tsumo}");
+            let _ = write!(result, " {}", Hand::make_short_str(vec![tsumo]));
         }
         result
     }
 
-    /// 文字列から`Vec<Tile>`を返す
-    fn str_to_tiles(hand_str: &str) -> Vec<Tile> {
-        let mut result: Vec<Tile> = Vec::new();
-        let mut stack: VecDeque<char> = VecDeque::new();
-        for c in hand_str.chars() {
-            if matches!(c, '1'..='9') {
-                stack.push_back(c);
-            } else if matches!(c, 'm' | 'p' | 's' | 'z') {
-                while let Some(t) = stack.pop_front() {
-                    // 字牌の場合は`8z`と`9z`は存在しない
-                    if (matches!(c, 'm' | 'p' | 's') || (c == 'z' && matches!(t, '1'..='7')))
-                        && let Some(t) = Tile::from(&format!("{t}{c}"))
-                    {
-                        result.push(t);
-                    }
-                }
-            }
+    /// 文字列から手牌を生成する
+    ///
+    /// 空白区切りで`手牌 副露... ツモ牌`の順に記述する。
+    /// `n`で始まる区切り（例: `n44z`）は抜きドラとして抜き出した北、
+    /// `a`で始まる区切り（例: `a1111z`）は暗カンを表す。
+    /// 書式の詳細は`parser`モジュールを参照。解釈できない部分は読み飛ばす。
+    pub fn from(hand_str: &str) -> Hand {
+        match parser::tokenize(hand_str, ParseMode::Lenient) {
+            Ok(groups) => Hand::from_groups(groups),
+            Err(_) => Hand::new(Vec::new(), None),
         }
-        result
     }
 
-    pub fn from(hand_str: &str) -> Hand {
-        let mut itr = hand_str.split_ascii_whitespace();
-        let hand = Hand::str_to_tiles(itr.next().unwrap_or(""));
+    /// 解釈モードを指定して文字列から手牌を生成する
+    ///
+    /// `ParseMode::Strict`の場合は以下をエラー（`ParseError`）とする。
+    /// - 存在しない牌（`8z`など）や解釈できない文字
+    /// - 3枚・4枚以外の副露、複数のツモ牌
+    /// - 同じ牌が5枚以上ある
+    pub fn parse(hand_str: &str, mode: ParseMode) -> Result<Hand> {
+        let groups = parser::tokenize(hand_str, mode)?;
+        Ok(Hand::from_groups(groups))
+    }
+
+    /// 他のツールの手牌表記（`m123p456`やカンマ区切り）をこの crate の書式に正規化する
+    ///
+    /// `ParseMode::Lenient`での解釈はこの正規化を経て行われる。
+    pub fn normalize_notation(hand_str: &str) -> String {
+        parser::normalize_aliases(hand_str)
+    }
+
+    /// 字句解析したまとまりから手牌を生成する
+    fn from_groups(groups: Vec<parser::Group>) -> Hand {
+        let mut hand = Vec::new();
         let mut melds: Vec<Meld> = Vec::new();
         let mut drawn: Option<Tile> = None;
+        let mut nuki: Vec<Tile> = Vec::new();
 
-        for tile_str in itr {
-            let tile_vec = Hand::str_to_tiles(tile_str);
-            match tile_vec.len() {
-                1 => {
-                    drawn = Some(tile_vec[0]);
+        for group in groups {
+            match group.kind {
+                GroupKind::Concealed => hand = group.tiles,
+                GroupKind::Nuki => nuki.extend(group.tiles),
+                GroupKind::ClosedKan if group.tiles.len() == 4 => {
+                    melds.push(Hand::make_meld(group.tiles, None, MeldFrom::Myself));
                 }
-                3 => {
-                    melds.push(Meld {
-                        tiles: tile_vec.clone(),
-                        category: if tile_vec[0] == tile_vec[1] {
-                            MeldType::Pon
-                        } else {
-                            MeldType::Chi
-                        },
-                        from: MeldFrom::Unknown,
-                        called_tile: None,
-                    });
-                }
-                4 => {
-                    melds.push(Meld {
-                        tiles: tile_vec[..3].to_vec(),
-                        category: MeldType::Kan,
-                        from: MeldFrom::Unknown,
-                        called_tile: None,
-                    });
-                }
-                _ => {}
+                GroupKind::Open | GroupKind::ClosedKan => match group.tiles.len() {
+                    1 => drawn = Some(group.tiles[0]),
+                    3 | 4 => melds.push(Hand::make_meld(
+                        group.tiles,
+                        group.called,
+                        MeldFrom::Unknown,
+                    )),
+                    _ => {}
+                },
+            }
+        }
+        let mut result = Hand::new_with_melds(hand, melds, drawn);
+        result.nuki = nuki;
+        result
+    }
+
+    /// 副露を生成する
+    ///
+    /// 鳴いた牌の位置が与えられた場合は、その位置から誰から鳴いたかを決める
+    /// （左端は上家、右端は下家、それ以外は対面。チーは常に上家）。
+    fn make_meld(mut tiles: Vec<Tile>, called: Option<usize>, default_from: MeldFrom) -> Meld {
+        let is_pon = tiles[0].get() == tiles[1].get();
+        let category = match (tiles.len(), is_pon) {
+            (4, _) => MeldType::Kan,
+            (_, true) => MeldType::Pon,
+            (_, false) => MeldType::Chi,
+        };
+        let from = match called {
+            None => default_from,
+            Some(_) if category == MeldType::Chi => MeldFrom::Previous,
+            Some(0) => MeldFrom::Previous,
+            Some(i) if i == tiles.len() - 1 => MeldFrom::Following,
+            Some(_) => MeldFrom::Opposite,
+        };
+        let called_tile = called.map(|i| tiles[i]);
+
+        if category == MeldType::Kan {
+            // 4枚目は`kan_fourth_tile`で補うため、鳴いた牌以外（赤ドラ優先）の3枚を保持する
+            if let Some(i) = called {
+                tiles.remove(i);
             }
+            tiles.sort_by_key(|tile| !tile.is_red_dora());
+            tiles.truncate(3);
+        }
+        tiles.sort();
+
+        Meld {
+            tiles,
+            category,
+            from,
+            called_tile,
         }
-        Hand::new_with_melds(hand, melds, drawn)
     }
 
     pub fn from_summarized(sum: &TileSummarize) -> Hand {
@@ -256,7 +651,9 @@ impl Hand {
 
 /// 文字列として出力する
 ///
-/// `to_short_string`と違い、こちらは牌の種類を省略せずに`1m2m3m1p2p3p...`と必ず2文字単位で出力する。
+/// `to_short_string`と違い、こちらは手牌の種類を省略せずに`1m2m3m1p2p3p...`と必ず2文字単位で出力する。
+/// 副露は`Meld`の表示（例: `5'55z`、`a1111z`）で出力するため、鳴いた牌と暗カンも
+/// `Hand::from`で読み戻せる。
 impl fmt::Display for Hand {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for tile in &self.tiles {
@@ -264,8 +661,12 @@ impl fmt::Display for Hand {
         }
 
         for meld in &self.melds {
-            f.write_str(" ")?;
-            for tile in meld.expanded_tiles() {
+            write!(f, " {meld}")?;
+        }
+
+        if !self.nuki.is_empty() {
+            f.write_str(" n")?;
+            for tile in &self.nuki {
                 write!(f, "{tile}")?;
             }
         }
@@ -277,6 +678,18 @@ impl fmt::Display for Hand {
         Ok(())
     }
 }
+
+/// 手牌文字列を`ParseMode::Strict`で解釈する（`Hand::parse`と同じ）
+///
+/// 例: `"123m456p789s1122z 2z".parse::<Hand>()?`
+impl FromStr for Hand {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Hand::parse(s, ParseMode::Strict)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,7 +706,8 @@ mod tests {
     }
     #[test]
     fn str_to_tiles_test() {
-        let test = Hand::str_to_tiles("123m456p789s1234z");
+        let hand = Hand::from("123m456p789s1234z");
+        let test = hand.tiles();
         assert_eq!(test[0], Tile::new(Tile::M1));
         assert_eq!(test[1], Tile::new(Tile::M2));
         assert_eq!(test[2], Tile::new(Tile::M3));
@@ -310,7 +724,8 @@ mod tests {
     }
     #[test]
     fn str_to_tiles_test2() {
-        let test = Hand::str_to_tiles("1m2m3m4p5p6p");
+        let hand = Hand::from("1m2m3m4p5p6p");
+        let test = hand.tiles();
         assert_eq!(test[0], Tile::new(Tile::M1));
         assert_eq!(test[1], Tile::new(Tile::M2));
         assert_eq!(test[2], Tile::new(Tile::M3));
@@ -320,7 +735,8 @@ mod tests {
     }
     #[test]
     fn str_to_tiles_test3() {
-        let test = Hand::str_to_tiles("");
+        let hand = Hand::from("");
+        let test = hand.tiles();
         assert_eq!(test.len(), 0);
     }
 
@@ -389,4 +805,294 @@ mod tests {
         assert_eq!(test.drawn, Some(Tile::new(Tile::Z5)));
         assert_eq!(test.to_short_string(), test_str);
     }
+
+    #[test]
+    fn from_with_nuki_test() {
+        let test_str = "123m456p789s1115z n44z 5z";
+        let test = Hand::from(test_str);
+        assert_eq!(test.nuki(), &[Tile::new(Tile::Z4), Tile::new(Tile::Z4)]);
+        assert!(test.melds.is_empty());
+        assert_eq!(test.drawn, Some(Tile::new(Tile::Z5)));
+        assert_eq!(test.to_short_string(), test_str);
+        assert_eq!(test.to_string(), "1m2m3m4p5p6p7s8s9s1z1z1z5z n4z4z 5z");
+    }
+
+    #[test]
+    fn nuki_is_excluded_from_summary() {
+        let mut test = Hand::from("123m456p789s1115z 5z");
+        let before = test.summarize_tiles();
+        test.add_nuki(Tile::new(Tile::Z4));
+        assert_eq!(test.summarize_tiles(), before);
+        assert_eq!(test.nuki().len(), 1);
+    }
+
+    #[test]
+    fn to_ascii_art_test() {
+        let test = Hand::from("12m 555z n4z 3m");
+        assert_eq!(test.to_ascii_art(), "|1m||2m|  |5z||5z||5z|  |4z|  |3m|");
+        let closed = Hand::from("19m");
+        assert_eq!(closed.to_ascii_art(), "|1m||9m|");
+    }
+
+    #[test]
+    fn to_emoji_test() {
+        let test = Hand::from("12m 55'5z 3m");
+        assert_eq!(test.to_emoji(), "🀇🀈 🀆🀆'🀆 🀉");
+        let closed = Hand::from("19m a1111z");
+        assert_eq!(closed.to_emoji(), "🀇🀏 🀀🀀🀀🀀");
+    }
+
+    #[test]
+    fn parse_lenient_test() {
+        let test = Hand::parse("123m456p789s1238z 9z", ParseMode::Lenient).unwrap();
+        assert_eq!(test.to_short_string(), "123m456p789s123z");
+    }
+
+    #[test]
+    fn parse_strict_test() {
+        let test = Hand::parse("123m456p789s11z n4z 555z 1z", ParseMode::Strict).unwrap();
+        assert_eq!(test.to_short_string(), "123m456p789s11z 555z n4z 1z");
+
+        for invalid in [
+            "123m456p789s1238z",
+            "123m456p789s123x",
+            "123m456p789s123",
+            "123m456p789s1234z 1z 2z",
+            "123m456p789s1234z 12z",
+            "11111m456p789s123z",
+            "1111m456p789s123z 1m",
+        ] {
+            assert!(
+                Hand::parse(invalid, ParseMode::Strict).is_err(),
+                "{invalid} should be rejected"
+            );
+            assert!(Hand::parse(invalid, ParseMode::Lenient).is_ok());
+        }
+    }
+
+    #[test]
+    fn validate_test() {
+        assert!(Hand::from("123m456p789s1122z").validate().is_ok());
+        assert!(Hand::from("123m456p789s1122z 2z").validate().is_ok());
+        assert!(Hand::from("123m456p78s22z a9999m 9s").validate().is_ok());
+        assert!(Hand::from("123p456789s3m 2222m 3m").validate().is_ok());
+
+        // 枚数が足りない・多すぎる
+        assert!(Hand::from("123m456p789s112z").validate().is_err());
+        assert!(Hand::from("123m456p789s11223z 3z").validate().is_err());
+        // 同じ牌が5枚以上
+        assert!(Hand::from("11111m456p789s12z").validate().is_err());
+        assert!(Hand::from("1m456p789s123z 1111m 1m").validate().is_err());
+        assert!(matches!(
+            Hand::from("123m456p789s112z").validate(),
+            Err(MahjongError::InvalidHand(_))
+        ));
+        assert!(matches!(
+            Hand::parse("123m 18z", ParseMode::Strict),
+            Err(MahjongError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn from_with_extended_notation_test() {
+        let test = Hand::from("406m789s11z 55'5z 1'23s a0555p 1z");
+        assert_eq!(test.tiles()[0], Tile::new(Tile::M4));
+        assert!(test.tiles()[1].is_red_dora());
+
+        let pon = &test.melds()[0];
+        assert_eq!(pon.category, MeldType::Pon);
+        assert_eq!(pon.from, MeldFrom::Opposite);
+        assert_eq!(pon.called_tile, Some(Tile::new(Tile::Z5)));
+
+        let chi = &test.melds()[1];
+        assert_eq!(chi.category, MeldType::Chi);
+        assert_eq!(chi.from, MeldFrom::Previous);
+        assert_eq!(chi.called_tile, Some(Tile::new(Tile::S1)));
+
+        let ankan = &test.melds()[2];
+        assert_eq!(ankan.category, MeldType::Kan);
+        assert_eq!(ankan.from, MeldFrom::Myself);
+        assert!(ankan.is_concealed());
+        assert!(!test.is_concealed());
+        assert!(Hand::from("123m456p78s22z a1111z 9s").is_concealed());
+        assert!(!Hand::from("123m456p78s22z 1111z 9s").is_concealed());
+        assert_eq!(ankan.called_tile, None);
+        assert_eq!(
+            ankan
+                .expanded_tiles()
+                .iter()
+                .filter(|t| t.is_red_dora())
+                .count(),
+            1
+        );
+
+        assert_eq!(test.drawn(), Some(Tile::new(Tile::Z1)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let hand = Hand::from("123m456p789s1z 555z 1z");
+        let json = serde_json::to_string(&hand).unwrap();
+        let restored: Hand = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.to_string(), hand.to_string());
+        assert_eq!(restored.melds().len(), 1);
+        assert_eq!(restored.drawn(), Some(Tile::new(Tile::Z1)));
+    }
+
+    #[test]
+    fn display_round_trips_melds() {
+        let hand = Hand::from("123m456p78s22z 5'55z a1111m 9s");
+        assert_eq!(hand.to_string(), "1m2m3m4p5p6p7s8s2z2z 5'55z a1111m 9s");
+        let restored = Hand::from(&hand.to_string());
+        assert_eq!(restored.to_string(), hand.to_string());
+        assert_eq!(restored.melds()[0].from, MeldFrom::Previous);
+        assert!(restored.melds()[1].is_concealed());
+    }
+
+    #[test]
+    fn from_str_test() {
+        let hand: Hand = "123m456p789s11z 555z 1z".parse().unwrap();
+        assert_eq!(hand.to_short_string(), "123m456p789s11z 555z 1z");
+        assert!(matches!(
+            "123m456p789s1238z".parse::<Hand>(),
+            Err(MahjongError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn draw_and_discard() {
+        let mut hand = Hand::from("123m456p789s1122z");
+        hand.draw(Tile::new(Tile::Z3));
+        // 手出しするとツモ牌は手牌に入り、ソートされる
+        assert_eq!(
+            hand.discard(Tile::new(Tile::M1)).unwrap(),
+            Tile::new(Tile::M1)
+        );
+        assert_eq!(hand.drawn(), None);
+        assert_eq!(hand.to_short_string(), "23m456p789s11223z");
+
+        hand.draw(Tile::new(Tile::S1));
+        assert_eq!(
+            hand.discard(Tile::new(Tile::S1)).unwrap(),
+            Tile::new(Tile::S1)
+        );
+        assert_eq!(hand.tiles().len(), 13);
+        assert!(hand.discard(Tile::new(Tile::S1)).is_err());
+        assert!(hand.discard(Tile::new_red(Tile::P5)).is_err());
+    }
+
+    #[test]
+    fn apply_chi_and_pon() {
+        let mut hand = Hand::from("13m456p789s11222z");
+        hand.apply_chi(
+            Tile::new(Tile::M2),
+            [Tile::new(Tile::M1), Tile::new(Tile::M3)],
+        )
+        .unwrap();
+        assert_eq!(hand.to_short_string(), "456p789s11222z 2'13m");
+        hand.apply_pon(
+            Tile::new(Tile::Z2),
+            [Tile::new(Tile::Z2), Tile::new(Tile::Z2)],
+            MeldFrom::Opposite,
+        )
+        .unwrap();
+        assert_eq!(hand.tiles().len(), 9);
+        assert_eq!(hand.melds()[1].category, MeldType::Pon);
+
+        // 順子にならない・手牌にない・ツモ牌がある
+        let mut hand = Hand::from("13m456p789s11222z");
+        let before = hand.to_short_string();
+        assert!(
+            hand.apply_chi(
+                Tile::new(Tile::M4),
+                [Tile::new(Tile::M1), Tile::new(Tile::M3)]
+            )
+            .is_err()
+        );
+        assert!(
+            hand.apply_pon(
+                Tile::new(Tile::M3),
+                [Tile::new(Tile::M3), Tile::new(Tile::M3)],
+                MeldFrom::Following
+            )
+            .is_err()
+        );
+        assert_eq!(hand.to_short_string(), before);
+        hand.draw(Tile::new(Tile::M2));
+        assert!(
+            hand.apply_chi(
+                Tile::new(Tile::M2),
+                [Tile::new(Tile::M1), Tile::new(Tile::M3)]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn apply_kans() {
+        let mut hand = Hand::from("1110m456p789s122z");
+        hand.draw(Tile::new(Tile::M5));
+        hand.apply_ankan(Tile::M1).unwrap_err();
+        hand.discard(Tile::new(Tile::M5)).unwrap();
+        hand.draw(Tile::new(Tile::M1));
+        hand.apply_ankan(Tile::M1).unwrap();
+        assert_eq!(hand.drawn(), None);
+        assert!(hand.melds()[0].is_concealed());
+        assert_eq!(hand.tiles().len(), 10);
+
+        // 赤ドラは保持する3枚に残す
+        let mut hand = Hand::from("5550m456p789s12z");
+        hand.draw(Tile::new(Tile::Z3));
+        hand.apply_ankan(Tile::M5).unwrap();
+        assert!(hand.melds()[0].tiles.iter().any(|t| t.is_red_dora()));
+        assert_eq!(hand.tiles().len(), 9);
+
+        let mut hand = Hand::from("111m456p789s1222z");
+        hand.apply_daiminkan(Tile::new(Tile::Z2), MeldFrom::Following)
+            .unwrap();
+        assert_eq!(hand.tiles().len(), 10);
+        assert!(
+            hand.apply_daiminkan(Tile::new(Tile::M9), MeldFrom::Following)
+                .is_err()
+        );
+
+        let mut hand = Hand::from("23m456p789s1z 1'11m 3z");
+        assert!(hand.apply_kakan(Tile::M2).is_err());
+        hand.draw(Tile::new(Tile::M1));
+        assert_eq!(hand.apply_kakan(Tile::M1).unwrap(), Tile::new(Tile::M1));
+        assert_eq!(hand.melds()[0].category, MeldType::Kakan);
+        assert_eq!(hand.drawn(), None);
+        assert_eq!(hand.to_short_string(), "23m456p789s13z 1'111m");
+    }
+
+    #[test]
+    fn has_no_melds_and_concealed_count() {
+        let hand = Hand::from("123m456p789s1122z 2z");
+        assert!(hand.has_no_melds());
+        assert_eq!(hand.concealed_count(), 14);
+
+        // 暗カンは門前を崩さないが、副露なしではない
+        let hand = Hand::from("123m456p789s1z a2222z 1z");
+        assert!(hand.is_concealed());
+        assert!(!hand.has_no_melds());
+        assert_eq!(hand.concealed_count(), 11);
+    }
+
+    #[rstest::rstest]
+    #[case("123m456p789s1122z", &[Tile::Z1, Tile::Z2])]
+    #[case("1112345678999m", &[Tile::M1, Tile::M2, Tile::M3, Tile::M4, Tile::M5, Tile::M6, Tile::M7, Tile::M8, Tile::M9])]
+    #[case("19m19p19s1234567z", &[Tile::M1, Tile::M9, Tile::P1, Tile::P9, Tile::S1, Tile::S9, Tile::Z1, Tile::Z2, Tile::Z3, Tile::Z4, Tile::Z5, Tile::Z6, Tile::Z7])]
+    #[case("1122m3344p5566s7z", &[Tile::Z7])]
+    // 手牌で4枚使い切っている牌も和了牌に含む
+    #[case("1112345679999m", &[Tile::M8, Tile::M9])]
+    #[case("123m456p1z 777z 2'13s", &[Tile::Z1])]
+    #[case("123m456p789s1357z", &[])]
+    fn wins_with_test(#[case] hand_str: &str, #[case] expected: &[TileType]) {
+        let hand = Hand::from(hand_str);
+        assert_eq!(hand.winning_tile_types(), expected);
+        for t in 0..Tile::LEN as TileType {
+            assert_eq!(hand.wins_with(Tile::new(t)), expected.contains(&t));
+        }
+    }
 }
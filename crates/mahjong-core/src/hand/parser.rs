@@ -0,0 +1,383 @@
+//! 手牌文字列の字句解析
+//!
+//! 手牌文字列は空白区切りのまとまりからなり、以下の文法で解釈する。
+//!
+//! - 先頭のまとまり: 手牌（`<牌列>`）
+//! - `n<牌列>`: 抜きドラとして抜き出した北
+//! - `a<牌列>`: 暗カン（4枚）
+//! - `<牌列>`: 副露（3枚・4枚）またはツモ牌（1枚）
+//!
+//! 牌列は`<数字>...<スート>`の繰り返しで、スートは`m`・`p`・`s`・`z`のいずれか。
+//! 数字の`0`または`r5`は赤5を表す（字牌には使えない。例: `0m`・`r5m`）。
+//! 数字の直後の`'`はその牌が鳴いた牌であることを示し、副露内の位置から誰から鳴いたかを決める
+//! （例: `5'55z`は上家、`55'5z`は対面、`555'z`は下家からのポン）。
+//!
+//! `ParseMode::Lenient`では他のツールの表記も受け付け、解釈前に上記の書式へ正規化する。
+//!
+//! - スートを先に書く表記（例: `m123p456`）
+//! - カンマ区切りのまとまり（例: `123m456p,555z,1z`）
+
+use super::ParseMode;
+use crate::error::ParseError;
+use crate::tile::*;
+
+/// 空白区切りのまとまりの種類
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum GroupKind {
+    /// 手牌
+    Concealed,
+    /// 副露またはツモ牌
+    Open,
+    /// 暗カン
+    ClosedKan,
+    /// 抜きドラ
+    Nuki,
+}
+
+/// 空白区切りのまとまり
+#[derive(Debug, Clone)]
+pub(super) struct Group {
+    /// まとまりの種類
+    pub kind: GroupKind,
+    /// まとまりに含まれる牌
+    pub tiles: Vec<Tile>,
+    /// 鳴いた牌の`tiles`内の位置
+    pub called: Option<usize>,
+    /// まとまりの開始位置（バイトオフセット）
+    pub offset: usize,
+}
+
+/// 手牌文字列をまとまりの列に分解する
+///
+/// `ParseMode::Lenient`の場合は解釈できない部分を読み飛ばし、エラーを返さない。
+pub(super) fn tokenize(input: &str, mode: ParseMode) -> Result<Vec<Group>, ParseError> {
+    let strict = mode == ParseMode::Strict;
+    let normalized;
+    let input = if strict {
+        input
+    } else {
+        normalized = normalize_aliases(input);
+        normalized.as_str()
+    };
+    let mut groups = Vec::new();
+
+    for (offset, text) in split_groups(input) {
+        let (kind, body_offset) = if groups.is_empty() {
+            (GroupKind::Concealed, offset)
+        } else if text.starts_with('n') {
+            (GroupKind::Nuki, offset + 1)
+        } else if text.starts_with('a') {
+            (GroupKind::ClosedKan, offset + 1)
+        } else {
+            (GroupKind::Open, offset)
+        };
+        let (tiles, called) = scan_tiles(
+            &input[body_offset..offset + text.len()],
+            body_offset,
+            strict,
+        )?;
+        groups.push(Group {
+            kind,
+            tiles,
+            called,
+            offset,
+        });
+    }
+
+    if strict {
+        check_groups(&groups)?;
+    }
+    Ok(groups)
+}
+
+/// 他のツールの表記をこの crate の書式に正規化する
+///
+/// カンマを区切りとして扱い、スートを先に書いたまとまり（例: `m123p456`）を
+/// 数字を先に書く形（例: `123m456p`）に並べ替える。まとまりは空白1つで区切り直す。
+pub(super) fn normalize_aliases(input: &str) -> String {
+    input
+        .split(|c: char| c == ',' || c.is_ascii_whitespace())
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            let (prefix, body) = match group.strip_prefix(['n', 'a']) {
+                Some(body) => (&group[..1], body),
+                None => ("", group),
+            };
+            if body.starts_with(['m', 'p', 's', 'z']) {
+                format!("{prefix}{}", suit_last(body))
+            } else {
+                group.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// スートを先に書いた牌列を、数字を先に書く形に並べ替える
+fn suit_last(body: &str) -> String {
+    let mut result = String::new();
+    let mut suit = None;
+    for c in body.chars() {
+        if matches!(c, 'm' | 'p' | 's' | 'z') {
+            result.extend(suit);
+            suit = Some(c);
+        } else {
+            result.push(c);
+        }
+    }
+    result.extend(suit);
+    result
+}
+
+/// 空白区切りのまとまりを開始位置とともに返す
+fn split_groups(input: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start = None;
+    for (i, c) in input.char_indices() {
+        match (c.is_ascii_whitespace(), start) {
+            (true, Some(s)) => {
+                result.push((s, &input[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        result.push((s, &input[s..]));
+    }
+    result
+}
+
+/// 牌列を解釈し、牌と鳴いた牌の位置を返す
+///
+/// `base`は`text`の入力文字列全体における開始位置。
+pub(super) fn scan_tiles(
+    text: &str,
+    base: usize,
+    strict: bool,
+) -> Result<(Vec<Tile>, Option<usize>), ParseError> {
+    let mut tiles = Vec::new();
+    let mut called = None;
+    // スート待ちの数字（数字, 位置, 鳴いた牌か）
+    let mut pending: Vec<(u8, usize, bool)> = Vec::new();
+    // 直前の`r`（赤5の指定）の位置
+    let mut red_marker: Option<usize> = None;
+
+    for (i, c) in text.char_indices() {
+        let pos = base + i;
+        let red_marker_pos = red_marker.take();
+        if strict
+            && c != '5'
+            && let Some(marker_pos) = red_marker_pos
+        {
+            return Err(ParseError::new(marker_pos, "`r` must be followed by 5"));
+        }
+        match c {
+            'r' => red_marker = Some(pos),
+            '5' if red_marker_pos.is_some() => pending.push((0, pos, false)),
+            '0'..='9' => pending.push((c as u8 - b'0', pos, false)),
+            '\'' => {
+                let marked = called.is_some() || pending.iter().any(|p| p.2);
+                match pending.last_mut() {
+                    Some(last) if !marked => last.2 = true,
+                    _ if strict => {
+                        return Err(ParseError::new(pos, "misplaced called-tile marker"));
+                    }
+                    _ => {}
+                }
+            }
+            'm' | 'p' | 's' | 'z' => {
+                if strict && pending.is_empty() {
+                    return Err(ParseError::new(pos, format!("suit `{c}` has no numbers")));
+                }
+                for (number, number_pos, is_called) in pending.drain(..) {
+                    match make_tile(number, c) {
+                        Some(tile) => {
+                            if is_called {
+                                called = Some(tiles.len());
+                            }
+                            tiles.push(tile);
+                        }
+                        None if strict => {
+                            return Err(ParseError::new(
+                                number_pos,
+                                format!("unknown tile `{number}{c}`"),
+                            ));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            _ if strict => {
+                return Err(ParseError::new(pos, format!("unexpected character `{c}`")));
+            }
+            _ => {}
+        }
+    }
+
+    if strict && let Some(marker_pos) = red_marker {
+        return Err(ParseError::new(marker_pos, "`r` must be followed by 5"));
+    }
+    if strict && let Some(&(_, pos, _)) = pending.first() {
+        return Err(ParseError::new(pos, "numbers without suit"));
+    }
+    Ok((tiles, called))
+}
+
+/// 数字とスートから牌を生成する（`0`は赤5）
+fn make_tile(number: u8, suit: char) -> Option<Tile> {
+    let base = match suit {
+        'm' => Tile::M1,
+        'p' => Tile::P1,
+        's' => Tile::S1,
+        'z' if (1..=7).contains(&number) => Tile::Z1,
+        _ => return None,
+    };
+    match number {
+        0 => Some(Tile::new_red(base + 4)),
+        1..=9 => Some(Tile::new(base + number as TileType - 1)),
+        _ => None,
+    }
+}
+
+/// 厳密モードでまとまりの構成を検証する
+fn check_groups(groups: &[Group]) -> Result<(), ParseError> {
+    let mut counts: TileSummarize = [0; Tile::LEN];
+    let mut has_drawn = false;
+
+    for group in groups {
+        let len = group.tiles.len();
+        match group.kind {
+            GroupKind::Open => match len {
+                1 if has_drawn => {
+                    return Err(ParseError::new(group.offset, "duplicate drawn tile"));
+                }
+                1 => has_drawn = true,
+                3 | 4 => {}
+                n => {
+                    return Err(ParseError::new(
+                        group.offset,
+                        format!("meld has {n} tiles (expected 3 or 4)"),
+                    ));
+                }
+            },
+            GroupKind::ClosedKan if len != 4 => {
+                return Err(ParseError::new(
+                    group.offset,
+                    format!("closed kan has {len} tiles (expected 4)"),
+                ));
+            }
+            _ => {}
+        }
+
+        if group.called.is_some() && (group.kind != GroupKind::Open || len < 3) {
+            return Err(ParseError::new(
+                group.offset,
+                "called-tile marker is only allowed in melds",
+            ));
+        }
+
+        for tile in &group.tiles {
+            counts[tile.get() as usize] += 1;
+            if counts[tile.get() as usize] > 4 {
+                return Err(ParseError::new(
+                    group.offset,
+                    format!("more than 4 copies of {tile}"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_groups_test() {
+        let groups = tokenize("123m 5'55z a1111p n4z 1z", ParseMode::Strict).unwrap();
+        let kinds: Vec<_> = groups.iter().map(|g| g.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                GroupKind::Concealed,
+                GroupKind::Open,
+                GroupKind::ClosedKan,
+                GroupKind::Nuki,
+                GroupKind::Open,
+            ]
+        );
+        assert_eq!(groups[1].called, Some(0));
+        assert_eq!(groups[2].offset, 11);
+        assert_eq!(groups[3].tiles, vec![Tile::new(Tile::Z4)]);
+    }
+
+    #[test]
+    fn red_five_test() {
+        let groups = tokenize("05m0p0s", ParseMode::Strict).unwrap();
+        assert_eq!(
+            groups[0].tiles,
+            vec![
+                Tile::new_red(Tile::M5),
+                Tile::new(Tile::M5),
+                Tile::new_red(Tile::P5),
+                Tile::new_red(Tile::S5),
+            ]
+        );
+        assert!(tokenize("0z", ParseMode::Strict).is_err());
+
+        let groups = tokenize("4r56m r5p 34r5s", ParseMode::Strict).unwrap();
+        assert_eq!(groups[0].tiles[1], Tile::new_red(Tile::M5));
+        assert_eq!(groups[1].tiles, vec![Tile::new_red(Tile::P5)]);
+        assert_eq!(groups[2].tiles[2], Tile::new_red(Tile::S5));
+        assert!(tokenize("r4m", ParseMode::Strict).is_err());
+        assert!(tokenize("4mr", ParseMode::Strict).is_err());
+        assert!(tokenize("r5z", ParseMode::Strict).is_err());
+    }
+
+    #[test]
+    fn error_offset_test() {
+        let err = tokenize("123m 45x", ParseMode::Strict).unwrap_err();
+        assert_eq!(err.offset, 7);
+        let err = tokenize("123m 18z", ParseMode::Strict).unwrap_err();
+        assert_eq!(err.offset, 6);
+        let err = tokenize("123m 1z 2z", ParseMode::Strict).unwrap_err();
+        assert_eq!(err.offset, 8);
+        let err = tokenize("123m 1'23'm", ParseMode::Strict).unwrap_err();
+        assert_eq!(err.offset, 9);
+    }
+
+    #[test]
+    fn normalize_aliases_test() {
+        assert_eq!(normalize_aliases("m123p456s789z11"), "123m456p789s11z");
+        assert_eq!(
+            normalize_aliases("123m456p,  z5'55,am1111 ,z1"),
+            "123m456p 5'55z a1111m 1z"
+        );
+        assert_eq!(normalize_aliases("123m n4z"), "123m n4z");
+    }
+
+    #[test]
+    fn lenient_accepts_aliases_test() {
+        let canonical = tokenize("123m456p 5'55z 1z", ParseMode::Strict).unwrap();
+        let alias = tokenize("m123p456,z5'55,z1", ParseMode::Lenient).unwrap();
+        assert_eq!(alias.len(), canonical.len());
+        for (a, c) in alias.iter().zip(&canonical) {
+            assert_eq!(a.kind, c.kind);
+            assert_eq!(a.tiles, c.tiles);
+            assert_eq!(a.called, c.called);
+        }
+        assert!(tokenize("m123", ParseMode::Strict).is_err());
+    }
+
+    #[test]
+    fn lenient_skips_invalid_test() {
+        let groups = tokenize("1x2m89z'", ParseMode::Lenient).unwrap();
+        assert_eq!(
+            groups[0].tiles,
+            vec![Tile::new(Tile::M1), Tile::new(Tile::M2)]
+        );
+    }
+}
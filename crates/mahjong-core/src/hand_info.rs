@@ -1,4 +1,15 @@
 pub mod block;
+pub mod calls;
+pub mod defense;
+pub mod discard_eval;
+pub mod efficiency_trace;
+pub mod furiten;
 pub mod hand_analyzer;
+pub mod identical_sequences;
+pub mod iishanten;
 pub mod meld;
+pub mod pinfu;
+pub mod shanten_fast;
 pub mod status;
+pub mod ukeire;
+pub mod wait;
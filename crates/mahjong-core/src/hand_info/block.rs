@@ -1,5 +1,6 @@
-use anyhow::Result;
-use anyhow::anyhow;
+use crate::error::{MahjongError, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 use crate::tile::*;
@@ -26,7 +27,10 @@ fn is_proper_tile(tile: TileType) -> Result<()> {
     if matches!(tile, Tile::M1..=Tile::Z7) {
         Ok(())
     } else {
-        Err(anyhow!("invalid tile: {}", tile))
+        Err(MahjongError::InvalidBlock(format!(
+            "invalid tile: {}",
+            tile
+        )))
     }
 }
 
@@ -66,43 +70,29 @@ fn has_dragon(t: TileType, dragon: Dragon) -> Result<bool> {
     }
 }
 
+fn suit(t: TileType) -> Result<Suit> {
+    Suit::of(t).ok_or_else(|| MahjongError::InvalidBlock(format!("invalid tile: {}", t)))
+}
+
 fn is_character(t: TileType) -> Result<bool> {
-    is_proper_tile(t)?;
-    match t {
-        Tile::M1..=Tile::M9 => Ok(true),
-        _ => Ok(false),
-    }
+    Ok(suit(t)? == Suit::Man)
 }
 
 fn is_circle(t: TileType) -> Result<bool> {
-    is_proper_tile(t)?;
-    match t {
-        Tile::P1..=Tile::P9 => Ok(true),
-        _ => Ok(false),
-    }
+    Ok(suit(t)? == Suit::Pin)
 }
 
 fn is_bamboo(t: TileType) -> Result<bool> {
-    is_proper_tile(t)?;
-    match t {
-        Tile::S1..=Tile::S9 => Ok(true),
-        _ => Ok(false),
-    }
+    Ok(suit(t)? == Suit::Sou)
 }
 
 fn is_same_suit(t1: TileType, t2: TileType) -> Result<bool> {
-    is_proper_tile(t1)?;
-    is_proper_tile(t2)?;
-    match t1 {
-        Tile::M1..=Tile::M9 => Ok(matches!(t2, Tile::M1..=Tile::M9)),
-        Tile::P1..=Tile::P9 => Ok(matches!(t2, Tile::P1..=Tile::P9)),
-        Tile::S1..=Tile::S9 => Ok(matches!(t2, Tile::S1..=Tile::S9)),
-        _ => Ok(matches!(t2, Tile::Z1..=Tile::Z7)),
-    }
+    Ok(suit(t1)? == suit(t2)?)
 }
 
 /// 対子（同じ2枚）
 #[derive(Debug, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Same2 {
     tiles: [TileType; 2],
 }
@@ -111,7 +101,10 @@ impl Same2 {
         is_proper_tile(tile1)?;
         is_proper_tile(tile2)?;
         if tile1 != tile2 {
-            return Err(anyhow!("Not same tiles in `Same2`: {}, {} !", tile1, tile2));
+            return Err(MahjongError::InvalidBlock(format!(
+                "Not same tiles in `Same2`: {}, {} !",
+                tile1, tile2
+            )));
         }
         Ok(Same2 {
             tiles: [tile1, tile2],
@@ -162,6 +155,7 @@ impl Ord for Same2 {
 
 /// 刻子（同じ3枚）
 #[derive(Debug, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Same3 {
     tiles: [TileType; 3],
 }
@@ -171,12 +165,10 @@ impl Same3 {
         is_proper_tile(tile2)?;
         is_proper_tile(tile3)?;
         if tile1 != tile2 || tile1 != tile3 {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Not same tiles in `Same3`: {}, {}, {}!",
-                tile1,
-                tile2,
-                tile3
-            ));
+                tile1, tile2, tile3
+            )));
         }
         Ok(Same3 {
             tiles: [tile1, tile2, tile3],
@@ -227,6 +219,7 @@ impl Ord for Same3 {
 
 /// 塔子（連続した牌2枚）または嵌張（1枚飛ばしの牌2枚）
 #[derive(Debug, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Sequential2 {
     tiles: [TileType; 2],
 }
@@ -235,25 +228,22 @@ impl Sequential2 {
         is_proper_tile(tile1)?;
         is_proper_tile(tile2)?;
         if !(tile2 == tile1 + 1 || tile2 == tile1 + 2) {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Not sequential tiles in `Sequential2`: {}, {} !",
-                tile1,
-                tile2
-            ));
+                tile1, tile2
+            )));
         }
         if has_honour(tile1)? || has_honour(tile2)? {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Cannot assign Honor tiles to `Sequential2`: {}, {} !",
-                tile1,
-                tile2
-            ));
+                tile1, tile2
+            )));
         }
         if !is_same_suit(tile1, tile2)? {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Cannot assign different suits to `Sequential2`: {}, {} !",
-                tile1,
-                tile2
-            ));
+                tile1, tile2
+            )));
         }
         Ok(Sequential2 {
             tiles: [tile1, tile2],
@@ -307,6 +297,7 @@ impl Ord for Sequential2 {
 
 /// 順子（連続した3枚）
 #[derive(Debug, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Sequential3 {
     tiles: [TileType; 3],
 }
@@ -316,28 +307,22 @@ impl Sequential3 {
         is_proper_tile(tile2)?;
         is_proper_tile(tile3)?;
         if tile2 != tile1 + 1 || tile3 != tile2 + 1 {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Not sequential tiles in `Sequential3`:{}, {}, {} !",
-                tile1,
-                tile2,
-                tile3
-            ));
+                tile1, tile2, tile3
+            )));
         }
         if has_honour(tile1)? || has_honour(tile2)? || has_honour(tile3)? {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Cannot assign Honor tiles to `Sequential3`: {}, {}, {} !",
-                tile1,
-                tile2,
-                tile3
-            ));
+                tile1, tile2, tile3
+            )));
         }
         if !is_same_suit(tile1, tile2)? || !is_same_suit(tile2, tile3)? {
-            return Err(anyhow!(
+            return Err(MahjongError::InvalidBlock(format!(
                 "Cannot assign different suits to `Sequential3`: {}, {}, {} !",
-                tile1,
-                tile2,
-                tile3
-            ));
+                tile1, tile2, tile3
+            )));
         }
         Ok(Sequential3 {
             tiles: [tile1, tile2, tile3],
@@ -351,13 +336,15 @@ impl Sequential3 {
     ///
     /// 辺張（123の3待ち・789の7待ち）と嵌張は両面待ちではない
     pub fn is_two_sided_wait(&self, winning_tile: TileType) -> bool {
-        if winning_tile == self.tiles[0] && suit_rank(self.tiles[0]) != Some(7) {
-            return true;
-        }
-        if winning_tile == self.tiles[2] && suit_rank(self.tiles[2]) != Some(3) {
-            return true;
-        }
-        false
+        (winning_tile == self.tiles[0] || winning_tile == self.tiles[2])
+            && !self.is_edge_wait(winning_tile)
+    }
+
+    /// 指定した牌がこの順子の辺張待ち牌（123の3待ち・789の7待ち）かを返す
+    pub fn is_edge_wait(&self, winning_tile: TileType) -> bool {
+        let [low, _, high] = self.tiles.map(Tile::new);
+        (winning_tile == high.get() && high.number() == Some(3))
+            || (winning_tile == low.get() && low.number() == Some(7))
     }
 }
 impl BlockProperty for Sequential3 {
@@ -600,6 +587,15 @@ mod tests {
         assert!(seq3(Tile::S2, Tile::S3, Tile::S4).is_two_sided_wait(Tile::S4));
     }
 
+    // is_edge_wait: 辺張は数字で判定し、スートをまたがない
+    #[test]
+    fn test_edge_wait() {
+        assert!(seq3(Tile::P1, Tile::P2, Tile::P3).is_edge_wait(Tile::P3));
+        assert!(seq3(Tile::S7, Tile::S8, Tile::S9).is_edge_wait(Tile::S7));
+        assert!(!seq3(Tile::P1, Tile::P2, Tile::P3).is_edge_wait(Tile::P1));
+        assert!(!seq3(Tile::M2, Tile::M3, Tile::M4).is_edge_wait(Tile::M4));
+    }
+
     // --- Same2 BlockProperty ---
 
     #[test]
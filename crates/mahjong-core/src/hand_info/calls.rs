@@ -0,0 +1,312 @@
+use std::collections::HashSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::hand::Hand;
+use crate::hand_info::meld::{Meld, MeldFrom, MeldType};
+use crate::settings::Settings;
+use crate::tile::{Tile, TileType};
+
+/// 宣言できる鳴きの候補
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CallOption {
+    /// チー（手牌から使う2枚）
+    Chi { tiles: [Tile; 2] },
+    /// ポン（手牌から使う2枚）
+    Pon { tiles: [Tile; 2] },
+    /// 大明カン
+    Daiminkan,
+    /// 暗カン（自分の手番のみ）
+    Ankan { tile_type: TileType },
+    /// 加カン（自分の手番のみ）
+    Kakan { tile_type: TileType },
+}
+
+/// 手牌（ツモ牌を含む）の牌種ごとの枚数を返す
+fn count_tiles(hand: &Hand) -> [u8; Tile::LEN] {
+    let mut counts = [0u8; Tile::LEN];
+    for tile in hand.tiles().iter().chain(hand.drawn().iter()) {
+        counts[tile.get() as usize] += 1;
+    }
+    counts
+}
+
+/// ポン可能な手牌の組み合わせを返す
+///
+/// 通常は1通りだが、手牌に赤ドラと通常牌の両方がある場合は
+/// 「赤ドラを含む刻子」と「赤ドラを含まない刻子」の2通りを返す。
+pub fn pon_options(hand: &Hand, tile: Tile) -> Vec<[Tile; 2]> {
+    let tiles_of_type: Vec<Tile> = hand
+        .tiles()
+        .iter()
+        .filter(|t| t.get() == tile.get())
+        .cloned()
+        .collect();
+
+    // (赤ドラを含むペアか否か) で重複を除いた組み合わせを列挙する
+    let mut seen = HashSet::new();
+    let mut options = vec![];
+    for i in 0..tiles_of_type.len() {
+        for j in (i + 1)..tiles_of_type.len() {
+            let includes_red = tiles_of_type[i].is_red_dora() || tiles_of_type[j].is_red_dora();
+            if seen.insert(includes_red) {
+                options.push([tiles_of_type[i], tiles_of_type[j]]);
+            }
+        }
+    }
+    options
+}
+
+/// チー可能な組み合わせを返す
+///
+/// 各要素は手牌から使う2枚の牌を表す。
+/// 同じ牌種に赤ドラと通常牌の両方がある場合は別の組み合わせとして返す。字牌はチー不可。
+pub fn chi_options(hand: &Hand, tile: Tile) -> Vec<[Tile; 2]> {
+    let Some(num) = tile.number() else {
+        return vec![];
+    };
+    let tt = tile.get();
+    let tiles = hand.tiles();
+    let mut options = vec![];
+
+    // パターン (a, b) に対して、手牌から実際の牌インスタンスを列挙して選択肢を追加する。
+    let mut add_pattern = |a: TileType, b: TileType| {
+        let tiles_a: Vec<Tile> = tiles.iter().filter(|t| t.get() == a).cloned().collect();
+        let tiles_b: Vec<Tile> = tiles.iter().filter(|t| t.get() == b).cloned().collect();
+        // (赤ドラか否か, 赤ドラか否か) の組み合わせで重複を除く
+        let mut seen = HashSet::new();
+        for ta in &tiles_a {
+            for tb in &tiles_b {
+                if seen.insert((ta.is_red_dora(), tb.is_red_dora())) {
+                    options.push([*ta, *tb]);
+                }
+            }
+        }
+    };
+
+    // パターン1: [tt-2, tt-1] + tt （例: 鳴く牌が3m, 手牌に1m2mがある）
+    if num >= 3 {
+        add_pattern(tt - 2, tt - 1);
+    }
+
+    // パターン2: [tt-1, tt+1] + tt （例: 鳴く牌が5m, 手牌に4m6mがある）
+    if (2..=8).contains(&num) {
+        add_pattern(tt - 1, tt + 1);
+    }
+
+    // パターン3: [tt+1, tt+2] + tt （例: 鳴く牌が1m, 手牌に2m3mがある）
+    if num <= 7 {
+        add_pattern(tt + 1, tt + 2);
+    }
+
+    options
+}
+
+/// 大明カン可能か判定する
+pub fn can_daiminkan(hand: &Hand, tile: Tile) -> bool {
+    hand.tiles()
+        .iter()
+        .filter(|t| t.get() == tile.get())
+        .count()
+        >= 3
+}
+
+/// 暗カン可能な牌種一覧を返す
+pub fn ankan_options(hand: &Hand) -> Vec<TileType> {
+    count_tiles(hand)
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &count)| (count == 4).then_some(idx as TileType))
+        .collect()
+}
+
+/// 加カン可能な牌種一覧を返す
+pub fn kakan_options(hand: &Hand) -> Vec<TileType> {
+    let counts = count_tiles(hand);
+    hand.melds()
+        .iter()
+        .filter(|meld| meld.category == MeldType::Pon)
+        .filter_map(|meld| {
+            let tile_type = meld.tiles[0].get();
+            (counts[tile_type as usize] >= 1).then_some(tile_type)
+        })
+        .collect()
+}
+
+/// 鳴いた後に喰い替えの制限で捨てられる牌が残るかを返す
+fn has_legal_discard_after(
+    hand: &Hand,
+    category: MeldType,
+    called: Tile,
+    used: [Tile; 2],
+    settings: &Settings,
+) -> bool {
+    let mut tiles = vec![used[0], used[1], called];
+    tiles.sort();
+    let meld = Meld {
+        tiles,
+        category,
+        from: MeldFrom::Unknown,
+        called_tile: Some(called),
+    };
+    let forbidden = meld.forbidden_discards(settings.kuikae);
+
+    let mut remaining = hand.tiles().to_vec();
+    for tile in used {
+        if let Some(idx) = remaining.iter().position(|t| *t == tile) {
+            remaining.remove(idx);
+        }
+    }
+    remaining.iter().any(|t| !forbidden.contains(&t.get()))
+}
+
+/// 他家の打牌に対して宣言できる鳴きを列挙する
+///
+/// - `hand`: 自分の手牌（13枚、ツモ牌なし）
+/// - `discarded`: 他家の打牌
+/// - `from`: 打牌した他家の位置（チーは上家からのみ）
+/// - `settings`: ルール設定（三人麻雀ではチー不可。喰い替えで打牌できなくなる鳴きは除外する）
+///
+/// 立直中かどうかや、場全体のカンの回数による制限は呼び出し側で判定する。
+pub fn available_calls(
+    hand: &Hand,
+    discarded: Tile,
+    from: MeldFrom,
+    settings: &Settings,
+) -> Vec<CallOption> {
+    let mut calls = vec![];
+
+    if from == MeldFrom::Previous && !settings.three_player {
+        calls.extend(
+            chi_options(hand, discarded)
+                .into_iter()
+                .filter(|&tiles| {
+                    has_legal_discard_after(hand, MeldType::Chi, discarded, tiles, settings)
+                })
+                .map(|tiles| CallOption::Chi { tiles }),
+        );
+    }
+
+    calls.extend(
+        pon_options(hand, discarded)
+            .into_iter()
+            .filter(|&tiles| {
+                has_legal_discard_after(hand, MeldType::Pon, discarded, tiles, settings)
+            })
+            .map(|tiles| CallOption::Pon { tiles }),
+    );
+
+    if can_daiminkan(hand, discarded) {
+        calls.push(CallOption::Daiminkan);
+    }
+    calls
+}
+
+/// 自分の手番（ツモ後）に宣言できる暗カン・加カンを列挙する
+pub fn available_self_kans(hand: &Hand) -> Vec<CallOption> {
+    ankan_options(hand)
+        .into_iter()
+        .map(|tile_type| CallOption::Ankan { tile_type })
+        .chain(
+            kakan_options(hand)
+                .into_iter()
+                .map(|tile_type| CallOption::Kakan { tile_type }),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_calls_chi_pon_kan() {
+        let settings = Settings::new();
+        let hand = Hand::from("2344456m123p789s");
+
+        let calls = available_calls(&hand, Tile::new(Tile::M4), MeldFrom::Previous, &settings);
+        let m = |t| Tile::new(t);
+        assert_eq!(
+            calls,
+            vec![
+                CallOption::Chi {
+                    tiles: [m(Tile::M2), m(Tile::M3)]
+                },
+                CallOption::Chi {
+                    tiles: [m(Tile::M3), m(Tile::M5)]
+                },
+                CallOption::Chi {
+                    tiles: [m(Tile::M5), m(Tile::M6)]
+                },
+                CallOption::Pon {
+                    tiles: [m(Tile::M4), m(Tile::M4)]
+                },
+                CallOption::Daiminkan,
+            ]
+        );
+
+        // 上家以外からはチーできない
+        let calls = available_calls(&hand, Tile::new(Tile::M4), MeldFrom::Opposite, &settings);
+        assert_eq!(
+            calls,
+            vec![
+                CallOption::Pon {
+                    tiles: [m(Tile::M4), m(Tile::M4)]
+                },
+                CallOption::Daiminkan,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_available_calls_red_five_variants() {
+        let settings = Settings::new();
+        let hand = Hand::from("550m123p789s1234z");
+        let calls = available_calls(&hand, Tile::new(Tile::M5), MeldFrom::Following, &settings);
+        assert_eq!(calls.len(), 3);
+        assert!(calls.contains(&CallOption::Daiminkan));
+    }
+
+    #[test]
+    fn test_available_calls_excludes_calls_leaving_only_forbidden_discards() {
+        // 4m5m6m + 3m: 3m をチー（4m5m使用）すると残りは 6m のみでスジ喰い替えになる
+        let mut settings = Settings::new();
+        let mut hand = Hand::from("456m");
+        hand.add_meld(Meld {
+            tiles: vec![Tile::new(Tile::P1); 3],
+            category: MeldType::Pon,
+            from: MeldFrom::Opposite,
+            called_tile: Some(Tile::new(Tile::P1)),
+        });
+        let calls = available_calls(&hand, Tile::new(Tile::M3), MeldFrom::Previous, &settings);
+        assert!(calls.is_empty());
+
+        settings.kuikae = crate::settings::Kuikae::OnlySameTile;
+        let calls = available_calls(&hand, Tile::new(Tile::M3), MeldFrom::Previous, &settings);
+        assert_eq!(calls.len(), 1);
+    }
+
+    #[test]
+    fn test_available_self_kans() {
+        let mut hand = Hand::from("1111m23p 5z");
+        hand.add_meld(Meld {
+            tiles: vec![Tile::new(Tile::Z5); 3],
+            category: MeldType::Pon,
+            from: MeldFrom::Opposite,
+            called_tile: Some(Tile::new(Tile::Z5)),
+        });
+        assert_eq!(
+            available_self_kans(&hand),
+            vec![
+                CallOption::Ankan {
+                    tile_type: Tile::M1
+                },
+                CallOption::Kakan {
+                    tile_type: Tile::Z5
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! 守備（押し引き）のための牌の危険度
+//!
+//! 相手の捨て牌と場に見えている牌から、現物・筋・壁（ノーチャンス）・ワンチャンスを判定し、
+//! 牌種ごとの危険度を求める。受け入れ（[`calc_ukeire`](crate::hand_info::ukeire::calc_ukeire)）
+//! が攻撃側の指標であるのに対し、こちらは振り込みやすさの指標になる。
+//!
+//! 判定はいずれも両面待ちを基準にしており、単騎・シャンポン・カンチャン待ちは考慮しない。
+
+use crate::hand::Hand;
+use crate::hand_info::meld::Meld;
+use crate::tile::*;
+
+/// 危険度の最大値
+pub const MAX_DANGER: u8 = 100;
+
+/// 牌1種の安全度の判定結果と危険度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileDanger {
+    /// 牌の種類
+    pub tile: TileType,
+    /// 現物（相手の捨て牌にある）か
+    pub genbutsu: bool,
+    /// 筋（当たりうる両面待ちが全て筋で否定されている）か
+    pub suji: bool,
+    /// 片筋（4〜6の牌で、片側の筋だけが通っている）か
+    pub half_suji: bool,
+    /// ノーチャンス（当たりうる両面待ちの構成牌が全て見えている）か
+    pub no_chance: bool,
+    /// ワンチャンス（当たりうる両面待ちの構成牌が残り1枚以下）か
+    pub one_chance: bool,
+    /// 場に見えている枚数
+    pub visible: u8,
+    /// 危険度（0が安全、[`MAX_DANGER`]が最も危険）
+    pub danger: u8,
+}
+
+/// 場に見えている牌の枚数を数える
+///
+/// - `hand`: 自分の手牌（ツモ牌・副露を含む）
+/// - `discards`: 全員の捨て牌やドラ表示牌など、表向きに見えている牌
+/// - `melds`: 他家の副露
+///
+/// 鳴かれた牌を捨て牌と副露の両方に含めても、1種4枚を超えては数えない。
+pub fn visible_counts(hand: &Hand, discards: &[Tile], melds: &[Meld]) -> TileSummarize {
+    let mut counts: TileSummarize = [0; Tile::LEN];
+    let own_melds = hand.melds().iter().flat_map(|m| m.expanded_tiles());
+    let other_melds = melds.iter().flat_map(|m| m.expanded_tiles());
+    for tile in hand
+        .tiles()
+        .iter()
+        .copied()
+        .chain(hand.drawn())
+        .chain(discards.iter().copied())
+        .chain(own_melds)
+        .chain(other_melds)
+    {
+        let count = &mut counts[tile.get() as usize];
+        *count = (*count + 1).min(4);
+    }
+    counts
+}
+
+/// 現物（相手の捨て牌にある牌）か
+pub fn is_genbutsu(tile_type: TileType, opponent_discards: &[Tile]) -> bool {
+    opponent_discards.iter().any(|t| t.get() == tile_type)
+}
+
+/// 牌に当たりうる両面待ちの構成（2枚の数牌）を返す
+///
+/// 例: 4m には 23m（1-4m待ち）と 56m（4-7m待ち）、1m には 23m だけが当たる。
+/// 12m・89m は辺張なので含めない。
+fn ryanmen_shapes(tile_type: TileType) -> Vec<[TileType; 2]> {
+    let Some(num) = suit_rank(tile_type).map(|r| r - 1) else {
+        return Vec::new();
+    };
+    let mut shapes = Vec::new();
+    if num >= 3 {
+        shapes.push([tile_type - 2, tile_type - 1]);
+    }
+    if num <= 5 {
+        shapes.push([tile_type + 1, tile_type + 2]);
+    }
+    shapes
+}
+
+/// 両面待ちの相方（筋の牌）を返す（4m なら 1m と 7m）
+fn suji_partners(tile_type: TileType) -> Vec<TileType> {
+    let Some(num) = suit_rank(tile_type).map(|r| r - 1) else {
+        return Vec::new();
+    };
+    let mut partners = Vec::new();
+    if num >= 3 {
+        partners.push(tile_type - 3);
+    }
+    if num <= 5 {
+        partners.push(tile_type + 3);
+    }
+    partners
+}
+
+/// 筋（当たりうる両面待ちが全て筋で否定されている）か
+///
+/// 1・9は4・6、2・8は5、3・7は6・4が捨てられていれば筋になる。
+/// 4〜6は両側（例: 5mなら2mと8m）が捨てられている場合のみ筋とする。字牌は常に`false`。
+pub fn is_suji(tile_type: TileType, opponent_discards: &[Tile]) -> bool {
+    let partners = suji_partners(tile_type);
+    !partners.is_empty() && partners.iter().all(|&p| is_genbutsu(p, opponent_discards))
+}
+
+/// 片筋（4〜6の牌で、片側の筋だけが捨てられている）か
+pub fn is_half_suji(tile_type: TileType, opponent_discards: &[Tile]) -> bool {
+    let partners = suji_partners(tile_type);
+    partners.len() == 2
+        && partners
+            .iter()
+            .filter(|&&p| is_genbutsu(p, opponent_discards))
+            .count()
+            == 1
+}
+
+/// 当たりうる両面待ちの全てで、構成牌のいずれかが `min_visible` 枚以上見えているか
+fn is_blocked(tile_type: TileType, visible: &TileSummarize, min_visible: u32) -> bool {
+    let shapes = ryanmen_shapes(tile_type);
+    !shapes.is_empty()
+        && shapes
+            .iter()
+            .all(|shape| shape.iter().any(|&t| visible[t as usize] >= min_visible))
+}
+
+/// ノーチャンス（壁）か
+///
+/// 当たりうる両面待ちの構成牌が4枚とも見えていれば、その両面待ちは存在しない。
+/// 例: 8mが4枚見えていれば 78m の両面待ちがないため、9mはノーチャンス。
+pub fn is_no_chance(tile_type: TileType, visible: &TileSummarize) -> bool {
+    is_blocked(tile_type, visible, 4)
+}
+
+/// ワンチャンスか（当たりうる両面待ちの構成牌が残り1枚以下）
+///
+/// ノーチャンスの牌もワンチャンスに含む。
+pub fn is_one_chance(tile_type: TileType, visible: &TileSummarize) -> bool {
+    is_blocked(tile_type, visible, 3)
+}
+
+/// 牌1種の危険度を求める
+///
+/// - `opponent_discards`: 警戒する相手の捨て牌
+/// - `visible`: 場に見えている牌の枚数（[`visible_counts`]）
+pub fn tile_danger(
+    tile_type: TileType,
+    opponent_discards: &[Tile],
+    visible: &TileSummarize,
+) -> TileDanger {
+    let genbutsu = is_genbutsu(tile_type, opponent_discards);
+    let suji = is_suji(tile_type, opponent_discards);
+    let half_suji = is_half_suji(tile_type, opponent_discards);
+    let no_chance = is_no_chance(tile_type, visible);
+    let one_chance = is_one_chance(tile_type, visible);
+    let seen = visible[tile_type as usize].min(4) as u8;
+
+    let danger = if genbutsu {
+        0
+    } else if tile_type >= Tile::Z1 {
+        // 字牌は単騎・シャンポンでしか当たらないため、残り枚数で決まる
+        match seen {
+            4 => 0,
+            3 => 5,
+            2 => 40,
+            1 => 60,
+            _ => 70,
+        }
+    } else {
+        let base = match suit_rank(tile_type) {
+            Some(1 | 9) => 60,
+            Some(2 | 8) => 70,
+            Some(3 | 7) => 80,
+            _ => 85,
+        };
+        if suji {
+            25
+        } else if no_chance {
+            30
+        } else if one_chance {
+            base.min(50)
+        } else if half_suji {
+            base.min(65)
+        } else {
+            base
+        }
+    };
+
+    TileDanger {
+        tile: tile_type,
+        genbutsu,
+        suji,
+        half_suji,
+        no_chance,
+        one_chance,
+        visible: seen,
+        danger,
+    }
+}
+
+/// 全ての牌種の危険度を牌種の順に返す
+pub fn danger_map(opponent_discards: &[Tile], visible: &TileSummarize) -> Vec<TileDanger> {
+    (0..Tile::LEN as TileType)
+        .map(|t| tile_danger(t, opponent_discards, visible))
+        .collect()
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn tiles(types: &[TileType]) -> Vec<Tile> {
+        types.iter().map(|&t| Tile::new(t)).collect()
+    }
+
+    #[test]
+    fn visible_counts_include_hand_discards_and_melds() {
+        let hand = Hand::from("123m456p789s1122z 2z");
+        let discards = tiles(&[Tile::M1, Tile::Z2]);
+        let counts = visible_counts(&hand, &discards, &[]);
+        assert_eq!(counts[Tile::M1 as usize], 2);
+        // 手牌2枚 + ツモ牌1枚 + 捨て牌1枚
+        assert_eq!(counts[Tile::Z2 as usize], 4);
+        assert_eq!(counts[Tile::M9 as usize], 0);
+    }
+
+    #[test]
+    fn visible_counts_are_capped_at_four() {
+        let hand = Hand::from("123m456p789s1111z 2z");
+        let counts = visible_counts(&hand, &tiles(&[Tile::Z1]), &[]);
+        assert_eq!(counts[Tile::Z1 as usize], 4);
+    }
+
+    #[rstest]
+    #[case(Tile::M1, &[Tile::M4], true)]
+    #[case(Tile::M7, &[Tile::M4], true)]
+    #[case(Tile::M2, &[Tile::M5], true)]
+    #[case(Tile::M9, &[Tile::M6], true)]
+    #[case(Tile::M5, &[Tile::M2], false)]
+    #[case(Tile::M5, &[Tile::M2, Tile::M8], true)]
+    #[case(Tile::P1, &[Tile::M4], false)]
+    #[case(Tile::Z1, &[Tile::Z1], false)]
+    fn suji_cases(#[case] tile: TileType, #[case] discards: &[TileType], #[case] expected: bool) {
+        assert_eq!(is_suji(tile, &tiles(discards)), expected);
+    }
+
+    #[test]
+    fn half_suji_only_for_middle_tiles() {
+        assert!(is_half_suji(Tile::M5, &tiles(&[Tile::M2])));
+        assert!(!is_half_suji(Tile::M5, &tiles(&[Tile::M2, Tile::M8])));
+        assert!(!is_half_suji(Tile::M1, &tiles(&[Tile::M4])));
+    }
+
+    #[test]
+    fn no_chance_and_one_chance() {
+        let mut visible: TileSummarize = [0; Tile::LEN];
+        visible[Tile::S8 as usize] = 4;
+        // 78s の両面がないため 9s はノーチャンス、6s は 45s の両面が残る
+        assert!(is_no_chance(Tile::S9, &visible));
+        assert!(!is_no_chance(Tile::S6, &visible));
+        visible[Tile::S4 as usize] = 3;
+        assert!(is_one_chance(Tile::S6, &visible));
+        assert!(!is_no_chance(Tile::S6, &visible));
+        assert!(!is_one_chance(Tile::Z1, &visible));
+    }
+
+    #[test]
+    fn danger_orders_safety_levels() {
+        let discards = tiles(&[Tile::M4, Tile::P5]);
+        let mut visible: TileSummarize = [0; Tile::LEN];
+        visible[Tile::Z1 as usize] = 3;
+        let map = danger_map(&discards, &visible);
+        assert_eq!(map.len(), Tile::LEN);
+        assert_eq!(map[Tile::M4 as usize].danger, 0);
+        assert!(map[Tile::M4 as usize].genbutsu);
+        assert!(map[Tile::M1 as usize].suji);
+        assert!(map[Tile::Z1 as usize].danger < map[Tile::M1 as usize].danger);
+        assert!(map[Tile::M1 as usize].danger < map[Tile::P4 as usize].danger);
+        assert!(map[Tile::P2 as usize].danger < map[Tile::S5 as usize].danger);
+        assert!(map.iter().all(|d| d.danger <= MAX_DANGER));
+    }
+}
@@ -0,0 +1,122 @@
+//! 打牌候補の評価
+//!
+//! ツモ後の手牌について、各打牌の後の向聴数と受け入れ枚数を求め、良い順に並べる。
+//! 牌効率の練習ツールや CPU の打牌選択で用いる。
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MahjongError, Result};
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::ShantenNumber;
+use crate::hand_info::shanten_fast;
+use crate::hand_info::ukeire::calc_ukeire;
+use crate::settings::Settings;
+use crate::tile::Tile;
+
+/// 1つの打牌候補の評価
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiscardEval {
+    /// 捨てる牌
+    pub tile: Tile,
+    /// 打牌後の向聴数
+    pub shanten: ShantenNumber,
+    /// 打牌後の有効牌と、その残り枚数（牌種の昇順）
+    pub ukeire: Vec<(Tile, u8)>,
+    /// 有効牌の残り枚数の合計
+    pub ukeire_count: u32,
+}
+
+/// ツモ後の手牌の打牌候補を評価し、良い順に返す
+///
+/// 打牌後の向聴数が小さい順、同じ向聴数なら受け入れ枚数が多い順、さらに同じなら牌の順に並べる。
+/// 同じ牌（赤ドラは通常牌と区別する）の打牌は1つにまとめる。
+/// ツモ牌のない手牌はエラーとする。
+pub fn best_discards(hand: &Hand) -> Result<Vec<DiscardEval>> {
+    best_discards_with_settings(hand, &Settings::new())
+}
+
+/// ルール設定を考慮して打牌候補を評価し、良い順に返す
+///
+/// 三人麻雀の場合、存在しない牌（二萬〜八萬）は有効牌に数えない。
+/// それ以外は`best_discards`と同じ結果を返す。
+pub fn best_discards_with_settings(hand: &Hand, settings: &Settings) -> Result<Vec<DiscardEval>> {
+    let Some(drawn) = hand.drawn() else {
+        return Err(MahjongError::InvalidHand(
+            "discard evaluation requires a drawn tile".to_string(),
+        ));
+    };
+
+    let mut all: Vec<Tile> = hand.tiles().to_vec();
+    all.push(drawn);
+    let mut candidates = all.clone();
+    candidates.sort();
+    candidates.dedup();
+
+    let mut evals = Vec::with_capacity(candidates.len());
+    for tile in candidates {
+        let mut after = hand.clone();
+        let mut rest = all.clone();
+        if let Some(idx) = rest.iter().position(|t| *t == tile) {
+            rest.remove(idx);
+        }
+        *after.tiles_mut() = rest;
+        after.set_drawn(None);
+
+        let ukeire = calc_ukeire(&after, settings)?;
+        let ukeire_count = ukeire.iter().map(|&(_, n)| n as u32).sum();
+        evals.push(DiscardEval {
+            tile,
+            shanten: shanten_fast::shanten(&after),
+            ukeire,
+            ukeire_count,
+        });
+    }
+
+    evals.sort_by(|a, b| {
+        a.shanten
+            .cmp(&b.shanten)
+            .then(b.ukeire_count.cmp(&a.ukeire_count))
+            .then(a.tile.cmp(&b.tile))
+    });
+    Ok(evals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_discards_prefers_isolated_honour() {
+        // 1z を切れば 23m の両面待ち聴牌（1m・4m 各4枚）
+        let hand = Hand::from("23m456p789s55z111z 1z");
+        let evals = best_discards(&hand).unwrap();
+        let best = &evals[0];
+        assert_eq!(best.tile, Tile::new(Tile::Z1));
+        assert!(best.shanten.is_ready());
+        assert_eq!(best.ukeire_count, 8);
+        assert!(evals.windows(2).all(|w| w[0].shanten <= w[1].shanten));
+    }
+
+    #[test]
+    fn test_best_discards_sorted_by_shanten_then_ukeire() {
+        let hand = Hand::from("13m2399p456s1157z 9s");
+        let evals = best_discards(&hand).unwrap();
+        assert!(evals.windows(2).all(|w| {
+            w[0].shanten < w[1].shanten
+                || (w[0].shanten == w[1].shanten && w[0].ukeire_count >= w[1].ukeire_count)
+        }));
+        // 同じ牌の打牌は1つにまとめる
+        let mut tiles: Vec<Tile> = evals.iter().map(|e| e.tile).collect();
+        tiles.sort();
+        tiles.dedup();
+        assert_eq!(tiles.len(), evals.len());
+    }
+
+    #[test]
+    fn test_best_discards_requires_drawn_tile() {
+        let hand = Hand::from("23m456p789s111z55z");
+        assert!(best_discards(&hand).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! 牌効率の振り返り
+//!
+//! 配牌（ツモ牌のない手牌）と、毎巡のツモ牌・打牌の組を順に適用し、
+//! 巡目ごとの向聴数・受け入れ枚数と、打牌が牌効率の上で最善だったかを記録する。
+//! 牌効率の練習ツールで対局後の振り返りを表示するために用いる。
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MahjongError, Result};
+use crate::hand::Hand;
+use crate::hand_info::discard_eval::best_discards_with_settings;
+use crate::hand_info::hand_analyzer::ShantenNumber;
+use crate::hand_info::shanten_fast;
+use crate::hand_info::ukeire::calc_ukeire;
+use crate::settings::Settings;
+use crate::tile::Tile;
+
+/// 1巡分の記録
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TurnTrace {
+    /// ツモ牌
+    pub draw: Tile,
+    /// 打牌
+    pub discard: Tile,
+    /// 打牌後の向聴数
+    pub shanten: ShantenNumber,
+    /// 打牌後の有効牌と、その残り枚数（牌種の昇順）
+    pub ukeire: Vec<(Tile, u8)>,
+    /// 有効牌の残り枚数の合計
+    pub ukeire_count: u32,
+    /// 向聴数・受け入れ枚数とも最善の打牌と同じだったか
+    pub is_optimal: bool,
+    /// 最善の打牌（同じ評価の打牌が複数あれば全て、牌の順）
+    pub best: Vec<Tile>,
+    /// 最善の打牌をした場合の受け入れ枚数の合計
+    pub best_ukeire_count: u32,
+}
+
+/// 配牌から最後の打牌までの記録
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EfficiencyTrace {
+    /// 配牌の向聴数
+    pub initial_shanten: ShantenNumber,
+    /// 配牌の受け入れ枚数の合計
+    pub initial_ukeire_count: u32,
+    /// 巡目ごとの記録
+    pub turns: Vec<TurnTrace>,
+}
+
+impl EfficiencyTrace {
+    /// 最善の打牌をした巡の数
+    pub fn optimal_count(&self) -> usize {
+        self.turns.iter().filter(|t| t.is_optimal).count()
+    }
+
+    /// 最後の打牌後の向聴数（打牌がなければ配牌の向聴数）
+    pub fn final_shanten(&self) -> ShantenNumber {
+        self.turns
+            .last()
+            .map_or(self.initial_shanten, |t| t.shanten)
+    }
+}
+
+/// 配牌に毎巡のツモ・打牌を適用し、巡目ごとの向聴数・受け入れ枚数を記録する
+///
+/// - `hand`: ツモ牌のない配牌
+/// - `turns`: 毎巡の（ツモ牌, 打牌）。打牌はツモ後の手牌（ツモ切りを含む）にある牌でなければならない
+///
+/// ツモ牌のある配牌や、手牌にない牌の打牌はエラーとする。
+pub fn trace_efficiency(
+    hand: &Hand,
+    turns: &[(Tile, Tile)],
+    settings: &Settings,
+) -> Result<EfficiencyTrace> {
+    if hand.drawn().is_some() {
+        return Err(MahjongError::InvalidHand(
+            "efficiency trace starts from a hand without a drawn tile".to_string(),
+        ));
+    }
+
+    let initial_ukeire = calc_ukeire(hand, settings)?;
+    let mut trace = EfficiencyTrace {
+        initial_shanten: shanten_fast::shanten(hand),
+        initial_ukeire_count: initial_ukeire.iter().map(|&(_, n)| n as u32).sum(),
+        turns: Vec::with_capacity(turns.len()),
+    };
+
+    let mut current = hand.clone();
+    for (index, &(draw, discard)) in turns.iter().enumerate() {
+        current.set_drawn(Some(draw));
+        let evals = best_discards_with_settings(&current, settings)?;
+        let Some(chosen) = evals.iter().find(|e| e.tile == discard) else {
+            return Err(MahjongError::InvalidHand(format!(
+                "turn {}: {} is not in the hand",
+                index + 1,
+                discard
+            )));
+        };
+        let top = &evals[0];
+        let best: Vec<Tile> = evals
+            .iter()
+            .take_while(|e| e.shanten == top.shanten && e.ukeire_count == top.ukeire_count)
+            .map(|e| e.tile)
+            .collect();
+
+        trace.turns.push(TurnTrace {
+            draw,
+            discard,
+            shanten: chosen.shanten,
+            ukeire: chosen.ukeire.clone(),
+            ukeire_count: chosen.ukeire_count,
+            is_optimal: best.contains(&discard),
+            best,
+            best_ukeire_count: top.ukeire_count,
+        });
+
+        let mut rest = current.tiles().to_vec();
+        rest.push(draw);
+        if let Some(idx) = rest.iter().position(|t| *t == discard) {
+            rest.remove(idx);
+        }
+        rest.sort();
+        *current.tiles_mut() = rest;
+        current.set_drawn(None);
+    }
+    Ok(trace)
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(name: &str) -> Tile {
+        Tile::from(name).unwrap()
+    }
+
+    #[test]
+    fn optimal_and_suboptimal_turns() {
+        // 1z をツモって 7z を切れば 1m・4m 待ちの聴牌
+        let hand = Hand::from("23m456p789s1155z7z");
+        let trace = trace_efficiency(
+            &hand,
+            &[(tile("1z"), tile("7z")), (tile("9m"), tile("4p"))],
+            &Settings::new(),
+        )
+        .unwrap();
+        assert_eq!(trace.turns.len(), 2);
+
+        let first = &trace.turns[0];
+        assert!(first.is_optimal);
+        assert!(first.shanten.is_ready());
+        assert_eq!(first.best, vec![tile("7z")]);
+
+        // 聴牌を崩す 4p 切りは最善ではない
+        let second = &trace.turns[1];
+        assert!(!second.is_optimal);
+        assert!(second.best.contains(&tile("9m")));
+        assert!(second.shanten > first.shanten);
+        assert_eq!(trace.optimal_count(), 1);
+        assert_eq!(trace.final_shanten(), second.shanten);
+    }
+
+    #[test]
+    fn tsumogiri_is_allowed() {
+        let hand = Hand::from("23m456p789s11155z");
+        let trace = trace_efficiency(&hand, &[(tile("9p"), tile("9p"))], &Settings::new()).unwrap();
+        assert!(trace.turns[0].is_optimal);
+        assert_eq!(trace.initial_shanten, trace.final_shanten());
+        assert_eq!(trace.initial_ukeire_count, trace.turns[0].ukeire_count);
+    }
+
+    #[test]
+    fn errors_on_invalid_input() {
+        let settings = Settings::new();
+        assert!(trace_efficiency(&Hand::from("23m456p789s1115z 5z"), &[], &settings).is_err());
+        let hand = Hand::from("23m456p789s11155z");
+        assert!(trace_efficiency(&hand, &[(tile("9p"), tile("1s"))], &settings).is_err());
+    }
+
+    #[test]
+    fn empty_progression_reports_initial_hand() {
+        let hand = Hand::from("23m456p789s11155z");
+        let trace = trace_efficiency(&hand, &[], &Settings::new()).unwrap();
+        assert!(trace.turns.is_empty());
+        assert!(trace.final_shanten().is_ready());
+        assert_eq!(trace.initial_ukeire_count, 8);
+    }
+}
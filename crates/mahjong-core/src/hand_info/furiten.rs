@@ -0,0 +1,138 @@
+//! 振聴を避ける打牌の助言
+//!
+//! 自分の捨て牌・打牌後の待ち・有効牌の枚数を合わせて、打牌が振聴（フリテン）に
+//! なるかを判定し、振聴にならない代わりの打牌を提示する。
+
+use crate::error::Result;
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::count_acceptance;
+use crate::hand_info::wait::{hands_after_discard, winning_tiles};
+use crate::settings::Settings;
+use crate::tile::*;
+
+/// 打牌1つ分の助言
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscardAdvice {
+    /// 打牌
+    pub discard: Tile,
+    /// 打牌後の和了牌（聴牌していなければ空）
+    pub waits: Vec<TileType>,
+    /// 打牌後に振聴になるか（和了牌のいずれかが自分の捨て牌にある）
+    pub is_furiten: bool,
+    /// 打牌後の有効牌の残り枚数
+    pub acceptance: u32,
+}
+
+/// ツモ牌のある手牌の打牌ごとに、振聴になるかと有効牌の枚数を返す
+///
+/// `river` は自分の捨て牌。打牌した牌も捨て牌に加えて判定する。
+pub fn advise_discards(
+    hand: &Hand,
+    river: &[Tile],
+    settings: &Settings,
+) -> Result<Vec<DiscardAdvice>> {
+    let mut result = Vec::new();
+    for (discard, after) in hands_after_discard(hand)? {
+        let waits = winning_tiles(&after)?;
+        let is_furiten = waits
+            .iter()
+            .any(|&w| w == discard.get() || river.iter().any(|t| t.get() == w));
+        let acceptance = count_acceptance(&after, settings)?;
+        result.push(DiscardAdvice {
+            discard,
+            waits,
+            is_furiten,
+            acceptance,
+        });
+    }
+    Ok(result)
+}
+
+/// 打牌が振聴になる場合に、振聴にならない聴牌の代わりの打牌を返す
+///
+/// `discard` が振聴にならなければ None を返す。代わりの打牌は有効牌の多い順に並べる。
+pub fn furiten_alternatives(
+    hand: &Hand,
+    river: &[Tile],
+    discard: Tile,
+    settings: &Settings,
+) -> Result<Option<Vec<DiscardAdvice>>> {
+    let advice = advise_discards(hand, river, settings)?;
+    if !advice
+        .iter()
+        .any(|a| a.discard.get() == discard.get() && a.is_furiten)
+    {
+        return Ok(None);
+    }
+
+    let mut alternatives: Vec<DiscardAdvice> = advice
+        .into_iter()
+        .filter(|a| !a.is_furiten && !a.waits.is_empty())
+        .collect();
+    alternatives.sort_by_key(|a| std::cmp::Reverse(a.acceptance));
+    Ok(Some(alternatives))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn river(s: &str) -> Vec<Tile> {
+        Hand::from(s).tiles().to_vec()
+    }
+
+    /// 123m456p11z3456s9s + 7s: 打9s は 258s 待ちで、捨て牌に 5s があれば振聴
+    #[test]
+    fn advise_discards_test() {
+        let hand = Hand::from("123m456p11z3456s9s 7s");
+        let advice = advise_discards(&hand, &river("5s"), &Settings::new()).unwrap();
+
+        let nine = advice
+            .iter()
+            .find(|a| a.discard == Tile::new(Tile::S9))
+            .unwrap();
+        assert_eq!(nine.waits, vec![Tile::S2, Tile::S5, Tile::S8]);
+        assert!(nine.is_furiten);
+
+        let three = advice
+            .iter()
+            .find(|a| a.discard == Tile::new(Tile::S3))
+            .unwrap();
+        assert_eq!(three.waits, vec![Tile::S8]);
+        assert!(!three.is_furiten);
+    }
+
+    #[test]
+    fn furiten_alternatives_test() {
+        let hand = Hand::from("123m456p11z3456s9s 7s");
+        let settings = Settings::new();
+
+        let alternatives =
+            furiten_alternatives(&hand, &river("5s"), Tile::new(Tile::S9), &settings)
+                .unwrap()
+                .unwrap();
+        assert!(!alternatives.is_empty());
+        assert!(alternatives.iter().all(|a| !a.is_furiten));
+        assert!(
+            alternatives
+                .windows(2)
+                .all(|w| w[0].acceptance >= w[1].acceptance)
+        );
+
+        // 打牌した牌自体が和了牌なら、捨て牌が空でも振聴になる
+        let hand = Hand::from("123m456p789s11z45s 6s");
+        assert!(
+            furiten_alternatives(&hand, &[], Tile::new(Tile::S6), &settings)
+                .unwrap()
+                .is_some()
+        );
+
+        let hand = Hand::from("123m456p11z3456s9s 7s");
+        assert!(
+            furiten_alternatives(&hand, &[], Tile::new(Tile::S9), &settings)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,15 +1,26 @@
-use anyhow::Result;
+use crate::error::{MahjongError, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use std::cmp::*;
 use std::fmt;
 
 use crate::hand::Hand;
 use crate::hand_info::block::*;
+use crate::hand_info::meld::MeldType;
+use crate::hand_info::shanten_fast;
+use crate::hand_info::status::Status;
+use crate::hand_info::ukeire::calc_ukeire;
+use crate::hand_info::wait::{WaitShape, winning_tiles};
+use crate::scoring::fu::{FuResult, calculate_fu};
+use crate::settings::Settings;
 use crate::tile::*;
+use crate::tile_counts::TileCounts;
 use crate::winning_hand::name::Form;
 
 /// 向聴数
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ShantenNumber(i32);
 
 impl ShantenNumber {
@@ -31,6 +42,11 @@ impl ShantenNumber {
         self.0 <= 0
     }
 
+    /// 生の`i32`値から向聴数を作る
+    pub fn new(value: i32) -> ShantenNumber {
+        ShantenNumber(value)
+    }
+
     /// 生の`i32`値を返す
     pub fn as_i32(&self) -> i32 {
         self.0
@@ -55,11 +71,89 @@ impl fmt::Display for ShantenNumber {
     }
 }
 
+/// 3つの和了形それぞれの向聴数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShantenReport {
+    /// 通常形（4面子1雀頭）の向聴数
+    pub normal: ShantenNumber,
+    /// 七対子の向聴数（副露時は該当なし）
+    pub seven_pairs: ShantenNumber,
+    /// 国士無双の向聴数（副露時は該当なし）
+    pub thirteen_orphans: ShantenNumber,
+    /// `HandAnalyzer::new`が採用する和了形
+    pub best_form: Form,
+}
+
+impl ShantenReport {
+    /// 採用した和了形の向聴数を返す
+    pub fn best(&self) -> ShantenNumber {
+        match self.best_form {
+            Form::Normal => self.normal,
+            Form::SevenPairs => self.seven_pairs,
+            Form::ThirteenOrphans => self.thirteen_orphans,
+        }
+    }
+}
+
+/// 七対子で和了した手の対子の構成
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SevenPairsInfo {
+    /// 7つの対子の牌種（昇順）
+    pub pairs: [TileType; 7],
+}
+
+/// 国士無双の対象となる么九牌13種
+pub const THIRTEEN_ORPHAN_TILES: [TileType; 13] = [
+    Tile::M1,
+    Tile::M9,
+    Tile::P1,
+    Tile::P9,
+    Tile::S1,
+    Tile::S9,
+    Tile::Z1,
+    Tile::Z2,
+    Tile::Z3,
+    Tile::Z4,
+    Tile::Z5,
+    Tile::Z6,
+    Tile::Z7,
+];
+
+/// 国士無双形として見た手牌の構成
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThirteenOrphansInfo {
+    /// 手牌（ツモ牌を含む）にある么九牌の種類
+    pub present: Vec<TileType>,
+    /// 手牌（ツモ牌を含む）にない么九牌の種類
+    pub missing: Vec<TileType>,
+    /// 対子になっている么九牌（複数ある場合は最初の1種）
+    pub pair: Option<TileType>,
+    /// 十三面待ちで和了しているか
+    pub is_thirteen_wait: bool,
+}
+
+/// 和了牌と和了方法を指定して解析した和了形（`HandAnalyzer::new_with_winning_tile`の結果）
+#[derive(Debug)]
+pub struct WinningAnalysis {
+    /// 和了牌をツモ牌として加えた手牌
+    pub hand: Hand,
+    /// 採用した和了形の面子構成
+    pub analyzer: HandAnalyzer,
+    /// 採用した面子構成で和了牌が完成させた待ちの形
+    pub wait_shapes: Vec<WaitShape>,
+    /// 採用した面子構成の符
+    pub fu: FuResult,
+}
+
 /// 与えられた手牌について、向聴数が最小になる時の面子・対子等の組み合わせを計算して格納する
 ///
 /// 通常形・七対子の場合は面子・対子等の情報もVecに格納される。
 /// 国士無双の場合は向聴数のみが格納される。
 #[derive(Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HandAnalyzer {
     /// 向聴数：あと牌を何枚交換すれば聴牌できるかの最小数。
     pub shanten: ShantenNumber,
@@ -75,6 +169,9 @@ pub struct HandAnalyzer {
     pub sequential2: Vec<Sequential2>,
     /// 面子や対子・塔子などを構成しない、単独の牌が入るVec
     pub single: Vec<TileType>,
+    /// 国士無双形の構成（国士無双形として解析した場合のみ）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub thirteen_orphans: Option<ThirteenOrphansInfo>,
 }
 impl Ord for HandAnalyzer {
     fn cmp(&self, other: &Self) -> Ordering {
@@ -94,7 +191,61 @@ impl PartialEq for HandAnalyzer {
     }
 }
 
+/// 面子構成を出力する
+///
+/// `<和了形> shanten=<向聴数>`に続けて、面子（刻子・順子）・対子・塔子・浮き牌を
+/// それぞれ`[]`内に空白区切りで出力する。
+/// 例: `Normal shanten=0 mentsu=[123m 456p 111z] pairs=[55z] partial=[23s] single=[9m]`
+impl fmt::Display for HandAnalyzer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn blocks<const N: usize>(blocks: impl Iterator<Item = [TileType; N]>) -> String {
+            blocks
+                .map(|b| Hand::make_short_str(b.iter().map(|&t| Tile::new(t)).collect()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        write!(f, "{:?} shanten={}", self.form, self.shanten)?;
+        write!(
+            f,
+            " mentsu=[{}]",
+            [
+                blocks(self.sequential3.iter().map(|b| b.get())),
+                blocks(self.same3.iter().map(|b| b.get())),
+            ]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+        )?;
+        write!(f, " pairs=[{}]", blocks(self.same2.iter().map(|b| b.get())))?;
+        write!(
+            f,
+            " partial=[{}]",
+            blocks(self.sequential2.iter().map(|b| b.get()))
+        )?;
+        write!(f, " single=[{}]", blocks(self.single.iter().map(|&t| [t])))
+    }
+}
+
 impl HandAnalyzer {
+    /// 面子・雀頭（刻子・順子・対子）に含まれるスートを順に返す（重複なし）
+    ///
+    /// 混一色・清一色・九蓮宝燈の判定に用いる。塔子・浮き牌は含めない。
+    pub fn block_suits(&self) -> Vec<Suit> {
+        let mut suits: Vec<Suit> = self
+            .same3
+            .iter()
+            .map(|b| b.get()[0])
+            .chain(self.sequential3.iter().map(|b| b.get()[0]))
+            .chain(self.same2.iter().map(|b| b.get()[0]))
+            .map(|t| Tile::new(t).suit())
+            .collect();
+        suits.sort();
+        suits.dedup();
+        suits
+    }
+
     fn unavailable(form: Form) -> HandAnalyzer {
         HandAnalyzer {
             shanten: ShantenNumber::UNAVAILABLE,
@@ -104,6 +255,7 @@ impl HandAnalyzer {
             same2: Vec::new(),
             sequential2: Vec::new(),
             single: Vec::new(),
+            thirteen_orphans: None,
         }
     }
 
@@ -138,6 +290,265 @@ impl HandAnalyzer {
         }
     }
 
+    /// 通常形・七対子・国士無双の向聴数と、採用する和了形をまとめて返す
+    ///
+    /// 牌の集計を1回だけ行い、ブロック分解はしない。3つの向聴数を並べて表示する
+    /// UI などで`new_by_form`を3回呼ぶ代わりに用いる。
+    /// 和了形の選び方は`HandAnalyzer::new`と同じ（通常形で和了していれば通常形を優先）。
+    pub fn full_report(hand: &Hand) -> ShantenReport {
+        let is_closed = hand.has_no_melds();
+        let t = hand.summarize_tiles();
+        let seven_pairs = if is_closed {
+            ShantenNumber(calc_seven_pairs_shanten(&t).0)
+        } else {
+            ShantenNumber::UNAVAILABLE
+        };
+        let thirteen_orphans = if is_closed {
+            ShantenNumber(calc_thirteen_orphans_shanten(&t))
+        } else {
+            ShantenNumber::UNAVAILABLE
+        };
+        let normal = calc_normal_shanten::<CountOnly>(hand)
+            .map(|(s, _)| ShantenNumber(s))
+            .unwrap_or(ShantenNumber::UNAVAILABLE);
+
+        // `new`の min(min(七対子, 国士無双), 通常形) と同じく、同点なら先の形を採用する
+        let best_form = if normal.has_won() {
+            Form::Normal
+        } else {
+            let (form, shanten) = if thirteen_orphans < seven_pairs {
+                (Form::ThirteenOrphans, thirteen_orphans)
+            } else {
+                (Form::SevenPairs, seven_pairs)
+            };
+            if normal < shanten { Form::Normal } else { form }
+        };
+
+        ShantenReport {
+            normal,
+            seven_pairs,
+            thirteen_orphans,
+            best_form,
+        }
+    }
+
+    /// 向聴数が最小になる分解を全て返す
+    ///
+    /// 通常形で複数の面子の取り方がある手（例: `111222333m`を刻子3つと見るか順子3つと見るか）や、
+    /// 通常形と七対子の両方で同じ向聴数になる手について、全ての解釈を列挙する。
+    /// 高点法で最も点数の高い解釈を選ぶために用いる。
+    /// 同じブロック構成の分解は1つにまとめる。
+    pub fn all_decompositions(hand: &Hand) -> Result<Vec<HandAnalyzer>> {
+        let best = HandAnalyzer::full_report(hand);
+        let min = [best.normal, best.seven_pairs, best.thirteen_orphans]
+            .into_iter()
+            .min()
+            .unwrap_or(ShantenNumber::UNAVAILABLE);
+
+        let mut result: Vec<HandAnalyzer> = Vec::new();
+        if best.normal == min {
+            let (shanten_raw, tracking) = calc_normal_shanten::<AllTracking>(hand)?;
+            for found in tracking.found {
+                let analyzer = HandAnalyzer {
+                    shanten: ShantenNumber(shanten_raw),
+                    form: Form::Normal,
+                    same3: found.same3,
+                    sequential3: found.sequential3,
+                    same2: found.same2,
+                    sequential2: found.sequential2,
+                    single: found.single,
+                    thirteen_orphans: None,
+                };
+                if !result.iter().any(|r| r.same_blocks(&analyzer)) {
+                    result.push(analyzer);
+                }
+            }
+        }
+        if best.seven_pairs == min {
+            result.push(HandAnalyzer::analyze_seven_pairs(hand)?);
+        }
+        if best.thirteen_orphans == min {
+            result.push(HandAnalyzer::analyze_thirteen_orphans(hand)?);
+        }
+        Ok(result)
+    }
+
+    /// ブロック構成（順序は問わない）が同じかを返す
+    fn same_blocks(&self, other: &HandAnalyzer) -> bool {
+        fn sorted<T: Ord>(v: impl Iterator<Item = T>) -> Vec<T> {
+            let mut v: Vec<T> = v.collect();
+            v.sort();
+            v
+        }
+        self.form == other.form
+            && sorted(self.same3.iter().map(|b| b.get()))
+                == sorted(other.same3.iter().map(|b| b.get()))
+            && sorted(self.sequential3.iter().map(|b| b.get()))
+                == sorted(other.sequential3.iter().map(|b| b.get()))
+            && sorted(self.same2.iter().map(|b| b.get()))
+                == sorted(other.same2.iter().map(|b| b.get()))
+            && sorted(self.sequential2.iter().map(|b| b.get()))
+                == sorted(other.sequential2.iter().map(|b| b.get()))
+            && sorted(self.single.iter().copied()) == sorted(other.single.iter().copied())
+    }
+
+    /// 向聴数のみを返す
+    ///
+    /// ブロック分解やVecへの格納を一切行わず、分解表（`shanten_fast`）を用いて計算する。
+    /// モンテカルロ法による打牌評価など、大量の手牌を評価する用途に用いる。
+    pub fn shanten_only(hand: &Hand) -> i32 {
+        shanten_fast::shanten(hand).as_i32()
+    }
+
+    /// 門前の手牌の牌種ごとの枚数から、向聴数をまとめて計算する
+    ///
+    /// 各要素は副露を含まない13枚または14枚の枚数とする。結果は入力と同じ順で返す。
+    pub fn shanten_many(hands: &[TileCounts]) -> Vec<i32> {
+        hands.iter().map(shanten_fast::closed_shanten).collect()
+    }
+
+    /// 聴牌している手牌の和了牌を返す（牌種の昇順）
+    ///
+    /// 通常形・七対子・国士無双のいずれかで和了できる牌を全て含む。
+    /// ツモ牌のある手牌や聴牌していない手牌では空を返す。
+    pub fn waits(hand: &Hand) -> Vec<Tile> {
+        if hand.drawn().is_some() || HandAnalyzer::full_report(hand).best() != ShantenNumber(0) {
+            return Vec::new();
+        }
+        winning_tiles(hand)
+            .unwrap_or_default()
+            .into_iter()
+            .map(Tile::new)
+            .collect()
+    }
+
+    /// ルール設定を考慮して向聴数を計算する
+    ///
+    /// 三人麻雀の場合、存在しない牌（二萬〜八萬）を含む手牌はエラーを返す。
+    /// それ以外は`HandAnalyzer::new`と同じ結果を返す。
+    pub fn new_with_settings(hand: &Hand, settings: &Settings) -> Result<HandAnalyzer> {
+        validate_tiles_for_mode(hand, settings)?;
+        HandAnalyzer::new(hand)
+    }
+
+    /// 和了牌と和了方法を指定して、和了形・待ち・符を同じ解釈のもとでまとめて求める
+    ///
+    /// `hand`はツモ牌のない聴牌形、`status.is_self_drawn`が和了方法（ツモかロンか）となる。
+    /// 和了形として解釈できる全ての面子構成のうち、通常形を七対子より優先し（二盃口は七対子より高い）、
+    /// その中で符が最も高いものを採用する。待ちの形と符は採用した面子構成から求めるため、
+    /// 通常形を採用したのに七対子の単騎待ちとして扱う、といった食い違いは起きない。
+    /// 役を含めた高点法の判定は`calculate_score`で行う。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mahjong_core::hand::*;
+    /// use mahjong_core::hand_info::hand_analyzer::*;
+    /// use mahjong_core::hand_info::status::Status;
+    /// use mahjong_core::hand_info::wait::WaitShape;
+    /// use mahjong_core::settings::Settings;
+    /// use mahjong_core::tile::Tile;
+    /// use mahjong_core::winning_hand::name::*;
+    ///
+    /// // 二盃口にも七対子にも取れる手の7s単騎ロン
+    /// let hand = Hand::from("112233m445566p7s");
+    /// let status = Status::builder().ron().build().unwrap();
+    /// let win = HandAnalyzer::new_with_winning_tile(&hand, Tile::new(Tile::S7), &status, &Settings::new())
+    ///     .unwrap();
+    /// assert_eq!(win.analyzer.form, Form::Normal);
+    /// assert_eq!(win.wait_shapes, vec![WaitShape::Tanki]);
+    /// assert_eq!(win.fu.total, 40);
+    /// ```
+    pub fn new_with_winning_tile(
+        hand: &Hand,
+        winning_tile: Tile,
+        status: &Status,
+        settings: &Settings,
+    ) -> Result<WinningAnalysis> {
+        if hand.drawn().is_some() {
+            return Err(MahjongError::InvalidHand(
+                "the winning tile must be given separately from a drawn tile".to_string(),
+            ));
+        }
+        let mut completed = hand.clone();
+        completed.set_drawn(Some(winning_tile));
+
+        let mut best: Option<(HandAnalyzer, FuResult)> = None;
+        for candidate in HandAnalyzer::all_decompositions(&completed)? {
+            if !candidate.shanten.has_won() {
+                continue;
+            }
+            let fu = calculate_fu(&candidate, &completed, status, settings)?;
+            let is_better = best.as_ref().is_none_or(|(current, current_fu)| {
+                let key = |a: &HandAnalyzer, f: &FuResult| (a.form == Form::Normal, f.total);
+                key(&candidate, &fu) > key(current, current_fu)
+            });
+            if is_better {
+                best = Some((candidate, fu));
+            }
+        }
+        let Some((analyzer, fu)) = best else {
+            return Err(MahjongError::InvalidHand(format!(
+                "{} does not complete the hand",
+                winning_tile
+            )));
+        };
+        let wait_shapes = analyzer.wait_shapes_for(&completed, winning_tile.get());
+        Ok(WinningAnalysis {
+            hand: completed,
+            analyzer,
+            wait_shapes,
+            fu,
+        })
+    }
+
+    /// この面子構成で和了牌が完成させうる待ちの形を返す（昇順）
+    fn wait_shapes_for(&self, hand: &Hand, winning_tile: TileType) -> Vec<WaitShape> {
+        let mut shapes = Vec::new();
+        match self.form {
+            Form::SevenPairs => shapes.push(WaitShape::SevenPairs),
+            Form::ThirteenOrphans => shapes.push(
+                if self
+                    .thirteen_orphans
+                    .as_ref()
+                    .is_some_and(|info| info.is_thirteen_wait)
+                {
+                    WaitShape::ThirteenOrphansThirteenSided
+                } else {
+                    WaitShape::ThirteenOrphans
+                },
+            ),
+            Form::Normal => {
+                if self.same2.iter().any(|pair| pair.get()[0] == winning_tile) {
+                    shapes.push(WaitShape::Tanki);
+                }
+                for seq in self
+                    .sequential3
+                    .iter()
+                    .filter(|s| s.get().contains(&winning_tile))
+                {
+                    shapes.push(if seq.is_two_sided_wait(winning_tile) {
+                        WaitShape::Ryanmen
+                    } else if seq.get()[1] == winning_tile {
+                        WaitShape::Kanchan
+                    } else {
+                        WaitShape::Penchan
+                    });
+                }
+                if self
+                    .concealed_same3(hand)
+                    .iter()
+                    .any(|triplet| triplet.get()[0] == winning_tile)
+                {
+                    shapes.push(WaitShape::Shanpon);
+                }
+            }
+        }
+        shapes.sort();
+        shapes.dedup();
+        shapes
+    }
+
     /// 和了形を指定して向聴数を計算する
     /// # Examples
     ///
@@ -169,12 +580,97 @@ impl HandAnalyzer {
         })
     }
 
+    /// 七対子で和了している場合に、7つの対子の牌種を返す
+    ///
+    /// 七対子形でない場合や和了していない場合は`None`を返す。
+    pub fn seven_pairs_info(&self) -> Option<SevenPairsInfo> {
+        if self.form != Form::SevenPairs || !self.shanten.has_won() {
+            return None;
+        }
+        let mut pairs: Vec<TileType> = self.same2.iter().map(|pair| pair.get()[0]).collect();
+        pairs.sort_unstable();
+        Some(SevenPairsInfo {
+            pairs: pairs.try_into().ok()?,
+        })
+    }
+
+    /// 刻子のうち、副露（ポン・明槓・加槓）でないものを返す
+    ///
+    /// 解析では副露も面子として`same3`に含まれるため、手牌の副露と牌種で照合して取り除く。
+    /// 暗槓は暗刻として残す。ロン和了で完成した刻子の扱いは呼び出し側で判定する。
+    pub fn concealed_same3(&self, hand: &Hand) -> Vec<Same3> {
+        let mut concealed = self.same3.clone();
+        for meld in hand.melds() {
+            if meld.category == MeldType::Chi || meld.is_concealed() {
+                continue;
+            }
+            let tile_type = meld.tiles[0].get();
+            if let Some(idx) = concealed.iter().position(|s| s.get()[0] == tile_type) {
+                concealed.remove(idx);
+            }
+        }
+        concealed
+    }
+
+    /// 順子のうち、チーで鳴いたものでないものを返す
+    ///
+    /// 解析ではチーも面子として`sequential3`に含まれるため、手牌の副露と最小の牌種で照合して取り除く。
+    pub fn concealed_sequential3(&self, hand: &Hand) -> Vec<Sequential3> {
+        let mut concealed = self.sequential3.clone();
+        for meld in hand.melds() {
+            if meld.category != MeldType::Chi {
+                continue;
+            }
+            let Some(first) = meld.tiles.iter().map(|t| t.get()).min() else {
+                continue;
+            };
+            if let Some(idx) = concealed.iter().position(|s| s.get()[0] == first) {
+                concealed.remove(idx);
+            }
+        }
+        concealed
+    }
+
+    /// 和了時点での暗刻（暗槓を含む）を返す
+    ///
+    /// `concealed_same3`に加え、ロン和了で和了牌が刻子を完成させた場合はその刻子を明刻として除く。
+    /// 和了牌を雀頭（単騎待ち）や手牌の順子の一部として解釈できる場合は、刻子は暗刻のままとする（高点法）。
+    /// チーで鳴いた順子は和了牌で完成させられないため、この解釈には含めない。
+    pub fn concealed_triplets_on_win(&self, hand: &Hand, is_self_drawn: bool) -> Vec<Same3> {
+        let mut concealed = self.concealed_same3(hand);
+        if is_self_drawn {
+            return concealed;
+        }
+        let Some(winning_tile) = hand.drawn() else {
+            return concealed;
+        };
+        let wt = winning_tile.get();
+        let fits_elsewhere = self.same2.iter().any(|pair| pair.get()[0] == wt)
+            || self
+                .concealed_sequential3(hand)
+                .iter()
+                .any(|seq| seq.get().contains(&wt));
+        if !fits_elsewhere && let Some(idx) = concealed.iter().position(|s| s.get()[0] == wt) {
+            concealed.remove(idx);
+        }
+        concealed
+    }
+
+    /// 国士無双の十三面待ちで和了しているかを返す
+    pub fn is_thirteen_wait(&self) -> bool {
+        self.form == Form::ThirteenOrphans
+            && self
+                .thirteen_orphans
+                .as_ref()
+                .is_some_and(|info| info.is_thirteen_wait)
+    }
+
     /// 七対子への向聴数を計算・ブロック分解する
     ///
     /// Vecへの詰め込みは`same2`（対子）以外は`single`（単独）に詰め込まれる。
     /// 七対子はVecを使用する役として断么九・混老頭・混一色・清一色と複合しうる
     fn analyze_seven_pairs(hand: &Hand) -> Result<HandAnalyzer> {
-        if !hand.melds().is_empty() {
+        if !hand.has_no_melds() {
             return Ok(HandAnalyzer::unavailable(Form::SevenPairs));
         }
 
@@ -202,19 +698,35 @@ impl HandAnalyzer {
             same2,
             sequential2: Vec::new(),
             single,
+            thirteen_orphans: None,
         })
     }
 
     /// 国士無双への向聴数を計算する
     ///
-    /// ブロック分解・Vecへの詰め込みはしない（詰め込んでも意味がない）
+    /// 面子・対子のVecへの詰め込みはせず、么九牌の有無を`thirteen_orphans`に格納する
     fn analyze_thirteen_orphans(hand: &Hand) -> Result<HandAnalyzer> {
-        if !hand.melds().is_empty() {
+        if !hand.has_no_melds() {
             return Ok(HandAnalyzer::unavailable(Form::ThirteenOrphans));
         }
 
         let t = hand.summarize_tiles();
         let shanten_raw = calc_thirteen_orphans_shanten(&t);
+
+        let (present, missing): (Vec<TileType>, Vec<TileType>) = THIRTEEN_ORPHAN_TILES
+            .iter()
+            .partition(|&&tile| t[tile as usize] > 0);
+        let pair = THIRTEEN_ORPHAN_TILES
+            .iter()
+            .copied()
+            .find(|&tile| t[tile as usize] >= 2);
+        // 十三面待ち: 和了牌を除く13枚が么九牌13種を1枚ずつ含む
+        let is_thirteen_wait = shanten_raw == -1
+            && hand.drawn().is_some()
+            && THIRTEEN_ORPHAN_TILES
+                .iter()
+                .all(|&tile| hand.tiles().iter().any(|t| t.get() == tile));
+
         Ok(HandAnalyzer {
             shanten: ShantenNumber(shanten_raw),
             form: Form::ThirteenOrphans,
@@ -223,6 +735,12 @@ impl HandAnalyzer {
             same2: Vec::new(),
             sequential2: Vec::new(),
             single: Vec::new(),
+            thirteen_orphans: Some(ThirteenOrphansInfo {
+                present,
+                missing,
+                pair,
+                is_thirteen_wait,
+            }),
         })
     }
 
@@ -244,6 +762,7 @@ impl HandAnalyzer {
             same2,
             sequential2,
             single,
+            thirteen_orphans: None,
         })
     }
 }
@@ -255,7 +774,7 @@ impl HandAnalyzer {
 /// CPU打牌評価など大量に呼び出す箇所で使用する。
 pub fn calc_shanten_number(hand: &Hand) -> ShantenNumber {
     let t = hand.summarize_tiles();
-    let is_closed = hand.melds().is_empty();
+    let is_closed = hand.has_no_melds();
     let sp = if is_closed {
         calc_seven_pairs_shanten(&t).0
     } else {
@@ -281,7 +800,7 @@ pub fn calc_shanten_number(hand: &Hand) -> ShantenNumber {
 ///
 /// 副露がある場合、七対子・国士無双は該当なし（`i32::MAX` 相当）を返す。
 pub fn calc_shanten_number_by_form(hand: &Hand, form: Form) -> ShantenNumber {
-    let is_closed = hand.melds().is_empty();
+    let is_closed = hand.has_no_melds();
     match form {
         Form::SevenPairs => {
             if is_closed {
@@ -305,10 +824,53 @@ pub fn calc_shanten_number_by_form(hand: &Hand, form: Form) -> ShantenNumber {
     }
 }
 
+/// 手牌・副露・ツモ牌に、ルール上存在しない牌が含まれていないかを検証する
+///
+/// 三人麻雀では二萬〜八萬を使用しないため、含まれていればエラーを返す。
+/// 抜きドラは三人麻雀の北のみ認める。
+pub fn validate_tiles_for_mode(hand: &Hand, settings: &Settings) -> Result<()> {
+    if let Some(tile) = hand.nuki().iter().find(|t| t.get() != Tile::Z4) {
+        return Err(MahjongError::UnsupportedTile(format!(
+            "only north can be extracted: {}",
+            tile
+        )));
+    }
+    if !settings.three_player {
+        if !hand.nuki().is_empty() {
+            return Err(MahjongError::UnsupportedTile(
+                "north extraction requires three-player mahjong".to_string(),
+            ));
+        }
+        return Ok(());
+    }
+    let melded = hand.melds().iter().flat_map(|m| m.tiles.iter());
+    for tile in hand.tiles().iter().chain(melded).chain(hand.drawn().iter()) {
+        if is_unused_in_three_player(tile.get()) {
+            return Err(MahjongError::UnsupportedTile(format!(
+                "tile not used in three-player mahjong: {}",
+                tile
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 有効牌（向聴数が下がる牌）の残り枚数の合計を返す
+///
+/// ツモ牌のない手牌（副露がなければ13枚）を対象とし、各牌種を1枚加えたときに
+/// 向聴数が下がるものについて、自分の手牌・副露で使っていない残り枚数を合計する。
+/// 三人麻雀では二萬〜八萬は残り0枚として扱う。
+pub fn count_acceptance(hand: &Hand, settings: &Settings) -> Result<u32> {
+    Ok(calc_ukeire(hand, settings)?
+        .iter()
+        .map(|&(_, remaining)| remaining as u32)
+        .sum())
+}
+
 /// 七対子のシャンテン数を計算する共通ロジック
 ///
 /// 戻り値: `(shanten, pair_count)`
-fn calc_seven_pairs_shanten(t: &TileSummarize) -> (i32, u32) {
+pub(crate) fn calc_seven_pairs_shanten(t: &TileCounts) -> (i32, u32) {
     let mut pair: u32 = 0;
     let mut kind: u32 = 0;
     for &count in t.iter().take(Tile::LEN) {
@@ -324,25 +886,11 @@ fn calc_seven_pairs_shanten(t: &TileSummarize) -> (i32, u32) {
 }
 
 /// 国士無双のシャンテン数を計算する共通ロジック
-fn calc_thirteen_orphans_shanten(t: &TileSummarize) -> i32 {
-    const TO_TILES: [usize; 13] = [
-        Tile::M1 as usize,
-        Tile::M9 as usize,
-        Tile::P1 as usize,
-        Tile::P9 as usize,
-        Tile::S1 as usize,
-        Tile::S9 as usize,
-        Tile::Z1 as usize,
-        Tile::Z2 as usize,
-        Tile::Z3 as usize,
-        Tile::Z4 as usize,
-        Tile::Z5 as usize,
-        Tile::Z6 as usize,
-        Tile::Z7 as usize,
-    ];
+pub(crate) fn calc_thirteen_orphans_shanten(t: &TileCounts) -> i32 {
     let mut pair: u32 = 0;
     let mut kind: u32 = 0;
-    for &i in &TO_TILES {
+    for &tile in &THIRTEEN_ORPHAN_TILES {
+        let i = tile as usize;
         if t[i] > 0 {
             kind += 1;
             if t[i] >= 2 {
@@ -372,7 +920,7 @@ trait ShantenAccumulator: Sized {
     type Preprocess: PreprocessResult;
 
     /// 前処理: 独立した刻子・順子・孤立牌を抽出する
-    fn preprocess(t: &mut TileSummarize) -> Result<Self::Preprocess>;
+    fn preprocess(t: &mut TileCounts) -> Result<Self::Preprocess>;
 
     /// 新しい空の追跡状態を作成する
     fn new_tracking() -> Self;
@@ -394,7 +942,24 @@ trait ShantenAccumulator: Sized {
     fn seq2_count(&self) -> usize;
 
     /// 新しい最良結果が見つかったときに呼ばれる。現在の状態をスナップショットする。
-    fn snapshot_best(&self, pre: &Self::Preprocess, t: &TileSummarize, head: usize) -> Self;
+    fn snapshot_best(&self, pre: &Self::Preprocess, t: &TileCounts, head: usize) -> Self;
+
+    /// 最良と同じかそれより良い分解が見つかったときに呼ばれる
+    ///
+    /// `improved` は向聴数が更新されたか。既定では更新時のみスナップショットを取る。
+    #[inline(always)]
+    fn record_best(
+        &self,
+        best_acc: &mut Self,
+        improved: bool,
+        pre: &Self::Preprocess,
+        t: &TileCounts,
+        head: usize,
+    ) {
+        if improved {
+            *best_acc = self.snapshot_best(pre, t, head);
+        }
+    }
 
     /// 最終結果に独立ブロックをマージする
     fn finalize(self, pre: Self::Preprocess) -> Self;
@@ -427,7 +992,7 @@ struct CountOnly {
 impl ShantenAccumulator for CountOnly {
     type Preprocess = CountOnlyPreprocess;
 
-    fn preprocess(t: &mut TileSummarize) -> Result<CountOnlyPreprocess> {
+    fn preprocess(t: &mut TileCounts) -> Result<CountOnlyPreprocess> {
         let same3 = extract_independent_same3(t);
         let seq3 = extract_independent_seq3(t);
         let _ = remove_independent_singles(t);
@@ -497,7 +1062,7 @@ impl ShantenAccumulator for CountOnly {
     }
 
     #[inline(always)]
-    fn snapshot_best(&self, _pre: &CountOnlyPreprocess, _t: &TileSummarize, _head: usize) -> Self {
+    fn snapshot_best(&self, _pre: &CountOnlyPreprocess, _t: &TileCounts, _head: usize) -> Self {
         // カウンタのみなのでスナップショット不要
         CountOnly {
             same3: 0,
@@ -516,6 +1081,7 @@ impl ShantenAccumulator for CountOnly {
 // Vec に個々の面子などを格納する
 // 役判定や符計算用に使用する、ややコストのかかるバージョン
 
+#[derive(Clone)]
 struct FullTrackingPreprocess {
     same3: Vec<Same3>,
     seq3: Vec<Sequential3>,
@@ -531,6 +1097,7 @@ impl PreprocessResult for FullTrackingPreprocess {
     }
 }
 
+#[derive(Clone)]
 struct FullTracking {
     same3: Vec<Same3>,
     sequential3: Vec<Sequential3>,
@@ -542,7 +1109,7 @@ struct FullTracking {
 impl ShantenAccumulator for FullTracking {
     type Preprocess = FullTrackingPreprocess;
 
-    fn preprocess(t: &mut TileSummarize) -> Result<FullTrackingPreprocess> {
+    fn preprocess(t: &mut TileCounts) -> Result<FullTrackingPreprocess> {
         let same3 = extract_independent_same3_full(t)?;
         let seq3 = extract_independent_seq3_full(t)?;
         let singles = extract_independent_singles_full(t)?;
@@ -613,12 +1180,7 @@ impl ShantenAccumulator for FullTracking {
         self.sequential2.len()
     }
 
-    fn snapshot_best(
-        &self,
-        _pre: &FullTrackingPreprocess,
-        t: &TileSummarize,
-        _head: usize,
-    ) -> Self {
+    fn snapshot_best(&self, _pre: &FullTrackingPreprocess, t: &TileCounts, _head: usize) -> Self {
         let mut single = Vec::new();
         for (i, &count) in t.iter().enumerate().take(Tile::LEN) {
             for _ in 0..count {
@@ -642,6 +1204,103 @@ impl ShantenAccumulator for FullTracking {
     }
 }
 
+// 向聴数が最小になるブロック分解を全て集める
+// 高点法で最も点数の高い解釈を選ぶために使用する
+
+struct AllTracking {
+    current: FullTracking,
+    found: Vec<FullTracking>,
+}
+
+impl ShantenAccumulator for AllTracking {
+    type Preprocess = FullTrackingPreprocess;
+
+    fn preprocess(t: &mut TileCounts) -> Result<FullTrackingPreprocess> {
+        FullTracking::preprocess(t)
+    }
+
+    fn new_tracking() -> Self {
+        AllTracking {
+            current: FullTracking::new_tracking(),
+            found: Vec::new(),
+        }
+    }
+
+    fn push_same3(&mut self, tile: usize) {
+        self.current.push_same3(tile);
+    }
+    fn pop_same3(&mut self) {
+        self.current.pop_same3();
+    }
+    fn same3_count(&self) -> usize {
+        self.current.same3_count()
+    }
+
+    fn push_seq3(&mut self, tile: usize) {
+        self.current.push_seq3(tile);
+    }
+    fn pop_seq3(&mut self) {
+        self.current.pop_seq3();
+    }
+    fn seq3_count(&self) -> usize {
+        self.current.seq3_count()
+    }
+
+    fn push_same2(&mut self, tile: usize) {
+        self.current.push_same2(tile);
+    }
+    fn pop_same2(&mut self) {
+        self.current.pop_same2();
+    }
+    fn same2_count(&self) -> usize {
+        self.current.same2_count()
+    }
+
+    fn push_seq2(&mut self, tile1: usize, tile2: usize) {
+        self.current.push_seq2(tile1, tile2);
+    }
+    fn pop_seq2(&mut self) {
+        self.current.pop_seq2();
+    }
+    fn seq2_count(&self) -> usize {
+        self.current.seq2_count()
+    }
+
+    fn snapshot_best(&self, pre: &FullTrackingPreprocess, t: &TileCounts, head: usize) -> Self {
+        AllTracking {
+            current: FullTracking::new_tracking(),
+            found: vec![self.current.snapshot_best(pre, t, head)],
+        }
+    }
+
+    fn record_best(
+        &self,
+        best_acc: &mut Self,
+        improved: bool,
+        pre: &FullTrackingPreprocess,
+        t: &TileCounts,
+        head: usize,
+    ) {
+        if improved {
+            best_acc.found.clear();
+        }
+        best_acc
+            .found
+            .push(self.current.snapshot_best(pre, t, head));
+    }
+
+    fn finalize(self, pre: FullTrackingPreprocess) -> Self {
+        AllTracking {
+            current: self.current,
+            found: self
+                .found
+                .into_iter()
+                .map(|f| f.finalize(pre.clone()))
+                .collect(),
+        }
+    }
+}
+
 /// 通常形のシャンテン数を計算する共通エントリポイント
 fn calc_normal_shanten<A: ShantenAccumulator>(hand: &Hand) -> Result<(i32, A)> {
     let mut t = hand.summarize_tiles();
@@ -674,7 +1333,7 @@ fn find_mentsu<A: ShantenAccumulator>(
     pre: &A::Preprocess,
     acc: &mut A,
     head: usize,
-    t: &mut TileSummarize,
+    t: &mut TileCounts,
     best: &mut i32,
     best_acc: &mut A,
 ) {
@@ -727,7 +1386,7 @@ fn find_tatsu<A: ShantenAccumulator>(
     idx: usize,
     ctx: &mut TatsuSearch<'_, A>,
     acc: &mut A,
-    t: &mut TileSummarize,
+    t: &mut TileCounts,
 ) {
     // 現在の分解で向聴数を計算
     let block2_raw = acc.same2_count() + acc.seq2_count();
@@ -735,9 +1394,9 @@ fn find_tatsu<A: ShantenAccumulator>(
     let block2_net = block2_raw.saturating_sub(ctx.head);
     let block2_capped = block2_net.min(4usize.saturating_sub(ctx.block3));
     let shanten = 8i32 - (ctx.block3 * 2 + block2_capped + ctx.head) as i32;
-    if shanten < *ctx.best {
+    if shanten <= *ctx.best {
+        acc.record_best(ctx.best_acc, shanten < *ctx.best, ctx.pre, t, ctx.head);
         *ctx.best = shanten;
-        *ctx.best_acc = acc.snapshot_best(ctx.pre, t, ctx.head);
     }
 
     // 枝刈り: これ以上 block2 を増やしても改善しない場合
@@ -782,7 +1441,7 @@ fn find_tatsu<A: ShantenAccumulator>(
 // ============================================================================
 
 /// 数牌において、隣接2マス以内に他の牌がないかを判定する
-fn is_isolated(t: &TileSummarize, i: usize) -> bool {
+fn is_isolated(t: &TileCounts, i: usize) -> bool {
     if i >= 27 {
         return true; // 字牌は常に独立
     }
@@ -796,7 +1455,7 @@ fn is_isolated(t: &TileSummarize, i: usize) -> bool {
 }
 
 /// 独立した刻子を抽出する（カウントのみ返す）
-fn extract_independent_same3(t: &mut TileSummarize) -> usize {
+fn extract_independent_same3(t: &mut TileCounts) -> usize {
     let mut count = 0;
     for i in 0..Tile::LEN {
         if t[i] >= 3 && is_isolated(t, i) {
@@ -808,7 +1467,7 @@ fn extract_independent_same3(t: &mut TileSummarize) -> usize {
 }
 
 /// 独立した刻子を抽出する（Vec で返す）
-fn extract_independent_same3_full(t: &mut TileSummarize) -> Result<Vec<Same3>> {
+fn extract_independent_same3_full(t: &mut TileCounts) -> Result<Vec<Same3>> {
     let mut result = Vec::new();
     for i in 0..Tile::LEN {
         if t[i] >= 3 && is_isolated(t, i) {
@@ -824,7 +1483,7 @@ fn extract_independent_same3_full(t: &mut TileSummarize) -> Result<Vec<Same3>> {
 ///
 /// 一盃口を先に処理してから通常処理する。
 /// `on_found` は見つかった順子の先頭インデックスと個数（1 or 2）を受け取る。
-fn extract_independent_seq3_impl(t: &mut TileSummarize, mut on_found: impl FnMut(usize, u32)) {
+fn extract_independent_seq3_impl(t: &mut TileCounts, mut on_found: impl FnMut(usize, u32)) {
     for n in (1u32..=2).rev() {
         for suit_start in (0..27).step_by(9) {
             for k in 0..=6usize {
@@ -853,7 +1512,7 @@ fn extract_independent_seq3_impl(t: &mut TileSummarize, mut on_found: impl FnMut
 }
 
 /// 独立した順子を抽出する（カウントのみ返す）
-fn extract_independent_seq3(t: &mut TileSummarize) -> usize {
+fn extract_independent_seq3(t: &mut TileCounts) -> usize {
     let mut count = 0usize;
     extract_independent_seq3_impl(t, |_l, n| {
         count += n as usize;
@@ -862,9 +1521,9 @@ fn extract_independent_seq3(t: &mut TileSummarize) -> usize {
 }
 
 /// 独立した順子を抽出する（Vec で返す）
-fn extract_independent_seq3_full(t: &mut TileSummarize) -> Result<Vec<Sequential3>> {
+fn extract_independent_seq3_full(t: &mut TileCounts) -> Result<Vec<Sequential3>> {
     let mut result = Vec::new();
-    let mut err: Option<anyhow::Error> = None;
+    let mut err: Option<MahjongError> = None;
     extract_independent_seq3_impl(t, |l, n| {
         if err.is_some() {
             return;
@@ -886,7 +1545,7 @@ fn extract_independent_seq3_full(t: &mut TileSummarize) -> Result<Vec<Sequential
 }
 
 /// 独立した孤立牌を除去する（カウントのみ返す）
-fn remove_independent_singles(t: &mut TileSummarize) -> usize {
+fn remove_independent_singles(t: &mut TileCounts) -> usize {
     let mut count = 0;
     for i in 0..Tile::LEN {
         if t[i] == 1 && is_isolated(t, i) {
@@ -898,7 +1557,7 @@ fn remove_independent_singles(t: &mut TileSummarize) -> usize {
 }
 
 /// 独立した孤立牌を除去する（Vec で返す）
-fn extract_independent_singles_full(t: &mut TileSummarize) -> Result<Vec<TileType>> {
+fn extract_independent_singles_full(t: &mut TileCounts) -> Result<Vec<TileType>> {
     let mut result = Vec::new();
     for i in 0..Tile::LEN {
         if t[i] == 1 && is_isolated(t, i) {
@@ -1153,6 +1812,224 @@ mod tests {
         );
     }
 
+    #[test]
+    /// 三人麻雀では二萬〜八萬を含む手牌はエラーになる
+    fn three_player_rejects_unused_tiles() {
+        let settings = Settings {
+            three_player: true,
+            ..Settings::new()
+        };
+        let hand = Hand::from("19m123p456p789s11z 5m");
+        assert!(HandAnalyzer::new_with_settings(&hand, &settings).is_err());
+        assert!(HandAnalyzer::new_with_settings(&hand, &Settings::new()).is_ok());
+
+        let hand = Hand::from("19m123p456p789s11z 9m");
+        assert!(HandAnalyzer::new_with_settings(&hand, &settings).is_ok());
+    }
+
+    #[test]
+    /// 有効牌の枚数は三人麻雀では二萬〜八萬を数えない
+    fn acceptance_depends_on_player_count() {
+        let four = Settings::new();
+        let three = Settings {
+            three_player: true,
+            ..Settings::new()
+        };
+        // 1m単騎の聴牌: 残り3枚の1mのみが有効牌
+        let hand = Hand::from("1m123p456p789s111z");
+        assert_eq!(count_acceptance(&hand, &four).unwrap(), 3);
+        assert_eq!(count_acceptance(&hand, &three).unwrap(), 3);
+
+        // 一向聴: 四人麻雀では2m・3mも1mと塔子になるが、三人麻雀では存在しない
+        let hand = Hand::from("1m9p123p456p789s11z");
+        let four_count = count_acceptance(&hand, &four).unwrap();
+        let three_count = count_acceptance(&hand, &three).unwrap();
+        assert_eq!(four_count - three_count, 8);
+    }
+
+    #[test]
+    /// 抜きドラは三人麻雀の北のみ認められ、解析の対象外になる
+    fn nuki_is_validated_and_excluded() {
+        let three = Settings {
+            three_player: true,
+            ..Settings::new()
+        };
+        let hand = Hand::from("11m123p456p789s11z n4z 1z");
+        let analyzer = HandAnalyzer::new_with_settings(&hand, &three).unwrap();
+        assert!(analyzer.shanten.has_won());
+        assert!(HandAnalyzer::new_with_settings(&hand, &Settings::new()).is_err());
+
+        let hand = Hand::from("11m123p456p789s11z n1z 1z");
+        assert!(HandAnalyzer::new_with_settings(&hand, &three).is_err());
+    }
+
+    #[test]
+    /// ツモ牌がある手牌の有効牌は計算しない
+    fn acceptance_requires_hand_without_drawn_tile() {
+        let hand = Hand::from("19m123p456p789s11z 1z");
+        assert!(count_acceptance(&hand, &Settings::new()).is_err());
+    }
+
+    /// full_report は各和了形の new_by_form・new と同じ結果を返す
+    #[rstest::rstest]
+    #[case("226699m99p228s66z 1z")]
+    #[case("19m19p11s1234567z 5m")]
+    #[case("1122m3344p5566s7z 7z")]
+    #[case("112233m445566p7z 7z")]
+    #[case("147m258p369s1234z")]
+    #[case("333m456p1789s 333z 1s")]
+    fn full_report_matches_each_form(#[case] hand_str: &str) {
+        let hand = Hand::from(hand_str);
+        let report = HandAnalyzer::full_report(&hand);
+        let by_form = |form| HandAnalyzer::new_by_form(&hand, form).unwrap().shanten;
+        assert_eq!(report.normal, by_form(Form::Normal));
+        assert_eq!(report.seven_pairs, by_form(Form::SevenPairs));
+        assert_eq!(report.thirteen_orphans, by_form(Form::ThirteenOrphans));
+
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert_eq!(report.best_form, analyzer.form);
+        assert_eq!(report.best(), analyzer.shanten);
+    }
+
+    #[rstest::rstest]
+    #[case("23m456p789s111z55z", vec![Tile::M1, Tile::M4])]
+    #[case("1122m3344p5566s7z", vec![Tile::Z7])]
+    #[case(
+        "19m19p19s1234567z",
+        vec![
+            Tile::M1, Tile::M9, Tile::P1, Tile::P9, Tile::S1, Tile::S9,
+            Tile::Z1, Tile::Z2, Tile::Z3, Tile::Z4, Tile::Z5, Tile::Z6, Tile::Z7,
+        ]
+    )]
+    #[case("3456m456p789s111z", vec![Tile::M3, Tile::M6])]
+    #[case("147m258p369s1234z", vec![])]
+    #[case("23m456p789s111z55z 1m", vec![])]
+    fn waits_test(#[case] hand_str: &str, #[case] expected: Vec<TileType>) {
+        let hand = Hand::from(hand_str);
+        let expected: Vec<Tile> = expected.into_iter().map(Tile::new).collect();
+        assert_eq!(HandAnalyzer::waits(&hand), expected);
+    }
+
+    #[test]
+    fn all_decompositions_test() {
+        // 111222333m は刻子3つとも順子3つとも取れる
+        let hand = Hand::from("111222333m456p7s 7s");
+        let all = HandAnalyzer::all_decompositions(&hand).unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(
+            all.iter()
+                .all(|a| a.shanten.has_won() && a.form == Form::Normal)
+        );
+        assert!(all.iter().any(|a| a.same3.len() == 3));
+        assert!(all.iter().any(|a| a.sequential3.len() == 4));
+
+        // 二盃口形は通常形と七対子の両方で和了している
+        let hand = Hand::from("112233m445566p7s 7s");
+        let forms: Vec<Form> = HandAnalyzer::all_decompositions(&hand)
+            .unwrap()
+            .iter()
+            .map(|a| a.form)
+            .collect();
+        assert_eq!(forms, vec![Form::Normal, Form::SevenPairs]);
+
+        // 分解が1通りしかない手
+        let hand = Hand::from("123m456p789s1122z 2z");
+        assert_eq!(HandAnalyzer::all_decompositions(&hand).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn seven_pairs_info_test() {
+        let hand = Hand::from("1122m3344p5566s7z 7z");
+        let analyzer = HandAnalyzer::new_by_form(&hand, Form::SevenPairs).unwrap();
+        assert_eq!(
+            analyzer.seven_pairs_info(),
+            Some(SevenPairsInfo {
+                pairs: [
+                    Tile::M1,
+                    Tile::M2,
+                    Tile::P3,
+                    Tile::P4,
+                    Tile::S5,
+                    Tile::S6,
+                    Tile::Z7
+                ]
+            })
+        );
+
+        // 同じ牌4枚は対子1つと単独の牌2枚として扱う
+        let hand = Hand::from("1111m3344p5566s7z 7z");
+        let analyzer = HandAnalyzer::new_by_form(&hand, Form::SevenPairs).unwrap();
+        assert_eq!(analyzer.same2.len(), 6);
+        assert_eq!(analyzer.single, vec![Tile::M1, Tile::M1]);
+        assert_eq!(analyzer.seven_pairs_info(), None);
+
+        // 通常形は対象外
+        let hand = Hand::from("123m456p789s1122z 2z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert_eq!(analyzer.seven_pairs_info(), None);
+    }
+
+    #[test]
+    fn thirteen_orphans_info_test() {
+        let hand = Hand::from("19m19p19s1234567z 1m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert!(analyzer.is_thirteen_wait());
+        let info = analyzer.thirteen_orphans.unwrap();
+        assert_eq!(info.present.len(), 13);
+        assert!(info.missing.is_empty());
+        assert_eq!(info.pair, Some(Tile::M1));
+
+        // 単騎待ちの国士無双
+        let hand = Hand::from("119m19p19s123456z 7z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert!(analyzer.shanten.has_won());
+        assert!(!analyzer.is_thirteen_wait());
+
+        // 聴牌していない国士無双形は欠けている么九牌を持つ
+        let hand = Hand::from("119m19p19s12345z5m");
+        let analyzer = HandAnalyzer::new_by_form(&hand, Form::ThirteenOrphans).unwrap();
+        let info = analyzer.thirteen_orphans.unwrap();
+        assert_eq!(info.missing, vec![Tile::Z6, Tile::Z7]);
+        assert!(!info.is_thirteen_wait);
+    }
+
+    #[test]
+    fn shanten_only_and_many_test() {
+        let hands = [
+            "123m456p789s1122z 2z",
+            "226699m99p228s66z 1z",
+            "19m19p11s1234567z 5m",
+            "147m258p369s1234z",
+        ];
+        let expected = [-1, 0, 0, 6];
+        for (hand_str, expected) in hands.iter().zip(expected) {
+            assert_eq!(HandAnalyzer::shanten_only(&Hand::from(hand_str)), expected);
+        }
+        let counts: Vec<TileCounts> = hands
+            .iter()
+            .map(|s| Hand::from(s).summarize_tiles())
+            .collect();
+        assert_eq!(HandAnalyzer::shanten_many(&counts), expected);
+
+        // 副露がある場合は通常形のみ
+        let hand = Hand::from("333m456p1789s 333z 1s");
+        assert_eq!(HandAnalyzer::shanten_only(&hand), -1);
+    }
+
+    #[test]
+    fn concealed_same3_test() {
+        // ポンした 999s は除き、暗槓の 9m は残す
+        let hand = Hand::from("111m333p1z 999s a9999m 1z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert_eq!(analyzer.same3.len(), 4);
+        let concealed: Vec<TileType> = analyzer
+            .concealed_same3(&hand)
+            .iter()
+            .map(|s| s.get()[0])
+            .collect();
+        assert_eq!(concealed, vec![Tile::M1, Tile::M9, Tile::P3]);
+    }
+
     /// 様々なパターンの手牌でシャンテン数が正しいことを検証する回帰テスト
     #[rstest::rstest]
     #[case::seven_pairs_ready("226699m99p228s66z 1z", 0)]
@@ -1176,4 +2053,14 @@ mod tests {
             "hand '{hand_str}': expected {expected}, got {shanten}"
         );
     }
+
+    #[test]
+    fn display_lists_blocks() {
+        let hand = Hand::from("123m456p111z55z23s 9m");
+        let analyzer = HandAnalyzer::new_by_form(&hand, Form::Normal).unwrap();
+        assert_eq!(
+            analyzer.to_string(),
+            "Normal shanten=0 mentsu=[123m 456p 111z] pairs=[55z] partial=[23s] single=[9m]"
+        );
+    }
 }
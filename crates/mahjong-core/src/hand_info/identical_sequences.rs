@@ -0,0 +1,94 @@
+//! 同じ順子の組の数え上げ
+//!
+//! 一盃口・二盃口の判定、一色三順（ローカル役）、役の成立根拠の表示で共有する。
+
+/// 同じ順子の重なり方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdenticalSequences<T> {
+    /// 同じ順子2つからなる組（各組の順子を1つずつ、昇順）
+    ///
+    /// 同じ順子が4つあれば2組、3つなら1組として数える。
+    pub pairs: Vec<T>,
+    /// 最も多く重なっている順子の数（重なりがなければ順子があるとき1、ないとき0）
+    pub max_multiplicity: usize,
+}
+
+impl<T> IdenticalSequences<T> {
+    /// 同じ順子の組の数（一盃口なら1、二盃口なら2）
+    pub fn pair_count(&self) -> usize {
+        self.pairs.len()
+    }
+}
+
+/// 順子の一覧から同じ順子の組を数える
+///
+/// 並べ替えてから連続する同じ順子をまとめるので、順子の数に対して O(n log n) で済む。
+pub fn identical_sequences<T: Ord + Copy>(sequences: &[T]) -> IdenticalSequences<T> {
+    let mut sorted = sequences.to_vec();
+    sorted.sort();
+    let mut pairs = Vec::new();
+    let mut max_multiplicity = 0;
+    for group in sorted.chunk_by(|a, b| a == b) {
+        max_multiplicity = max_multiplicity.max(group.len());
+        pairs.extend(std::iter::repeat_n(group[0], group.len() / 2));
+    }
+    IdenticalSequences {
+        pairs,
+        max_multiplicity,
+    }
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand_info::block::Sequential3;
+    use crate::tile::Tile;
+
+    fn seq(first: u32) -> Sequential3 {
+        Sequential3::new(first, first + 1, first + 2).unwrap()
+    }
+
+    #[test]
+    fn no_sequences() {
+        let result = identical_sequences::<Sequential3>(&[]);
+        assert_eq!(result.pair_count(), 0);
+        assert_eq!(result.max_multiplicity, 0);
+    }
+
+    #[test]
+    fn distinct_sequences_have_no_pairs() {
+        let result = identical_sequences(&[seq(Tile::M1), seq(Tile::M2), seq(Tile::P1)]);
+        assert_eq!(result.pair_count(), 0);
+        assert_eq!(result.max_multiplicity, 1);
+    }
+
+    #[test]
+    fn one_pair_regardless_of_order() {
+        let result = identical_sequences(&[seq(Tile::P4), seq(Tile::M1), seq(Tile::P4)]);
+        assert_eq!(result.pairs, vec![seq(Tile::P4)]);
+        assert_eq!(result.max_multiplicity, 2);
+    }
+
+    #[test]
+    fn two_different_pairs() {
+        let result =
+            identical_sequences(&[seq(Tile::P4), seq(Tile::M1), seq(Tile::M1), seq(Tile::P4)]);
+        assert_eq!(result.pairs, vec![seq(Tile::M1), seq(Tile::P4)]);
+        assert_eq!(result.max_multiplicity, 2);
+    }
+
+    #[test]
+    fn three_identical_count_as_one_pair() {
+        let result = identical_sequences(&[seq(Tile::S2), seq(Tile::S2), seq(Tile::S2)]);
+        assert_eq!(result.pair_count(), 1);
+        assert_eq!(result.max_multiplicity, 3);
+    }
+
+    #[test]
+    fn four_identical_count_as_two_pairs() {
+        let result = identical_sequences(&[seq(Tile::S2); 4]);
+        assert_eq!(result.pair_count(), 2);
+        assert_eq!(result.max_multiplicity, 4);
+    }
+}
@@ -0,0 +1,213 @@
+//! 一向聴の形の分類
+//!
+//! 一向聴（あと1枚で聴牌）の手牌を、打牌選択や解説で用いる代表的な形に分類する。
+//! 通常形は手牌のブロック分解（面子・対子・塔子・浮き牌）から判定する。
+
+use crate::error::{MahjongError, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::calc_shanten_number_by_form;
+use crate::tile::*;
+use crate::winning_hand::name::Form;
+
+/// 一向聴の形
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IishantenShape {
+    /// くっつき一向聴（面子が揃い、雀頭と浮き牌2枚が残る形）
+    Kuttsuki,
+    /// 雀頭なし一向聴（面子が揃い、雀頭がなく塔子が2つ残る形）
+    Headless,
+    /// 完全一向聴（両面2つと、雀頭に浮き牌が隣接した形（例: 556）が残る形）
+    Perfect,
+    /// 一般的な一向聴（雀頭と塔子2つ、浮き牌1枚が残る形）
+    Standard,
+    /// 七対子の一向聴
+    SevenPairs,
+    /// 国士無双の一向聴
+    ThirteenOrphans,
+}
+
+/// 手牌のブロック
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    /// 面子
+    Set,
+    /// 対子
+    Pair(TileType),
+    /// 塔子（両面なら true）
+    Partial(bool),
+    /// 浮き牌
+    Single(TileType),
+}
+
+/// ツモ牌のない手牌が一向聴であれば、該当する形を全て返す（昇順）
+///
+/// 一向聴でない場合は空を返す。1つの手牌が複数の形に該当することもある
+/// （例: 通常形でくっつき一向聴かつ七対子の一向聴）。
+pub fn classify_iishanten(hand: &Hand) -> Result<Vec<IishantenShape>> {
+    if hand.drawn().is_some() {
+        return Err(MahjongError::InvalidHand(
+            "iishanten shape is defined for a hand without a drawn tile".to_string(),
+        ));
+    }
+
+    let mut shapes = Vec::new();
+    if calc_shanten_number_by_form(hand, Form::Normal) == 1 {
+        let sets_needed = 4usize.saturating_sub(hand.melds().len());
+        let mut counts: TileSummarize = [0; Tile::LEN];
+        for tile in hand.tiles() {
+            counts[tile.get() as usize] += 1;
+        }
+        let mut blocks = Vec::new();
+        decompose(&mut counts, 0, &mut blocks, &mut |blocks| {
+            if let Some(shape) = classify_blocks(blocks, sets_needed) {
+                shapes.push(shape);
+            }
+        });
+    }
+    if calc_shanten_number_by_form(hand, Form::SevenPairs) == 1 {
+        shapes.push(IishantenShape::SevenPairs);
+    }
+    if calc_shanten_number_by_form(hand, Form::ThirteenOrphans) == 1 {
+        shapes.push(IishantenShape::ThirteenOrphans);
+    }
+
+    // 一般的な一向聴は、より具体的な形（くっつき・雀頭なし・完全）に該当しない場合のみとする
+    if shapes.iter().any(|s| {
+        matches!(
+            s,
+            IishantenShape::Kuttsuki | IishantenShape::Headless | IishantenShape::Perfect
+        )
+    }) {
+        shapes.retain(|s| *s != IishantenShape::Standard);
+    }
+    shapes.sort();
+    shapes.dedup();
+    Ok(shapes)
+}
+
+/// 1通りのブロック分解から通常形の一向聴の形を判定する
+fn classify_blocks(blocks: &[Block], sets_needed: usize) -> Option<IishantenShape> {
+    let sets = blocks.iter().filter(|b| **b == Block::Set).count();
+    let pairs: Vec<TileType> = blocks
+        .iter()
+        .filter_map(|b| match b {
+            Block::Pair(t) => Some(*t),
+            _ => None,
+        })
+        .collect();
+    let partials: Vec<bool> = blocks
+        .iter()
+        .filter_map(|b| match b {
+            Block::Partial(two_sided) => Some(*two_sided),
+            _ => None,
+        })
+        .collect();
+    let singles: Vec<TileType> = blocks
+        .iter()
+        .filter_map(|b| match b {
+            Block::Single(t) => Some(*t),
+            _ => None,
+        })
+        .collect();
+
+    if sets + 1 == sets_needed {
+        return match (pairs.len(), partials.len()) {
+            (1, 0) => Some(IishantenShape::Kuttsuki),
+            (0, 2) => Some(IishantenShape::Headless),
+            _ => None,
+        };
+    }
+    if sets + 2 == sets_needed && pairs.len() == 1 && partials.len() == 2 && singles.len() == 1 {
+        let (head, single) = (pairs[0], singles[0]);
+        let (head, single) = (Tile::new(head), Tile::new(single));
+        let is_complex = head.is_suited()
+            && head.suit() == single.suit()
+            && head.get().abs_diff(single.get()) == 1;
+        if partials.iter().all(|two_sided| *two_sided) && is_complex {
+            return Some(IishantenShape::Perfect);
+        }
+        return Some(IishantenShape::Standard);
+    }
+    None
+}
+
+/// 牌を全てブロックに分解する全ての方法について `found` を呼ぶ
+fn decompose(
+    counts: &mut TileSummarize,
+    start: usize,
+    blocks: &mut Vec<Block>,
+    found: &mut dyn FnMut(&[Block]),
+) {
+    let Some(i) = (start..Tile::LEN).find(|&i| counts[i] > 0) else {
+        found(blocks);
+        return;
+    };
+    let tile = i as TileType;
+    let rank = suit_rank(tile);
+
+    let mut try_block = |counts: &mut TileSummarize, used: &[usize], block: Block| {
+        if used.iter().any(|&j| counts[j] == 0) {
+            return;
+        }
+        for &j in used {
+            counts[j] -= 1;
+        }
+        blocks.push(block);
+        decompose(counts, i, blocks, found);
+        blocks.pop();
+        for &j in used {
+            counts[j] += 1;
+        }
+    };
+
+    if counts[i] >= 3 {
+        try_block(counts, &[i, i, i], Block::Set);
+    }
+    if rank.is_some_and(|r| r <= 7) {
+        try_block(counts, &[i, i + 1, i + 2], Block::Set);
+    }
+    if counts[i] >= 2 {
+        try_block(counts, &[i, i], Block::Pair(tile));
+    }
+    if let Some(r) = rank {
+        if r <= 8 {
+            try_block(counts, &[i, i + 1], Block::Partial(r != 1 && r != 8));
+        }
+        if r <= 7 {
+            try_block(counts, &[i, i + 2], Block::Partial(false));
+        }
+    }
+    try_block(counts, &[i], Block::Single(tile));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("123m456p789s11z5s9m", &[IishantenShape::Kuttsuki])]
+    #[case("123m456p789s34m68p", &[IishantenShape::Headless])]
+    #[case("123m456p34s67s556m", &[IishantenShape::Perfect])]
+    #[case("123m456p34s67s55m1z", &[IishantenShape::Standard])]
+    #[case("1199m1199p119s1z2z", &[IishantenShape::SevenPairs])]
+    #[case("19m19p19s12345z15m", &[IishantenShape::ThirteenOrphans])]
+    fn classify_iishanten_test(#[case] hand: &str, #[case] expected: &[IishantenShape]) {
+        assert_eq!(classify_iishanten(&Hand::from(hand)).unwrap(), expected);
+    }
+
+    #[test]
+    fn classify_iishanten_not_applicable_test() {
+        // 聴牌
+        assert!(
+            classify_iishanten(&Hand::from("123m456p789s11z34s"))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(classify_iishanten(&Hand::from("123m456p789s11z34s 5s")).is_err());
+    }
+}
@@ -1,9 +1,16 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::hand::Hand;
+use crate::settings::{Kuikae, Settings};
 use crate::tile::*;
+use crate::winning_hand::name::Kind;
 
 /// 副露の種類
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MeldType {
     /// チー
     Chi,
@@ -23,7 +30,8 @@ impl MeldType {
 }
 
 /// 誰から副露したか
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MeldFrom {
     /// 上家（チー・ポン・明カン）
     Previous,
@@ -37,8 +45,21 @@ pub enum MeldFrom {
     Unknown,
 }
 
+impl MeldFrom {
+    /// 鳴いた座席 `caller` から見た鳴き元の座席を返す（自家・不明は `caller`）
+    pub fn source_seat(self, caller: usize) -> usize {
+        match self {
+            MeldFrom::Previous => (caller + 3) % 4,
+            MeldFrom::Opposite => (caller + 2) % 4,
+            MeldFrom::Following => (caller + 1) % 4,
+            MeldFrom::Myself | MeldFrom::Unknown => caller,
+        }
+    }
+}
+
 /// 副露状態を表す構造体
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Meld {
     /// 副露で公開された牌
     pub tiles: Vec<Tile>,
@@ -47,11 +68,16 @@ pub struct Meld {
     /// 誰から副露したか
     pub from: MeldFrom,
     /// 鳴いた牌（捨て牌から取った牌。暗カンの場合は None）
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub called_tile: Option<Tile>,
 }
 
 impl Meld {
+    /// 暗カンかどうかを返す（暗カンは門前を崩さない）
+    pub fn is_concealed(&self) -> bool {
+        self.category == MeldType::Kan && self.from == MeldFrom::Myself
+    }
+
     /// カンの4枚目の牌を返す
     ///
     /// 解析用に `tiles` には3枚のみ保持するため、表示・ドラ計算用の4枚目は
@@ -79,6 +105,30 @@ impl Meld {
         tiles
     }
 
+    /// 表示順に並べた副露の牌（カンは4枚目を補う）と、鳴いた牌の位置を返す
+    ///
+    /// 鳴いた牌は誰から鳴いたかに応じて左端（上家）・中央（対面）・右端（下家）に置き、
+    /// チーは常に左端に置く。残りの牌は昇順に並べる。
+    pub fn display_tiles(&self) -> (Vec<Tile>, Option<usize>) {
+        let mut tiles = self.expanded_tiles();
+        let called = self.called_tile.and_then(|called| {
+            let idx = tiles.iter().position(|t| *t == called)?;
+            Some(tiles.remove(idx))
+        });
+        tiles.sort();
+
+        let Some(called) = called else {
+            return (tiles, None);
+        };
+        let position = match (self.category, self.from) {
+            (MeldType::Chi, _) | (_, MeldFrom::Previous) => 0,
+            (_, MeldFrom::Opposite) => 1,
+            _ => tiles.len(),
+        };
+        tiles.insert(position, called);
+        (tiles, Some(position))
+    }
+
     /// 喰い替え（swap-calling）で、この副露の直後に打牌が禁止される牌種を返す。
     ///
     /// - ポン: 鳴いた牌と同種（現物喰い替え）。
@@ -103,13 +153,11 @@ impl Meld {
                 // self.tiles はソート済みの順子 [low, low+1, low+2]
                 let low = self.tiles[0].get();
                 let high = self.tiles[2].get();
-                let suit_start = (called_tt / 9) * 9;
-                let suit_end = suit_start + 9;
 
-                if called_tt == low && high + 1 < suit_end {
+                if called_tt == low && self.tiles[2].number() < Some(9) {
                     // 鳴いた牌が下端: 上端の1つ上を禁止（例: 3 をチーして 4-5 使用 → 6）
                     forbidden.push(high + 1);
-                } else if called_tt == high && low > suit_start {
+                } else if called_tt == high && self.tiles[0].number() > Some(1) {
                     // 鳴いた牌が上端: 下端の1つ下を禁止（例: 7 をチーして 5-6 使用 → 4）
                     forbidden.push(low - 1);
                 }
@@ -119,11 +167,113 @@ impl Meld {
             MeldType::Kan | MeldType::Kakan => Vec::new(),
         }
     }
+
+    /// 喰い替えの設定に従って、この副露の直後に打牌が禁止される牌種を返す
+    pub fn forbidden_discards(&self, kuikae: Kuikae) -> Vec<TileType> {
+        match kuikae {
+            Kuikae::Forbidden => self.forbidden_swap_tiles(),
+            Kuikae::OnlySameTile => self.forbidden_swap_tiles().into_iter().take(1).collect(),
+            Kuikae::Allowed => Vec::new(),
+        }
+    }
+}
+
+/// 手牌文字列（`Hand::from`）の副露と同じ書式で出力する
+///
+/// 同じスートの数字を並べてスートを付け、鳴いた牌は数字の直後に`'`を付ける。
+/// 鳴いた牌は誰から鳴いたかに応じて左端（上家）・中央（対面）・右端（下家）に置き、
+/// チーは常に左端に置く（例: `5'55z`、`55'5z`、`555'z`、`3'45m`、`55'55p`）。
+/// 暗カンは先頭に`a`を付ける（例: `a1111z`）。カンは4枚目を補って出力し、赤5は`0`で表す。
+impl fmt::Display for Meld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (tiles, called) = self.display_tiles();
+        let rank = |tile: Tile| Hand::short_tile_parts(tile).0;
+        let mut ranks = String::new();
+        for (i, &tile) in tiles.iter().enumerate() {
+            ranks.push(rank(tile));
+            if called == Some(i) {
+                ranks.push('\'');
+            }
+        }
+
+        if self.is_concealed() {
+            f.write_str("a")?;
+        }
+        let suit = tiles.first().map_or('?', |&t| Hand::short_tile_parts(t).1);
+        write!(f, "{ranks}{suit}")
+    }
+}
+
+/// チー・ポンした直後の手牌で、喰い替えにより打牌が禁止される牌種を返す
+///
+/// 直前の副露（`hand.melds()` の末尾）を対象とし、手牌に含まれる牌種のみを返す。
+pub fn forbidden_discards_after_call(hand: &Hand, settings: &Settings) -> Vec<TileType> {
+    let Some(meld) = hand.melds().last() else {
+        return Vec::new();
+    };
+    meld.forbidden_discards(settings.kuikae)
+        .into_iter()
+        .filter(|&t| hand.tiles().iter().any(|tile| tile.get() == t))
+        .collect()
+}
+
+/// 責任払い（包）の対象になる役満
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PaoYakuman {
+    /// 大三元
+    BigDragons,
+    /// 大四喜
+    BigWinds,
+}
+
+impl PaoYakuman {
+    /// 対応する役の種類を返す
+    pub fn kind(self) -> Kind {
+        match self {
+            PaoYakuman::BigDragons => Kind::BigDragons,
+            PaoYakuman::BigWinds => Kind::BigWinds,
+        }
+    }
+}
+
+/// 責任払い（包）が発生している役満と、その責任を負う鳴き元を返す
+///
+/// 三元牌の3つ目、または風牌の4つ目の刻子・槓子を他家から鳴いた場合に、
+/// その副露の鳴き元が大三元・大四喜の責任払いの対象になる。副露は鳴いた順に並んでいるものとし、
+/// 暗カンで揃えた場合や鳴き元が不明な場合は`None`を返す。
+pub fn pao_source(hand: &Hand) -> Option<(PaoYakuman, MeldFrom)> {
+    let mut dragons = 0;
+    let mut winds = 0;
+    for meld in hand.melds() {
+        if meld.category == MeldType::Chi {
+            continue;
+        }
+        let tile = meld.tiles[0];
+        let (count, needed, yakuman) = if tile.is_dragon() {
+            (&mut dragons, 3, PaoYakuman::BigDragons)
+        } else if tile.is_wind() {
+            (&mut winds, 4, PaoYakuman::BigWinds)
+        } else {
+            continue;
+        };
+        *count += 1;
+        if *count == needed
+            && matches!(
+                meld.from,
+                MeldFrom::Previous | MeldFrom::Opposite | MeldFrom::Following
+            )
+        {
+            return Some((yakuman, meld.from));
+        }
+    }
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     fn chi(tiles: [TileType; 3], called: TileType) -> Meld {
         Meld {
@@ -201,6 +351,41 @@ mod tests {
         assert!(forbidden.contains(&Tile::M6));
     }
 
+    #[test]
+    fn forbidden_discards_follow_kuikae_setting() {
+        let meld = chi([Tile::M3, Tile::M4, Tile::M5], Tile::M3);
+        assert_eq!(
+            meld.forbidden_discards(Kuikae::Forbidden),
+            vec![Tile::M3, Tile::M6]
+        );
+        assert_eq!(
+            meld.forbidden_discards(Kuikae::OnlySameTile),
+            vec![Tile::M3]
+        );
+        assert!(meld.forbidden_discards(Kuikae::Allowed).is_empty());
+    }
+
+    #[test]
+    fn forbidden_discards_after_call_only_lists_tiles_in_hand() {
+        let mut hand = Hand::from("36m234567p678s1z");
+        hand.add_meld(chi([Tile::M3, Tile::M4, Tile::M5], Tile::M3));
+        let settings = Settings::new();
+        assert_eq!(
+            forbidden_discards_after_call(&hand, &settings),
+            vec![Tile::M3, Tile::M6]
+        );
+
+        let mut hand = Hand::from("6m1234567p678s1z");
+        hand.add_meld(chi([Tile::M3, Tile::M4, Tile::M5], Tile::M3));
+        assert_eq!(
+            forbidden_discards_after_call(&hand, &settings),
+            vec![Tile::M6]
+        );
+        assert!(
+            forbidden_discards_after_call(&Hand::from("6m1234567p678s1z"), &settings).is_empty()
+        );
+    }
+
     #[test]
     fn kan_has_no_swap_restriction() {
         let meld = Meld {
@@ -211,4 +396,33 @@ mod tests {
         };
         assert!(meld.forbidden_swap_tiles().is_empty());
     }
+
+    #[rstest]
+    #[case("5'55z")]
+    #[case("55'5z")]
+    #[case("555'z")]
+    #[case("3'45m")]
+    #[case("55'55p")]
+    #[case("a1111z")]
+    #[case("2222s")]
+    fn display_round_trips_through_hand_notation(#[case] notation: &str) {
+        let hand = Hand::from(&format!("123m {notation} 1z"));
+        assert_eq!(hand.melds()[0].to_string(), notation);
+    }
+
+    #[test]
+    fn display_places_chi_called_tile_first() {
+        let meld = chi([Tile::M3, Tile::M4, Tile::M5], Tile::M5);
+        assert_eq!(meld.to_string(), "5'34m");
+    }
+
+    #[rstest]
+    #[case("123m11p 5'55z 66'6z 777'z 1m", Some((PaoYakuman::BigDragons, MeldFrom::Following)))]
+    #[case("123m11p 777z 5'55z 66'6z 1m", Some((PaoYakuman::BigDragons, MeldFrom::Opposite)))]
+    #[case("123m11p 5'55z 66'6z a7777z 1m", None)]
+    #[case("123m11p 5'55z 66'6z 4'56m 1m", None)]
+    #[case("11p 1'11z 2'22z a3333z 44'4z 1p", Some((PaoYakuman::BigWinds, MeldFrom::Opposite)))]
+    fn pao_source_cases(#[case] hand: &str, #[case] expected: Option<(PaoYakuman, MeldFrom)>) {
+        assert_eq!(pao_source(&Hand::from(hand)), expected);
+    }
 }
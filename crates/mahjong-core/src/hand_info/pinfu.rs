@@ -0,0 +1,77 @@
+//! 平和形の判定
+//!
+//! 役判定（`check_pinfu`）と符計算（平和ツモの20符）で同じ判定を共有する。
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::HandAnalyzer;
+use crate::hand_info::status::Status;
+use crate::tile::{Dragon, TileType, Wind};
+use crate::winning_hand::name::Form;
+
+/// 和了形が平和の条件を満たすかを返す
+///
+/// - 門前であること
+/// - 4つの順子と役牌でない雀頭で構成されていること
+/// - 和了牌が両面待ちとして解釈できること（和了牌がない場合は待ちを問わない）
+pub fn is_pinfu(analyzer: &HandAnalyzer, hand: &Hand, status: &Status) -> bool {
+    if !analyzer.shanten.has_won() || analyzer.form != Form::Normal {
+        return false;
+    }
+    if status.has_claimed_open {
+        return false;
+    }
+    if analyzer.sequential3.len() != 4 || analyzer.same2.len() != 1 {
+        return false;
+    }
+    if is_yakuhai_tile(analyzer.same2[0].get()[0], status) {
+        return false;
+    }
+    match hand.drawn() {
+        Some(winning_tile) => analyzer
+            .sequential3
+            .iter()
+            .any(|seq| seq.is_two_sided_wait(winning_tile.get())),
+        None => true,
+    }
+}
+
+/// 役牌（三元牌・自風牌・場風牌）かどうかを判定する
+pub fn is_yakuhai_tile(tile: TileType, status: &Status) -> bool {
+    Dragon::is_tile_type(tile).is_some()
+        || Wind::is_tile_type(tile) == Some(status.seat_wind)
+        || Wind::is_tile_type(tile) == Some(status.round_wind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Wind;
+
+    fn status() -> Status {
+        let mut status = Status::new();
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        status
+    }
+
+    #[test]
+    fn test_is_pinfu() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert!(is_pinfu(&analyzer, &hand, &status()));
+
+        // 嵌張待ち
+        let hand = Hand::from("123456m234p5799s 6s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert!(!is_pinfu(&analyzer, &hand, &status()));
+
+        // 自風の雀頭
+        let hand = Hand::from("123456m234p67s22z 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert!(!is_pinfu(&analyzer, &hand, &status()));
+        // 客風の雀頭
+        let hand = Hand::from("123456m234p67s33z 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        assert!(is_pinfu(&analyzer, &hand, &status()));
+    }
+}
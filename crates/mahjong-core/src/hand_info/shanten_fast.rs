@@ -0,0 +1,390 @@
+//! 牌種ごとの分解表を用いた高速な向聴数計算
+//!
+//! 数牌は色ごとに9種の枚数の並びを1つのキーとし、そのキーについて
+//! 「雀頭の有無・面子数ごとに取れる塔子の最大数」を初回に一括で求めた表から引く。
+//! 字牌は順子を作らないため、表を使わず牌ごとに直接数える。
+//!
+//! 手牌全体の探索を行わないため、同じ色の牌が多い手牌でも計算量が増えない。
+//! 面子・対子の分解結果が必要な場合（役判定・符計算）は `HandAnalyzer` を使う。
+
+use std::sync::OnceLock;
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::{
+    ShantenNumber, calc_seven_pairs_shanten, calc_thirteen_orphans_shanten,
+};
+use crate::tile::{Tile, TileType};
+use crate::tile_counts::TileCounts;
+
+/// 面子数の上限
+const MAX_MELDS: usize = 4;
+
+/// 取りえない組み合わせを表す値
+const NONE: i8 = -1;
+
+/// 1色（または字牌）の分解表
+///
+/// `tatsu[head][mentsu]` は雀頭を`head`個（0か1）、面子を`mentsu`個取ったときに
+/// 残りから取れる塔子（対子を含む）の最大数。取れない組み合わせは`NONE`。
+#[derive(Debug, Clone, Copy)]
+struct BlockTable {
+    tatsu: [[i8; MAX_MELDS + 1]; 2],
+}
+
+impl BlockTable {
+    const EMPTY: BlockTable = BlockTable {
+        tatsu: [[NONE; MAX_MELDS + 1]; 2],
+    };
+
+    fn record(&mut self, head: usize, mentsu: usize, tatsu: usize) {
+        if mentsu <= MAX_MELDS {
+            let slot = &mut self.tatsu[head][mentsu];
+            *slot = (*slot).max(tatsu as i8);
+        }
+    }
+
+    /// 2つの表を合成する（雀頭は合計1個まで）
+    fn combine(&self, other: &BlockTable) -> BlockTable {
+        let mut result = BlockTable::EMPTY;
+        for h1 in 0..2 {
+            for h2 in 0..(2 - h1) {
+                for m1 in 0..=MAX_MELDS {
+                    if self.tatsu[h1][m1] == NONE {
+                        continue;
+                    }
+                    for m2 in 0..=(MAX_MELDS - m1) {
+                        if other.tatsu[h2][m2] == NONE {
+                            continue;
+                        }
+                        result.record(
+                            h1 + h2,
+                            m1 + m2,
+                            (self.tatsu[h1][m1] + other.tatsu[h2][m2]) as usize,
+                        );
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// 表に載せる1種あたりの枚数の上限
+const TABLE_MAX_COUNT: u32 = 4;
+
+/// 表に載せる1色分の枚数の合計の上限（手牌14枚）
+const TABLE_MAX_TOTAL: u32 = 14;
+
+/// 1色分の分解表の数（1種あたり0〜4枚の5通りを9種並べた数）
+const SUIT_TABLE_LEN: usize = 5usize.pow(9);
+
+/// 数牌1色分の分解表（[`suit_index`] の順）
+///
+/// 初めて参照したときに全ての並びについて一度だけ求める。1色で15枚以上になる並びは
+/// 手牌に現れないため求めず、参照された場合は [`compute_suit_table`] で都度求める。
+static SUIT_TABLES: OnceLock<Box<[PackedTable]>> = OnceLock::new();
+
+/// 分解表を1つの整数に詰めたもの（1欄あたり3ビット）
+///
+/// 面子と塔子は合わせて4つまでしか有効にならないため、塔子の数は4で打ち切って保持する。
+#[derive(Debug, Clone, Copy, Default)]
+struct PackedTable(u32);
+
+impl PackedTable {
+    fn pack(table: &BlockTable) -> PackedTable {
+        let mut packed = 0;
+        for (i, &tatsu) in table.tatsu.iter().flatten().enumerate() {
+            let slot = if tatsu == NONE {
+                0
+            } else {
+                tatsu.min(MAX_MELDS as i8) as u32 + 1
+            };
+            packed |= slot << (3 * i);
+        }
+        PackedTable(packed)
+    }
+
+    fn unpack(self) -> BlockTable {
+        let mut table = BlockTable::EMPTY;
+        for (i, tatsu) in table.tatsu.iter_mut().flatten().enumerate() {
+            let slot = (self.0 >> (3 * i)) & 0b111;
+            if slot != 0 {
+                *tatsu = slot as i8 - 1;
+            }
+        }
+        table
+    }
+}
+
+/// 数牌1色分の枚数の並びを表の位置に変換する（1枚目の牌が最下位の5進数）
+fn suit_index(counts: &[u32; 9]) -> usize {
+    counts
+        .iter()
+        .rev()
+        .fold(0, |index, &c| index * 5 + c as usize)
+}
+
+/// 全ての並びの分解表を求める
+///
+/// 牌を取り除いた並びは必ず表の手前に来るため、先頭から順に求めれば
+/// 残りの並びの表は既に求まっている。
+fn build_suit_tables() -> Box<[PackedTable]> {
+    let mut tables = vec![PackedTable::default(); SUIT_TABLE_LEN];
+    let mut counts = [0u32; 9];
+    for index in 0..SUIT_TABLE_LEN {
+        if counts.iter().sum::<u32>() <= TABLE_MAX_TOTAL {
+            let table = compute_suit_table(&counts, |rest| tables[suit_index(rest)].unpack());
+            tables[index] = PackedTable::pack(&table);
+        }
+        // 次の並びへ（5進数の繰り上がり）
+        for c in counts.iter_mut() {
+            *c += 1;
+            if *c <= TABLE_MAX_COUNT {
+                break;
+            }
+            *c = 0;
+        }
+    }
+    tables.into_boxed_slice()
+}
+
+/// 数牌1色分の分解表を引く
+fn suit_table(counts: &[u32]) -> BlockTable {
+    let mut t = [0u32; 9];
+    t.copy_from_slice(counts);
+    // 和了牌の判定では4枚使い切った牌の5枚目を加えることがあるため、表の範囲外は都度求める
+    if t.iter().any(|&c| c > TABLE_MAX_COUNT) || t.iter().sum::<u32>() > TABLE_MAX_TOTAL {
+        return compute_suit_table(&t, |rest| suit_table(rest));
+    }
+    SUIT_TABLES.get_or_init(build_suit_tables)[suit_index(&t)].unpack()
+}
+
+/// 数牌1色分の分解表を求める
+///
+/// 最も小さい牌を含むブロックの取り方ごとに、残りの並びの分解表（`sub_table`）を引いて合成する。
+fn compute_suit_table(t: &[u32; 9], sub_table: impl Fn(&[u32; 9]) -> BlockTable) -> BlockTable {
+    let mut table = BlockTable::EMPTY;
+    match (0..9).find(|&i| t[i] > 0) {
+        None => table.record(0, 0, 0),
+        Some(i) => {
+            // removed の牌を取り除き、取り除いたブロックの面子・雀頭・塔子の数を加えて残りの表と合成する
+            let mut take = |removed: &[usize], mentsu: usize, head: usize, tatsu: usize| {
+                let mut rest = *t;
+                for &r in removed {
+                    if r >= 9 || rest[r] == 0 {
+                        return;
+                    }
+                    rest[r] -= 1;
+                }
+                let sub = sub_table(&rest);
+                for h in 0..(2 - head) {
+                    for m in 0..=(MAX_MELDS - mentsu) {
+                        let sub_tatsu = sub.tatsu[h][m];
+                        if sub_tatsu != NONE {
+                            table.record(h + head, m + mentsu, sub_tatsu as usize + tatsu);
+                        }
+                    }
+                }
+            };
+            // 刻子
+            take(&[i, i, i], 1, 0, 0);
+            // 順子
+            take(&[i, i + 1, i + 2], 1, 0, 0);
+            // 雀頭
+            take(&[i, i], 0, 1, 0);
+            // 対子（塔子として扱う）
+            take(&[i, i], 0, 0, 1);
+            // 両面・辺張
+            take(&[i, i + 1], 0, 0, 1);
+            // 嵌張
+            take(&[i, i + 2], 0, 0, 1);
+            // 孤立牌として残す
+            take(&[i], 0, 0, 0);
+        }
+    }
+    table
+}
+
+/// 字牌の分解表を求める
+///
+/// 字牌は牌ごとに独立しているため、各牌を刻子・雀頭・対子のいずれかとして取る表を順に合成する。
+fn honour_table(counts: &[u32]) -> BlockTable {
+    let mut result = BlockTable::EMPTY;
+    result.record(0, 0, 0);
+    for &count in counts {
+        let mut single = BlockTable::EMPTY;
+        single.record(0, 0, 0);
+        if count >= 2 {
+            single.record(1, 0, 0);
+            single.record(0, 0, 1);
+        }
+        if count >= 3 {
+            single.record(0, 1, 0);
+        }
+        result = result.combine(&single);
+    }
+    result
+}
+
+/// 副露を除いた手牌（ツモ牌を含む）の牌種ごとの枚数を返す
+fn concealed_counts(hand: &Hand) -> TileCounts {
+    hand.tiles().iter().chain(hand.drawn().iter()).collect()
+}
+
+/// 通常形（4面子1雀頭）の向聴数を分解表を用いて計算する
+///
+/// 副露は1つにつき1面子として数える。
+pub fn normal_shanten(hand: &Hand) -> ShantenNumber {
+    let called = hand.melds().len().min(MAX_MELDS);
+    ShantenNumber::new(normal_shanten_of(&concealed_counts(hand), called))
+}
+
+/// 副露を除いた牌種ごとの枚数と副露数から通常形の向聴数を求める
+fn normal_shanten_of(counts: &TileCounts, called: usize) -> i32 {
+    let table = [0usize, 9, 18]
+        .iter()
+        .map(|&start| suit_table(&counts[start..start + 9]))
+        .fold(honour_table(&counts[27..34]), |acc, suit| {
+            acc.combine(&suit)
+        });
+
+    let mut best = i32::MAX;
+    for head in 0..2 {
+        for mentsu in 0..=(MAX_MELDS - called) {
+            let tatsu = table.tatsu[head][mentsu];
+            if tatsu == NONE {
+                continue;
+            }
+            let blocks = mentsu + called;
+            // 面子と塔子は合わせて4つまでしか有効にならない
+            let tatsu = (tatsu as usize).min(MAX_MELDS - blocks);
+            let shanten = 8 - 2 * blocks as i32 - tatsu as i32 - head as i32;
+            best = best.min(shanten);
+        }
+    }
+    best
+}
+
+/// 七対子・国士無双・通常形のうち最小の向聴数を計算する
+///
+/// `calc_shanten_number` と同じ意味の値を返すが、通常形の計算に分解表を用いる。
+pub fn shanten(hand: &Hand) -> ShantenNumber {
+    if hand.has_no_melds() {
+        return ShantenNumber::new(closed_shanten(&concealed_counts(hand)));
+    }
+    normal_shanten(hand)
+}
+
+/// 門前の手牌の牌種ごとの枚数から、3つの和了形のうち最小の向聴数を求める
+///
+/// `counts` には副露を含めない（13枚または14枚を想定する）。
+pub fn closed_shanten(counts: &TileCounts) -> i32 {
+    normal_shanten_of(counts, 0)
+        .min(calc_seven_pairs_shanten(counts).0)
+        .min(calc_thirteen_orphans_shanten(counts))
+}
+
+/// ツモ牌を除いた手牌に`tile_type`を1枚加えると和了形になるかを返す
+///
+/// 牌種ごとの枚数に1枚足して向聴数を求めるだけなので、手牌の複製や文字列の組み立ては行わない。
+/// 手牌で4枚使い切っている牌でも判定する（残り枚数は考慮しない）。
+pub fn wins_with(hand: &Hand, tile_type: TileType) -> bool {
+    let mut counts: TileCounts = hand.tiles().iter().collect();
+    wins_with_counts(
+        &mut counts,
+        hand.has_no_melds(),
+        hand.melds().len(),
+        tile_type,
+    )
+}
+
+/// ツモ牌を除いた手牌の和了牌を全て返す（牌種の昇順）
+pub fn winning_tile_types(hand: &Hand) -> Vec<TileType> {
+    let mut counts: TileCounts = hand.tiles().iter().collect();
+    let is_closed = hand.has_no_melds();
+    let called = hand.melds().len();
+    (0..Tile::LEN as TileType)
+        .filter(|&t| wins_with_counts(&mut counts, is_closed, called, t))
+        .collect()
+}
+
+/// `counts`に1枚加えて和了形か判定し、`counts`を元に戻す
+fn wins_with_counts(
+    counts: &mut TileCounts,
+    is_closed: bool,
+    called: usize,
+    tile_type: TileType,
+) -> bool {
+    counts[tile_type as usize] += 1;
+    let shanten = if is_closed {
+        closed_shanten(counts)
+    } else {
+        normal_shanten_of(counts, called.min(MAX_MELDS))
+    };
+    counts[tile_type as usize] -= 1;
+    shanten == -1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand_info::hand_analyzer::calc_shanten_number;
+    use rand::seq::SliceRandom;
+    use rand::{SeedableRng, rngs::SmallRng};
+
+    #[rstest::rstest]
+    #[case("123m456p789s1122z 2z", -1)]
+    #[case("1112345678999m 5m", -1)]
+    #[case("123m456p789s1122z", 0)]
+    #[case("147m258p369s1234z", 8)]
+    #[case("11112222333344m", -1)]
+    #[case("1112345678999p", 0)]
+    #[case("333m456p1789s 333z 1s", -1)]
+    fn normal_shanten_test(#[case] hand_str: &str, #[case] expected: i32) {
+        let hand = Hand::from(hand_str);
+        assert_eq!(normal_shanten(&hand), ShantenNumber::new(expected));
+    }
+
+    /// 無作為な門前の手牌で、分解を行う向聴数計算と結果が一致する
+    #[test]
+    fn matches_decomposing_analyzer() {
+        let mut rng = SmallRng::seed_from_u64(283);
+        let mut wall: Vec<Tile> = (0..Tile::LEN as u32)
+            .flat_map(|t| std::iter::repeat_n(Tile::new(t), 4))
+            .collect();
+        for _ in 0..500 {
+            wall.shuffle(&mut rng);
+            let hand = Hand::new(wall[..13].to_vec(), Some(wall[13]));
+            assert_eq!(shanten(&hand), calc_shanten_number(&hand), "hand: {hand}");
+        }
+    }
+
+    /// 表から引いた分解表が、表を使わずに求めた分解表と一致する
+    #[test]
+    fn suit_tables_match_direct_computation() {
+        fn direct(t: &[u32; 9]) -> BlockTable {
+            compute_suit_table(t, direct)
+        }
+        let mut rng = SmallRng::seed_from_u64(9);
+        let mut tiles: Vec<usize> = (0..9).flat_map(|i| [i; 4]).collect();
+        for len in 0..=14 {
+            tiles.shuffle(&mut rng);
+            let mut counts = [0u32; 9];
+            for &i in &tiles[..len] {
+                counts[i] += 1;
+            }
+            assert_eq!(
+                PackedTable::pack(&suit_table(&counts)).0,
+                PackedTable::pack(&direct(&counts)).0,
+                "counts: {counts:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn wins_with_fifth_copy_outside_table() {
+        // 1mを4枚使い切っていても5枚目で判定できる（表の範囲外: 111m 11m 234m）
+        let hand = Hand::from("1111234m456p789s");
+        assert!(wins_with(&hand, Tile::M1));
+    }
+}
@@ -1,9 +1,12 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::tile::Wind;
+use crate::error::{MahjongError, Result};
+use crate::tile::{Tile, Wind};
 
 /// 手牌の（牌以外の）状態
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Status {
     /// 立直したか
     pub has_claimed_riichi: bool,
@@ -25,16 +28,31 @@ pub struct Status {
     pub is_after_a_quad: bool,
     /// 搶槓か
     pub is_robbing_a_quad: bool,
+    /// 搶槓の対象となった加槓の牌（搶槓は和了牌がこの牌と同種の場合のみ成立する）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub robbed_quad_tile: Option<Tile>,
     /// ダブル立直か
     pub is_double_riichi: bool,
     /// 親（東家）か
     pub is_dealer: bool,
     /// 第一ツモか（天和・地和の判定用）
     pub is_first_turn: bool,
-    /// 流し満貫か
+    /// 流し満貫か（和了役の判定には使わない。流局時の精算は `board::draw_outcome` で行う）
     pub is_nagashi_mangan: bool,
     /// 槓子の数
     pub kan_count: u32,
+    /// ドラ表示牌（先頭が表ドラ、以降はカンドラ）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dora_indicators: Vec<Tile>,
+    /// 裏ドラ表示牌（立直時のみ数える）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ura_dora_indicators: Vec<Tile>,
+    /// 本場
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub honba: u32,
+    /// 供託の立直棒の本数（和了者が受け取る）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub riichi_sticks: u32,
 }
 
 impl Default for Status {
@@ -56,11 +74,218 @@ impl Status {
             is_last_tile_claim: false,
             is_after_a_quad: false,
             is_robbing_a_quad: false,
+            robbed_quad_tile: None,
             is_double_riichi: false,
             is_dealer: false,
             is_first_turn: false,
             is_nagashi_mangan: false,
             kan_count: 0,
+            dora_indicators: Vec::new(),
+            ura_dora_indicators: Vec::new(),
+            honba: 0,
+            riichi_sticks: 0,
+        }
+    }
+
+    /// 局の状態を組み立てるビルダーを返す
+    pub fn builder() -> StatusBuilder {
+        StatusBuilder::new()
+    }
+
+    /// 立直を宣言し、宣言牌を捨てたときに呼ぶ
+    ///
+    /// 一発を有効にする。第一ツモで鳴きが入っていなければダブル立直とする。
+    /// 宣言牌の打牌では`on_turn_passed`を呼ばないこと（一発が消えてしまうため）。
+    pub fn on_riichi_declared(&mut self) {
+        self.is_double_riichi = self.is_first_turn && !self.has_claimed_open;
+        self.has_claimed_riichi = true;
+        self.is_unbroken = true;
+        self.is_first_turn = false;
+    }
+
+    /// 自分の打牌が終わったときに呼ぶ（立直宣言牌を除く）
+    ///
+    /// 立直後の一巡が過ぎたため一発が消え、第一ツモも終わる。
+    pub fn on_turn_passed(&mut self) {
+        self.is_unbroken = false;
+        self.is_first_turn = false;
+    }
+
+    /// 卓上で誰かが鳴き（暗槓を含む）を行ったときに呼ぶ
+    ///
+    /// 一発と、天和・地和・ダブル立直の条件となる第一ツモの扱いが無効になる。
+    pub fn on_call_made(&mut self) {
+        self.is_unbroken = false;
+        self.is_first_turn = false;
+    }
+}
+
+/// 局の状態を組み立てるビルダー
+///
+/// `build`で矛盾する組み合わせ（ロンでの海底摸月、立直なしのダブル立直など）を検出する。
+///
+/// ```
+/// use mahjong_core::hand_info::status::Status;
+/// use mahjong_core::tile::Wind;
+///
+/// let status = Status::builder()
+///     .riichi()
+///     .tsumo()
+///     .player_wind(Wind::South)
+///     .build()
+///     .unwrap();
+/// assert!(status.has_claimed_riichi && status.is_self_drawn);
+/// assert!(!status.is_dealer);
+///
+/// assert!(Status::builder().last_tile_draw().build().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatusBuilder {
+    status: Status,
+}
+
+impl Default for StatusBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatusBuilder {
+    /// 東場の東家（親）・ロン和了・門前の状態から始める
+    pub fn new() -> Self {
+        let mut status = Status::new();
+        status.is_dealer = true;
+        StatusBuilder { status }
+    }
+
+    /// 立直している
+    pub fn riichi(mut self) -> Self {
+        self.status.has_claimed_riichi = true;
+        self
+    }
+
+    /// ダブル立直している（`riichi`も必要）
+    pub fn double_riichi(mut self) -> Self {
+        self.status.is_double_riichi = true;
+        self
+    }
+
+    /// 一発が有効（`riichi`も必要）
+    pub fn ippatsu(mut self) -> Self {
+        self.status.is_unbroken = true;
+        self
+    }
+
+    /// 鳴いている
+    pub fn open(mut self) -> Self {
+        self.status.has_claimed_open = true;
+        self
+    }
+
+    /// ツモ和了
+    pub fn tsumo(mut self) -> Self {
+        self.status.is_self_drawn = true;
+        self
+    }
+
+    /// ロン和了（既定）
+    pub fn ron(mut self) -> Self {
+        self.status.is_self_drawn = false;
+        self
+    }
+
+    /// 自風を指定する（東なら親になる）
+    pub fn player_wind(mut self, wind: Wind) -> Self {
+        self.status.seat_wind = wind;
+        self.status.is_dealer = wind == Wind::East;
+        self
+    }
+
+    /// 場風を指定する
+    pub fn round_wind(mut self, wind: Wind) -> Self {
+        self.status.round_wind = wind;
+        self
+    }
+
+    /// 海底摸月（ツモ和了のみ）
+    pub fn last_tile_draw(mut self) -> Self {
+        self.status.is_last_tile_draw = true;
+        self
+    }
+
+    /// 河底撈魚（ロン和了のみ）
+    pub fn last_tile_claim(mut self) -> Self {
+        self.status.is_last_tile_claim = true;
+        self
+    }
+
+    /// 嶺上開花（ツモ和了のみ）
+    pub fn after_a_quad(mut self) -> Self {
+        self.status.is_after_a_quad = true;
+        self
+    }
+
+    /// 搶槓（ロン和了のみ）。`tile`は加槓された牌
+    pub fn robbing_a_quad(mut self, tile: Tile) -> Self {
+        self.status.is_robbing_a_quad = true;
+        self.status.robbed_quad_tile = Some(tile);
+        self
+    }
+
+    /// 第一ツモ（天和・地和の判定用）
+    pub fn first_turn(mut self) -> Self {
+        self.status.is_first_turn = true;
+        self
+    }
+
+    /// ドラ表示牌（先頭が表ドラ、以降はカンドラ）を指定する
+    pub fn dora_indicators(mut self, indicators: Vec<Tile>) -> Self {
+        self.status.dora_indicators = indicators;
+        self
+    }
+
+    /// 裏ドラ表示牌を指定する
+    pub fn ura_dora_indicators(mut self, indicators: Vec<Tile>) -> Self {
+        self.status.ura_dora_indicators = indicators;
+        self
+    }
+
+    /// 本場と供託の立直棒の本数を指定する
+    pub fn sticks(mut self, honba: u32, riichi_sticks: u32) -> Self {
+        self.status.honba = honba;
+        self.status.riichi_sticks = riichi_sticks;
+        self
+    }
+
+    /// 状態を検証して`Status`を返す
+    pub fn build(self) -> Result<Status> {
+        let s = &self.status;
+        let contradiction = if s.has_claimed_riichi && s.has_claimed_open {
+            Some("riichi with an open hand")
+        } else if s.is_double_riichi && !s.has_claimed_riichi {
+            Some("double riichi without riichi")
+        } else if s.is_unbroken && !s.has_claimed_riichi {
+            Some("ippatsu without riichi")
+        } else if s.is_last_tile_draw && !s.is_self_drawn {
+            Some("last tile draw on ron")
+        } else if s.is_last_tile_claim && s.is_self_drawn {
+            Some("last tile claim on tsumo")
+        } else if s.is_after_a_quad && !s.is_self_drawn {
+            Some("after a quad on ron")
+        } else if s.is_robbing_a_quad && s.is_self_drawn {
+            Some("robbing a quad on tsumo")
+        } else if s.is_after_a_quad && s.is_last_tile_draw {
+            Some("after a quad and last tile draw at once")
+        } else if s.is_first_turn && s.has_claimed_open {
+            Some("first turn with an open hand")
+        } else {
+            None
+        };
+        match contradiction {
+            Some(reason) => Err(MahjongError::InvalidStatus(format!(
+                "contradictory status: {reason}"
+            ))),
+            None => Ok(self.status),
         }
     }
 }
@@ -82,10 +307,92 @@ mod tests {
         assert!(!s.is_last_tile_claim);
         assert!(!s.is_after_a_quad);
         assert!(!s.is_robbing_a_quad);
+        assert!(s.robbed_quad_tile.is_none());
         assert!(!s.is_double_riichi);
         assert!(!s.is_dealer);
         assert!(!s.is_first_turn);
         assert!(!s.is_nagashi_mangan);
         assert_eq!(s.kan_count, 0);
     }
+
+    #[test]
+    fn test_ippatsu_transitions() {
+        let mut s = Status::new();
+        s.is_first_turn = true;
+        s.on_riichi_declared();
+        assert!(s.has_claimed_riichi);
+        assert!(s.is_double_riichi);
+        assert!(s.is_unbroken);
+        assert!(!s.is_first_turn);
+
+        // 一巡後の打牌で一発が消える
+        s.on_turn_passed();
+        assert!(!s.is_unbroken);
+        assert!(s.is_double_riichi);
+
+        // 一巡以内でも鳴きが入れば一発は消える
+        let mut s = Status::new();
+        s.on_riichi_declared();
+        assert!(!s.is_double_riichi);
+        assert!(s.is_unbroken);
+        s.on_call_made();
+        assert!(!s.is_unbroken);
+        assert!(s.has_claimed_riichi);
+    }
+
+    #[test]
+    fn test_status_builder() {
+        let status = Status::builder()
+            .riichi()
+            .ippatsu()
+            .tsumo()
+            .last_tile_draw()
+            .player_wind(Wind::East)
+            .round_wind(Wind::South)
+            .sticks(2, 1)
+            .build()
+            .unwrap();
+        assert!(status.has_claimed_riichi && status.is_unbroken && status.is_self_drawn);
+        assert!(status.is_last_tile_draw);
+        assert!(status.is_dealer);
+        assert!(matches!(status.round_wind, Wind::South));
+        assert_eq!((status.honba, status.riichi_sticks), (2, 1));
+    }
+
+    #[test]
+    fn test_status_builder_defaults_to_east_dealer() {
+        let status = Status::builder().build().unwrap();
+        assert!(matches!(status.seat_wind, Wind::East));
+        assert!(status.is_dealer);
+
+        let status = StatusBuilder::default()
+            .player_wind(Wind::West)
+            .build()
+            .unwrap();
+        assert!(!status.is_dealer);
+    }
+
+    #[test]
+    fn test_status_builder_rejects_contradictions() {
+        assert!(Status::builder().tsumo().last_tile_claim().build().is_err());
+        assert!(Status::builder().double_riichi().build().is_err());
+        assert!(Status::builder().riichi().open().build().is_err());
+        assert!(Status::builder().after_a_quad().build().is_err());
+        assert!(
+            Status::builder()
+                .tsumo()
+                .robbing_a_quad(Tile::new(Tile::M1))
+                .build()
+                .is_err()
+        );
+        assert!(
+            Status::builder()
+                .riichi()
+                .double_riichi()
+                .ron()
+                .last_tile_claim()
+                .build()
+                .is_ok()
+        );
+    }
 }
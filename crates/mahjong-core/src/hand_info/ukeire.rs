@@ -0,0 +1,94 @@
+//! 受け入れ（有効牌）の計算
+//!
+//! ツモ牌のない手牌について、向聴数が下がる牌と、その牌が自分から見て何枚残っているかを求める。
+//! 牌効率の練習ツールや CPU の打牌選択の土台として用いる。
+
+use crate::error::{MahjongError, Result};
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::{calc_shanten_number, validate_tiles_for_mode};
+use crate::settings::Settings;
+use crate::tile::*;
+
+/// 有効牌と、その残り枚数の一覧を返す（牌種の昇順）
+///
+/// ツモ牌のない手牌（副露がなければ13枚）を対象とし、各牌種を1枚加えたときに
+/// 向聴数が下がるものについて、自分の手牌・副露で使っていない残り枚数を返す。
+/// 残り枚数が0枚の牌（自分で4枚使い切っている牌や、三人麻雀の二萬〜八萬）は含めない。
+pub fn calc_ukeire(hand: &Hand, settings: &Settings) -> Result<Vec<(Tile, u8)>> {
+    validate_tiles_for_mode(hand, settings)?;
+    if hand.drawn().is_some() {
+        return Err(MahjongError::InvalidHand(
+            "ukeire is defined for a hand without a drawn tile".to_string(),
+        ));
+    }
+
+    let mut held = [0u32; Tile::LEN];
+    for tile in hand.tiles() {
+        held[tile.get() as usize] += 1;
+    }
+    for meld in hand.melds() {
+        for tile in meld.expanded_tiles() {
+            held[tile.get() as usize] += 1;
+        }
+    }
+
+    let current = calc_shanten_number(hand);
+    let mut result = Vec::new();
+    for tile_type in 0..Tile::LEN as TileType {
+        let remaining = copies_in_wall(tile_type, settings.three_player)
+            .saturating_sub(held[tile_type as usize]);
+        if remaining == 0 {
+            continue;
+        }
+        let mut test_hand = hand.clone();
+        test_hand.set_drawn(Some(Tile::new(tile_type)));
+        if calc_shanten_number(&test_hand) < current {
+            result.push((Tile::new(tile_type), remaining as u8));
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ukeire_ryanmen_tenpai() {
+        // 23m 待ち: 1m・4m が各4枚
+        let hand = Hand::from("23m456p789s111z55z");
+        let ukeire = calc_ukeire(&hand, &Settings::new()).unwrap();
+        assert_eq!(
+            ukeire,
+            vec![(Tile::new(Tile::M1), 4), (Tile::new(Tile::M4), 4)]
+        );
+    }
+
+    #[test]
+    fn test_ukeire_excludes_own_tiles() {
+        // 1222m の 1m・3m 待ちで、1m は手牌で1枚、2m は3枚使っている
+        let hand = Hand::from("1222m456p789s111z");
+        let ukeire = calc_ukeire(&hand, &Settings::new()).unwrap();
+        assert_eq!(
+            ukeire,
+            vec![(Tile::new(Tile::M1), 3), (Tile::new(Tile::M3), 4)]
+        );
+    }
+
+    #[test]
+    fn test_ukeire_three_player_skips_unused_tiles() {
+        let mut three = Settings::new();
+        three.three_player = true;
+        // 1m 単騎。二萬〜八萬は牌山にないので数えない
+        let hand = Hand::from("1m111p456p789s111z");
+        let ukeire = calc_ukeire(&hand, &three).unwrap();
+        assert_eq!(ukeire, vec![(Tile::new(Tile::M1), 3)]);
+    }
+
+    #[test]
+    fn test_ukeire_rejects_drawn_tile() {
+        let hand = Hand::from("23m456p789s111z55z 1m");
+        assert!(calc_ukeire(&hand, &Settings::new()).is_err());
+    }
+}
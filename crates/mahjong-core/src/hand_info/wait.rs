@@ -0,0 +1,506 @@
+//! 聴牌形の待ちの分類
+//!
+//! 聴牌している手牌について、和了牌ごとの基本的な待ち（両面・嵌張・辺張・単騎・双碰）と、
+//! それらの組み合わせからなる名前付きの多面待ち（ノベタン・三面張・亜両面など）を求める。
+
+use crate::error::{MahjongError, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::validate_tiles_for_mode;
+use crate::settings::Settings;
+use crate::tile::*;
+use crate::tile_counts::TileCounts;
+
+/// 待ちの形
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WaitShape {
+    /// 両面（例: 34 で 2・5 待ち）
+    Ryanmen,
+    /// 嵌張（例: 35 で 4 待ち）
+    Kanchan,
+    /// 辺張（例: 12 で 3 待ち）
+    Penchan,
+    /// 単騎（例: 3 で 3 待ち）
+    Tanki,
+    /// 双碰（例: 33・55 で 3・5 待ち）
+    Shanpon,
+    /// 七対子の単騎
+    SevenPairs,
+    /// 国士無双の単騎
+    ThirteenOrphans,
+    /// 国士無双の十三面待ち
+    ThirteenOrphansThirteenSided,
+    /// ノベタン（例: 3456 で 3・6 待ち）
+    Nobetan,
+    /// 三面張（例: 34567 で 2・5・8 待ち）
+    Sanmenchan,
+    /// 亜両面（例: 3444 で 2・3・5 待ち）
+    Aryanmen,
+    /// 亜嵌張（例: 3555 で 3・4 待ち）
+    Akanchan,
+    /// 煙突（例: 66678 と対子 44 で 6・9 と 4 待ち）
+    Entotsu,
+}
+
+/// 和了牌1種類分の待ち
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wait {
+    /// 和了牌
+    pub tile: TileType,
+    /// その牌で和了るときに取りうる基本的な待ちの形
+    pub shapes: Vec<WaitShape>,
+}
+
+/// 聴牌形の待ちの分類結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitClassification {
+    /// 和了牌ごとの待ち（牌種の昇順）
+    pub waits: Vec<Wait>,
+    /// 名前付きの多面待ち（該当がなければ空）
+    pub patterns: Vec<WaitShape>,
+}
+
+impl WaitClassification {
+    /// 和了牌の一覧を返す
+    pub fn tiles(&self) -> Vec<TileType> {
+        self.waits.iter().map(|w| w.tile).collect()
+    }
+
+    /// 指定した和了牌で取りうる基本的な待ちの形を返す
+    pub fn shapes_for(&self, tile: TileType) -> &[WaitShape] {
+        self.waits
+            .iter()
+            .find(|w| w.tile == tile)
+            .map_or(&[], |w| w.shapes.as_slice())
+    }
+
+    fn has(&self, tile: TileType, shape: WaitShape) -> bool {
+        self.shapes_for(tile).contains(&shape)
+    }
+}
+
+/// ツモ牌のない手牌の和了牌を返す（牌種の昇順）
+///
+/// 手牌で4枚使い切っている牌も含む。
+pub fn winning_tiles(hand: &Hand) -> Result<Vec<TileType>> {
+    if hand.drawn().is_some() {
+        return Err(MahjongError::InvalidHand(
+            "waits are defined for a hand without a drawn tile".to_string(),
+        ));
+    }
+    Ok(hand.winning_tile_types())
+}
+
+/// ツモ牌のない手牌の和了牌の種類数を返す
+pub fn count_waits(hand: &Hand) -> Result<usize> {
+    Ok(winning_tiles(hand)?.len())
+}
+
+/// 聴牌を維持する打牌
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenpaiDiscard {
+    /// 打牌
+    pub discard: Tile,
+    /// 打牌後の和了牌（牌種の昇順）
+    pub waits: Vec<TileType>,
+    /// 和了牌の残り枚数の合計（自分の手牌・副露で使っていない枚数）
+    pub remaining: u32,
+}
+
+/// ツモ牌のある手牌から、聴牌を維持する打牌を全て返す
+///
+/// 同じ牌（赤ドラの区別を含む）は1つにまとめ、手牌の並び順で返す。
+pub fn tenpai_discards(hand: &Hand, settings: &Settings) -> Result<Vec<TenpaiDiscard>> {
+    validate_tiles_for_mode(hand, settings)?;
+    let held = held_counts(hand);
+
+    let mut result = Vec::new();
+    for (discard, test_hand) in hands_after_discard(hand)? {
+        let waits = winning_tiles(&test_hand)?;
+        if waits.is_empty() {
+            continue;
+        }
+        let remaining = waits
+            .iter()
+            .map(|&t| copies_in_wall(t, settings.three_player).saturating_sub(held[t as usize]))
+            .sum();
+        result.push(TenpaiDiscard {
+            discard,
+            waits,
+            remaining,
+        });
+    }
+    Ok(result)
+}
+
+/// ツモ牌のある手牌について、打牌ごとの打牌後の手牌を返す
+///
+/// 同じ牌（赤ドラの区別を含む）は1つにまとめ、手牌の並び順で返す。
+pub(crate) fn hands_after_discard(hand: &Hand) -> Result<Vec<(Tile, Hand)>> {
+    let Some(drawn) = hand.drawn() else {
+        return Err(MahjongError::InvalidHand(
+            "discards are defined for a hand with a drawn tile".to_string(),
+        ));
+    };
+
+    let mut all_tiles = hand.tiles().to_vec();
+    all_tiles.push(drawn);
+
+    let mut result: Vec<(Tile, Hand)> = Vec::new();
+    for (i, &discard) in all_tiles.iter().enumerate() {
+        if result.iter().any(|(d, _)| *d == discard) {
+            continue;
+        }
+        let mut rest = all_tiles.clone();
+        rest.remove(i);
+        let mut after = hand.clone();
+        *after.tiles_mut() = rest;
+        after.set_drawn(None);
+        result.push((discard, after));
+    }
+    Ok(result)
+}
+
+/// ツモ牌のある手牌から、打牌後の和了牌の種類が最も多くなる打牌を返す
+///
+/// 種類数が同じ場合は残り枚数の多いものを優先する。聴牌を維持できない場合は None を返す。
+pub fn widest_wait_discard(hand: &Hand, settings: &Settings) -> Result<Option<TenpaiDiscard>> {
+    let mut best: Option<TenpaiDiscard> = None;
+    for candidate in tenpai_discards(hand, settings)? {
+        let is_better = best.as_ref().is_none_or(|b| {
+            (candidate.waits.len(), candidate.remaining) > (b.waits.len(), b.remaining)
+        });
+        if is_better {
+            best = Some(candidate);
+        }
+    }
+    Ok(best)
+}
+
+/// 手牌・ツモ牌・副露で使っている牌種ごとの枚数を返す
+fn held_counts(hand: &Hand) -> TileSummarize {
+    let mut counts: TileSummarize = [0; Tile::LEN];
+    let melded = hand.melds().iter().flat_map(|m| m.expanded_tiles());
+    for tile in hand
+        .tiles()
+        .iter()
+        .copied()
+        .chain(hand.drawn())
+        .chain(melded)
+    {
+        counts[tile.get() as usize] += 1;
+    }
+    counts
+}
+
+/// 聴牌形の待ちを分類する
+///
+/// ツモ牌のない手牌を対象とし、聴牌していない場合は和了牌のない結果を返す。
+///
+/// # Examples
+///
+/// ```
+/// use mahjong_core::hand::Hand;
+/// use mahjong_core::hand_info::wait::*;
+/// use mahjong_core::tile::Tile;
+///
+/// let waits = classify_waits(&Hand::from("111m456p999s3456s")).unwrap();
+/// assert_eq!(waits.tiles(), vec![Tile::S3, Tile::S6]);
+/// assert_eq!(waits.patterns, vec![WaitShape::Nobetan]);
+/// ```
+pub fn classify_waits(hand: &Hand) -> Result<WaitClassification> {
+    let counts = concealed_counts(hand);
+    let is_closed = hand.has_no_melds();
+
+    let mut waits = Vec::new();
+    for tile in winning_tiles(hand)? {
+        let mut completed = counts;
+        completed[tile as usize] += 1;
+
+        let mut shapes = Vec::new();
+        collect_normal_shapes(&completed, tile, &mut shapes);
+        if is_closed && is_seven_pairs(&completed) {
+            shapes.push(WaitShape::SevenPairs);
+        }
+        if is_closed && is_thirteen_orphans(&completed) {
+            if counts[tile as usize] == 1 && orphan_kinds(&counts) == 13 {
+                shapes.push(WaitShape::ThirteenOrphansThirteenSided);
+            } else {
+                shapes.push(WaitShape::ThirteenOrphans);
+            }
+        }
+        shapes.sort();
+        shapes.dedup();
+        waits.push(Wait { tile, shapes });
+    }
+
+    let mut result = WaitClassification {
+        waits,
+        patterns: Vec::new(),
+    };
+    result.patterns = find_patterns(&result);
+    Ok(result)
+}
+
+/// 副露を除いた手牌の牌種ごとの枚数を返す
+fn concealed_counts(hand: &Hand) -> TileCounts {
+    hand.tiles().iter().collect()
+}
+
+/// 通常形の全ての分解について、和了牌を含むブロックから待ちの形を集める
+fn collect_normal_shapes(counts: &TileSummarize, winning: TileType, shapes: &mut Vec<WaitShape>) {
+    for pair in 0..Tile::LEN {
+        if counts[pair] < 2 {
+            continue;
+        }
+        let mut rest = *counts;
+        rest[pair] -= 2;
+        let mut sets = Vec::new();
+        decompose_sets(&mut rest, 0, &mut sets, &mut |sets| {
+            if pair as TileType == winning {
+                shapes.push(WaitShape::Tanki);
+            }
+            for set in sets {
+                if let Some(shape) = shape_in_set(*set, winning) {
+                    shapes.push(shape);
+                }
+            }
+        });
+    }
+}
+
+/// 面子（刻子は`(牌, true)`、順子は`(先頭の牌, false)`）
+type Set = (TileType, bool);
+
+/// 残りの牌を全て面子に分解する全ての方法について `found` を呼ぶ
+fn decompose_sets(
+    counts: &mut TileSummarize,
+    start: usize,
+    sets: &mut Vec<Set>,
+    found: &mut dyn FnMut(&[Set]),
+) {
+    let Some(i) = (start..Tile::LEN).find(|&i| counts[i] > 0) else {
+        found(sets);
+        return;
+    };
+
+    if counts[i] >= 3 {
+        counts[i] -= 3;
+        sets.push((i as TileType, true));
+        decompose_sets(counts, i, sets, found);
+        sets.pop();
+        counts[i] += 3;
+    }
+
+    let tile = i as TileType;
+    if suit_rank(tile).is_some_and(|rank| rank <= 7) && counts[i + 1] > 0 && counts[i + 2] > 0 {
+        counts[i] -= 1;
+        counts[i + 1] -= 1;
+        counts[i + 2] -= 1;
+        sets.push((tile, false));
+        decompose_sets(counts, i, sets, found);
+        sets.pop();
+        counts[i] += 1;
+        counts[i + 1] += 1;
+        counts[i + 2] += 1;
+    }
+}
+
+/// 和了牌が面子のどの位置で使われたかから待ちの形を返す
+fn shape_in_set((head, is_triplet): Set, winning: TileType) -> Option<WaitShape> {
+    if is_triplet {
+        return (head == winning).then_some(WaitShape::Shanpon);
+    }
+    if winning == head + 1 {
+        return Some(WaitShape::Kanchan);
+    }
+    let rank = suit_rank(head)?;
+    if winning == head {
+        // 789 の 7 は辺張
+        return Some(if rank == 7 {
+            WaitShape::Penchan
+        } else {
+            WaitShape::Ryanmen
+        });
+    }
+    if winning == head + 2 {
+        // 123 の 3 は辺張
+        return Some(if rank == 1 {
+            WaitShape::Penchan
+        } else {
+            WaitShape::Ryanmen
+        });
+    }
+    None
+}
+
+fn is_seven_pairs(counts: &TileSummarize) -> bool {
+    counts.iter().filter(|&&c| c == 2).count() == 7
+}
+
+fn is_thirteen_orphans(counts: &TileSummarize) -> bool {
+    orphan_kinds(counts) == 13
+        && counts
+            .iter()
+            .enumerate()
+            .all(|(i, &c)| c == 0 || Tile::new(i as TileType).is_1_9_honour())
+}
+
+/// 么九牌の種類数を返す
+fn orphan_kinds(counts: &TileSummarize) -> usize {
+    counts
+        .iter()
+        .enumerate()
+        .filter(|&(i, &c)| c > 0 && Tile::new(i as TileType).is_1_9_honour())
+        .count()
+}
+
+/// 同じスートで `tile` から `offset` 離れた牌を返す
+fn shifted(tile: TileType, offset: i32) -> Option<TileType> {
+    let rank = suit_rank(tile)? as i32 + offset;
+    (1..=9)
+        .contains(&rank)
+        .then(|| (tile as i32 + offset) as TileType)
+}
+
+/// 和了牌ごとの待ちの形から、名前付きの多面待ちを求める
+fn find_patterns(result: &WaitClassification) -> Vec<WaitShape> {
+    use WaitShape::*;
+
+    let has = |tile: Option<TileType>, shape| tile.is_some_and(|t| result.has(t, shape));
+    let mut patterns = Vec::new();
+    for wait in &result.waits {
+        let t = wait.tile;
+        let at = |offset| shifted(t, offset);
+        if has(Some(t), Ryanmen) && has(at(3), Ryanmen) && has(at(6), Ryanmen) {
+            patterns.push(Sanmenchan);
+        }
+        if has(Some(t), Tanki) && has(at(3), Tanki) {
+            patterns.push(Nobetan);
+        }
+        if has(Some(t), Tanki)
+            && ((has(at(-1), Ryanmen) && has(at(2), Ryanmen))
+                || (has(at(-2), Ryanmen) && has(at(1), Ryanmen)))
+        {
+            patterns.push(Aryanmen);
+        }
+        if has(Some(t), Tanki) && (has(at(1), Kanchan) || has(at(-1), Kanchan)) {
+            patterns.push(Akanchan);
+        }
+        if has(Some(t), Shanpon)
+            && has(Some(t), Ryanmen)
+            && (has(at(3), Ryanmen) || has(at(-3), Ryanmen))
+        {
+            patterns.push(Entotsu);
+        }
+    }
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn tiles(s: &str) -> Vec<TileType> {
+        Hand::from(s).tiles().iter().map(|t| t.get()).collect()
+    }
+
+    #[rstest]
+    #[case("123m456p789s11z34s", "25s", &[])]
+    #[case("111m456p999s3456s", "36s", &[WaitShape::Nobetan])]
+    #[case("123m456p11z34567s", "258s", &[WaitShape::Sanmenchan])]
+    #[case("123m456p789s3444s", "235s", &[WaitShape::Aryanmen])]
+    #[case("123m456p789s3555s", "34s", &[WaitShape::Akanchan])]
+    #[case("123m456p44z66678s", "69s4z", &[WaitShape::Entotsu])]
+    fn classify_patterns_test(
+        #[case] hand: &str,
+        #[case] expected_tiles: &str,
+        #[case] expected_patterns: &[WaitShape],
+    ) {
+        let result = classify_waits(&Hand::from(hand)).unwrap();
+        let mut expected = tiles(expected_tiles);
+        expected.sort();
+        assert_eq!(result.tiles(), expected);
+        assert_eq!(result.patterns, expected_patterns);
+    }
+
+    #[test]
+    fn basic_shapes_test() {
+        let result = classify_waits(&Hand::from("123m456p789s11z12s")).unwrap();
+        assert_eq!(result.shapes_for(Tile::S3), &[WaitShape::Penchan]);
+
+        let result = classify_waits(&Hand::from("123m456p789s11z13s")).unwrap();
+        assert_eq!(result.shapes_for(Tile::S2), &[WaitShape::Kanchan]);
+
+        let result = classify_waits(&Hand::from("123m456p789s11z22s")).unwrap();
+        assert_eq!(result.shapes_for(Tile::Z1), &[WaitShape::Shanpon]);
+        assert_eq!(result.shapes_for(Tile::S2), &[WaitShape::Shanpon]);
+    }
+
+    #[test]
+    fn special_forms_test() {
+        let result = classify_waits(&Hand::from("1199m1199p1199s1z")).unwrap();
+        assert_eq!(result.tiles(), vec![Tile::Z1]);
+        assert_eq!(result.shapes_for(Tile::Z1), &[WaitShape::SevenPairs]);
+
+        let result = classify_waits(&Hand::from("19m19p19s1234567z")).unwrap();
+        assert_eq!(result.waits.len(), 13);
+        assert_eq!(
+            result.shapes_for(Tile::M1),
+            &[WaitShape::ThirteenOrphansThirteenSided]
+        );
+
+        let result = classify_waits(&Hand::from("119m19p19s123456z")).unwrap();
+        assert_eq!(result.tiles(), vec![Tile::Z7]);
+        assert_eq!(result.shapes_for(Tile::Z7), &[WaitShape::ThirteenOrphans]);
+    }
+
+    #[test]
+    fn count_waits_test() {
+        assert_eq!(count_waits(&Hand::from("123m456p11z34567s")).unwrap(), 3);
+        assert_eq!(count_waits(&Hand::from("159m159p159s1234z")).unwrap(), 0);
+    }
+
+    /// 11112345678999m から 1m を切ると 1〜9m の九面待ちになる
+    #[test]
+    fn widest_wait_discard_test() {
+        let hand = Hand::from("1112345678999m 1m");
+        let settings = Settings::new();
+        let best = widest_wait_discard(&hand, &settings).unwrap().unwrap();
+        assert_eq!(best.discard, Tile::new(Tile::M1));
+        assert_eq!(best.waits.len(), 9);
+
+        let discards = tenpai_discards(&hand, &settings).unwrap();
+        assert!(discards.iter().all(|d| !d.waits.is_empty()));
+        assert!(discards.iter().any(|d| d.discard == Tile::new(Tile::M9)));
+    }
+
+    #[test]
+    fn widest_wait_discard_remaining_test() {
+        // 打1s で 14s 待ち（切った 1s を除き 1s 残り3枚・4s 残り4枚）
+        let hand = Hand::from("123m456p789m11z23s 1s");
+        let best = widest_wait_discard(&hand, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(best.waits.len(), 2);
+        assert_eq!(best.remaining, 7);
+        assert!(
+            widest_wait_discard(&Hand::from("159m159p159s1234z 7z"), &Settings::new())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn not_ready_test() {
+        let result = classify_waits(&Hand::from("159m159p159s1234z")).unwrap();
+        assert!(result.waits.is_empty());
+        assert!(classify_waits(&Hand::from("123m456p789s11z34s 5s")).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+/// 天鳳の牌譜
+#[cfg(feature = "tenhou")]
+pub mod tenhou;
@@ -0,0 +1,802 @@
+//! 天鳳の牌譜の読み込み
+//!
+//! tenhou.net/6 形式の JSON 牌譜を局ごとに読み込み、ツモ・打牌・鳴きのイベント列と
+//! 局終了時の手牌を復元する。和了した座席の手牌は和了牌をツモ牌として持つため、
+//! そのまま `HandAnalyzer`・`calculate_score` に渡して点数計算の検証に使える。
+//!
+//! tenhou.net/6 形式の牌は2桁の数値で表す（11〜19: 萬子、21〜29: 筒子、31〜39: 索子、
+//! 41〜47: 東南西北白發中、51〜53: 赤五萬・赤五筒・赤五索）。
+//! mjlog（XML）形式の 0〜135 の牌 ID は [`tile_from_id`] で変換する。
+
+use crate::error::{MahjongError, Result};
+use serde_json::Value;
+
+use crate::hand::Hand;
+use crate::hand_info::meld::{Meld, MeldFrom, MeldType};
+use crate::tile::{Tile, TileType};
+
+/// 打牌欄でツモ切りを表す値
+const TSUMOGIRI: u32 = 60;
+
+/// 牌譜1つ分（半荘）
+#[derive(Debug, Clone)]
+pub struct TenhouLog {
+    /// 対局者名（起家から順）
+    pub names: Vec<String>,
+    /// 各局
+    pub rounds: Vec<TenhouRound>,
+}
+
+/// 1局分の記録
+#[derive(Debug, Clone)]
+pub struct TenhouRound {
+    /// 局のインデックス（0 = 東1局、4 = 南1局、…）
+    pub kyoku: usize,
+    /// 本場
+    pub honba: u32,
+    /// 供託の立直棒の本数
+    pub riichi_sticks: u32,
+    /// 局開始時の持ち点（起家から順）
+    pub scores: [i32; 4],
+    /// ドラ表示牌（カンドラを含む）
+    pub dora_indicators: Vec<Tile>,
+    /// 裏ドラ表示牌（立直して和了した場合のみ記録される）
+    pub ura_dora_indicators: Vec<Tile>,
+    /// 配牌
+    pub initial_hands: [Vec<Tile>; 4],
+    /// ツモ・打牌・鳴きを起きた順に並べたもの
+    pub events: Vec<TenhouEvent>,
+    /// 局終了時の手牌（和了した座席は和了牌をツモ牌として持つ）
+    pub hands: [Hand; 4],
+    /// 局の結果
+    pub result: TenhouResult,
+}
+
+/// 局中の出来事
+#[derive(Debug, Clone)]
+pub enum TenhouEvent {
+    /// ツモ（嶺上牌を含む）
+    Draw { seat: usize, tile: Tile },
+    /// 打牌
+    Discard {
+        seat: usize,
+        tile: Tile,
+        is_tsumogiri: bool,
+        is_riichi: bool,
+    },
+    /// チー・ポン・カン（加カンは加カン後の副露）
+    Call { seat: usize, meld: Meld },
+    /// 抜きドラ（三人麻雀の北抜き）
+    Nuki { seat: usize, tile: Tile },
+}
+
+/// 局の結果
+#[derive(Debug, Clone)]
+pub enum TenhouResult {
+    /// 和了（ダブロンでは複数）
+    Win(Vec<TenhouWin>),
+    /// 流局（流局の種類は牌譜の表記のまま。例: "流局"、"九種九牌"）
+    Draw { reason: String, deltas: [i32; 4] },
+}
+
+/// 1人分の和了
+#[derive(Debug, Clone)]
+pub struct TenhouWin {
+    /// 和了した座席
+    pub seat: usize,
+    /// 放銃した座席（ツモ和了なら和了した座席と同じ）
+    pub from: usize,
+    /// 責任払いの座席（なければ和了した座席と同じ）
+    pub pao: usize,
+    /// 点数の移動
+    pub deltas: [i32; 4],
+    /// 符・翻・点数の表記（例: "30符1飜1000点"、"満貫8000点"）
+    pub summary: String,
+    /// 役の表記（例: "立直(1飜)"、"ドラ(2飜)"）
+    pub yaku: Vec<String>,
+}
+
+impl TenhouWin {
+    /// ツモ和了か
+    pub fn is_self_drawn(&self) -> bool {
+        self.seat == self.from
+    }
+}
+
+/// mjlog 形式の牌 ID（0〜135）を牌に変換する
+///
+/// ID を4で割った値が牌種になる。赤ドラありの牌譜では 16・52・88 が赤五萬・赤五筒・赤五索。
+pub fn tile_from_id(id: u32) -> Result<Tile> {
+    if id >= 136 {
+        return Err(MahjongError::InvalidLog(format!("invalid tile id: {}", id)));
+    }
+    let tile_type = id / 4;
+    if matches!(id, 16 | 52 | 88) {
+        Ok(Tile::new_red(tile_type))
+    } else {
+        Ok(Tile::new(tile_type))
+    }
+}
+
+/// tenhou.net/6 形式の牌（11〜53）を牌に変換する
+pub fn tile_from_code(code: u32) -> Result<Tile> {
+    let (suit, num) = (code / 10, code % 10);
+    match (suit, num) {
+        (1..=3, 1..=9) => Ok(Tile::new((suit - 1) * 9 + num - 1)),
+        (4, 1..=7) => Ok(Tile::new(Tile::Z1 + num - 1)),
+        (5, 1..=3) => Ok(Tile::new_red((num - 1) * 9 + 4)),
+        _ => Err(MahjongError::InvalidLog(format!(
+            "invalid tile code: {}",
+            code
+        ))),
+    }
+}
+
+/// tenhou.net/6 形式の JSON 牌譜を読み込む
+pub fn parse_tenhou6(json: &str) -> Result<TenhouLog> {
+    let root: Value = serde_json::from_str(json)?;
+    let names = root
+        .get("name")
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .map(|n| n.as_str().unwrap_or_default().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let rounds = root
+        .get("log")
+        .and_then(Value::as_array)
+        .ok_or_else(|| MahjongError::InvalidLog("missing log".to_string()))?
+        .iter()
+        .map(parse_round)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(TenhouLog { names, rounds })
+}
+
+/// 配牌・ツモ欄の1項目
+enum TakeEntry {
+    Tile(Tile),
+    Call(Meld),
+}
+
+/// 打牌欄の1項目
+enum DiscardEntry {
+    Tile {
+        tile: Option<Tile>,
+        is_riichi: bool,
+    },
+    Kan(Meld),
+    Nuki(Tile),
+    /// 大明カンの直後に置かれる空欄
+    Skip,
+}
+
+/// 1局分の記録を読み込み、イベント列と手牌を復元する
+fn parse_round(value: &Value) -> Result<TenhouRound> {
+    let entries = value
+        .as_array()
+        .filter(|entries| entries.len() >= 17)
+        .ok_or_else(|| MahjongError::InvalidLog("malformed round".to_string()))?;
+
+    let header = int_array(&entries[0])?;
+    let [kyoku, honba, riichi_sticks] = header[..] else {
+        return Err(MahjongError::InvalidLog(
+            "malformed round header".to_string(),
+        ));
+    };
+    let scores = int_array(&entries[1])?;
+    let scores: [i32; 4] = std::array::from_fn(|i| scores.get(i).copied().unwrap_or(0) as i32);
+    let dora_indicators = tiles(&entries[2])?;
+    let ura_dora_indicators = tiles(&entries[3])?;
+
+    let mut initial_hands: [Vec<Tile>; 4] = Default::default();
+    let mut takes: [Vec<TakeEntry>; 4] = Default::default();
+    let mut drops: [Vec<DiscardEntry>; 4] = Default::default();
+    for seat in 0..4 {
+        initial_hands[seat] = tiles(&entries[4 + seat * 3])?;
+        takes[seat] = items(&entries[5 + seat * 3])?
+            .iter()
+            .map(|item| parse_take(item, seat))
+            .collect::<Result<_>>()?;
+        drops[seat] = items(&entries[6 + seat * 3])?
+            .iter()
+            .map(parse_drop)
+            .collect::<Result<_>>()?;
+    }
+
+    let mut replay = Replay::new(initial_hands.clone(), takes, drops);
+    replay.run(kyoku as usize % 4)?;
+
+    let result = parse_result(&entries[16])?;
+    if let TenhouResult::Win(wins) = &result {
+        for win in wins.iter().filter(|win| !win.is_self_drawn()) {
+            replay.hands[win.seat].drawn = replay.last_discard;
+        }
+    }
+
+    Ok(TenhouRound {
+        kyoku: kyoku as usize,
+        honba,
+        riichi_sticks,
+        scores,
+        dora_indicators,
+        ura_dora_indicators,
+        initial_hands,
+        events: replay.events,
+        hands: replay.hands.map(SeatHand::into_hand),
+        result,
+    })
+}
+
+/// 局の結果欄を読み込む
+fn parse_result(value: &Value) -> Result<TenhouResult> {
+    let entries = value
+        .as_array()
+        .filter(|entries| !entries.is_empty())
+        .ok_or_else(|| MahjongError::InvalidLog("malformed result".to_string()))?;
+    let reason = entries[0].as_str().unwrap_or_default().to_string();
+    if reason != "和了" {
+        let deltas = match entries.get(1) {
+            Some(deltas) => to_deltas(&int_array_signed(deltas)?),
+            None => [0; 4],
+        };
+        return Ok(TenhouResult::Draw { reason, deltas });
+    }
+
+    let mut wins = Vec::new();
+    for pair in entries[1..].chunks(2) {
+        let [deltas, info] = pair else {
+            return Err(MahjongError::InvalidLog("malformed win result".to_string()));
+        };
+        let info = info
+            .as_array()
+            .filter(|info| info.len() >= 4)
+            .ok_or_else(|| MahjongError::InvalidLog("malformed win info".to_string()))?;
+        let seat_of = |v: &Value| -> Result<usize> {
+            v.as_u64()
+                .filter(|&s| s < 4)
+                .map(|s| s as usize)
+                .ok_or_else(|| MahjongError::InvalidLog(format!("invalid seat: {}", v)))
+        };
+        wins.push(TenhouWin {
+            seat: seat_of(&info[0])?,
+            from: seat_of(&info[1])?,
+            pao: seat_of(&info[2])?,
+            deltas: to_deltas(&int_array_signed(deltas)?),
+            summary: info[3].as_str().unwrap_or_default().to_string(),
+            yaku: info[4..]
+                .iter()
+                .filter_map(|y| y.as_str().map(str::to_string))
+                .collect(),
+        });
+    }
+    Ok(TenhouResult::Win(wins))
+}
+
+/// ツモ欄の1項目を読み込む（数値はツモ牌、文字列はチー・ポン・大明カン）
+fn parse_take(value: &Value, seat: usize) -> Result<TakeEntry> {
+    if let Some(code) = value.as_u64() {
+        return Ok(TakeEntry::Tile(tile_from_code(code as u32)?));
+    }
+    let text = value
+        .as_str()
+        .ok_or_else(|| MahjongError::InvalidLog(format!("invalid take: {}", value)))?;
+    let (marker, pos, called, own) = split_meld(text)?;
+    let meld = match marker {
+        'c' if own.len() == 2 => {
+            let mut tiles = vec![called, own[0], own[1]];
+            tiles.sort();
+            Meld {
+                tiles,
+                category: MeldType::Chi,
+                from: MeldFrom::Previous,
+                called_tile: Some(called),
+            }
+        }
+        'p' if own.len() == 2 => Meld {
+            tiles: vec![own[0], own[1], called],
+            category: MeldType::Pon,
+            from: source_by_position(pos, 4)?,
+            called_tile: Some(called),
+        },
+        'm' if own.len() == 3 => Meld {
+            tiles: own,
+            category: MeldType::Kan,
+            from: source_by_position(pos, 6)?,
+            called_tile: Some(called),
+        },
+        _ => {
+            return Err(MahjongError::InvalidLog(format!(
+                "invalid call by seat {}: {}",
+                seat, text
+            )));
+        }
+    };
+    Ok(TakeEntry::Call(meld))
+}
+
+/// 打牌欄の1項目を読み込む（数値は打牌、文字列は立直・暗カン・加カン・抜きドラ）
+fn parse_drop(value: &Value) -> Result<DiscardEntry> {
+    if let Some(code) = value.as_u64() {
+        return Ok(match code as u32 {
+            0 => DiscardEntry::Skip,
+            TSUMOGIRI => DiscardEntry::Tile {
+                tile: None,
+                is_riichi: false,
+            },
+            code => DiscardEntry::Tile {
+                tile: Some(tile_from_code(code)?),
+                is_riichi: false,
+            },
+        });
+    }
+    let text = value
+        .as_str()
+        .ok_or_else(|| MahjongError::InvalidLog(format!("invalid discard: {}", value)))?;
+    if let Some(code) = text.strip_prefix('r') {
+        let code = parse_code(code)?;
+        let tile = if code == TSUMOGIRI {
+            None
+        } else {
+            Some(tile_from_code(code)?)
+        };
+        return Ok(DiscardEntry::Tile {
+            tile,
+            is_riichi: true,
+        });
+    }
+    if let Some(code) = text.strip_prefix('f') {
+        return Ok(DiscardEntry::Nuki(tile_from_code(parse_code(code)?)?));
+    }
+    let (marker, pos, called, own) = split_meld(text)?;
+    match marker {
+        'a' => {
+            let mut tiles = own;
+            tiles.push(called);
+            Ok(DiscardEntry::Kan(Meld {
+                tiles: stored_kan_tiles(tiles),
+                category: MeldType::Kan,
+                from: MeldFrom::Myself,
+                called_tile: None,
+            }))
+        }
+        'k' => Ok(DiscardEntry::Kan(Meld {
+            tiles: own,
+            category: MeldType::Kakan,
+            from: source_by_position(pos, 4)?,
+            called_tile: Some(called),
+        })),
+        _ => Err(MahjongError::InvalidLog(format!(
+            "invalid discard: {}",
+            text
+        ))),
+    }
+}
+
+/// 文字列中の牌の番号（`11`〜`47`, `51`〜`53`, `60`）を数値にする
+fn parse_code(text: &str) -> Result<u32> {
+    text.parse()
+        .map_err(|_| MahjongError::InvalidLog(format!("invalid tile code: {}", text)))
+}
+
+/// 鳴きの表記を（種類の文字, 文字の位置, 文字の直後の牌, その他の牌）に分ける
+fn split_meld(text: &str) -> Result<(char, usize, Tile, Vec<Tile>)> {
+    let pos = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .ok_or_else(|| MahjongError::InvalidLog(format!("invalid meld: {}", text)))?;
+    let marker = text[pos..].chars().next().unwrap_or_default();
+    let digits: String = text.chars().filter(char::is_ascii_digit).collect();
+    if !digits.len().is_multiple_of(2) || !pos.is_multiple_of(2) {
+        return Err(MahjongError::InvalidLog(format!("invalid meld: {}", text)));
+    }
+    let mut tiles = (0..digits.len())
+        .step_by(2)
+        .map(|i| tile_from_code(parse_code(&digits[i..i + 2])?))
+        .collect::<Result<Vec<_>>>()?;
+    if pos / 2 >= tiles.len() {
+        return Err(MahjongError::InvalidLog(format!("invalid meld: {}", text)));
+    }
+    let called = tiles.remove(pos / 2);
+    Ok((marker, pos, called, tiles))
+}
+
+/// 鳴きの表記上の文字の位置から、誰から鳴いたかを返す
+///
+/// 先頭なら上家、2文字目なら対面、`following` の位置なら下家。
+fn source_by_position(pos: usize, following: usize) -> Result<MeldFrom> {
+    match pos {
+        0 => Ok(MeldFrom::Previous),
+        2 => Ok(MeldFrom::Opposite),
+        p if p == following => Ok(MeldFrom::Following),
+        _ => Err(MahjongError::InvalidLog(format!(
+            "invalid meld source position: {}",
+            pos
+        ))),
+    }
+}
+
+/// 暗カンの4枚から、副露として保持する3枚を選ぶ（赤ドラを優先して残す）
+fn stored_kan_tiles(mut tiles: Vec<Tile>) -> Vec<Tile> {
+    tiles.sort_by_key(|t| !t.is_red_dora());
+    tiles.truncate(3);
+    tiles
+}
+
+/// 局の進行を再生する途中の手牌
+#[derive(Default)]
+struct SeatHand {
+    tiles: Vec<Tile>,
+    melds: Vec<Meld>,
+    drawn: Option<Tile>,
+    nuki: Vec<Tile>,
+}
+
+impl SeatHand {
+    /// ツモ牌を手牌に入れる
+    fn merge_drawn(&mut self) {
+        if let Some(tile) = self.drawn.take() {
+            self.tiles.push(tile);
+        }
+    }
+
+    /// 手牌から牌を1枚取り除く（赤ドラの区別が付かなければ同じ牌種の牌を取り除く）
+    fn remove(&mut self, tile: Tile) -> Result<Tile> {
+        let pos = self
+            .tiles
+            .iter()
+            .position(|t| *t == tile)
+            .or_else(|| self.tiles.iter().position(|t| t.get() == tile.get()))
+            .ok_or_else(|| MahjongError::InvalidLog(format!("tile not in hand: {}", tile)))?;
+        Ok(self.tiles.remove(pos))
+    }
+
+    fn remove_type(&mut self, tile_type: TileType) -> Result<Tile> {
+        self.remove(Tile::new(tile_type))
+    }
+
+    fn into_hand(mut self) -> Hand {
+        self.tiles.sort();
+        let mut hand = Hand::new_with_melds(self.tiles, self.melds, self.drawn);
+        for tile in self.nuki {
+            hand.add_nuki(tile);
+        }
+        hand
+    }
+}
+
+/// 座席ごとのツモ欄・打牌欄から、打牌と鳴きを突き合わせて局の進行を再生する
+struct Replay {
+    hands: [SeatHand; 4],
+    takes: [std::vec::IntoIter<TakeEntry>; 4],
+    drops: [std::vec::IntoIter<DiscardEntry>; 4],
+    /// 次に読むツモ欄の項目（鳴きの判定のために先読みする）
+    next_take: [Option<TakeEntry>; 4],
+    events: Vec<TenhouEvent>,
+    last_discard: Option<Tile>,
+}
+
+impl Replay {
+    fn new(
+        initial: [Vec<Tile>; 4],
+        takes: [Vec<TakeEntry>; 4],
+        drops: [Vec<DiscardEntry>; 4],
+    ) -> Replay {
+        let mut takes = takes.map(Vec::into_iter);
+        let next_take = std::array::from_fn(|seat| takes[seat].next());
+        Replay {
+            hands: initial.map(|tiles| SeatHand {
+                tiles,
+                ..SeatHand::default()
+            }),
+            takes,
+            drops: drops.map(Vec::into_iter),
+            next_take,
+            events: Vec::new(),
+            last_discard: None,
+        }
+    }
+
+    fn take(&mut self, seat: usize) -> Option<TakeEntry> {
+        let next = self.takes[seat].next();
+        std::mem::replace(&mut self.next_take[seat], next)
+    }
+
+    /// 親から順にツモ・打牌を再生する。ツモ欄か打牌欄が尽きたら局の終了とみなす
+    fn run(&mut self, dealer: usize) -> Result<()> {
+        let mut current = dealer;
+        let mut needs_draw = true;
+        loop {
+            if needs_draw && !self.draw(current)? {
+                return Ok(());
+            }
+            let Some(discard) = self.discard(current)? else {
+                return Ok(());
+            };
+            self.last_discard = Some(discard);
+            match self.find_caller(current, discard) {
+                Some(caller) => {
+                    let Some(TakeEntry::Call(meld)) = self.take(caller) else {
+                        unreachable!("find_caller only returns seats with a pending call");
+                    };
+                    let is_kan = meld.category == MeldType::Kan;
+                    self.call(caller, meld)?;
+                    current = caller;
+                    // 大明カンは打牌欄に空欄が入り、嶺上牌をツモってから打牌する
+                    needs_draw = is_kan;
+                }
+                None => {
+                    current = (current + 1) % 4;
+                    needs_draw = true;
+                }
+            }
+        }
+    }
+
+    /// ツモ欄から1枚ツモる。ツモ欄が尽きていれば false を返す
+    fn draw(&mut self, seat: usize) -> Result<bool> {
+        match self.take(seat) {
+            None => Ok(false),
+            Some(TakeEntry::Tile(tile)) => {
+                self.hands[seat].merge_drawn();
+                self.hands[seat].drawn = Some(tile);
+                self.events.push(TenhouEvent::Draw { seat, tile });
+                Ok(true)
+            }
+            Some(TakeEntry::Call(_)) => Err(MahjongError::InvalidLog(format!(
+                "unexpected call by seat {}",
+                seat
+            ))),
+        }
+    }
+
+    /// 打牌欄を読み、暗カン・加カン・抜きドラなら嶺上牌をツモって続ける。
+    /// 打牌欄が尽きていれば（ツモ和了など）None を返す
+    fn discard(&mut self, seat: usize) -> Result<Option<Tile>> {
+        loop {
+            match self.drops[seat].next() {
+                None => return Ok(None),
+                Some(DiscardEntry::Skip) => continue,
+                Some(DiscardEntry::Tile { tile, is_riichi }) => {
+                    let hand = &mut self.hands[seat];
+                    let (tile, is_tsumogiri) = match tile {
+                        None => {
+                            let tile = hand.drawn.take().ok_or_else(|| {
+                                MahjongError::InvalidLog(
+                                    "tsumogiri without a drawn tile".to_string(),
+                                )
+                            })?;
+                            (tile, true)
+                        }
+                        Some(tile) => {
+                            hand.merge_drawn();
+                            (hand.remove(tile)?, false)
+                        }
+                    };
+                    self.events.push(TenhouEvent::Discard {
+                        seat,
+                        tile,
+                        is_tsumogiri,
+                        is_riichi,
+                    });
+                    return Ok(Some(tile));
+                }
+                Some(DiscardEntry::Kan(meld)) => {
+                    let hand = &mut self.hands[seat];
+                    hand.merge_drawn();
+                    let tile_type = meld.tiles[0].get();
+                    let meld = if meld.category == MeldType::Kakan {
+                        let added = hand.remove(meld.called_tile.unwrap_or(meld.tiles[0]))?;
+                        let pon = hand
+                            .melds
+                            .iter_mut()
+                            .find(|m| m.category == MeldType::Pon && m.tiles[0].get() == tile_type)
+                            .ok_or_else(|| {
+                                MahjongError::InvalidLog("added kan without a pon".to_string())
+                            })?;
+                        pon.category = MeldType::Kakan;
+                        pon.called_tile = Some(added);
+                        pon.clone()
+                    } else {
+                        for _ in 0..4 {
+                            hand.remove_type(tile_type)?;
+                        }
+                        hand.melds.push(meld.clone());
+                        meld
+                    };
+                    self.events.push(TenhouEvent::Call { seat, meld });
+                    if !self.draw(seat)? {
+                        return Ok(None);
+                    }
+                }
+                Some(DiscardEntry::Nuki(tile)) => {
+                    let hand = &mut self.hands[seat];
+                    hand.merge_drawn();
+                    let tile = hand.remove(tile)?;
+                    hand.nuki.push(tile);
+                    self.events.push(TenhouEvent::Nuki { seat, tile });
+                    if !self.draw(seat)? {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 打牌を鳴く座席を探す（ポン・大明カンをチーより優先する）
+    fn find_caller(&self, discarder: usize, discard: Tile) -> Option<usize> {
+        let pending = |seat: usize| match &self.next_take[seat] {
+            Some(TakeEntry::Call(meld))
+                if meld.called_tile.map(|t| t.get()) == Some(discard.get()) =>
+            {
+                Some(meld)
+            }
+            _ => None,
+        };
+        (1..4)
+            .map(|offset| (discarder + offset) % 4)
+            .find(|&seat| {
+                pending(seat).is_some_and(|meld| {
+                    meld.category != MeldType::Chi && meld.from.source_seat(seat) == discarder
+                })
+            })
+            .or_else(|| {
+                let next = (discarder + 1) % 4;
+                pending(next)
+                    .is_some_and(|meld| meld.category == MeldType::Chi)
+                    .then_some(next)
+            })
+    }
+
+    /// チー・ポン・大明カンを手牌に反映する
+    fn call(&mut self, seat: usize, meld: Meld) -> Result<()> {
+        let hand = &mut self.hands[seat];
+        let called = meld.called_tile;
+        let mut remaining = meld.tiles.clone();
+        if meld.category != MeldType::Kan
+            && let Some(pos) = remaining.iter().position(|t| Some(*t) == called)
+        {
+            remaining.remove(pos);
+        }
+        for tile in remaining {
+            hand.remove(tile)?;
+        }
+        hand.melds.push(meld.clone());
+        self.events.push(TenhouEvent::Call { seat, meld });
+        Ok(())
+    }
+}
+
+fn items(value: &Value) -> Result<&Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| MahjongError::InvalidLog(format!("expected an array: {}", value)))
+}
+
+fn int_array(value: &Value) -> Result<Vec<u32>> {
+    items(value)?
+        .iter()
+        .map(|v| {
+            v.as_u64()
+                .map(|n| n as u32)
+                .ok_or_else(|| MahjongError::InvalidLog(format!("expected a number: {}", v)))
+        })
+        .collect()
+}
+
+fn int_array_signed(value: &Value) -> Result<Vec<i64>> {
+    items(value)?
+        .iter()
+        .map(|v| {
+            v.as_i64()
+                .ok_or_else(|| MahjongError::InvalidLog(format!("expected a number: {}", v)))
+        })
+        .collect()
+}
+
+fn to_deltas(values: &[i64]) -> [i32; 4] {
+    std::array::from_fn(|i| values.get(i).copied().unwrap_or(0) as i32)
+}
+
+fn tiles(value: &Value) -> Result<Vec<Tile>> {
+    int_array(value)?.into_iter().map(tile_from_code).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand_info::hand_analyzer::HandAnalyzer;
+
+    #[test]
+    fn tile_conversion_test() {
+        assert_eq!(tile_from_id(0).unwrap(), Tile::new(Tile::M1));
+        assert_eq!(tile_from_id(16).unwrap(), Tile::new_red(Tile::M5));
+        assert_eq!(tile_from_id(17).unwrap(), Tile::new(Tile::M5));
+        assert_eq!(tile_from_id(135).unwrap(), Tile::new(Tile::Z7));
+        assert!(tile_from_id(136).is_err());
+
+        assert_eq!(tile_from_code(11).unwrap(), Tile::new(Tile::M1));
+        assert_eq!(tile_from_code(29).unwrap(), Tile::new(Tile::P9));
+        assert_eq!(tile_from_code(47).unwrap(), Tile::new(Tile::Z7));
+        assert_eq!(tile_from_code(53).unwrap(), Tile::new_red(Tile::S5));
+        assert!(tile_from_code(48).is_err());
+        assert!(tile_from_code(10).is_err());
+    }
+
+    #[test]
+    fn parse_take_rejects_short_meld_test() {
+        assert!(parse_take(&Value::from("c11"), 1).is_err());
+        assert!(parse_take(&Value::from("p11"), 1).is_err());
+        assert!(parse_take(&Value::from("c373839"), 1).is_ok());
+    }
+
+    /// 親が 37 を切り、下家がチー、その打牌を親の対家がポンし、親が 46 でロンする局
+    const LOG: &str = r#"{
+        "name": ["A", "B", "C", "D"],
+        "log": [[
+            [0, 1, 0],
+            [25000, 25000, 25000, 25000],
+            [45],
+            [],
+            [11, 12, 13, 21, 22, 23, 31, 32, 33, 41, 41, 46, 37], [46], [37],
+            [38, 39, 47, 14, 15, 16, 24, 25, 26, 34, 35, 36, 43], ["c373839"], [47],
+            [11, 12, 13, 14, 15, 16, 17, 18, 19, 21, 22, 23, 24], [], [],
+            [47, 47, 46, 17, 18, 19, 27, 28, 29, 42, 42, 44, 44], ["47p4747"], [46],
+            ["和了", [3200, 0, 0, -3200], [0, 3, 0, "30符2飜2900点", "役牌 發(1飜)", "ドラ(1飜)"]]
+        ]]
+    }"#;
+
+    #[test]
+    fn parse_tenhou6_test() {
+        let log = parse_tenhou6(LOG).unwrap();
+        assert_eq!(log.names, vec!["A", "B", "C", "D"]);
+        let round = &log.rounds[0];
+        assert_eq!(round.honba, 1);
+        assert_eq!(round.dora_indicators, vec![Tile::new(Tile::Z5)]);
+
+        // ツモ → 打牌 → チー → 打牌 → ポン → 打牌
+        assert_eq!(round.events.len(), 6);
+        let TenhouEvent::Call { seat: 1, meld } = &round.events[2] else {
+            panic!("expected chi by seat 1");
+        };
+        assert_eq!(meld.category, MeldType::Chi);
+        let TenhouEvent::Call { seat: 3, meld } = &round.events[4] else {
+            panic!("expected pon by seat 3");
+        };
+        assert_eq!(meld.category, MeldType::Pon);
+        assert_eq!(meld.from, MeldFrom::Opposite);
+
+        let TenhouResult::Win(wins) = &round.result else {
+            panic!("expected a win");
+        };
+        assert_eq!(wins[0].seat, 0);
+        assert_eq!(wins[0].from, 3);
+        assert!(!wins[0].is_self_drawn());
+        assert_eq!(wins[0].deltas, [3200, 0, 0, -3200]);
+        assert_eq!(wins[0].yaku.len(), 2);
+
+        // 和了者の手牌はロン牌をツモ牌として持つ
+        let hand = &round.hands[0];
+        assert_eq!(hand.drawn(), Some(Tile::new(Tile::Z6)));
+        assert!(HandAnalyzer::new(hand).unwrap().shanten.has_won());
+        assert_eq!(round.hands[1].melds().len(), 1);
+        assert_eq!(round.hands[3].tiles().len(), 10);
+    }
+
+    #[test]
+    fn parse_kan_test() {
+        let DiscardEntry::Kan(meld) = parse_drop(&Value::from("151551a15")).unwrap() else {
+            panic!("expected a closed kan");
+        };
+        assert_eq!(meld.category, MeldType::Kan);
+        assert_eq!(meld.from, MeldFrom::Myself);
+        assert!(meld.tiles.iter().any(|t| t.is_red_dora()));
+
+        let TakeEntry::Call(meld) = parse_take(&Value::from("393939m39"), 0).unwrap() else {
+            panic!("expected an open kan");
+        };
+        assert_eq!(meld.category, MeldType::Kan);
+        assert_eq!(meld.from, MeldFrom::Following);
+    }
+}
@@ -1,14 +1,23 @@
 /// 卓
 pub mod board;
+/// エラー型
+pub mod error;
 /// 手牌
 pub mod hand;
 /// 手牌の情報（副露しているか、面子があるかなど）
 pub mod hand_info;
+/// 他のツールの形式との相互変換
+pub mod interop;
 /// 符計算・点数計算
 pub mod scoring;
 /// ルールなどの設定
 pub mod settings;
+/// SVG 形式での描画
+#[cfg(feature = "svg")]
+pub mod svg;
 /// 牌
 pub mod tile;
+/// 牌種ごとの枚数
+pub mod tile_counts;
 /// 和了役
 pub mod winning_hand;
@@ -3,3 +3,15 @@ pub mod fu;
 
 /// 点数計算
 pub mod score;
+
+/// 申告点数の検証
+pub mod verify;
+
+/// 立直宣言牌の候補
+pub mod riichi;
+
+/// 和了・流局時の点数移動
+pub mod payment;
+
+/// 翻・符から引く点数表
+pub mod table;
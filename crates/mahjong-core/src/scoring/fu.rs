@@ -1,14 +1,22 @@
-use anyhow::Result;
+use crate::error::Result;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 use crate::hand::Hand;
+use crate::hand_info::block::Sequential3;
 use crate::hand_info::hand_analyzer::HandAnalyzer;
-use crate::hand_info::meld::{MeldFrom, MeldType};
+use crate::hand_info::meld::MeldType;
+use crate::hand_info::pinfu::is_pinfu;
 use crate::hand_info::status::Status;
-use crate::tile::{Dragon, Tile, TileType, Wind, suit_rank};
+use crate::settings::Settings;
+use crate::tile::{Dragon, Tile, TileType, Wind};
 use crate::winning_hand::name::Form;
 
 /// 符計算の結果
-#[derive(Debug, PartialEq, Eq)]
+///
+/// 符の名称は静的な文字列のため、シリアライズのみに対応する。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct FuResult {
     /// 合計符（10符単位に切り上げ済み）
     pub total: u32,
@@ -18,6 +26,7 @@ pub struct FuResult {
 
 /// 符の内訳を表す構造体
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct FuDetail {
     /// 符の名称
     pub name: &'static str,
@@ -31,11 +40,19 @@ pub struct FuDetail {
 /// * `analyzer` - 手牌解析結果
 /// * `hand` - 手牌
 /// * `status` - 局の状態
+/// * `settings` - ルール設定（平和ツモの符の扱い）
 ///
 /// # Returns
 /// 符計算の結果（切り上げ済み合計 + 内訳）
-pub fn calculate_fu(analyzer: &HandAnalyzer, hand: &Hand, status: &Status) -> Result<FuResult> {
-    // 七対子は固定25符
+///
+/// 和了牌が複数のブロック（雀頭・順子・刻子）に当てはまる場合は、最も符が高くなる解釈を採用する。
+pub fn calculate_fu(
+    analyzer: &HandAnalyzer,
+    hand: &Hand,
+    status: &Status,
+    settings: &Settings,
+) -> Result<FuResult> {
+    // 七対子は固定25符（10符単位に切り上げない。点数は25符のまま基本点を計算する）
     if analyzer.form == Form::SevenPairs {
         return Ok(FuResult {
             total: 25,
@@ -46,17 +63,105 @@ pub fn calculate_fu(analyzer: &HandAnalyzer, hand: &Hand, status: &Status) -> Re
         });
     }
 
-    // 国士無双は符計算なし（便宜上30符）
+    // 国士無双は役満のため符を計算しない（点数計算の結果でも符は `None` になる）
     if analyzer.form == Form::ThirteenOrphans {
         return Ok(FuResult {
-            total: 30,
+            total: 0,
+            details: Vec::new(),
+        });
+    }
+
+    let pinfu = is_pinfu(analyzer, hand, status);
+
+    // 平和ツモは20符固定（設定で無効な場合はツモ符を加えて30符）
+    if pinfu && status.is_self_drawn && settings.pinfu_tsumo_20fu {
+        return Ok(FuResult {
+            total: 20,
             details: vec![FuDetail {
-                name: "国士無双",
-                fu: 30,
+                name: "平和ツモ",
+                fu: 20,
             }],
         });
     }
 
+    // 和了牌をどのブロックの完成に使ったかの解釈ごとに計算し、最も符が高いものを採用する
+    // （平和が成立する場合は平和と矛盾しない両面待ちの解釈に限る）
+    let mut candidates = winning_block_candidates(analyzer, hand);
+    if pinfu {
+        candidates.retain(
+            |block| matches!(block, WinningBlock::Sequence(seq) if is_two_sided(seq, hand)),
+        );
+    }
+
+    let mut best: Option<FuResult> = None;
+    for winning_block in candidates {
+        let result = calculate_fu_for(analyzer, hand, status, winning_block)?;
+        if best.as_ref().is_none_or(|b| result.total > b.total) {
+            best = Some(result);
+        }
+    }
+    match best {
+        Some(result) => Ok(result),
+        None => calculate_fu_for(analyzer, hand, status, WinningBlock::Unknown),
+    }
+}
+
+/// 和了牌で完成したブロックの解釈
+#[derive(Debug, Clone, Copy)]
+enum WinningBlock {
+    /// 和了牌が不明（待ちの符を付けない）
+    Unknown,
+    /// 雀頭（単騎待ち）
+    Head,
+    /// 順子（両面・嵌張・辺張待ち）
+    Sequence(Sequential3),
+    /// 刻子（双碰待ち）
+    Triplet(TileType),
+}
+
+/// 和了牌を含むブロックの解釈を列挙する
+///
+/// 副露した面子は和了牌で完成させられないため、手牌のブロックだけを候補にする。
+fn winning_block_candidates(analyzer: &HandAnalyzer, hand: &Hand) -> Vec<WinningBlock> {
+    let Some(winning_tile) = hand.drawn() else {
+        return vec![WinningBlock::Unknown];
+    };
+    let wt = winning_tile.get();
+
+    let mut candidates = Vec::new();
+    if analyzer.same2.iter().any(|head| head.get()[0] == wt) {
+        candidates.push(WinningBlock::Head);
+    }
+    candidates.extend(
+        analyzer
+            .concealed_sequential3(hand)
+            .into_iter()
+            .filter(|seq| seq.get().contains(&wt))
+            .map(WinningBlock::Sequence),
+    );
+    if analyzer
+        .concealed_same3(hand)
+        .iter()
+        .any(|same| same.get()[0] == wt)
+    {
+        candidates.push(WinningBlock::Triplet(wt));
+    }
+    candidates
+}
+
+/// 順子が和了牌に対して両面待ちかを返す
+fn is_two_sided(seq: &Sequential3, hand: &Hand) -> bool {
+    hand.drawn()
+        .is_some_and(|winning_tile| seq.is_two_sided_wait(winning_tile.get()))
+}
+
+/// 和了牌の解釈を1つに固定して符を計算する
+fn calculate_fu_for(
+    analyzer: &HandAnalyzer,
+    hand: &Hand,
+    status: &Status,
+    winning_block: WinningBlock,
+) -> Result<FuResult> {
     let mut details: Vec<FuDetail> = Vec::new();
 
     // 副底（基本符）：20符
@@ -66,13 +171,13 @@ pub fn calculate_fu(analyzer: &HandAnalyzer, hand: &Hand, status: &Status) -> Re
     });
 
     // 面子の符
-    calculate_mentsu_fu(analyzer, hand, status, &mut details)?;
+    calculate_mentsu_fu(analyzer, hand, status, winning_block, &mut details)?;
 
     // 雀頭の符
     calculate_jantou_fu(analyzer, status, &mut details)?;
 
     // 待ちの符
-    calculate_machi_fu(analyzer, hand, &mut details)?;
+    calculate_machi_fu(hand, winning_block, &mut details)?;
 
     // ツモ符
     calculate_tsumo_fu(analyzer, status, &mut details)?;
@@ -82,17 +187,6 @@ pub fn calculate_fu(analyzer: &HandAnalyzer, hand: &Hand, status: &Status) -> Re
 
     let raw_total: u32 = details.iter().map(|d| d.fu).sum();
 
-    // 平和ツモは20符固定
-    if is_pinfu(analyzer, hand, status) && status.is_self_drawn {
-        return Ok(FuResult {
-            total: 20,
-            details: vec![FuDetail {
-                name: "平和ツモ",
-                fu: 20,
-            }],
-        });
-    }
-
     // 鳴き平和形（副底のみ）のロンは30符
     let total = if raw_total == 20 && !status.is_self_drawn && status.has_claimed_open {
         30
@@ -109,58 +203,12 @@ fn round_up_to_10(fu: u32) -> u32 {
     fu.div_ceil(10) * 10
 }
 
-/// 平和判定（簡易版：符計算用）
-fn is_pinfu(analyzer: &HandAnalyzer, hand: &Hand, status: &Status) -> bool {
-    if status.has_claimed_open {
-        return false;
-    }
-    if analyzer.form != Form::Normal {
-        return false;
-    }
-    if analyzer.sequential3.len() != 4 || analyzer.same2.len() != 1 {
-        return false;
-    }
-    // 雀頭が役牌でないこと
-    for head in &analyzer.same2 {
-        let tile = head.get()[0];
-        if is_yakuhai_tile(tile, status) {
-            return false;
-        }
-    }
-    // 両面待ちであること
-    if let Some(winning_tile) = hand.drawn() {
-        for seq in &analyzer.sequential3 {
-            if seq.is_two_sided_wait(winning_tile.get()) {
-                return true;
-            }
-        }
-        return false;
-    }
-    false
-}
-
-/// 役牌かどうかを判定する
-fn is_yakuhai_tile(tile: TileType, status: &Status) -> bool {
-    // 三元牌
-    if Dragon::is_tile_type(tile).is_some() {
-        return true;
-    }
-    // 自風牌
-    if Wind::is_tile_type(tile) == Some(status.seat_wind) {
-        return true;
-    }
-    // 場風牌
-    if Wind::is_tile_type(tile) == Some(status.round_wind) {
-        return true;
-    }
-    false
-}
-
 /// 面子（刻子・槓子・順子）の符を計算する
 fn calculate_mentsu_fu(
     analyzer: &HandAnalyzer,
     hand: &Hand,
     status: &Status,
+    winning_block: WinningBlock,
     details: &mut Vec<FuDetail>,
 ) -> Result<()> {
     // 副露面子の牌種を収集（analyzer.same3 との重複排除用）
@@ -182,16 +230,9 @@ fn calculate_mentsu_fu(
 
         let is_terminal_or_honour = Tile::new(tile).is_1_9_honour();
 
-        // 和了牌を含む刻子がロン和了の場合は明刻扱い
-        let is_concealed = if !status.is_self_drawn {
-            if let Some(drawn) = hand.drawn() {
-                drawn.get() != tile
-            } else {
-                true
-            }
-        } else {
-            true
-        };
+        // ロン和了で和了牌により完成した刻子は明刻扱い
+        let is_concealed =
+            status.is_self_drawn || !matches!(winning_block, WinningBlock::Triplet(t) if t == tile);
 
         let fu = if is_concealed {
             if is_terminal_or_honour { 8 } else { 4 }
@@ -231,7 +272,7 @@ fn calculate_mentsu_fu(
             }
             MeldType::Kan | MeldType::Kakan => {
                 let is_terminal_or_honour = open.tiles[0].is_1_9_honour();
-                let is_concealed = open.from == MeldFrom::Myself;
+                let is_concealed = open.is_concealed();
                 let fu = if is_concealed {
                     if is_terminal_or_honour { 32 } else { 16 }
                 } else {
@@ -300,54 +341,35 @@ fn calculate_jantou_fu(
 
 /// 待ちの形による符を計算する
 fn calculate_machi_fu(
-    analyzer: &HandAnalyzer,
     hand: &Hand,
+    winning_block: WinningBlock,
     details: &mut Vec<FuDetail>,
 ) -> Result<()> {
-    if let Some(winning_tile) = hand.drawn() {
-        let wt = winning_tile.get();
+    let Some(winning_tile) = hand.drawn() else {
+        return Ok(());
+    };
+    let wt = winning_tile.get();
 
+    let name = match winning_block {
         // 単騎待ち: 雀頭で待っていた場合
-        for head in &analyzer.same2 {
-            if head.get()[0] == wt {
-                details.push(FuDetail {
-                    name: "単騎待ち",
-                    fu: 2,
-                });
-                return Ok(());
-            }
-        }
-
-        // 嵌張待ち・辺張待ち
-        for seq in &analyzer.sequential3 {
+        WinningBlock::Head => "単騎待ち",
+        WinningBlock::Sequence(seq) => {
             let tiles = seq.get();
-            // 嵌張待ち: 真ん中の牌で待っていた
             if wt == tiles[1] {
-                details.push(FuDetail {
-                    name: "嵌張待ち",
-                    fu: 2,
-                });
-                return Ok(());
-            }
-            // 辺張待ち: 123の3待ち or 789の7待ち
-            if wt == tiles[2] && suit_rank(tiles[2]) == Some(3) {
-                details.push(FuDetail {
-                    name: "辺張待ち",
-                    fu: 2,
-                });
-                return Ok(());
-            }
-            if wt == tiles[0] && suit_rank(tiles[0]) == Some(7) {
-                details.push(FuDetail {
-                    name: "辺張待ち",
-                    fu: 2,
-                });
+                // 嵌張待ち: 真ん中の牌で待っていた
+                "嵌張待ち"
+            } else if seq.is_edge_wait(wt) {
+                // 辺張待ち: 123の3待ち or 789の7待ち
+                "辺張待ち"
+            } else {
+                // 両面待ちは0符
                 return Ok(());
             }
         }
-
-        // 両面待ちや双碰待ちは0符
-    }
+        // 双碰待ちは0符
+        WinningBlock::Triplet(_) | WinningBlock::Unknown => return Ok(()),
+    };
+    details.push(FuDetail { name, fu: 2 });
 
     Ok(())
 }
@@ -399,10 +421,26 @@ mod tests {
         status.is_self_drawn = true;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         assert_eq!(result.total, 20);
     }
 
+    /// 平和ツモを20符としない設定では、ツモ符を加えて30符
+    #[test]
+    fn test_pinfu_tsumo_30fu_setting() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let mut settings = Settings::new();
+        settings.pinfu_tsumo_20fu = false;
+        let result = calculate_fu(&analyzer, &hand, &status, &settings).unwrap();
+        assert_eq!(result.total, 30);
+        assert!(result.details.iter().any(|d| d.name == "自摸" && d.fu == 2));
+    }
+
     /// 平和ロンは30符
     #[test]
     fn test_pinfu_ron() {
@@ -412,7 +450,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 = 30
         assert_eq!(result.total, 30);
     }
@@ -423,7 +461,7 @@ mod tests {
         let hand = Hand::from("1122m3344p5566s7z 7z");
         let analyzer = HandAnalyzer::new(&hand).unwrap();
         let status = Status::new();
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         assert_eq!(result.total, 25);
     }
 
@@ -438,7 +476,7 @@ mod tests {
         status.is_self_drawn = true;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 中張牌暗刻4(222m) + 単騎待ち2(3m) + ツモ2 = 28 -> 30
         assert_eq!(result.total, 30);
     }
@@ -453,11 +491,77 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 么九牌暗刻8(111m) + 単騎待ち2 = 40
         assert_eq!(result.total, 40);
     }
 
+    /// 和了牌が両面にも嵌張にも取れる場合は符の高い嵌張として数える
+    #[test]
+    fn test_winning_tile_takes_highest_fu_wait() {
+        // 345m 456m 111p 789s 55z: ロン5m（345mの両面 or 456mの嵌張）
+        let hand = Hand::from("34456m111p789s55z 5m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = false;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
+        // 副底20 + 門前加符10 + 么九牌暗刻8 + 三元牌雀頭2 + 嵌張待ち2 = 42 -> 50
+        assert_eq!(result.total, 50);
+        assert!(result.details.iter().any(|d| d.name == "嵌張待ち"));
+    }
+
+    /// 和了牌が順子にも刻子にも取れる場合は刻子を暗刻のまま数える
+    #[test]
+    fn test_ron_tile_in_sequence_keeps_triplet_concealed() {
+        // 111m 123m 456p 789s 99s: ロン1m（23mの両面 or 11mとの双碰）
+        let hand = Hand::from("11123m456p789s99s 1m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = false;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
+        assert!(result.details.iter().any(|d| d.name == "么九牌暗刻"));
+        assert_eq!(result.total, 40);
+    }
+
+    /// 和了牌がチーした順子にも含まれる場合、チーの順子は待ちの解釈に使わない
+    #[test]
+    fn test_ron_tile_also_in_called_chi() {
+        // 22m 55p 444s 777z + チー2'13m: ロン2m（22mとの双碰のみ）
+        let hand = Hand::from("22m55p444s777z 2'13m 2m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = false;
+        status.has_claimed_open = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
+        // 副底20 + 中張牌明刻2(222m) + 中張牌暗刻4(444s) + 么九牌暗刻8(777z) = 34 -> 40
+        assert!(!result.details.iter().any(|d| d.name == "嵌張待ち"));
+        assert!(result.details.iter().any(|d| d.name == "中張牌明刻"));
+        assert_eq!(result.total, 40);
+    }
+
+    /// 暗カンを含む手は門前のまま: 門前加符がつく
+    #[test]
+    fn test_concealed_kan_keeps_menzen_ron_bonus() {
+        // 123m 456p 78s 22z + 暗カン9999m + ロン9s
+        let hand = Hand::from("123m456p78s22z a9999m 9s");
+        assert!(hand.is_concealed());
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.has_claimed_open = !hand.is_concealed();
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
+        // 副底20 + 門前加符10 + 么九牌暗槓32(9999m) = 62 -> 70
+        assert_eq!(result.total, 70);
+        assert!(result.details.iter().any(|d| d.name == "門前加符"));
+    }
+
     /// ポンした明刻（中張牌）: 2符
     #[test]
     fn test_open_triplet_simple() {
@@ -469,7 +573,7 @@ mod tests {
         status.is_self_drawn = true;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 中張牌明刻2(222m) + 単騎待ち2(3m) + ツモ2 = 26 -> 30
         assert_eq!(result.total, 30);
     }
@@ -485,7 +589,7 @@ mod tests {
         status.is_self_drawn = true;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 么九牌明刻4(111m) + 単騎待ち2(3m) + ツモ2 = 28 -> 30
         assert_eq!(result.total, 30);
     }
@@ -501,7 +605,7 @@ mod tests {
         status.is_self_drawn = true;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // from=Unknownなので明槓扱い
         // 副底20 + 中張牌明槓8 + 単騎待ち2(3m) + ツモ2 = 32 -> 40
         assert_eq!(result.total, 40);
@@ -516,7 +620,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 三元牌雀頭2 + 単騎待ち2 = 34 -> 40
         assert_eq!(result.total, 40);
     }
@@ -530,7 +634,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::East;
         status.round_wind = Wind::South;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 自風牌雀頭2 + 単騎待ち2 = 34 -> 40
         assert_eq!(result.total, 40);
     }
@@ -544,7 +648,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 場風牌雀頭2 + 単騎待ち2 = 34 -> 40
         assert_eq!(result.total, 40);
     }
@@ -558,7 +662,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::East;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 自風牌雀頭2 + 場風牌雀頭2 + 単騎待ち2 = 36 -> 40
         assert_eq!(result.total, 40);
     }
@@ -572,7 +676,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::South;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 嵌張待ち2 = 32 -> 40
         assert_eq!(result.total, 40);
     }
@@ -586,7 +690,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::South;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 辺張待ち2 + 場風牌雀頭2(南=2z? いや1z=東) = 32 -> 40
         // 1z=東、場風南なので雀頭加符なし
         // 副底20 + 門前加符10 + 辺張待ち2 = 32 -> 40
@@ -602,7 +706,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::South;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         // 副底20 + 門前加符10 + 辺張待ち2 = 32 -> 40
         assert_eq!(result.total, 40);
     }
@@ -629,7 +733,7 @@ mod tests {
         status.is_self_drawn = false;
         status.seat_wind = Wind::South;
         status.round_wind = Wind::East;
-        let result = calculate_fu(&analyzer, &hand, &status).unwrap();
+        let result = calculate_fu(&analyzer, &hand, &status, &Settings::new()).unwrap();
         assert_eq!(result.total, 30);
     }
 }
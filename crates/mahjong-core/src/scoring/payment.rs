@@ -0,0 +1,358 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::hand::Hand;
+use crate::hand_info::meld::{PaoYakuman, pao_source};
+use crate::scoring::score::{ScoreItem, ScoreRank, ScoreResult};
+use crate::settings::{Renchan, Settings};
+
+/// 和了の方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WinMethod {
+    /// ツモ和了
+    Tsumo,
+    /// ロン和了（`loser` は放銃者の座席）
+    Ron { loser: usize },
+}
+
+/// 一局の精算結果（四人麻雀）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Settlement {
+    /// 各座席の点数変動（正=増加、負=減少）
+    ///
+    /// 供託の立直棒は和了者の増加分に含まれる（支払者がいないため、合計は供託分だけ正になる）
+    pub deltas: [i32; 4],
+    /// 親が連荘するか
+    pub dealer_repeats: bool,
+}
+
+/// 和了時の各座席の点数変動を求める
+///
+/// - `score`: 点数計算の結果（本場・供託は `score.honba` / `score.riichi_sticks` を使う）
+/// - `winner`: 和了者の座席 (0-3)
+/// - `method`: 和了の方法
+/// - `dealer`: 親の座席 (0-3)
+/// - `settings`: ルール設定（連荘の条件）
+pub fn settle_win(
+    score: &ScoreResult,
+    winner: usize,
+    method: WinMethod,
+    dealer: usize,
+    settings: &Settings,
+) -> Settlement {
+    Settlement {
+        deltas: win_payment_deltas(score, winner, method, dealer),
+        dealer_repeats: winner == dealer && settings.renchan != Renchan::Never,
+    }
+}
+
+/// 和了時の各座席の点数変動を求める（連荘の判定を含まない）
+///
+/// 本場・供託は `score.honba` / `score.riichi_sticks` を使う。
+/// 和了者の増加分は本場・供託を含む収入の合計で、戻り値の合計は供託の立直棒の点数に等しい。
+pub fn win_payment_deltas(
+    score: &ScoreResult,
+    winner: usize,
+    method: WinMethod,
+    dealer: usize,
+) -> [i32; 4] {
+    let is_tsumo = method == WinMethod::Tsumo;
+    let payments = score.payments(winner == dealer, is_tsumo, score.honba, score.riichi_sticks);
+    match method {
+        WinMethod::Tsumo => payments.tsumo_deltas(winner, dealer),
+        WinMethod::Ron { loser } => payments.ron_deltas(winner, loser),
+    }
+}
+
+/// 責任払い（包）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pao {
+    /// 責任払いの対象の役満
+    pub yakuman: PaoYakuman,
+    /// 責任を負う座席 (0-3)
+    pub liable: usize,
+}
+
+impl Pao {
+    /// 和了者の副露から責任払いを求める（[`pao_source`]）
+    ///
+    /// - `hand`: 和了者の手牌
+    /// - `winner`: 和了者の座席 (0-3)
+    pub fn from_hand(hand: &Hand, winner: usize) -> Option<Pao> {
+        let (yakuman, from) = pao_source(hand)?;
+        Some(Pao {
+            yakuman,
+            liable: from.source_seat(winner),
+        })
+    }
+}
+
+/// 責任払い（包）を反映した和了時の各座席の点数変動を求める
+///
+/// - ツモ: 包の役満の分は責任者がロン和了と同額を1人で支払い、本場も責任者が支払う
+/// - 責任者以外からのロン: 包の役満の分は責任者と放銃者が折半し、本場は放銃者が支払う
+///
+/// 他の役満と複合している場合、包の役満以外の分は通常どおり支払う。
+/// 責任者からのロンや、包の役満が成立していない場合は [`win_payment_deltas`] と同じ結果になる。
+/// 本場・供託は `score.honba` / `score.riichi_sticks` を使い、
+/// 戻り値の合計は供託の立直棒の点数（`score.riichi_sticks` × 1000）に等しい。
+pub fn pao_payment_deltas(
+    score: &ScoreResult,
+    winner: usize,
+    method: WinMethod,
+    dealer: usize,
+    pao: Pao,
+) -> [i32; 4] {
+    let normal_deltas = win_payment_deltas(score, winner, method, dealer);
+
+    let total_multiple = match score.rank {
+        ScoreRank::Yakuman => 1,
+        ScoreRank::MultipleYakuman(multiple) => multiple,
+        _ => 0,
+    };
+    let pao_multiple = score
+        .yaku_list
+        .iter()
+        .find(|(item, _)| *item == ScoreItem::Yaku(pao.yakuman.kind()))
+        .map_or(0, |(_, han)| han / 13)
+        .min(total_multiple);
+    if pao_multiple == 0 || pao.liable == winner || method == (WinMethod::Ron { loser: pao.liable })
+    {
+        return normal_deltas;
+    }
+
+    let ron_value = if winner == dealer {
+        score.dealer_ron
+    } else {
+        score.non_dealer_ron
+    } as i32;
+    let pao_value = ron_value * pao_multiple as i32 / total_multiple as i32;
+    let mut deltas = [0i32; 4];
+    match method {
+        WinMethod::Tsumo => {
+            // 包の役満以外の分は通常のツモの支払いを按分する
+            let base = score.payments(winner == dealer, true, 0, 0);
+            let rest_multiple = (total_multiple - pao_multiple) as i32;
+            for (seat, delta) in base.tsumo_deltas(winner, dealer).into_iter().enumerate() {
+                if seat != winner {
+                    deltas[seat] = delta * rest_multiple / total_multiple as i32;
+                }
+            }
+            deltas[pao.liable] -= pao_value + score.honba as i32 * 300;
+        }
+        WinMethod::Ron { loser } => {
+            deltas[pao.liable] -= pao_value / 2;
+            deltas[loser] -= ron_value - pao_value / 2 + score.honba as i32 * 300;
+        }
+    }
+    deltas[winner] = -deltas.iter().sum::<i32>() + score.riichi_sticks as i32 * 1000;
+    deltas
+}
+
+/// 荒牌流局時のノーテン罰符の点数移動を求める
+///
+/// 聴牌者と不聴者がいる場合のみ、不聴者が合計3000点を聴牌者に支払う。
+/// 戻り値の合計は必ず0。
+pub fn tenpai_payment_deltas(tenpai: &[usize]) -> [i32; 4] {
+    let mut deltas = [0i32; 4];
+    let tenpai_count = tenpai.len() as i32;
+    if tenpai_count == 0 || tenpai_count == 4 {
+        return deltas;
+    }
+
+    let gain_each = 3000 / tenpai_count;
+    let loss_each = 3000 / (4 - tenpai_count);
+    for (seat, delta) in deltas.iter_mut().enumerate() {
+        *delta = if tenpai.contains(&seat) {
+            gain_each
+        } else {
+            -loss_each
+        };
+    }
+    deltas
+}
+
+/// 荒牌流局時の精算結果を求める
+///
+/// - `tenpai`: 聴牌している座席
+/// - `dealer`: 親の座席 (0-3)
+/// - `settings`: ルール設定（連荘の条件）
+pub fn settle_exhaustive_draw(tenpai: &[usize], dealer: usize, settings: &Settings) -> Settlement {
+    Settlement {
+        deltas: tenpai_payment_deltas(tenpai),
+        dealer_repeats: tenpai.contains(&dealer) && settings.renchan == Renchan::WinOrTenpai,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::fu::FuResult;
+    use crate::scoring::score::DoraSummary;
+    use crate::winning_hand::name::Kind;
+
+    fn mangan(honba: u32, riichi_sticks: u32) -> ScoreResult {
+        ScoreResult {
+            han: 5,
+            fu: Some(30),
+            rank: ScoreRank::Mangan,
+            yaku_list: Vec::new(),
+            has_opened: false,
+            dealer_ron: 12000,
+            dealer_tsumo_all: 4000,
+            non_dealer_ron: 8000,
+            non_dealer_tsumo_dealer: 4000,
+            non_dealer_tsumo_non_dealer: 2000,
+            fu_result: FuResult {
+                total: 30,
+                details: Vec::new(),
+            },
+            dora_summary: DoraSummary::default(),
+            honba,
+            riichi_sticks,
+        }
+    }
+
+    /// 子の役満（`yaku_list` は各項目の翻数）
+    fn yakuman(yaku_list: Vec<(ScoreItem, u32)>) -> ScoreResult {
+        let multiple = yaku_list.iter().map(|(_, han)| han / 13).sum::<u32>();
+        ScoreResult {
+            han: 13 * multiple,
+            fu: None,
+            rank: if multiple >= 2 {
+                ScoreRank::MultipleYakuman(multiple)
+            } else {
+                ScoreRank::Yakuman
+            },
+            yaku_list,
+            has_opened: true,
+            dealer_ron: 48000 * multiple,
+            dealer_tsumo_all: 16000 * multiple,
+            non_dealer_ron: 32000 * multiple,
+            non_dealer_tsumo_dealer: 16000 * multiple,
+            non_dealer_tsumo_non_dealer: 8000 * multiple,
+            fu_result: FuResult {
+                total: 0,
+                details: Vec::new(),
+            },
+            dora_summary: DoraSummary::default(),
+            honba: 0,
+            riichi_sticks: 0,
+        }
+    }
+
+    #[test]
+    fn test_settle_win_tsumo() {
+        let settings = Settings::new();
+        let score = mangan(1, 0);
+
+        // 子ツモ: 親 4100、子 2100
+        let settlement = settle_win(&score, 2, WinMethod::Tsumo, 0, &settings);
+        assert_eq!(settlement.deltas, [-4100, -2100, 8300, -2100]);
+        assert!(!settlement.dealer_repeats);
+
+        // 親ツモ: 4100 オール
+        let settlement = settle_win(&score, 0, WinMethod::Tsumo, 0, &settings);
+        assert_eq!(settlement.deltas, [12300, -4100, -4100, -4100]);
+        assert!(settlement.dealer_repeats);
+    }
+
+    #[test]
+    fn test_settle_win_ron_with_riichi_sticks() {
+        let mut settings = Settings::new();
+        let score = mangan(2, 2);
+
+        let settlement = settle_win(&score, 1, WinMethod::Ron { loser: 3 }, 1, &settings);
+        assert_eq!(settlement.deltas, [0, 14600, 0, -12600]);
+        assert!(settlement.dealer_repeats);
+
+        settings.renchan = Renchan::Never;
+        let settlement = settle_win(&score, 1, WinMethod::Ron { loser: 3 }, 1, &settings);
+        assert!(!settlement.dealer_repeats);
+    }
+
+    #[test]
+    fn test_settle_exhaustive_draw() {
+        let mut settings = Settings::new();
+        assert_eq!(tenpai_payment_deltas(&[1]), [-1000, 3000, -1000, -1000]);
+        assert_eq!(tenpai_payment_deltas(&[0, 2]), [1500, -1500, 1500, -1500]);
+        assert_eq!(tenpai_payment_deltas(&[]), [0; 4]);
+
+        let settlement = settle_exhaustive_draw(&[0, 2], 0, &settings);
+        assert!(settlement.dealer_repeats);
+        let settlement = settle_exhaustive_draw(&[1], 0, &settings);
+        assert!(!settlement.dealer_repeats);
+
+        settings.renchan = Renchan::WinOnly;
+        let settlement = settle_exhaustive_draw(&[0, 2], 0, &settings);
+        assert!(!settlement.dealer_repeats);
+    }
+
+    #[test]
+    fn test_win_payment_deltas_sum_to_riichi_sticks() {
+        let score = mangan(3, 2);
+        for winner in 0..4 {
+            for method in [
+                WinMethod::Tsumo,
+                WinMethod::Ron {
+                    loser: (winner + 1) % 4,
+                },
+            ] {
+                let deltas = win_payment_deltas(&score, winner, method, 0);
+                assert_eq!(deltas.iter().sum::<i32>(), 2000);
+                assert!(deltas[winner] > 0);
+            }
+        }
+        // 子のロン: 8000 + 900（3本場）+ 2000（供託）
+        let deltas = win_payment_deltas(&score, 2, WinMethod::Ron { loser: 0 }, 0);
+        assert_eq!(deltas, [-8900, 0, 10900, 0]);
+    }
+
+    #[test]
+    fn test_pao_payment_deltas() {
+        let pao = Pao {
+            yakuman: PaoYakuman::BigDragons,
+            liable: 3,
+        };
+        let mut score = yakuman(vec![(ScoreItem::Yaku(Kind::BigDragons), 13)]);
+        score.honba = 1;
+        score.riichi_sticks = 1;
+
+        // ツモ: 責任者が32000点と本場を1人で支払う
+        let deltas = pao_payment_deltas(&score, 1, WinMethod::Tsumo, 0, pao);
+        assert_eq!(deltas, [0, 33300, 0, -32300]);
+
+        // 責任者以外からのロン: 折半し、本場は放銃者が支払う
+        score.riichi_sticks = 0;
+        let deltas = pao_payment_deltas(&score, 1, WinMethod::Ron { loser: 2 }, 0, pao);
+        assert_eq!(deltas, [0, 32300, -16300, -16000]);
+
+        // 責任者からのロンは通常どおり
+        let deltas = pao_payment_deltas(&score, 1, WinMethod::Ron { loser: 3 }, 0, pao);
+        assert_eq!(
+            deltas,
+            win_payment_deltas(&score, 1, WinMethod::Ron { loser: 3 }, 0)
+        );
+    }
+
+    #[test]
+    fn test_pao_payment_deltas_with_other_yakuman() {
+        // 大三元 + 字一色: 包は大三元の分だけ
+        let pao = Pao {
+            yakuman: PaoYakuman::BigDragons,
+            liable: 3,
+        };
+        let score = yakuman(vec![
+            (ScoreItem::Yaku(Kind::BigDragons), 13),
+            (ScoreItem::Yaku(Kind::AllHonours), 13),
+        ]);
+        let deltas = pao_payment_deltas(&score, 1, WinMethod::Tsumo, 0, pao);
+        assert_eq!(deltas, [-16000, 64000, -8000, -40000]);
+
+        let deltas = pao_payment_deltas(&score, 1, WinMethod::Ron { loser: 2 }, 0, pao);
+        assert_eq!(deltas, [0, 64000, -48000, -16000]);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::error::{MahjongError, Result};
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::HandAnalyzer;
+use crate::hand_info::status::Status;
+use crate::hand_info::wait::tenpai_discards;
+use crate::scoring::score::{ScoreResult, calculate_score};
+use crate::scoring::verify::winner_points;
+use crate::settings::Settings;
+use crate::tile::{Tile, TileType};
+
+/// 和了牌1種類分の点数
+#[derive(Debug)]
+pub struct WaitScore {
+    /// 和了牌
+    pub tile: TileType,
+    /// 立直をかけてその牌で和了したときの点数（役がない場合は None）
+    pub score: Option<ScoreResult>,
+    /// 和了者が受け取る合計点（本場・供託を含まない）
+    pub points: u32,
+}
+
+/// 立直宣言牌の候補
+#[derive(Debug)]
+pub struct RiichiCandidate {
+    /// 宣言牌
+    pub discard: Tile,
+    /// 和了牌ごとの点数（牌種の昇順）
+    pub waits: Vec<WaitScore>,
+    /// 和了牌の残り枚数の合計
+    pub remaining: u32,
+}
+
+impl RiichiCandidate {
+    /// 高目（最も点数の高い和了牌）を返す
+    pub fn takame(&self) -> Option<&WaitScore> {
+        self.waits.iter().max_by_key(|w| w.points)
+    }
+}
+
+/// ツモ牌のある門前の手牌から、聴牌を維持する打牌（立直宣言牌の候補）を全て返す
+///
+/// 各和了牌の点数は `status` に立直を加えた状態で計算する。
+/// ツモ和了かロン和了かは `status.is_self_drawn` に従う。
+pub fn riichi_candidates(
+    hand: &Hand,
+    status: &Status,
+    settings: &Settings,
+) -> Result<Vec<RiichiCandidate>> {
+    if !hand.is_concealed() {
+        return Err(MahjongError::InvalidHand(
+            "riichi requires a closed hand".to_string(),
+        ));
+    }
+
+    let mut riichi_status = status.clone();
+    riichi_status.has_claimed_open = false;
+    riichi_status.has_claimed_riichi = true;
+
+    let mut all_tiles = hand.tiles().to_vec();
+    all_tiles.extend(hand.drawn());
+
+    let mut candidates = Vec::new();
+    for discard in tenpai_discards(hand, settings)? {
+        let mut rest = all_tiles.clone();
+        if let Some(i) = rest.iter().position(|&t| t == discard.discard) {
+            rest.remove(i);
+        }
+
+        let mut waits = Vec::new();
+        for &tile in &discard.waits {
+            let mut win_hand = hand.clone();
+            *win_hand.tiles_mut() = rest.clone();
+            win_hand.set_drawn(Some(Tile::new(tile)));
+            let analyzer = HandAnalyzer::new(&win_hand)?;
+            let score = calculate_score(&analyzer, &win_hand, &riichi_status, settings)?;
+            let points = score
+                .as_ref()
+                .map_or(0, |s| winner_points(s, &riichi_status));
+            waits.push(WaitScore {
+                tile,
+                score,
+                points,
+            });
+        }
+        candidates.push(RiichiCandidate {
+            discard: discard.discard,
+            waits,
+            remaining: discard.remaining,
+        });
+    }
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Wind;
+
+    fn ron_status() -> Status {
+        let mut status = Status::new();
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        status
+    }
+
+    /// 456m456p234s45s99m + 1z: 打1z で 36s 待ち（6s は三色同順の高目）
+    #[test]
+    fn riichi_candidates_test() {
+        let hand = Hand::from("456m99m456p234s45s 1z");
+        let candidates = riichi_candidates(&hand, &ron_status(), &Settings::new()).unwrap();
+        assert!(!candidates.is_empty());
+
+        let candidate = candidates
+            .iter()
+            .find(|c| c.discard == Tile::new(Tile::Z1))
+            .unwrap();
+        assert_eq!(
+            candidate.waits.iter().map(|w| w.tile).collect::<Vec<_>>(),
+            vec![Tile::S3, Tile::S6]
+        );
+        assert!(candidate.waits.iter().all(|w| w.score.is_some()));
+        assert_eq!(candidate.takame().unwrap().tile, Tile::S6);
+    }
+
+    #[test]
+    fn riichi_candidates_open_hand_test() {
+        let hand = Hand::from("456m99m456p45s 234s 1z");
+        assert!(riichi_candidates(&hand, &ron_status(), &Settings::new()).is_err());
+    }
+}
@@ -1,23 +1,29 @@
-use std::collections::HashMap;
-
-use anyhow::Result;
+use crate::error::Result;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 use crate::hand::Hand;
 use crate::hand_info::hand_analyzer::HandAnalyzer;
 use crate::hand_info::status::Status;
 use crate::scoring::fu::{FuResult, calculate_fu};
+use crate::scoring::verify::winner_points;
 use crate::settings::{Lang, Settings};
-use crate::winning_hand::checker;
+use crate::tile::{Tile, dora_indicator_to_dora};
+use crate::winning_hand::checker::{self, YakuResult};
+use crate::winning_hand::explain::{YakuEvidence, explain_yaku};
 use crate::winning_hand::name::Kind;
 
 /// 点数計算の結果
-#[derive(Debug, PartialEq, Eq)]
+///
+/// 符の内訳が静的な文字列を含むため、シリアライズのみに対応する。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ScoreResult {
     /// 翻数
     pub han: u32,
-    /// 符
-    pub fu: u32,
+    /// 符（役満は符を計算しないため `None`）
+    pub fu: Option<u32>,
     /// 点数等級名称
     pub rank: ScoreRank,
     /// 親の場合のロン和了点
@@ -36,13 +42,264 @@ pub struct ScoreResult {
     pub has_opened: bool,
     /// 符の内訳
     pub fu_result: FuResult,
+    /// ドラの内訳（`yaku_list` のドラ項目と同じ値を種別ごとに保持する）
+    pub dora_summary: DoraSummary,
+    /// 本場
+    pub honba: u32,
+    /// 供託の立直棒の本数
+    pub riichi_sticks: u32,
+}
+
+/// 本場・供託を含めた支払いの内訳（四人麻雀）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Payments {
+    /// 放銃者の支払い（ロンのみ。1本場につき300点を含む）
+    pub ron: u32,
+    /// 親の支払い（子のツモのみ。1本場につき100点を含む）
+    pub tsumo_dealer: u32,
+    /// 子1人あたりの支払い（ツモのみ。1本場につき100点を含む）
+    pub tsumo_non_dealer: u32,
+    /// 供託の立直棒の点数
+    pub riichi_sticks: u32,
+    /// 和了者の収入の合計（本場・供託を含む）
+    pub total: u32,
+}
+
+impl Payments {
+    /// ツモ和了の各座席の点数変動を返す（和了者の増加分は `total`）
+    pub fn tsumo_deltas(&self, winner: usize, dealer: usize) -> [i32; 4] {
+        let mut deltas = [0i32; 4];
+        for (seat, delta) in deltas.iter_mut().enumerate() {
+            *delta = if seat == winner {
+                self.total as i32
+            } else if seat == dealer {
+                -(self.tsumo_dealer as i32)
+            } else {
+                -(self.tsumo_non_dealer as i32)
+            };
+        }
+        deltas
+    }
+
+    /// ロン和了の各座席の点数変動を返す（和了者の増加分は `total`）
+    pub fn ron_deltas(&self, winner: usize, loser: usize) -> [i32; 4] {
+        let mut deltas = [0i32; 4];
+        deltas[loser] = -(self.ron as i32);
+        deltas[winner] = self.total as i32;
+        deltas
+    }
+}
+
+impl ScoreResult {
+    /// 本場・供託を含めた支払いの内訳を返す
+    ///
+    /// - `winner_is_dealer`: 和了者が親か
+    /// - `is_tsumo`: ツモ和了か
+    /// - `honba`: 本場数（ロンは1本場につき300点、ツモは各支払いに100点を加える）
+    /// - `riichi_sticks`: 和了者が受け取る供託の立直棒の本数
+    pub fn payments(
+        &self,
+        winner_is_dealer: bool,
+        is_tsumo: bool,
+        honba: u32,
+        riichi_sticks: u32,
+    ) -> Payments {
+        let riichi_sticks = riichi_sticks * 1000;
+        let mut payments = Payments {
+            riichi_sticks,
+            ..Payments::default()
+        };
+        match (winner_is_dealer, is_tsumo) {
+            (true, true) => {
+                payments.tsumo_non_dealer = self.dealer_tsumo_all + honba * 100;
+                payments.total = payments.tsumo_non_dealer * 3;
+            }
+            (false, true) => {
+                payments.tsumo_dealer = self.non_dealer_tsumo_dealer + honba * 100;
+                payments.tsumo_non_dealer = self.non_dealer_tsumo_non_dealer + honba * 100;
+                payments.total = payments.tsumo_dealer + payments.tsumo_non_dealer * 2;
+            }
+            (_, false) => {
+                let ron = if winner_is_dealer {
+                    self.dealer_ron
+                } else {
+                    self.non_dealer_ron
+                };
+                payments.ron = ron + honba * 300;
+                payments.total = payments.ron;
+            }
+        }
+        payments.total += riichi_sticks;
+        payments
+    }
+
+    /// ロン和了の本場・供託を含めた収入の合計を返す
+    pub fn total_ron_points(&self, is_dealer: bool) -> u32 {
+        self.payments(is_dealer, false, self.honba, self.riichi_sticks)
+            .total
+    }
+
+    /// ツモ和了の本場・供託を含めた収入の合計を返す
+    pub fn total_tsumo_points(&self, is_dealer: bool) -> u32 {
+        self.payments(is_dealer, true, self.honba, self.riichi_sticks)
+            .total
+    }
+
+    /// 支払い額の表記を返す
+    ///
+    /// ロンは`8000`、子のツモは`子の支払い-親の支払い`（例: `300-500`）、
+    /// 親のツモは各子の支払いのみ（例: `500`）を返す。
+    pub fn payment_text(&self, is_dealer: bool, is_self_drawn: bool) -> String {
+        match (is_dealer, is_self_drawn) {
+            (true, true) => self.dealer_tsumo_all.to_string(),
+            (true, false) => self.dealer_ron.to_string(),
+            (false, true) => format!(
+                "{}-{}",
+                self.non_dealer_tsumo_non_dealer, self.non_dealer_tsumo_dealer
+            ),
+            (false, false) => self.non_dealer_ron.to_string(),
+        }
+    }
+
+    /// 和了時の発声の形で点数を返す
+    ///
+    /// 例: `満貫 8000点` / `Mangan, 8000 points`、`300-500点` / `300-500`、
+    /// `4000点オール` / `4000 all`
+    pub fn spoken(&self, is_dealer: bool, is_self_drawn: bool, lang: Lang) -> String {
+        let payment = self.payment_text(is_dealer, is_self_drawn);
+        let rank = self.rank.name(lang);
+        let points = match (lang, is_dealer, is_self_drawn) {
+            (Lang::Ja, true, true) => format!("{payment}点オール"),
+            (Lang::Ja, _, _) => format!("{payment}点"),
+            (Lang::En, true, true) => format!("{payment} all"),
+            (Lang::En, false, true) => payment,
+            (Lang::En, _, false) => format!("{payment} points"),
+        };
+        match (lang, rank.is_empty()) {
+            (_, true) => points,
+            (Lang::Ja, false) => format!("{rank} {points}"),
+            (Lang::En, false) => format!("{rank}, {points}"),
+        }
+    }
+
+    /// `yaku_list`の役ごとに、成立の根拠となる面子・牌を返す
+    ///
+    /// 引数は点数計算に用いたものと同じ解析結果・手牌・状態を渡す。ドラは含めない。
+    pub fn yaku_evidence(
+        &self,
+        analyzer: &HandAnalyzer,
+        hand: &Hand,
+        status: &Status,
+    ) -> Vec<YakuEvidence> {
+        self.yaku_list
+            .iter()
+            .filter_map(|(item, _)| match item {
+                ScoreItem::Yaku(kind) => Some(explain_yaku(analyzer, hand, status, *kind)),
+                ScoreItem::Dora(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// 点数の内訳を日本語で出力する
+///
+/// 1行目に翻数・符と等級、続けて役・ドラを1行ずつ、
+/// 最後に親・子それぞれのロン／ツモの支払いを出力する。
+/// 役満の役で和了した場合は、翻数・符の代わりに役満の倍数（`役満`・`ダブル役満`など）を出力する。
+///
+/// ```text
+/// 3翻40符
+/// 立直 1翻
+/// 門前清自摸和 1翻
+/// ドラ 1翻
+/// 親: ロン 7700 / ツモ 2600オール
+/// 子: ロン 5200 / ツモ 1300-2600
+/// ```
+impl fmt::Display for ScoreResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 役満の役で和了した場合は翻数の代わりに役満の倍数を出力する（数え役満は翻数を残す）
+        let has_yakuman = self.yaku_list.iter().any(|(_, han)| *han >= 13);
+        let rank = self.rank.name(Lang::Ja);
+        if has_yakuman {
+            write!(f, "{rank}")?;
+        } else {
+            write!(f, "{}翻", self.han)?;
+            if let Some(fu) = self.fu {
+                write!(f, "{fu}符")?;
+            }
+            if !rank.is_empty() {
+                write!(f, " {rank}")?;
+            }
+        }
+        for (item, han) in &self.yaku_list {
+            let name = item.name(self.has_opened, Lang::Ja);
+            match han / 13 {
+                0 => write!(f, "\n{name} {han}翻")?,
+                1 => write!(f, "\n{name} {}", ScoreRank::Yakuman.name(Lang::Ja))?,
+                multiple => write!(
+                    f,
+                    "\n{name} {}",
+                    ScoreRank::MultipleYakuman(multiple).name(Lang::Ja)
+                )?,
+            }
+        }
+        write!(
+            f,
+            "\n親: ロン {} / ツモ {}オール\n子: ロン {} / ツモ {}",
+            self.dealer_ron,
+            self.dealer_tsumo_all,
+            self.non_dealer_ron,
+            self.payment_text(false, true)
+        )
+    }
+}
+
+/// ドラの種別ごとの枚数
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DoraSummary {
+    /// 表ドラ（最初のドラ表示牌）
+    pub dora: u32,
+    /// カンドラ（カンで追加されたドラ表示牌）
+    pub kan_dora: u32,
+    /// 裏ドラ（カン裏を含む）
+    pub ura_dora: u32,
+    /// 赤ドラ
+    pub red_dora: u32,
+    /// 抜きドラ（三人麻雀で抜いた北）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nuki_dora: u32,
+}
+
+impl DoraSummary {
+    /// ドラの合計枚数を返す
+    pub fn total(&self) -> u32 {
+        self.dora + self.kan_dora + self.ura_dora + self.red_dora + self.nuki_dora
+    }
+
+    /// 内訳を`yaku_list`に並べる順（ドラ・カンドラ・赤ドラ・抜きドラ・裏ドラ）で返す（0枚の種別は除く）
+    pub fn items(&self) -> Vec<(ScoreItem, u32)> {
+        [
+            (DoraLabel::Dora, self.dora),
+            (DoraLabel::KanDora, self.kan_dora),
+            (DoraLabel::RedDora, self.red_dora),
+            (DoraLabel::NukiDora, self.nuki_dora),
+            (DoraLabel::UraDora, self.ura_dora),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(label, count)| (ScoreItem::Dora(label), count))
+        .collect()
+    }
 }
 
 /// リザルトに表示する得点内訳の項目（役またはドラ）
 ///
 /// 役名やドラ名を整形済み文字列で持つのではなく、種別を表す値として保持する。
 /// これにより表示側（クライアント）が任意の言語へローカライズできる。
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ScoreItem {
     /// 役
     Yaku(Kind),
@@ -64,7 +321,8 @@ impl ScoreItem {
 }
 
 /// 点数の等級
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ScoreRank {
     /// 通常（満貫未満）
     Normal,
@@ -78,6 +336,8 @@ pub enum ScoreRank {
     Sanbaiman,
     /// 役満（13翻以上）
     Yakuman,
+    /// ダブル役満以上（値は役満の倍数、2以上）
+    MultipleYakuman(u32),
 }
 
 impl ScoreRank {
@@ -93,6 +353,9 @@ impl ScoreRank {
                 ScoreRank::Baiman => "Baiman",
                 ScoreRank::Sanbaiman => "Sanbaiman",
                 ScoreRank::Yakuman => "Yakuman",
+                ScoreRank::MultipleYakuman(2) => "Double Yakuman",
+                ScoreRank::MultipleYakuman(3) => "Triple Yakuman",
+                ScoreRank::MultipleYakuman(_) => "Multiple Yakuman",
             },
             Lang::Ja => match self {
                 ScoreRank::Normal => "",
@@ -101,20 +364,33 @@ impl ScoreRank {
                 ScoreRank::Baiman => "倍満",
                 ScoreRank::Sanbaiman => "三倍満",
                 ScoreRank::Yakuman => "役満",
+                ScoreRank::MultipleYakuman(2) => "ダブル役満",
+                ScoreRank::MultipleYakuman(3) => "トリプル役満",
+                ScoreRank::MultipleYakuman(_) => "多倍役満",
             },
         }
     }
+
+    /// 役満（ダブル役満以上を含む）か
+    pub fn is_yakuman(&self) -> bool {
+        matches!(self, ScoreRank::Yakuman | ScoreRank::MultipleYakuman(_))
+    }
 }
 
 /// ドラの種別（リザルト画面で役と並べて翻数を表示するために用いる）
 ///
 /// 翻数を生む通常の役ではないが、和了結果の内訳として役と同様に扱う。
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DoraLabel {
     /// ドラ
     Dora,
+    /// カンドラ
+    KanDora,
     /// 赤ドラ
     RedDora,
+    /// 抜きドラ（三人麻雀の北抜き）
+    NukiDora,
     /// 裏ドラ
     UraDora,
 }
@@ -127,12 +403,16 @@ impl DoraLabel {
         match lang {
             Lang::En => match self {
                 DoraLabel::Dora => "Dora",
+                DoraLabel::KanDora => "Kan Dora",
                 DoraLabel::RedDora => "Red Five",
+                DoraLabel::NukiDora => "Nuki Dora",
                 DoraLabel::UraDora => "Ura Dora",
             },
             Lang::Ja => match self {
                 DoraLabel::Dora => "ドラ",
+                DoraLabel::KanDora => "カンドラ",
                 DoraLabel::RedDora => "赤ドラ",
+                DoraLabel::NukiDora => "抜きドラ",
                 DoraLabel::UraDora => "裏ドラ",
             },
         }
@@ -149,34 +429,84 @@ impl DoraLabel {
 ///
 /// # Returns
 /// 点数計算の結果。役がない場合はNone。
+///
+/// 高点法に従い、和了形として解釈できるすべての面子構成（`HandAnalyzer::all_decompositions`）を
+/// 試し、和了者の得点が最も高くなる結果を返す。`analyzer`が和了形でない場合はそのまま計算する。
 pub fn calculate_score(
     analyzer: &HandAnalyzer,
     hand: &Hand,
     status: &Status,
     settings: &Settings,
+) -> Result<Option<ScoreResult>> {
+    if !analyzer.shanten.has_won() {
+        return calculate_score_for(analyzer, hand, status, settings);
+    }
+
+    let mut best = calculate_score_for(analyzer, hand, status, settings)?;
+    for candidate in HandAnalyzer::all_decompositions(hand)? {
+        if !candidate.shanten.has_won() {
+            continue;
+        }
+        let Some(result) = calculate_score_for(&candidate, hand, status, settings)? else {
+            continue;
+        };
+        let is_better = best.as_ref().is_none_or(|current| {
+            (winner_points(&result, status), result.han, result.fu)
+                > (winner_points(current, status), current.han, current.fu)
+        });
+        if is_better {
+            best = Some(result);
+        }
+    }
+    Ok(best)
+}
+
+/// 1つの面子構成について点数を計算する
+fn calculate_score_for(
+    analyzer: &HandAnalyzer,
+    hand: &Hand,
+    status: &Status,
+    settings: &Settings,
 ) -> Result<Option<ScoreResult>> {
     // 役判定
     let yaku_result = checker::check(analyzer, hand, status, settings)?;
 
     // 成立した役を抽出
-    let yaku_list = extract_yaku_list(&yaku_result);
+    let mut yaku_list = extract_yaku_list(&yaku_result);
 
     if yaku_list.is_empty() {
         return Ok(None);
     }
 
-    // 翻数の合計
-    let han: u32 = yaku_list.iter().map(|(_, h)| h).sum();
-
     // 役満判定
     let has_yakuman = yaku_list.iter().any(|(_, h)| *h >= 13);
 
-    // 符計算
-    let fu_result = calculate_fu(analyzer, hand, status)?;
+    // ドラは役がある場合のみ加算する（役満には加算しない）
+    let mut dora_summary = DoraSummary::default();
+    if !has_yakuman {
+        dora_summary = count_dora(hand, status, settings);
+        yaku_list.extend(dora_summary.items());
+    }
+
+    // 翻数の合計
+    let han: u32 = yaku_list.iter().map(|(_, h)| h).sum();
+
+    // 符計算（役満は点数が符によらないため、符を持たない）
+    let fu_result = calculate_fu(analyzer, hand, status, settings)?;
     let fu = fu_result.total;
 
-    // 等級を決定
-    let rank = determine_rank(han, fu, has_yakuman);
+    // 等級を決定（ダブル役満の役や役満の複合があれば役満の倍数を反映する）
+    let multipliers = yaku_list.iter().map(|(_, h)| h / 13);
+    let yakuman_multiplier = if settings.allow_multiple_yakuman {
+        multipliers.sum()
+    } else {
+        multipliers.max().unwrap_or(0)
+    };
+    let rank = if yakuman_multiplier >= 2 {
+        ScoreRank::MultipleYakuman(yakuman_multiplier)
+    } else {
+        determine_rank(han, fu, has_yakuman, settings)
+    };
 
     // 基本点を計算
     let base_points = calculate_base_points(han, fu, rank);
@@ -190,7 +520,7 @@ pub fn calculate_score(
 
     Ok(Some(ScoreResult {
         han,
-        fu,
+        fu: (!rank.is_yakuman()).then_some(fu),
         rank,
         dealer_ron,
         dealer_tsumo_all,
@@ -200,31 +530,119 @@ pub fn calculate_score(
         yaku_list,
         has_opened: status.has_claimed_open,
         fu_result,
+        dora_summary,
+        honba: status.honba,
+        riichi_sticks: status.riichi_sticks,
     }))
 }
 
-/// 役判定結果から成立した役のリストを抽出する
-fn extract_yaku_list(
-    yaku_result: &HashMap<Kind, (&'static str, bool, u32)>,
-) -> Vec<(ScoreItem, u32)> {
-    let mut list: Vec<(&Kind, u32)> = Vec::new();
-    let mut has_yakuman = false;
+/// 複数の手牌の点数をまとめて計算する
+///
+/// 牌譜の再計算など、同じルール設定で多数の和了を計算する場合に用いる。
+/// 結果は入力と同じ順で返し、各要素は`calculate_score`と同じ意味を持つ。
+///
+/// 向聴数・和了判定の表はプロセス全体で一度だけ作られて共有されるため、
+/// 1件ずつ`calculate_score`を呼ぶ場合と比べて追加で償却されるものはない。
+/// スレッドを使えない環境（wasm）でも使える一括計算の入口で、
+/// `calculate_scores_parallel`の各スレッドの処理でもある。
+pub fn calculate_scores(
+    entries: &[(Hand, Status)],
+    settings: &Settings,
+) -> Vec<Result<Option<ScoreResult>>> {
+    entries
+        .iter()
+        .map(|(hand, status)| score_entry(hand, status, settings))
+        .collect()
+}
 
-    // まず役満があるか確認
-    for (_, is_valid, han) in yaku_result.values() {
-        if *is_valid && *han >= 13 {
-            has_yakuman = true;
-            break;
-        }
+/// 複数の手牌の点数を複数スレッドで並列に計算する
+///
+/// `threads`個（最低1個）のスレッドに入力を分割して計算する。結果の順序は`calculate_scores`と同じ。
+/// OS スレッドを使うため、wasm 向けのビルドでは使えない。
+#[cfg(not(target_arch = "wasm32"))]
+pub fn calculate_scores_parallel(
+    entries: &[(Hand, Status)],
+    settings: &Settings,
+    threads: usize,
+) -> Vec<Result<Option<ScoreResult>>> {
+    if entries.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = entries.len().div_ceil(threads.max(1));
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = entries
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || calculate_scores(chunk, settings)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("scoring thread panicked"))
+            .collect()
+    })
+}
+
+fn score_entry(hand: &Hand, status: &Status, settings: &Settings) -> Result<Option<ScoreResult>> {
+    let analyzer = HandAnalyzer::new(hand)?;
+    calculate_score(&analyzer, hand, status, settings)
+}
+
+/// 和了形に含まれるドラを種別ごとに数える
+///
+/// ドラ表示牌は次の牌をドラとする（9の次は1、北の次は東、中の次は白）。
+/// 抜き出した北もドラ表示牌の対象として数える。
+/// 裏ドラは、`settings.ura_dora_requires_riichi` が有効なら立直している場合のみ数える。
+fn count_dora(hand: &Hand, status: &Status, settings: &Settings) -> DoraSummary {
+    let mut tiles: Vec<Tile> = hand.tiles().to_vec();
+    tiles.extend(hand.drawn());
+    tiles.extend(hand.melds().iter().flat_map(|meld| meld.expanded_tiles()));
+    tiles.extend(hand.nuki());
+
+    let count = |indicators: &[Tile]| -> u32 {
+        indicators
+            .iter()
+            .map(|indicator| {
+                let dora = dora_indicator_to_dora(indicator.get());
+                tiles.iter().filter(|t| t.get() == dora).count() as u32
+            })
+            .sum()
+    };
+
+    let (first, kan) = status
+        .dora_indicators
+        .split_at(status.dora_indicators.len().min(1));
+    let is_riichi = status.has_claimed_riichi || status.is_double_riichi;
+    DoraSummary {
+        dora: count(first),
+        kan_dora: count(kan),
+        ura_dora: if is_riichi || !settings.ura_dora_requires_riichi {
+            count(&status.ura_dora_indicators)
+        } else {
+            0
+        },
+        red_dora: tiles.iter().filter(|t| t.is_red_dora()).count() as u32,
+        nuki_dora: if settings.count_nuki_dora {
+            hand.nuki().len() as u32
+        } else {
+            0
+        },
     }
+}
+
+/// 役判定結果から成立した役のリストを抽出する
+fn extract_yaku_list(yaku_result: &[YakuResult]) -> Vec<(ScoreItem, u32)> {
+    // まず役満があるか確認
+    let has_yakuman = yaku_result
+        .iter()
+        .any(|yaku| yaku.is_established && yaku.is_yakuman);
 
-    for (kind, (_name, is_valid, han)) in yaku_result {
-        if *is_valid && *han > 0 {
+    let mut list: Vec<(&Kind, u32)> = Vec::new();
+    for yaku in yaku_result {
+        if yaku.is_established && yaku.han > 0 {
             // 役満がある場合は通常役を除外
-            if has_yakuman && *han < 13 {
+            if has_yakuman && !yaku.is_yakuman {
                 continue;
             }
-            list.push((kind, *han));
+            list.push((&yaku.kind, yaku.han));
         }
     }
 
@@ -236,8 +654,15 @@ fn extract_yaku_list(
 }
 
 /// 等級を決定する
-pub fn determine_rank(han: u32, fu: u32, has_yakuman: bool) -> ScoreRank {
-    if has_yakuman || han >= 13 {
+///
+/// `settings.kiriage_mangan` が有効なら、4翻30符・3翻60符（基本点1920点）も満貫とする。
+pub fn determine_rank(han: u32, fu: u32, has_yakuman: bool, settings: &Settings) -> ScoreRank {
+    let (min_fu_4han, min_fu_3han) = if settings.kiriage_mangan {
+        (30, 60)
+    } else {
+        (40, 70)
+    };
+    if has_yakuman || (han >= 13 && settings.kazoe_yakuman) {
         ScoreRank::Yakuman
     } else if han >= 11 {
         ScoreRank::Sanbaiman
@@ -245,7 +670,7 @@ pub fn determine_rank(han: u32, fu: u32, has_yakuman: bool) -> ScoreRank {
         ScoreRank::Baiman
     } else if han >= 6 {
         ScoreRank::Haneman
-    } else if han >= 5 || (han == 4 && fu >= 30) || (han == 3 && fu >= 60) {
+    } else if han >= 5 || (han == 4 && fu >= min_fu_4han) || (han == 3 && fu >= min_fu_3han) {
         ScoreRank::Mangan
     } else {
         ScoreRank::Normal
@@ -256,6 +681,7 @@ pub fn determine_rank(han: u32, fu: u32, has_yakuman: bool) -> ScoreRank {
 pub fn calculate_base_points(han: u32, fu: u32, rank: ScoreRank) -> u32 {
     match rank {
         ScoreRank::Yakuman => 8000,
+        ScoreRank::MultipleYakuman(multiplier) => 8000 * multiplier,
         ScoreRank::Sanbaiman => 6000,
         ScoreRank::Baiman => 4000,
         ScoreRank::Haneman => 3000,
@@ -281,7 +707,7 @@ mod tests {
     use crate::hand_info::hand_analyzer::HandAnalyzer;
     use crate::hand_info::status::Status;
     use crate::settings::Settings;
-    use crate::tile::Wind;
+    use crate::tile::{Tile, Wind};
 
     /// 満貫の子ロン: 8000点
     #[test]
@@ -340,7 +766,7 @@ mod tests {
     /// 1翻30符の子ロン: 1000点
     #[test]
     fn test_1han_30fu_non_dealer_ron() {
-        let rank = determine_rank(1, 30, false);
+        let rank = determine_rank(1, 30, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Normal);
         let base = calculate_base_points(1, 30, rank);
         // 30 * 2^3 = 240
@@ -352,7 +778,7 @@ mod tests {
     /// 1翻40符の子ロン: 1300点
     #[test]
     fn test_1han_40fu_non_dealer_ron() {
-        let rank = determine_rank(1, 40, false);
+        let rank = determine_rank(1, 40, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Normal);
         let base = calculate_base_points(1, 40, rank);
         // 40 * 2^3 = 320
@@ -364,7 +790,7 @@ mod tests {
     /// 2翻30符の子ロン: 2000点
     #[test]
     fn test_2han_30fu_non_dealer_ron() {
-        let rank = determine_rank(2, 30, false);
+        let rank = determine_rank(2, 30, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Normal);
         let base = calculate_base_points(2, 30, rank);
         // 30 * 2^4 = 480
@@ -376,7 +802,7 @@ mod tests {
     /// 3翻30符の子ロン: 3900点
     #[test]
     fn test_3han_30fu_non_dealer_ron() {
-        let rank = determine_rank(3, 30, false);
+        let rank = determine_rank(3, 30, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Normal);
         let base = calculate_base_points(3, 30, rank);
         // 30 * 2^5 = 960
@@ -388,21 +814,147 @@ mod tests {
     /// 3翻60符の子ロンは満貫: 8000点
     #[test]
     fn test_3han_60fu_is_mangan() {
-        let rank = determine_rank(3, 60, false);
+        let rank = determine_rank(3, 60, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Mangan);
     }
 
     /// 4翻30符の子ロンは満貫: 8000点
     #[test]
     fn test_4han_30fu_is_mangan() {
-        let rank = determine_rank(4, 30, false);
+        let rank = determine_rank(4, 30, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Mangan);
     }
 
+    /// 切り上げ満貫なしでは4翻30符の子ロンは7700点、3翻60符の親ロンは11600点
+    #[test]
+    fn test_no_kiriage_mangan() {
+        let mut settings = Settings::new();
+        settings.kiriage_mangan = false;
+
+        let rank = determine_rank(4, 30, false, &settings);
+        assert_eq!(rank, ScoreRank::Normal);
+        let base = calculate_base_points(4, 30, rank);
+        assert_eq!(base, 1920);
+        assert_eq!(round_up_to_100(base * 4), 7700);
+
+        let rank = determine_rank(3, 60, false, &settings);
+        assert_eq!(rank, ScoreRank::Normal);
+        assert_eq!(
+            round_up_to_100(calculate_base_points(3, 60, rank) * 6),
+            11600
+        );
+
+        assert_eq!(determine_rank(4, 40, false, &settings), ScoreRank::Mangan);
+        assert_eq!(determine_rank(3, 70, false, &settings), ScoreRank::Mangan);
+    }
+
+    /// 七対子のみ（2翻25符）の子ロンは1600点、親ロンは2400点（25符は切り上げない）
+    #[test]
+    fn test_seven_pairs_2han_25fu() {
+        let hand = Hand::from("1133m5577p99s1122z 2z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let status = Status::builder().player_wind(Wind::South).build().unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.han, 2);
+        assert_eq!(result.fu, Some(25));
+        assert_eq!(result.rank, ScoreRank::Normal);
+        assert_eq!(result.non_dealer_ron, 1600);
+        assert_eq!(result.dealer_ron, 2400);
+    }
+
+    /// 七対子の門前ツモ（3翻25符）: 子は800/1600、親は1600オール
+    #[test]
+    fn test_seven_pairs_3han_25fu_tsumo() {
+        let hand = Hand::from("1133m5577p99s1122z 2z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let status = Status::builder()
+            .tsumo()
+            .player_wind(Wind::South)
+            .build()
+            .unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.han, 3);
+        assert_eq!(result.fu, Some(25));
+        assert_eq!(result.non_dealer_tsumo_non_dealer, 800);
+        assert_eq!(result.non_dealer_tsumo_dealer, 1600);
+        assert_eq!(result.dealer_tsumo_all, 1600);
+    }
+
+    /// 二盃口と七対子の両方に取れる手は二盃口（3翻40符）として計算する
+    #[test]
+    fn test_double_twin_sequences_preferred_over_seven_pairs() {
+        let hand = Hand::from("112233m445566p7s 7s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let status = Status::builder().player_wind(Wind::South).build().unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result.yaku_list,
+            vec![(ScoreItem::Yaku(Kind::DoubleTwinSequences), 3)]
+        );
+        assert_eq!(result.han, 3);
+        assert_eq!(result.fu, Some(40));
+        assert_eq!(result.non_dealer_ron, 5200);
+    }
+
+    /// 断么九が付いても二盃口（4翻40符で満貫）が七対子（3翻25符）より優先される
+    #[test]
+    fn test_double_twin_sequences_with_all_inside_preferred_over_seven_pairs() {
+        let hand = Hand::from("223344m556677p8s 8s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let status = Status::builder()
+            .tsumo()
+            .player_wind(Wind::South)
+            .build()
+            .unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        let kinds: Vec<ScoreItem> = result.yaku_list.iter().map(|(item, _)| *item).collect();
+        assert!(kinds.contains(&ScoreItem::Yaku(Kind::DoubleTwinSequences)));
+        assert!(!kinds.contains(&ScoreItem::Yaku(Kind::SevenPairs)));
+        assert!(!kinds.contains(&ScoreItem::Yaku(Kind::TwinSequences)));
+        assert_eq!(result.han, 5);
+        assert_eq!(result.rank, ScoreRank::Mangan);
+    }
+
+    /// 同じ順子が1組だけなら二盃口にはならず、七対子とも取れない
+    #[test]
+    fn test_single_twin_sequences_is_not_double() {
+        let hand = Hand::from("112233m456p7899s 9s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let status = Status::builder().player_wind(Wind::South).build().unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        let kinds: Vec<ScoreItem> = result.yaku_list.iter().map(|(item, _)| *item).collect();
+        assert!(kinds.contains(&ScoreItem::Yaku(Kind::TwinSequences)));
+        assert!(!kinds.contains(&ScoreItem::Yaku(Kind::DoubleTwinSequences)));
+        assert!(!kinds.contains(&ScoreItem::Yaku(Kind::SevenPairs)));
+    }
+
+    /// 役満は符を持たない
+    #[test]
+    fn test_yakuman_has_no_fu() {
+        let hand = Hand::from("19m19p19s1234567z 1m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let result = calculate_score(&analyzer, &hand, &Status::new(), &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert!(result.rank.is_yakuman());
+        assert_eq!(result.fu, None);
+        assert_eq!(result.fu_result.total, 0);
+    }
+
     /// 4翻25符は通常計算（七対子）: 子ロン6400点
     #[test]
     fn test_4han_25fu_is_normal() {
-        let rank = determine_rank(4, 25, false);
+        let rank = determine_rank(4, 25, false, &Settings::new());
         assert_eq!(rank, ScoreRank::Normal);
         let base = calculate_base_points(4, 25, rank);
         // 25 * 2^6 = 1600
@@ -424,21 +976,75 @@ mod tests {
     /// 等級の判定
     #[test]
     fn test_determine_rank() {
-        assert_eq!(determine_rank(1, 30, false), ScoreRank::Normal);
-        assert_eq!(determine_rank(2, 30, false), ScoreRank::Normal);
-        assert_eq!(determine_rank(3, 30, false), ScoreRank::Normal);
-        assert_eq!(determine_rank(3, 60, false), ScoreRank::Mangan);
-        assert_eq!(determine_rank(4, 25, false), ScoreRank::Normal);
-        assert_eq!(determine_rank(4, 30, false), ScoreRank::Mangan);
-        assert_eq!(determine_rank(5, 30, false), ScoreRank::Mangan);
-        assert_eq!(determine_rank(6, 30, false), ScoreRank::Haneman);
-        assert_eq!(determine_rank(7, 30, false), ScoreRank::Haneman);
-        assert_eq!(determine_rank(8, 30, false), ScoreRank::Baiman);
-        assert_eq!(determine_rank(10, 30, false), ScoreRank::Baiman);
-        assert_eq!(determine_rank(11, 30, false), ScoreRank::Sanbaiman);
-        assert_eq!(determine_rank(12, 30, false), ScoreRank::Sanbaiman);
-        assert_eq!(determine_rank(13, 30, false), ScoreRank::Yakuman);
-        assert_eq!(determine_rank(13, 30, true), ScoreRank::Yakuman);
+        assert_eq!(
+            determine_rank(1, 30, false, &Settings::new()),
+            ScoreRank::Normal
+        );
+        assert_eq!(
+            determine_rank(2, 30, false, &Settings::new()),
+            ScoreRank::Normal
+        );
+        assert_eq!(
+            determine_rank(3, 30, false, &Settings::new()),
+            ScoreRank::Normal
+        );
+        assert_eq!(
+            determine_rank(3, 60, false, &Settings::new()),
+            ScoreRank::Mangan
+        );
+        assert_eq!(
+            determine_rank(4, 25, false, &Settings::new()),
+            ScoreRank::Normal
+        );
+        assert_eq!(
+            determine_rank(4, 30, false, &Settings::new()),
+            ScoreRank::Mangan
+        );
+        assert_eq!(
+            determine_rank(5, 30, false, &Settings::new()),
+            ScoreRank::Mangan
+        );
+        assert_eq!(
+            determine_rank(6, 30, false, &Settings::new()),
+            ScoreRank::Haneman
+        );
+        assert_eq!(
+            determine_rank(7, 30, false, &Settings::new()),
+            ScoreRank::Haneman
+        );
+        assert_eq!(
+            determine_rank(8, 30, false, &Settings::new()),
+            ScoreRank::Baiman
+        );
+        assert_eq!(
+            determine_rank(10, 30, false, &Settings::new()),
+            ScoreRank::Baiman
+        );
+        assert_eq!(
+            determine_rank(11, 30, false, &Settings::new()),
+            ScoreRank::Sanbaiman
+        );
+        assert_eq!(
+            determine_rank(12, 30, false, &Settings::new()),
+            ScoreRank::Sanbaiman
+        );
+        assert_eq!(
+            determine_rank(13, 30, false, &Settings::new()),
+            ScoreRank::Yakuman
+        );
+        assert_eq!(
+            determine_rank(13, 30, true, &Settings::new()),
+            ScoreRank::Yakuman
+        );
+
+        // 数え役満なしでは13翻以上でも三倍満
+        let mut settings = Settings::new();
+        settings.kazoe_yakuman = false;
+        assert_eq!(
+            determine_rank(13, 30, false, &settings),
+            ScoreRank::Sanbaiman
+        );
+        assert_eq!(determine_rank(13, 30, true, &settings), ScoreRank::Yakuman);
     }
 
     /// 満貫の子ツモ: 親4000 + 子2000×2 = 8000
@@ -475,7 +1081,7 @@ mod tests {
             .unwrap();
         // 平和 + 立直 = 2翻, 30符
         assert_eq!(result.han, 2);
-        assert_eq!(result.fu, 30);
+        assert_eq!(result.fu, Some(30));
         assert_eq!(result.non_dealer_ron, 2000);
     }
 
@@ -494,13 +1100,319 @@ mod tests {
             .unwrap();
         // 門前清自摸和 + 平和 = 2翻, 20符
         assert_eq!(result.han, 2);
-        assert_eq!(result.fu, 20);
+        assert_eq!(result.fu, Some(20));
         // 基本点 = 20 * 2^4 = 320
         // 子ツモ: 親700(640->700) + 子400(320->400)×2
         assert_eq!(result.non_dealer_tsumo_dealer, 700);
         assert_eq!(result.non_dealer_tsumo_non_dealer, 400);
     }
 
+    /// 高点法: 222333444m は三暗刻（刻子3つ）と一盃口（順子3つ）の両方に解釈でき、高い方を採る
+    #[test]
+    fn test_calculate_score_takes_highest_decomposition() {
+        let hand = Hand::from("222333444m567p8s 8s");
+        let mut status = Status::new();
+        status.has_claimed_riichi = true;
+        status.is_self_drawn = false;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let settings = Settings::new();
+
+        // 立直 + 断么九 + 三暗刻 = 4翻50符（満貫） > 立直 + 断么九 + 一盃口 = 3翻40符
+        for analyzer in HandAnalyzer::all_decompositions(&hand).unwrap() {
+            let result = calculate_score(&analyzer, &hand, &status, &settings)
+                .unwrap()
+                .unwrap();
+            assert_eq!(result.han, 4);
+            assert_eq!(result.fu, Some(50));
+            assert_eq!(result.non_dealer_ron, 8000);
+        }
+    }
+
+    /// 四暗刻形のロン: 単騎待ちは役満、双碰待ちで刻子が完成した場合は対々和 + 三暗刻
+    #[test]
+    fn test_calculate_score_four_concealed_triplets_ron() {
+        let mut status = Status::new();
+        status.is_self_drawn = false;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let settings = Settings::new();
+
+        let hand = Hand::from("111333m444s1777z 1z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.rank, ScoreRank::Yakuman);
+
+        let hand = Hand::from("111333m444s55s77z 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        let kinds: Vec<ScoreItem> = result.yaku_list.iter().map(|(k, _)| *k).collect();
+        assert!(kinds.contains(&ScoreItem::Yaku(Kind::AllTriplets)));
+        assert!(kinds.contains(&ScoreItem::Yaku(Kind::ThreeConcealedTriplets)));
+        assert!(!kinds.contains(&ScoreItem::Yaku(Kind::FourConcealedTriplets)));
+    }
+
+    /// 抜きドラ2枚は1枚1翻として加算され、設定で無効にできる
+    #[test]
+    fn test_calculate_score_counts_nuki_dora() {
+        let hand = Hand::from("111999m234p6799s n44z 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let mut settings = Settings::new();
+        settings.three_player = true;
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.dora_summary.nuki_dora, 2);
+        assert!(
+            result
+                .yaku_list
+                .contains(&(ScoreItem::Dora(DoraLabel::NukiDora), 2))
+        );
+        let han_without_nuki = result.han - 2;
+
+        settings.count_nuki_dora = false;
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.dora_summary.nuki_dora, 0);
+        assert_eq!(result.han, han_without_nuki);
+    }
+
+    /// 抜きドラだけでは和了にならない
+    #[test]
+    fn test_calculate_score_nuki_dora_is_not_yaku() {
+        let hand = Hand::from("111999m234p789s3z n4z 3z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.has_claimed_open = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new()).unwrap();
+        assert!(result.is_none());
+    }
+
+    /// 赤5は通常の5として役判定され、1枚1翻の赤ドラとして加算される
+    #[test]
+    fn test_calculate_score_counts_red_fives() {
+        let hand = Hand::from("123406m234p6799s r5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        // 門前清自摸和 + 平和 + 赤ドラ2 = 4翻20符
+        assert_eq!(result.dora_summary.red_dora, 2);
+        assert_eq!(result.han, 4);
+        assert!(
+            result
+                .yaku_list
+                .contains(&(ScoreItem::Dora(DoraLabel::RedDora), 2))
+        );
+    }
+
+    #[test]
+    fn test_calculate_score_counts_dora_indicators() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        // 9m の次は 1m、北の次は東（手牌にない）、3p の次は 4p（カンドラ）
+        status.dora_indicators = vec![Tile::new(Tile::M9), Tile::new(Tile::P3)];
+        status.ura_dora_indicators = vec![Tile::new(Tile::S5)];
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        // 門前清自摸和 + 平和 + ドラ1 + カンドラ1 = 4翻（立直していないので裏ドラは数えない）
+        assert_eq!(result.dora_summary.dora, 1);
+        assert_eq!(result.dora_summary.kan_dora, 1);
+        assert_eq!(result.dora_summary.ura_dora, 0);
+        assert_eq!(result.han, 4);
+        assert!(
+            result
+                .yaku_list
+                .contains(&(ScoreItem::Dora(DoraLabel::Dora), 1))
+        );
+
+        status.has_claimed_riichi = true;
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.dora_summary.ura_dora, 1);
+        assert_eq!(result.han, 6);
+    }
+
+    #[test]
+    fn test_calculate_score_ura_dora_without_riichi_setting() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        status.dora_indicators = vec![Tile::new(Tile::Z1)];
+        status.ura_dora_indicators = vec![Tile::new(Tile::S5)];
+        let settings = Settings {
+            ura_dora_requires_riichi: false,
+            ..Settings::new()
+        };
+        // 設定で無効化している場合は、立直していなくても裏ドラを数える
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.dora_summary.ura_dora, 1);
+
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.dora_summary.ura_dora, 0);
+    }
+
+    #[test]
+    fn test_calculate_score_honour_dora_wraps() {
+        let hand = Hand::from("123m456p789s111z5z 5z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        // 北の次は東、中の次は白
+        status.dora_indicators = vec![Tile::new(Tile::Z4), Tile::new(Tile::Z7)];
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.dora_summary.dora, 3);
+        assert_eq!(result.dora_summary.kan_dora, 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_score_result_serializes() {
+        let hand = Hand::from("123m456p789s11z55z 5z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let result = calculate_score(&analyzer, &hand, &Status::new(), &Settings::new())
+            .unwrap()
+            .unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["han"], result.han);
+        assert_eq!(json["fu_result"]["total"], result.fu_result.total);
+
+        // 解析結果は往復できる
+        let json = serde_json::to_string(&analyzer).unwrap();
+        let restored: HandAnalyzer = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.shanten, analyzer.shanten);
+        assert_eq!(restored.same3.len(), analyzer.same3.len());
+    }
+
+    #[test]
+    fn test_calculate_score_double_yakuman() {
+        let hand = Hand::from("19m19p19s1234567z 1m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = false;
+        let mut settings = Settings::new();
+        settings.double_yakuman = true;
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        // 国士無双の形では字一色・清老頭・緑一色は成立しない
+        assert_eq!(
+            result.yaku_list,
+            vec![(ScoreItem::Yaku(Kind::ThirteenOrphans), 26)]
+        );
+        assert_eq!(result.han, 26);
+        assert_eq!(result.rank, ScoreRank::MultipleYakuman(2));
+        assert!(result.rank.is_yakuman());
+        assert_eq!(result.non_dealer_ron, 64000);
+        assert_eq!(result.rank.name(Lang::Ja), "ダブル役満");
+    }
+
+    #[test]
+    fn test_calculate_score_multiple_yakuman() {
+        // 大三元 + 字一色 + 四暗刻単騎待ち
+        let hand = Hand::from("1112555666777z 2z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.is_first_turn = false;
+        let mut settings = Settings::new();
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.han, 39);
+        assert_eq!(result.rank, ScoreRank::Yakuman);
+        assert_eq!(result.non_dealer_ron, 32000);
+
+        settings.allow_multiple_yakuman = true;
+        let result = calculate_score(&analyzer, &hand, &status, &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.rank, ScoreRank::MultipleYakuman(3));
+        assert_eq!(result.non_dealer_ron, 96000);
+        assert_eq!(result.dealer_ron, 144000);
+        assert_eq!(result.non_dealer_tsumo_dealer, 48000);
+        assert_eq!(result.non_dealer_tsumo_non_dealer, 24000);
+    }
+
+    #[test]
+    fn test_payments_include_honba_and_riichi_sticks() {
+        // 子の平和手を2本場・供託1本で和了した場合
+        let hand = Hand::from("123456m234p6799s 5s");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.seat_wind = Wind::South;
+        status.honba = 2;
+        status.riichi_sticks = 1;
+        let result = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.honba, 2);
+
+        let ron = result.payments(false, false, 2, 1);
+        assert_eq!(ron.ron, result.non_dealer_ron + 600);
+        assert_eq!(ron.riichi_sticks, 1000);
+        assert_eq!(result.total_ron_points(false), ron.ron + 1000);
+
+        let tsumo = result.payments(false, true, 2, 1);
+        assert_eq!(tsumo.tsumo_dealer, result.non_dealer_tsumo_dealer + 200);
+        assert_eq!(
+            tsumo.tsumo_non_dealer,
+            result.non_dealer_tsumo_non_dealer + 200
+        );
+        assert_eq!(
+            result.total_tsumo_points(false),
+            tsumo.tsumo_dealer + tsumo.tsumo_non_dealer * 2 + 1000
+        );
+
+        let dealer_tsumo = result.payments(true, true, 2, 1);
+        assert_eq!(dealer_tsumo.tsumo_dealer, 0);
+        assert_eq!(
+            dealer_tsumo.total,
+            (result.dealer_tsumo_all + 200) * 3 + 1000
+        );
+
+        // 座席ごとの点数変動: 和了者は収入の合計、支払者は各自の支払い
+        let deltas = tsumo.tsumo_deltas(1, 0);
+        assert_eq!(deltas[1], tsumo.total as i32);
+        assert_eq!(deltas[0], -(tsumo.tsumo_dealer as i32));
+        assert_eq!(deltas[2], -(tsumo.tsumo_non_dealer as i32));
+        assert_eq!(deltas.iter().sum::<i32>(), 1000);
+        assert_eq!(
+            ron.ron_deltas(1, 3),
+            [0, ron.total as i32, 0, -(ron.ron as i32)]
+        );
+    }
+
     /// 役がない手は None を返す
     #[test]
     fn test_calculate_score_no_yaku() {
@@ -537,7 +1449,7 @@ mod tests {
     /// 2翻40符の親ロン: 2600点
     #[test]
     fn test_2han_40fu_dealer_ron() {
-        let rank = determine_rank(2, 40, false);
+        let rank = determine_rank(2, 40, false, &Settings::new());
         let base = calculate_base_points(2, 40, rank);
         // 40 * 2^4 = 640
         assert_eq!(base, 640);
@@ -637,7 +1549,9 @@ mod tests {
     #[test]
     fn dora_label_name_ja() {
         assert_eq!(DoraLabel::Dora.name(Lang::Ja), "ドラ");
+        assert_eq!(DoraLabel::KanDora.name(Lang::Ja), "カンドラ");
         assert_eq!(DoraLabel::RedDora.name(Lang::Ja), "赤ドラ");
+        assert_eq!(DoraLabel::NukiDora.name(Lang::Ja), "抜きドラ");
         assert_eq!(DoraLabel::UraDora.name(Lang::Ja), "裏ドラ");
     }
 
@@ -645,7 +1559,156 @@ mod tests {
     #[test]
     fn dora_label_name_en() {
         assert_eq!(DoraLabel::Dora.name(Lang::En), "Dora");
+        assert_eq!(DoraLabel::KanDora.name(Lang::En), "Kan Dora");
         assert_eq!(DoraLabel::RedDora.name(Lang::En), "Red Five");
+        assert_eq!(DoraLabel::NukiDora.name(Lang::En), "Nuki Dora");
         assert_eq!(DoraLabel::UraDora.name(Lang::En), "Ura Dora");
     }
+
+    fn batch_entries() -> Vec<(Hand, Status)> {
+        let mut ron = Status::new();
+        ron.has_claimed_riichi = true;
+        ron.seat_wind = Wind::South;
+        ron.round_wind = Wind::East;
+        let mut tsumo = Status::new();
+        tsumo.is_self_drawn = true;
+        tsumo.seat_wind = Wind::South;
+        tsumo.round_wind = Wind::East;
+        let mut open = ron.clone();
+        open.has_claimed_riichi = false;
+        open.has_claimed_open = true;
+
+        vec![
+            (Hand::from("123456m234p6799s 5s"), ron),
+            (Hand::from("123456m234p6799s 5s"), tsumo),
+            (Hand::from("123456m234p789s3z 3z"), open),
+            (Hand::from("123m"), Status::new()),
+        ]
+    }
+
+    #[test]
+    fn test_calculate_scores_batch() {
+        let results = calculate_scores(&batch_entries(), &Settings::new());
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0]
+                .as_ref()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .non_dealer_ron,
+            2000
+        );
+        assert_eq!(results[1].as_ref().unwrap().as_ref().unwrap().fu, Some(20));
+        assert!(results[2].as_ref().unwrap().is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_calculate_scores_parallel_matches_serial() {
+        let entries = batch_entries();
+        let settings = Settings::new();
+        let results = calculate_scores(&entries, &settings);
+        let parallel = calculate_scores_parallel(&entries, &settings, 3);
+        assert_eq!(parallel.len(), results.len());
+        for (a, b) in results.iter().zip(&parallel) {
+            match (a, b) {
+                (Ok(a), Ok(b)) => assert_eq!(a, b),
+                (Err(_), Err(_)) => {}
+                _ => panic!("parallel result differs"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_spoken_score() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let mut status = Status::new();
+        status.has_claimed_riichi = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let score = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(score.spoken(false, false, Lang::Ja), "2000点");
+        assert_eq!(score.spoken(false, false, Lang::En), "2000 points");
+        assert_eq!(score.payment_text(false, true), "500-1000");
+        assert_eq!(score.spoken(false, true, Lang::En), "500-1000");
+        assert_eq!(score.spoken(true, true, Lang::Ja), "1000点オール");
+        assert_eq!(score.spoken(true, true, Lang::En), "1000 all");
+
+        let mangan = ScoreResult {
+            rank: ScoreRank::Mangan,
+            non_dealer_ron: 8000,
+            ..score
+        };
+        assert_eq!(mangan.spoken(false, false, Lang::Ja), "満貫 8000点");
+        assert_eq!(mangan.spoken(false, false, Lang::En), "Mangan, 8000 points");
+    }
+
+    #[test]
+    fn test_score_display() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let mut status = Status::new();
+        status.has_claimed_riichi = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let calculate = || {
+            calculate_score(&analyzer, &hand, &status, &Settings::new())
+                .unwrap()
+                .unwrap()
+        };
+        let score = calculate();
+
+        let text = score.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], format!("{}翻{}符", score.han, score.fu.unwrap()));
+        assert_eq!(lines.len(), score.yaku_list.len() + 3);
+        assert!(lines.contains(&"立直 1翻"));
+        assert_eq!(lines[lines.len() - 2], "親: ロン 2900 / ツモ 1000オール");
+        assert_eq!(lines[lines.len() - 1], "子: ロン 2000 / ツモ 500-1000");
+
+        let kazoe = ScoreResult {
+            rank: ScoreRank::Yakuman,
+            han: 13,
+            fu: None,
+            yaku_list: vec![
+                (ScoreItem::Yaku(Kind::Riichi), 1),
+                (ScoreItem::Dora(DoraLabel::Dora), 12),
+            ],
+            ..calculate()
+        };
+        // 数え役満は翻数と役ごとの翻を残す
+        assert!(kazoe.to_string().starts_with("13翻 役満\n立直 1翻\n"));
+
+        let double = ScoreResult {
+            rank: ScoreRank::MultipleYakuman(2),
+            han: 26,
+            fu: None,
+            yaku_list: vec![(ScoreItem::Yaku(Kind::Riichi), 26)],
+            ..score
+        };
+        let text = double.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "ダブル役満");
+        assert_eq!(lines[1], "立直 ダブル役満");
+    }
+
+    #[test]
+    fn test_score_display_yakuman() {
+        // 国士無双: 翻数の代わりに役満と出力する
+        let hand = Hand::from("19m19p19s1234567z 1m");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let score = calculate_score(&analyzer, &hand, &Status::new(), &Settings::new())
+            .unwrap()
+            .unwrap();
+        let text = score.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "役満");
+        assert!(lines[1].ends_with(" 役満"));
+        assert!(!text.contains("翻"));
+    }
 }
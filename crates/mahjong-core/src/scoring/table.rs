@@ -0,0 +1,187 @@
+//! 翻・符から引く点数表
+//!
+//! 手牌を組み立てずに、翻数と符だけから和了点（本場・供託を含まない）を求める。
+//! 点数表の表示や、点数申告の練習問題の答え合わせに用いる。
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::scoring::score::{
+    Payments, ScoreRank, calculate_base_points, determine_rank, round_up_to_100,
+};
+use crate::settings::Settings;
+
+/// 点数表の1項目（本場・供託を含まない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScoreTableEntry {
+    /// 翻数
+    pub han: u32,
+    /// 符（5翻以上で符によらない項目は `None`）
+    pub fu: Option<u32>,
+    /// 点数等級
+    pub rank: ScoreRank,
+    /// 親のロン和了点
+    pub dealer_ron: u32,
+    /// 親のツモ和了点（各子の支払い）
+    pub dealer_tsumo_all: u32,
+    /// 子のロン和了点
+    pub non_dealer_ron: u32,
+    /// 子のツモ和了点（親の支払い）
+    pub non_dealer_tsumo_dealer: u32,
+    /// 子のツモ和了点（子の支払い）
+    pub non_dealer_tsumo_non_dealer: u32,
+}
+
+impl ScoreTableEntry {
+    /// 翻数・符から点数表の項目を求める
+    ///
+    /// 符は10符単位に切り上げる（七対子の25符を除く）。
+    /// 役満は`han`が13以上で`settings.kazoe_yakuman`が有効な場合のみ（数え役満）。
+    pub fn new(han: u32, fu: u32, settings: &Settings) -> ScoreTableEntry {
+        let fu = if fu == 25 { 25 } else { fu.div_ceil(10) * 10 };
+        let rank = determine_rank(han, fu, false, settings);
+        let base = calculate_base_points(han, fu, rank);
+        ScoreTableEntry {
+            han,
+            fu: (han < 5).then_some(fu),
+            rank,
+            dealer_ron: round_up_to_100(base * 6),
+            dealer_tsumo_all: round_up_to_100(base * 2),
+            non_dealer_ron: round_up_to_100(base * 4),
+            non_dealer_tsumo_dealer: round_up_to_100(base * 2),
+            non_dealer_tsumo_non_dealer: round_up_to_100(base),
+        }
+    }
+
+    /// 和了者の立場・和了方法に応じた支払いの内訳を返す
+    pub fn payments(&self, is_dealer: bool, is_self_drawn: bool) -> Payments {
+        let mut payments = Payments::default();
+        match (is_dealer, is_self_drawn) {
+            (true, true) => {
+                payments.tsumo_non_dealer = self.dealer_tsumo_all;
+                payments.total = self.dealer_tsumo_all * 3;
+            }
+            (false, true) => {
+                payments.tsumo_dealer = self.non_dealer_tsumo_dealer;
+                payments.tsumo_non_dealer = self.non_dealer_tsumo_non_dealer;
+                payments.total = payments.tsumo_dealer + payments.tsumo_non_dealer * 2;
+            }
+            (true, false) => {
+                payments.ron = self.dealer_ron;
+                payments.total = self.dealer_ron;
+            }
+            (false, false) => {
+                payments.ron = self.non_dealer_ron;
+                payments.total = self.non_dealer_ron;
+            }
+        }
+        payments
+    }
+}
+
+/// 翻数・符から支払いの内訳を求める（本場・供託を含まない）
+///
+/// 例: 子の3翻30符ロンは`ron == 3900`、親の4翻30符ツモは`tsumo_non_dealer == 3900`。
+pub fn score_for(
+    han: u32,
+    fu: u32,
+    is_dealer: bool,
+    is_self_drawn: bool,
+    settings: &Settings,
+) -> Payments {
+    ScoreTableEntry::new(han, fu, settings).payments(is_dealer, is_self_drawn)
+}
+
+/// 点数表の符の並び
+const TABLE_FU: [u32; 11] = [20, 25, 30, 40, 50, 60, 70, 80, 90, 100, 110];
+
+/// 満貫以上の等級ごとの代表の翻数
+const LIMIT_HAN: [u32; 5] = [5, 6, 8, 11, 13];
+
+/// 点数表の全項目を返す
+///
+/// 1〜4翻は符ごとに（成立しない1翻20符・1翻25符を除く）、満貫以上の等級は符によらず
+/// 1項目ずつ返す。満貫に達する符の項目は満貫として含める。
+/// 数え役満が無効なら13翻の項目は三倍満になるため含めない。
+pub fn score_table(settings: &Settings) -> Vec<ScoreTableEntry> {
+    let mut table: Vec<ScoreTableEntry> = (1..=4)
+        .flat_map(|han| TABLE_FU.iter().map(move |&fu| (han, fu)))
+        .filter(|&(han, fu)| !(han == 1 && fu <= 25))
+        .map(|(han, fu)| ScoreTableEntry::new(han, fu, settings))
+        .collect();
+    table.extend(
+        LIMIT_HAN
+            .iter()
+            .map(|&han| ScoreTableEntry::new(han, 30, settings))
+            .filter(|entry| entry.han < 13 || entry.rank == ScoreRank::Yakuman),
+    );
+    table
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 30, false, false, 1000)]
+    #[case(2, 30, false, false, 2000)]
+    #[case(3, 30, false, false, 3900)]
+    #[case(4, 30, false, false, 8000)]
+    #[case(3, 30, true, false, 5800)]
+    #[case(4, 40, true, false, 12000)]
+    #[case(2, 25, false, false, 1600)]
+    #[case(6, 30, false, false, 12000)]
+    #[case(13, 30, true, false, 48000)]
+    // 符は10符単位に切り上げる
+    #[case(1, 32, false, false, 1300)]
+    fn ron_scores(
+        #[case] han: u32,
+        #[case] fu: u32,
+        #[case] is_dealer: bool,
+        #[case] is_self_drawn: bool,
+        #[case] expected: u32,
+    ) {
+        let payments = score_for(han, fu, is_dealer, is_self_drawn, &Settings::new());
+        assert_eq!(payments.ron, expected);
+        assert_eq!(payments.total, expected);
+    }
+
+    #[test]
+    fn tsumo_scores() {
+        let settings = Settings::new();
+        let non_dealer = score_for(1, 30, false, true, &settings);
+        assert_eq!(
+            (non_dealer.tsumo_non_dealer, non_dealer.tsumo_dealer),
+            (300, 500)
+        );
+        assert_eq!(non_dealer.total, 1100);
+        let dealer = score_for(3, 30, true, true, &settings);
+        assert_eq!(dealer.tsumo_non_dealer, 2000);
+        assert_eq!(dealer.total, 6000);
+    }
+
+    #[test]
+    fn table_lists_each_han_fu_once() {
+        let settings = Settings::new();
+        let table = score_table(&settings);
+        assert_eq!(table.len(), 4 * TABLE_FU.len() - 2 + LIMIT_HAN.len());
+        assert!(!table.iter().any(|e| e.han == 1 && e.fu == Some(20)));
+        let three_thirty = table
+            .iter()
+            .find(|e| e.han == 3 && e.fu == Some(30))
+            .unwrap();
+        assert_eq!(three_thirty.non_dealer_ron, 3900);
+        assert_eq!(table.last().unwrap().rank, ScoreRank::Yakuman);
+    }
+
+    #[test]
+    fn table_without_kazoe_yakuman_stops_at_sanbaiman() {
+        let mut settings = Settings::new();
+        settings.kazoe_yakuman = false;
+        let table = score_table(&settings);
+        assert_eq!(table.last().unwrap().rank, ScoreRank::Sanbaiman);
+    }
+}
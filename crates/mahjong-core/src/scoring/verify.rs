@@ -0,0 +1,174 @@
+use crate::error::Result;
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::HandAnalyzer;
+use crate::hand_info::status::Status;
+use crate::scoring::score::{ScoreResult, calculate_score};
+use crate::settings::Settings;
+
+/// 申告された点数と計算結果の食い違い
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mismatch {
+    /// 役がなく和了できない
+    NoYaku,
+    /// 翻数が異なる
+    Han { claimed: u32, expected: u32 },
+    /// 符が異なる
+    Fu { claimed: u32, expected: u32 },
+    /// 和了点（和了者の受け取る合計点）が異なる
+    Points { claimed: u32, expected: u32 },
+}
+
+/// 点数検証の結果
+#[derive(Debug)]
+pub struct VerifyReport {
+    /// この crate のルールで計算した点数（役がない場合は None）
+    pub expected: Option<ScoreResult>,
+    /// 申告との食い違いの一覧（一致していれば空）
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl VerifyReport {
+    /// 申告がすべて計算結果と一致したかを返す
+    pub fn is_agreed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// 申告された翻数・符・和了点を検証する
+///
+/// 和了点は本場・供託を含まない、和了者が受け取る合計点（ツモの場合は全員の支払いの合計）とする。
+/// 符は点数に影響する場合（4翻以下）のみ比較する。
+///
+/// # Arguments
+/// * `hand` - 手牌
+/// * `status` - 局の状態
+/// * `settings` - ルール設定
+/// * `claimed_han` - 申告された翻数
+/// * `claimed_fu` - 申告された符
+/// * `claimed_points` - 申告された和了点
+pub fn verify(
+    hand: &Hand,
+    status: &Status,
+    settings: &Settings,
+    claimed_han: u32,
+    claimed_fu: u32,
+    claimed_points: u32,
+) -> Result<VerifyReport> {
+    let analyzer = HandAnalyzer::new(hand)?;
+    let Some(expected) = calculate_score(&analyzer, hand, status, settings)? else {
+        return Ok(VerifyReport {
+            expected: None,
+            mismatches: vec![Mismatch::NoYaku],
+        });
+    };
+
+    let mut mismatches = Vec::new();
+    if claimed_han != expected.han {
+        mismatches.push(Mismatch::Han {
+            claimed: claimed_han,
+            expected: expected.han,
+        });
+    }
+    if let Some(expected_fu) = expected.fu
+        && expected.han < 5
+        && claimed_fu != expected_fu
+    {
+        mismatches.push(Mismatch::Fu {
+            claimed: claimed_fu,
+            expected: expected_fu,
+        });
+    }
+    let expected_points = winner_points(&expected, status);
+    if claimed_points != expected_points {
+        mismatches.push(Mismatch::Points {
+            claimed: claimed_points,
+            expected: expected_points,
+        });
+    }
+
+    Ok(VerifyReport {
+        expected: Some(expected),
+        mismatches,
+    })
+}
+
+/// 和了者が受け取る合計点を返す
+pub(crate) fn winner_points(result: &ScoreResult, status: &Status) -> u32 {
+    match (status.is_dealer, status.is_self_drawn) {
+        (true, true) => result.dealer_tsumo_all * 3,
+        (true, false) => result.dealer_ron,
+        (false, true) => result.non_dealer_tsumo_dealer + result.non_dealer_tsumo_non_dealer * 2,
+        (false, false) => result.non_dealer_ron,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Wind;
+
+    fn ron_status() -> Status {
+        let mut status = Status::new();
+        status.has_claimed_riichi = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        status
+    }
+
+    /// 立直・平和 2翻30符の子ロン 2000点
+    #[test]
+    fn verify_agreed_test() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let report = verify(&hand, &ron_status(), &Settings::new(), 2, 30, 2000).unwrap();
+        assert!(report.is_agreed());
+        assert_eq!(report.expected.unwrap().han, 2);
+    }
+
+    #[test]
+    fn verify_mismatch_test() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let report = verify(&hand, &ron_status(), &Settings::new(), 3, 40, 5200).unwrap();
+        assert!(!report.is_agreed());
+        assert_eq!(
+            report.mismatches,
+            vec![
+                Mismatch::Han {
+                    claimed: 3,
+                    expected: 2
+                },
+                Mismatch::Fu {
+                    claimed: 40,
+                    expected: 30
+                },
+                Mismatch::Points {
+                    claimed: 5200,
+                    expected: 2000
+                },
+            ]
+        );
+    }
+
+    /// 子のツモは全員の支払いの合計で比較する（門前清自摸和・平和 2翻20符: 700 + 400×2）
+    #[test]
+    fn verify_tsumo_total_test() {
+        let hand = Hand::from("123456m234p6799s 5s");
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        let report = verify(&hand, &status, &Settings::new(), 2, 20, 1500).unwrap();
+        assert!(report.is_agreed());
+    }
+
+    #[test]
+    fn verify_no_yaku_test() {
+        let hand = Hand::from("123456m234p789s3z 3z");
+        let mut status = ron_status();
+        status.has_claimed_riichi = false;
+        status.has_claimed_open = true;
+        let report = verify(&hand, &status, &Settings::new(), 1, 30, 1000).unwrap();
+        assert!(report.expected.is_none());
+        assert_eq!(report.mismatches, vec![Mismatch::NoYaku]);
+    }
+}
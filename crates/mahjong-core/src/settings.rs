@@ -1,7 +1,9 @@
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// 表示をどの言語にするかの列挙型
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Lang {
     /// 英語
     En,
@@ -9,8 +11,88 @@ pub enum Lang {
     Ja,
 }
 
+/// 親の連荘の条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Renchan {
+    /// 親の和了、または流局時に親が聴牌していれば連荘する
+    #[default]
+    WinOrTenpai,
+    /// 親の和了でのみ連荘する（流局時は親が聴牌していても親が流れる）
+    WinOnly,
+    /// 連荘しない（親の和了でも親が流れる）
+    Never,
+}
+
+/// 喰い替え（チー・ポン直後に鳴いた面子と入れ替わる牌を捨てること）の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Kuikae {
+    /// 現物喰い替え（鳴いた牌と同種）とスジ喰い替え（チーで作った順子の反対端の外側の牌）を禁止する
+    #[default]
+    Forbidden,
+    /// 現物喰い替えのみ禁止する（スジ喰い替えは許可）
+    OnlySameTile,
+    /// 喰い替えを許可する
+    Allowed,
+}
+
+/// 1つの打牌に複数のプレイヤーがロン宣言したときの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MultipleRon {
+    /// 打順が最も早い1人のみ和了を認める（頭ハネ・上家取り）
+    HeadBump,
+    /// 全員の和了を認める（ダブロン・トリロン）
+    #[default]
+    AllWin,
+    /// 流局とする
+    AbortiveDraw,
+}
+
+/// 明槓（大明槓・加槓）の新ドラをめくるタイミング
+///
+/// 暗槓の新ドラはいずれの場合も即座にめくる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum KanDoraTiming {
+    /// カンの直後にめくる（嶺上開花でも新ドラが乗る）
+    #[default]
+    Immediate,
+    /// カンした人の次の打牌の後にめくる（嶺上開花では新ドラが乗らない）
+    AfterDiscard,
+}
+
+/// ローカル役ごとの有無（デフォルトはいずれもなし）
+///
+/// 判定は `local-yaku` フィーチャーを有効にしたときのみ行う。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct LocalYaku {
+    /// 三連刻（同じ色で数字が連続する3つの刻子、2翻）
+    pub three_consecutive_triplets: bool,
+    /// 四連刻（同じ色で数字が連続する4つの刻子、役満）
+    pub four_consecutive_triplets: bool,
+    /// 大車輪（筒子の二〜八の七対子、門前のみ、役満）
+    pub big_wheels: bool,
+    /// 一色三順（同じ順子3つ、3翻・喰い下がり2翻）
+    pub pure_triple_sequences: bool,
+}
+
+/// 終局時に残った供託リーチ棒の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LeftoverRiichiSticks {
+    /// トップのプレイヤーが受け取る（同点の場合は起家に近い座席）
+    #[default]
+    TopPlayer,
+    /// 誰も受け取らない（供託は没収される）
+    Forfeited,
+}
+
 /// 設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Settings {
     /// 表示言語（デフォルトは日本語）
     pub display_lang: Lang,
@@ -32,15 +114,129 @@ pub struct Settings {
     /// 三家和流局ありかなしか（デフォルトはなし）
     /// ありの場合: 1人の捨て牌に対して3人全員がロン宣言したら流局
     pub triple_ron_draw: bool,
-    /// 複数同時ロン（ダブロン・トリロン）を許可するか（デフォルトはあり）
-    /// ありの場合: 2人または3人がロン宣言した場合、全員の和了を認める
-    /// なしの場合: 打順が最も早い1人のみ和了を認める（上家取り）
+    /// 複数同時ロン（ダブロン・トリロン）の扱い（デフォルトは全員の和了を認める）
+    /// 頭ハネの場合: 打順が最も早い1人のみ和了を認める（上家取り）
+    /// 流局の場合: 2人以上がロン宣言したら流局とする
     /// ※ triple_ron_draw=true かつ 3人ロンの場合は、こちらより三家和流局が優先される
-    pub multiple_ron: bool,
-    /// 喰い替え禁止ありかなしか（デフォルトはあり＝禁止する）
-    /// ありの場合: チー・ポン直後の打牌で、鳴いた牌と同種（現物喰い替え）や
+    /// ※ 以前の形式（true＝全員和了、false＝頭ハネ）も読み込める
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_multiple_ron")
+    )]
+    pub multiple_ron: MultipleRon,
+    /// 喰い替えの扱い（デフォルトは現物・スジともに禁止）
+    /// 禁止の場合: チー・ポン直後の打牌で、鳴いた牌と同種（現物喰い替え）や
     /// チーで作った順子の反対端の牌（スジ喰い替え）を捨てられない
-    pub forbid_swap_calling: bool,
+    /// ※ 以前の形式（forbid_swap_calling: true＝禁止、false＝許可）も読み込める
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            alias = "forbid_swap_calling",
+            deserialize_with = "deserialize_kuikae"
+        )
+    )]
+    pub kuikae: Kuikae,
+    /// 三人麻雀（三麻）か（デフォルトはなし＝四人麻雀）
+    /// ありの場合: 二萬〜八萬を使用しないため、手牌に含まれていればエラーとし、
+    /// 有効牌の枚数計算でも残り枚数を0枚として扱う
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub three_player: bool,
+    /// 親の連荘の条件（デフォルトは和了または流局時聴牌で連荘）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub renchan: Renchan,
+    /// 終局時に残った供託リーチ棒の扱い（デフォルトはトップ取り）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub leftover_riichi_sticks: LeftoverRiichiSticks,
+    /// 抜きドラ（三人麻雀で抜いた北）を1枚1翻として数えるか（デフォルトはあり）
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub count_nuki_dora: bool,
+    /// 流し満貫の支払いに本場を加算するか（デフォルトはあり）
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub nagashi_mangan_honba: bool,
+    /// 流し満貫の成立時にもノーテン罰符を精算するか（デフォルトはなし）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nagashi_mangan_tenpai_payments: bool,
+    /// 四暗刻単騎待ち・国士無双十三面待ち・純正九蓮宝燈・大四喜をダブル役満とするか
+    /// （デフォルトはなし＝いずれもシングル役満）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub double_yakuman: bool,
+    /// 複数の役満が複合したとき、役満の倍数を合算するか（デフォルトはなし＝最も高い役満のみ）
+    /// ありの場合: 例えば大三元・字一色・四暗刻はトリプル役満になる
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allow_multiple_yakuman: bool,
+    /// 役満以外の役とドラで13翻以上になったとき、数え役満とするか（デフォルトはあり）
+    /// なしの場合: 13翻以上でも三倍満として扱う
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub kazoe_yakuman: bool,
+    /// 赤ドラの枚数（萬子・筒子・索子の五の順。デフォルトは各1枚）
+    /// 牌山を作るときに、各色の五のうちこの枚数を赤ドラにする（各色4枚まで）
+    #[cfg_attr(feature = "serde", serde(default = "default_red_fives"))]
+    pub red_fives: [u8; 3],
+    /// 平和ツモを20符とするか（デフォルトはあり）
+    /// なしの場合: 平和ツモにもツモ符2符を加え、30符とする
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub pinfu_tsumo_20fu: bool,
+    /// 切り上げ満貫ありかなしか（デフォルトはあり）
+    /// ありの場合: 4翻30符・3翻60符を満貫とする
+    /// なしの場合: 子7700点・親11600点とする
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub kiriage_mangan: bool,
+    /// 明槓の新ドラをめくるタイミング（デフォルトはカン直後）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kan_dora_timing: KanDoraTiming,
+    /// 裏ドラを立直した和了者のみに数えるか（デフォルトはあり）
+    /// なしの場合: 立直していない和了者にも裏ドラを数える
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub ura_dora_requires_riichi: bool,
+    /// ローカル役の有無（デフォルトはいずれもなし）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub local_yaku: LocalYaku,
+}
+
+#[cfg(feature = "serde")]
+fn default_true() -> bool {
+    true
+}
+
+/// `multiple_ron` を読み込む（以前の bool 形式にも対応する）
+#[cfg(feature = "serde")]
+fn deserialize_multiple_ron<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<MultipleRon, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Flag(bool),
+        Policy(MultipleRon),
+    }
+    Ok(match Repr::deserialize(deserializer)? {
+        Repr::Flag(true) => MultipleRon::AllWin,
+        Repr::Flag(false) => MultipleRon::HeadBump,
+        Repr::Policy(policy) => policy,
+    })
+}
+
+/// `kuikae` を読み込む（以前の `forbid_swap_calling` の bool 形式にも対応する）
+#[cfg(feature = "serde")]
+fn deserialize_kuikae<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Kuikae, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Flag(bool),
+        Policy(Kuikae),
+    }
+    Ok(match Repr::deserialize(deserializer)? {
+        Repr::Flag(true) => Kuikae::Forbidden,
+        Repr::Flag(false) => Kuikae::Allowed,
+        Repr::Policy(policy) => policy,
+    })
+}
+
+fn default_red_fives() -> [u8; 3] {
+    [1, 1, 1]
 }
 
 impl Default for Settings {
@@ -59,8 +255,144 @@ impl Settings {
             four_riichi_draw: false,
             nine_terminals_draw: true,
             triple_ron_draw: false,
-            multiple_ron: true,
-            forbid_swap_calling: true,
+            multiple_ron: MultipleRon::AllWin,
+            kuikae: Kuikae::Forbidden,
+            three_player: false,
+            renchan: Renchan::WinOrTenpai,
+            leftover_riichi_sticks: LeftoverRiichiSticks::TopPlayer,
+            count_nuki_dora: true,
+            nagashi_mangan_honba: true,
+            nagashi_mangan_tenpai_payments: false,
+            double_yakuman: false,
+            allow_multiple_yakuman: false,
+            kazoe_yakuman: true,
+            red_fives: default_red_fives(),
+            pinfu_tsumo_20fu: true,
+            kiriage_mangan: true,
+            kan_dora_timing: KanDoraTiming::Immediate,
+            ura_dora_requires_riichi: true,
+            local_yaku: LocalYaku::default(),
+        }
+    }
+
+    /// 天鳳（鳳凰卓）のルール
+    ///
+    /// 喰いタン・赤ドラ各1枚・ダブロンあり。切り上げ満貫なし。三家和・四家立直は流局とする。
+    /// 明槓の新ドラは打牌後にめくる。
+    /// 役満の複合はありだが、ダブル役満はなし。
+    pub fn tenhou() -> Settings {
+        Settings {
+            four_riichi_draw: true,
+            triple_ron_draw: true,
+            allow_multiple_yakuman: true,
+            kiriage_mangan: false,
+            kan_dora_timing: KanDoraTiming::AfterDiscard,
+            ..Settings::new()
+        }
+    }
+
+    /// Mリーグのルール
+    ///
+    /// 喰いタン・赤ドラ各1枚あり。複数のロンは上家取り（頭ハネ）で、四家立直は流局とする。
+    /// 役満の複合はありだが、ダブル役満はなし。
+    pub fn mleague() -> Settings {
+        Settings {
+            four_riichi_draw: true,
+            multiple_ron: MultipleRon::HeadBump,
+            allow_multiple_yakuman: true,
+            ..Settings::new()
+        }
+    }
+
+    /// 世界リーチ麻雀選手権（WRC）のルール
+    ///
+    /// 赤ドラなし・途中流局なし・頭ハネ・切り上げ満貫なし。数え役満はなく、13翻以上は三倍満とする。
+    /// 終局時の供託は没収し、役満の複合はありだが、ダブル役満はなし。
+    pub fn wrc() -> Settings {
+        Settings {
+            four_kans_draw: false,
+            four_winds_draw: false,
+            four_riichi_draw: false,
+            nine_terminals_draw: false,
+            triple_ron_draw: false,
+            multiple_ron: MultipleRon::HeadBump,
+            leftover_riichi_sticks: LeftoverRiichiSticks::Forfeited,
+            allow_multiple_yakuman: true,
+            kazoe_yakuman: false,
+            red_fives: [0, 0, 0],
+            kiriage_mangan: false,
+            ..Settings::new()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let tenhou = Settings::tenhou();
+        assert!(tenhou.opened_all_inside && tenhou.triple_ron_draw);
+        assert_eq!(tenhou.multiple_ron, MultipleRon::AllWin);
+        assert_eq!(tenhou.red_fives, [1, 1, 1]);
+        assert!(!tenhou.kiriage_mangan);
+        assert_eq!(tenhou.kan_dora_timing, KanDoraTiming::AfterDiscard);
+
+        let mleague = Settings::mleague();
+        assert_eq!(mleague.multiple_ron, MultipleRon::HeadBump);
+        assert!(mleague.kazoe_yakuman && mleague.kiriage_mangan);
+
+        let wrc = Settings::wrc();
+        assert!(!wrc.kazoe_yakuman && !wrc.nine_terminals_draw && !wrc.four_winds_draw);
+        assert_eq!(wrc.red_fives, [0, 0, 0]);
+        assert_eq!(wrc.leftover_riichi_sticks, LeftoverRiichiSticks::Forfeited);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_red_fives_default_when_missing() {
+        let mut json = serde_json::to_value(Settings::wrc()).unwrap();
+        json.as_object_mut().unwrap().remove("red_fives");
+        let settings: Settings = serde_json::from_value(json).unwrap();
+        assert_eq!(settings.red_fives, [1, 1, 1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_multiple_ron_accepts_legacy_bool() {
+        let mut json = serde_json::to_value(Settings::new()).unwrap();
+        json["multiple_ron"] = serde_json::Value::Bool(false);
+        let settings: Settings = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(settings.multiple_ron, MultipleRon::HeadBump);
+
+        json["multiple_ron"] = serde_json::json!("AbortiveDraw");
+        let settings: Settings = serde_json::from_value(json).unwrap();
+        assert_eq!(settings.multiple_ron, MultipleRon::AbortiveDraw);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_kuikae_accepts_legacy_forbid_swap_calling() {
+        let mut json = serde_json::to_value(Settings::new()).unwrap();
+        let obj = json.as_object_mut().unwrap();
+        obj.remove("kuikae");
+        obj.insert("forbid_swap_calling".into(), serde_json::Value::Bool(false));
+        let settings: Settings = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(settings.kuikae, Kuikae::Allowed);
+
+        json["forbid_swap_calling"] = serde_json::Value::Bool(true);
+        let settings: Settings = serde_json::from_value(json).unwrap();
+        assert_eq!(settings.kuikae, Kuikae::Forbidden);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_kuikae_round_trip() {
+        let mut settings = Settings::new();
+        settings.kuikae = Kuikae::OnlySameTile;
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: Settings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.kuikae, Kuikae::OnlySameTile);
+    }
+}
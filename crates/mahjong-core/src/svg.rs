@@ -0,0 +1,267 @@
+//! SVG 形式での手牌・卓の描画
+//!
+//! `svg` フィーチャーを有効にした場合のみ利用できる。
+//! 外部の画像素材に依存せず、牌を矩形と文字で表現した SVG 文字列を生成する。
+
+use std::fmt::Write;
+
+use crate::hand::Hand;
+use crate::hand_info::meld::{Meld, MeldFrom, MeldType};
+use crate::settings::Lang;
+use crate::tile::{Tile, Wind};
+
+/// 牌1枚の幅
+const TILE_WIDTH: u32 = 30;
+/// 牌1枚の高さ
+const TILE_HEIGHT: u32 = 40;
+/// 手牌・副露・ツモ牌の間の余白
+const GROUP_GAP: u32 = 10;
+/// 描画全体の外周の余白
+const MARGIN: u32 = 4;
+/// 卓描画時の見出し行の高さ
+const LABEL_HEIGHT: u32 = 20;
+
+/// 卓描画時の1人分の情報
+#[derive(Debug, Clone, Copy)]
+pub struct SvgSeat<'a> {
+    /// 自風
+    pub wind: Wind,
+    /// 手牌
+    pub hand: &'a Hand,
+    /// 河（捨て牌）
+    pub river: &'a [Tile],
+}
+
+/// 牌の描画方法
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum TileFace {
+    /// 通常（縦向き）
+    Upright,
+    /// 横向き（鳴いた牌）
+    Sideways,
+    /// 裏向き（暗カンの両端）
+    FaceDown,
+}
+
+/// 手牌を SVG として描画する
+///
+/// 副露は鳴いた相手に応じた位置の牌を横向きにし、暗カンは両端を裏向きにする。
+/// ツモ牌（和了牌）は手牌から離して右端に描画する。
+pub fn render_hand(hand: &Hand) -> String {
+    let mut body = String::new();
+    let width = write_hand(&mut body, hand, MARGIN, MARGIN);
+    wrap_svg(width + MARGIN, TILE_HEIGHT + MARGIN * 2, &body)
+}
+
+/// 卓全体（各家の河と手牌、ドラ表示牌）を SVG として描画する
+///
+/// 各家は上から順に見出し・河・手牌の行として並べる。
+pub fn render_table(seats: &[SvgSeat], dora_indicators: &[Tile], lang: Lang) -> String {
+    let mut body = String::new();
+    let mut y = MARGIN;
+    let mut width = 0;
+
+    write_label(&mut body, MARGIN, y, dora_label(lang));
+    y += LABEL_HEIGHT;
+    width = width.max(write_tiles(&mut body, dora_indicators, MARGIN, y));
+    y += TILE_HEIGHT + GROUP_GAP;
+
+    for seat in seats {
+        write_label(&mut body, MARGIN, y, seat.wind.name(lang));
+        y += LABEL_HEIGHT;
+        width = width.max(write_tiles(&mut body, seat.river, MARGIN, y));
+        y += TILE_HEIGHT + GROUP_GAP / 2;
+        width = width.max(write_hand(&mut body, seat.hand, MARGIN, y));
+        y += TILE_HEIGHT + GROUP_GAP;
+    }
+
+    wrap_svg(width + MARGIN, y - GROUP_GAP + MARGIN, &body)
+}
+
+fn dora_label(lang: Lang) -> &'static str {
+    match lang {
+        Lang::En => "Dora indicators",
+        Lang::Ja => "ドラ表示牌",
+    }
+}
+
+/// 手牌を描画し、描画後の右端の x 座標を返す
+fn write_hand(out: &mut String, hand: &Hand, x: u32, y: u32) -> u32 {
+    let mut x = write_tiles(out, hand.tiles(), x, y);
+
+    for meld in hand.melds() {
+        x += GROUP_GAP;
+        x = write_meld(out, meld, x, y);
+    }
+
+    if !hand.nuki().is_empty() {
+        x += GROUP_GAP;
+        x = write_tiles(out, hand.nuki(), x, y);
+    }
+
+    if let Some(drawn) = hand.drawn() {
+        x += GROUP_GAP;
+        x = write_tile(out, drawn, TileFace::Upright, x, y);
+    }
+    x
+}
+
+/// 副露を描画し、描画後の右端の x 座標を返す
+fn write_meld(out: &mut String, meld: &Meld, mut x: u32, y: u32) -> u32 {
+    let tiles = meld.expanded_tiles();
+    let faces = meld_faces(meld, tiles.len());
+    for (tile, face) in tiles.into_iter().zip(faces) {
+        x = write_tile(out, tile, face, x, y);
+    }
+    x
+}
+
+/// 副露の各牌の向きを返す
+///
+/// 上家からは左端、対面からは中央、下家からは右端の牌を横向きにする。
+fn meld_faces(meld: &Meld, len: usize) -> Vec<TileFace> {
+    let mut faces = vec![TileFace::Upright; len];
+    if len == 0 {
+        return faces;
+    }
+    let sideways = match meld.from {
+        MeldFrom::Previous => Some(0),
+        MeldFrom::Opposite => Some(1),
+        MeldFrom::Following => Some(len - 1),
+        MeldFrom::Myself => {
+            if meld.category == MeldType::Kan {
+                faces[0] = TileFace::FaceDown;
+                faces[len - 1] = TileFace::FaceDown;
+            }
+            None
+        }
+        MeldFrom::Unknown => None,
+    };
+    if let Some(i) = sideways {
+        faces[i] = TileFace::Sideways;
+    }
+    faces
+}
+
+/// 牌を横一列に描画し、描画後の右端の x 座標を返す
+fn write_tiles(out: &mut String, tiles: &[Tile], mut x: u32, y: u32) -> u32 {
+    for tile in tiles {
+        x = write_tile(out, *tile, TileFace::Upright, x, y);
+    }
+    x
+}
+
+/// 牌を1枚描画し、描画後の右端の x 座標を返す
+fn write_tile(out: &mut String, tile: Tile, face: TileFace, x: u32, y: u32) -> u32 {
+    // 横向きの牌は下端を揃える
+    let (w, h, top) = match face {
+        TileFace::Sideways => (TILE_HEIGHT, TILE_WIDTH, y + TILE_HEIGHT - TILE_WIDTH),
+        _ => (TILE_WIDTH, TILE_HEIGHT, y),
+    };
+    let fill = if face == TileFace::FaceDown {
+        "#2e7d32"
+    } else {
+        "#fffdf5"
+    };
+    let _ = write!(
+        out,
+        r##"<rect x="{x}" y="{top}" width="{w}" height="{h}" rx="3" fill="{fill}" stroke="#333"/>"##
+    );
+    if face != TileFace::FaceDown {
+        let color = if tile.is_red_dora() {
+            "#d32f2f"
+        } else {
+            "#111"
+        };
+        let _ = write!(
+            out,
+            r#"<text x="{}" y="{}" font-size="14" text-anchor="middle" dominant-baseline="central" fill="{color}">{tile}</text>"#,
+            x + w / 2,
+            top + h / 2,
+        );
+    }
+    x + w
+}
+
+fn write_label(out: &mut String, x: u32, y: u32, label: &str) {
+    let _ = write!(
+        out,
+        r##"<text x="{x}" y="{}" font-size="14" fill="#111">{label}</text>"##,
+        y + LABEL_HEIGHT - 6,
+    );
+}
+
+fn wrap_svg(width: u32, height: u32, body: &str) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">{body}</svg>"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sideways_rects(svg: &str) -> usize {
+        svg.matches(&format!(r#"width="{TILE_HEIGHT}" height="{TILE_WIDTH}""#))
+            .count()
+    }
+
+    #[test]
+    fn render_closed_hand_test() {
+        let hand = Hand::from("123m456p789s1122z 2z");
+        let svg = render_hand(&hand);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect").count(), 14);
+        assert_eq!(sideways_rects(&svg), 0);
+        // ツモ牌は余白を挟んで右端に置かれる
+        let drawn_x = MARGIN + TILE_WIDTH * 13 + GROUP_GAP;
+        assert!(svg.contains(&format!(r#"<rect x="{drawn_x}""#)));
+    }
+
+    #[test]
+    fn render_meld_rotation_test() {
+        let mut hand = Hand::from("123m456p789s1z 1z");
+        hand.add_meld(Meld {
+            tiles: vec![Tile::new(Tile::Z5); 3],
+            category: MeldType::Pon,
+            from: MeldFrom::Opposite,
+            called_tile: Some(Tile::new(Tile::Z5)),
+        });
+        let svg = render_hand(&hand);
+        assert_eq!(sideways_rects(&svg), 1);
+        // 対面からのポンは中央の牌が横向きになる
+        let sideways_x = MARGIN + TILE_WIDTH * 10 + GROUP_GAP + TILE_WIDTH;
+        assert!(svg.contains(&format!(r#"<rect x="{sideways_x}" y="{}""#, MARGIN + 10)));
+    }
+
+    #[test]
+    fn render_closed_kan_test() {
+        let mut hand = Hand::from("123m456p789s1z 1z");
+        hand.add_meld(Meld {
+            tiles: vec![Tile::new(Tile::Z7); 3],
+            category: MeldType::Kan,
+            from: MeldFrom::Myself,
+            called_tile: None,
+        });
+        let svg = render_hand(&hand);
+        assert_eq!(sideways_rects(&svg), 0);
+        assert_eq!(svg.matches("#2e7d32").count(), 2);
+    }
+
+    #[test]
+    fn render_table_test() {
+        let hand = Hand::from("123m456p789s1122z");
+        let river = [Tile::new(Tile::Z3), Tile::new_red(Tile::M5)];
+        let seats = Wind::ALL.map(|wind| SvgSeat {
+            wind,
+            hand: &hand,
+            river: &river,
+        });
+        let svg = render_table(&seats, &[Tile::new(Tile::P1)], Lang::Ja);
+        assert_eq!(svg.matches("<rect").count(), 1 + 4 * (13 + 2));
+        assert!(svg.contains("ドラ表示牌"));
+        assert!(svg.contains(">南<"));
+        assert_eq!(svg.matches("#d32f2f").count(), 4);
+    }
+}
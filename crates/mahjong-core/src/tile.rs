@@ -1,6 +1,9 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
+use crate::error::{MahjongError, ParseError};
 use crate::settings::Lang;
 
 /// 牌の種類を示す型
@@ -9,7 +12,8 @@ pub type TileType = u32;
 pub type TileSummarize = [u32; Tile::LEN];
 
 /// 牌
-#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Tile {
     index: TileType,
     red_dora: bool,
@@ -165,6 +169,63 @@ impl Tile {
         self.is_1_or_9() || self.is_honour()
     }
 
+    /// 老頭牌（数牌の1・9）か否かを返す（`is_1_or_9`と同じ）
+    pub fn is_terminal(&self) -> bool {
+        self.is_1_or_9()
+    }
+    /// 中張牌（数牌の2〜8）か否かを返す
+    pub fn is_simple(&self) -> bool {
+        matches!(self.number(), Some(2..=8))
+    }
+
+    /// 牌のスートを返す
+    pub fn suit(&self) -> Suit {
+        Suit::of(self.index).unwrap_or(Suit::Honour)
+    }
+    /// スートと数字（字牌は東南西北白發中の順に1〜7）から牌を作る（範囲外は`None`）
+    pub fn from_suit_number(suit: Suit, number: u8) -> Option<Tile> {
+        let len = suit.tile_types().count() as u8;
+        (1..=len)
+            .contains(&number)
+            .then(|| Tile::new(suit.first_tile() + TileType::from(number) - 1))
+    }
+    /// 数牌の数字（1〜9）を返す（字牌は`None`）
+    pub fn number(&self) -> Option<u8> {
+        suit_rank(self.index).map(|n| n as u8)
+    }
+
+    /// 同じスート（字牌は風牌・三元牌）の次の牌を返す（9→1、北→東、中→白と循環する）
+    ///
+    /// ドラ表示牌からドラを求めるときの順序。赤ドラの情報は引き継がない。
+    pub fn next_with_wrap(&self) -> Tile {
+        let (first, last) = self.wrap_range();
+        Tile::new(if self.index == last {
+            first
+        } else {
+            self.index + 1
+        })
+    }
+    /// `next_with_wrap`の逆順で前の牌を返す（1→9、東→北、白→中と循環する）
+    pub fn prev_with_wrap(&self) -> Tile {
+        let (first, last) = self.wrap_range();
+        Tile::new(if self.index == first {
+            last
+        } else {
+            self.index - 1
+        })
+    }
+
+    /// 循環する牌の範囲（最初と最後の牌種）
+    fn wrap_range(&self) -> (TileType, TileType) {
+        match self.index {
+            Tile::M1..=Tile::M9 => (Tile::M1, Tile::M9),
+            Tile::P1..=Tile::P9 => (Tile::P1, Tile::P9),
+            Tile::S1..=Tile::S9 => (Tile::S1, Tile::S9),
+            Tile::Z1..=Tile::Z4 => (Tile::Z1, Tile::Z4),
+            _ => (Tile::Z5, Tile::Z7),
+        }
+    }
+
     /// 対子（同じ2枚）か否かを返す
     pub fn is_same_to(&self, tile: Tile) -> bool {
         self.get() == tile.get()
@@ -233,6 +294,44 @@ impl fmt::Display for Tile {
     }
 }
 
+/// `"3s"`・`"🀒"`・赤5の`"0m"`（`"r5m"`）の形式の1枚を解釈する
+///
+/// 前後の空白は無視する。解釈できない場合は`MahjongError::Parse`を返す。
+impl FromStr for Tile {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let red = match s {
+            "0m" | "r5m" => Some(Tile::M5),
+            "0p" | "r5p" => Some(Tile::P5),
+            "0s" | "r5s" => Some(Tile::S5),
+            _ => None,
+        };
+        red.map(Tile::new_red)
+            .or_else(|| Tile::from(s))
+            .ok_or_else(|| invalid_name("tile", s))
+    }
+}
+
+/// 牌種の値（0〜33）から牌を作る。範囲外は`MahjongError::InvalidTile`を返す
+impl TryFrom<TileType> for Tile {
+    type Error = MahjongError;
+
+    fn try_from(tile_type: TileType) -> Result<Self, Self::Error> {
+        if (tile_type as usize) < Tile::LEN {
+            Ok(Tile::new(tile_type))
+        } else {
+            Err(MahjongError::InvalidTile(tile_type))
+        }
+    }
+}
+
+/// 解釈できない名前のエラー
+fn invalid_name(kind: &str, input: &str) -> MahjongError {
+    MahjongError::Parse(ParseError::new(0, format!("invalid {kind}: {input:?}")))
+}
+
 /// 数牌のスート内での数字（1〜9）を返す
 ///
 /// 例: `Tile::M7`、`Tile::P7`、`Tile::S7` はいずれも `Some(7)` を返す。
@@ -252,30 +351,104 @@ pub fn suit_rank(tile: TileType) -> Option<u32> {
     }
 }
 
-/// ドラ表示牌から実際のドラを返す
+/// 三人麻雀で使用しない牌（二萬〜八萬）か否かを返す
+pub fn is_unused_in_three_player(tile: TileType) -> bool {
+    matches!(tile, Tile::M2..=Tile::M8)
+}
+
+/// 1種類の牌が牌山に何枚存在するかを返す
+///
+/// 四人麻雀では全ての牌が4枚ずつ、三人麻雀では二萬〜八萬が0枚になる。
+pub fn copies_in_wall(tile: TileType, three_player: bool) -> u32 {
+    if three_player && is_unused_in_three_player(tile) {
+        0
+    } else {
+        4
+    }
+}
+
+/// ドラ表示牌から実際のドラを返す（`Tile::next_with_wrap`の牌種）
+///
+/// 存在しない牌種はそのまま返す。
 pub fn dora_indicator_to_dora(indicator: TileType) -> TileType {
-    match indicator {
-        // 萬子: 9m→1m にループ
-        Tile::M9 => Tile::M1,
-        Tile::M1..=Tile::M8 => indicator + 1,
-        // 筒子: 9p→1p にループ
-        Tile::P9 => Tile::P1,
-        Tile::P1..=Tile::P8 => indicator + 1,
-        // 索子: 9s→1s にループ
-        Tile::S9 => Tile::S1,
-        Tile::S1..=Tile::S8 => indicator + 1,
-        // 風牌: 北→東 にループ
-        Tile::Z4 => Tile::Z1,
-        Tile::Z1..=Tile::Z3 => indicator + 1,
-        // 三元牌: 中→白 にループ
-        Tile::Z7 => Tile::Z5,
-        Tile::Z5..=Tile::Z6 => indicator + 1,
-        _ => indicator,
+    if (indicator as usize) < Tile::LEN {
+        Tile::new(indicator).next_with_wrap().get()
+    } else {
+        indicator
+    }
+}
+
+/// 牌のスート
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Suit {
+    /// 萬子
+    Man,
+    /// 筒子
+    Pin,
+    /// 索子
+    Sou,
+    /// 字牌
+    Honour,
+}
+
+impl Suit {
+    /// 全てのスート（萬子・筒子・索子・字牌の順）
+    pub const ALL: [Suit; 4] = [Suit::Man, Suit::Pin, Suit::Sou, Suit::Honour];
+
+    /// 牌種のスートを返す（存在しない牌種は`None`）
+    pub fn of(tile_type: TileType) -> Option<Suit> {
+        match tile_type {
+            Tile::M1..=Tile::M9 => Some(Suit::Man),
+            Tile::P1..=Tile::P9 => Some(Suit::Pin),
+            Tile::S1..=Tile::S9 => Some(Suit::Sou),
+            Tile::Z1..=Tile::Z7 => Some(Suit::Honour),
+            _ => None,
+        }
+    }
+
+    /// 数牌のスートか否かを返す
+    pub fn is_suited(&self) -> bool {
+        *self != Suit::Honour
+    }
+
+    /// スートの最初の牌種（一萬・一筒・一索・東）
+    pub fn first_tile(&self) -> TileType {
+        match self {
+            Suit::Man => Tile::M1,
+            Suit::Pin => Tile::P1,
+            Suit::Sou => Tile::S1,
+            Suit::Honour => Tile::Z1,
+        }
+    }
+
+    /// スートに属する牌種を順に返す
+    pub fn tile_types(&self) -> std::ops::RangeInclusive<TileType> {
+        match self {
+            Suit::Honour => Tile::Z1..=Tile::Z7,
+            _ => self.first_tile()..=self.first_tile() + 8,
+        }
+    }
+
+    /// 手牌文字列でのスートの文字（`m`・`p`・`s`・`z`）
+    pub fn to_char(&self) -> char {
+        match self {
+            Suit::Man => 'm',
+            Suit::Pin => 'p',
+            Suit::Sou => 's',
+            Suit::Honour => 'z',
+        }
+    }
+
+    /// 手牌文字列でのスートの文字から作る
+    pub fn from_char(c: char) -> Option<Suit> {
+        Suit::ALL.into_iter().find(|suit| suit.to_char() == c)
     }
 }
 
 /// 自風／場風
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Wind {
     /// 東家（`Tile::Z1`）
     East = Tile::Z1 as isize,
@@ -288,6 +461,9 @@ pub enum Wind {
 }
 
 impl Wind {
+    /// 全ての風（東南西北の順）
+    pub const ALL: [Wind; 4] = [Wind::East, Wind::South, Wind::West, Wind::North];
+
     pub fn is_tile_type(tile_type: TileType) -> Option<Wind> {
         match tile_type {
             Tile::Z1 => Some(Wind::East),
@@ -311,6 +487,24 @@ impl Wind {
         }
     }
 
+    /// 前の風を返す（東→北→西→南→東）
+    pub fn prev(&self) -> Wind {
+        Wind::from_index(self.to_index() + 3)
+    }
+
+    /// 東から順に全ての風を返す
+    pub fn iter() -> impl Iterator<Item = Wind> {
+        Wind::ALL.into_iter()
+    }
+
+    /// 局のインデックスと座席から自風を返す
+    ///
+    /// `round` は起家から数えた局のインデックス（0 = 東1局）で、親は座席 `round % 4`。
+    /// 親が東家となり、そこから座席順に南・西・北となる。
+    pub fn from_seat(round: usize, seat: usize) -> Wind {
+        Wind::from_index(seat % 4 + 4 - round % 4)
+    }
+
     /// 風をインデックス（0-3）に変換する
     pub fn to_index(&self) -> usize {
         match self {
@@ -351,8 +545,35 @@ impl Wind {
     }
 }
 
+/// 英語名（`"East"`・`"E"`、大文字小文字を区別しない）・漢字（`"東"`）・牌（`"1z"`）を解釈する
+impl FromStr for Wind {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let wind = match s.to_ascii_lowercase().as_str() {
+            "east" | "e" | "東" => Some(Wind::East),
+            "south" | "s" | "南" => Some(Wind::South),
+            "west" | "w" | "西" => Some(Wind::West),
+            "north" | "n" | "北" => Some(Wind::North),
+            _ => Tile::from(s).and_then(|t| Wind::is_tile(&t)),
+        };
+        wind.ok_or_else(|| invalid_name("wind", s))
+    }
+}
+
+/// 風牌の牌種から風を作る。風牌でなければ`MahjongError::InvalidTile`を返す
+impl TryFrom<TileType> for Wind {
+    type Error = MahjongError;
+
+    fn try_from(tile_type: TileType) -> Result<Self, Self::Error> {
+        Wind::is_tile_type(tile_type).ok_or(MahjongError::InvalidTile(tile_type))
+    }
+}
+
 /// 三元牌
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Dragon {
     /// 白（`Tile::Z5`）
     White = Tile::Z5 as isize,
@@ -392,6 +613,33 @@ impl Dragon {
     }
 }
 
+/// 英語名（`"White"`・`"White dragon"`）・ローマ字（`"haku"`）・漢字（`"白"`）・牌（`"5z"`）を解釈する
+///
+/// 英語名とローマ字は大文字小文字を区別しない。
+impl FromStr for Dragon {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let dragon = match s.to_ascii_lowercase().as_str() {
+            "white" | "white dragon" | "haku" | "白" => Some(Dragon::White),
+            "green" | "green dragon" | "hatsu" | "發" | "発" => Some(Dragon::Green),
+            "red" | "red dragon" | "chun" | "中" => Some(Dragon::Red),
+            _ => Tile::from(s).and_then(|t| Dragon::is_tile(&t)),
+        };
+        dragon.ok_or_else(|| invalid_name("dragon", s))
+    }
+}
+
+/// 三元牌の牌種から三元牌を作る。三元牌でなければ`MahjongError::InvalidTile`を返す
+impl TryFrom<TileType> for Dragon {
+    type Error = MahjongError;
+
+    fn try_from(tile_type: TileType) -> Result<Self, Self::Error> {
+        Dragon::is_tile_type(tile_type).ok_or(MahjongError::InvalidTile(tile_type))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -546,6 +794,18 @@ mod tests {
         assert_eq!(Wind::from_index(4), Wind::East);
     }
 
+    #[test]
+    fn wind_rotation_test() {
+        assert_eq!(Wind::North.next(), Wind::East);
+        assert_eq!(Wind::East.prev(), Wind::North);
+        assert_eq!(Wind::iter().collect::<Vec<_>>(), Wind::ALL.to_vec());
+        assert_eq!(Wind::from_seat(0, 0), Wind::East);
+        assert_eq!(Wind::from_seat(0, 3), Wind::North);
+        assert_eq!(Wind::from_seat(1, 0), Wind::North);
+        assert_eq!(Wind::from_seat(1, 1), Wind::East);
+        assert_eq!(Wind::from_seat(6, 0), Wind::West);
+    }
+
     /// 風の表示名
     #[test]
     fn wind_name_test() {
@@ -570,6 +830,21 @@ mod tests {
         assert_eq!(Dragon::Red.name(Lang::En), "Red dragon");
     }
 
+    /// 三人麻雀で使用しない牌
+    #[test]
+    fn unused_in_three_player_test() {
+        assert!(!is_unused_in_three_player(Tile::M1));
+        for tile in Tile::M2..=Tile::M8 {
+            assert!(is_unused_in_three_player(tile));
+            assert_eq!(copies_in_wall(tile, true), 0);
+            assert_eq!(copies_in_wall(tile, false), 4);
+        }
+        assert!(!is_unused_in_three_player(Tile::M9));
+        assert!(!is_unused_in_three_player(Tile::P5));
+        assert_eq!(copies_in_wall(Tile::M1, true), 4);
+        assert_eq!(copies_in_wall(Tile::Z7, true), 4);
+    }
+
     #[test]
     fn suit_rank_manzu() {
         assert_eq!(suit_rank(Tile::M1), Some(1));
@@ -616,4 +891,108 @@ mod tests {
             assert_eq!(suit_rank(tile), None, "tile {tile} should return None");
         }
     }
+
+    #[test]
+    fn parse_tile_test() {
+        assert_eq!("3s".parse::<Tile>().unwrap(), Tile::new(Tile::S3));
+        assert_eq!(" 🀄 ".parse::<Tile>().unwrap(), Tile::new(Tile::Z7));
+        assert_eq!("0p".parse::<Tile>().unwrap(), Tile::new_red(Tile::P5));
+        assert_eq!("r5m".parse::<Tile>().unwrap(), Tile::new_red(Tile::M5));
+        for invalid in ["", "8z", "0z", "3", "3x", "33s"] {
+            assert!(
+                matches!(invalid.parse::<Tile>(), Err(MahjongError::Parse(_))),
+                "{invalid:?}"
+            );
+        }
+        assert_eq!(Tile::try_from(Tile::Z1).unwrap(), Tile::new(Tile::Z1));
+        assert!(matches!(
+            Tile::try_from(34),
+            Err(MahjongError::InvalidTile(34))
+        ));
+    }
+
+    #[test]
+    fn parse_wind_and_dragon_test() {
+        assert_eq!("East".parse::<Wind>().unwrap(), Wind::East);
+        assert_eq!("s".parse::<Wind>().unwrap(), Wind::South);
+        assert_eq!("西".parse::<Wind>().unwrap(), Wind::West);
+        assert_eq!("4z".parse::<Wind>().unwrap(), Wind::North);
+        assert!("5z".parse::<Wind>().is_err());
+        assert_eq!(Wind::try_from(Tile::Z2).unwrap(), Wind::South);
+        assert!(Wind::try_from(Tile::Z5).is_err());
+
+        assert_eq!("haku".parse::<Dragon>().unwrap(), Dragon::White);
+        assert_eq!("Green dragon".parse::<Dragon>().unwrap(), Dragon::Green);
+        assert_eq!("中".parse::<Dragon>().unwrap(), Dragon::Red);
+        assert_eq!("6z".parse::<Dragon>().unwrap(), Dragon::Green);
+        assert!("1z".parse::<Dragon>().is_err());
+        assert_eq!(Dragon::try_from(Tile::Z7).unwrap(), Dragon::Red);
+        assert!(Dragon::try_from(Tile::M1).is_err());
+    }
+
+    #[test]
+    fn metadata_test() {
+        let m9 = Tile::new(Tile::M9);
+        assert_eq!(m9.suit(), Suit::Man);
+        assert_eq!(m9.number(), Some(9));
+        assert!(m9.is_terminal() && !m9.is_simple());
+        assert_eq!(Tile::new(Tile::P5).suit(), Suit::Pin);
+        assert_eq!(Tile::new(Tile::S2).suit(), Suit::Sou);
+        assert!(Tile::new(Tile::S2).is_simple());
+        let east = Tile::new(Tile::Z1);
+        assert_eq!(east.suit(), Suit::Honour);
+        assert_eq!(east.number(), None);
+        assert!(!east.is_terminal() && !east.is_simple());
+    }
+
+    #[test]
+    fn next_and_prev_with_wrap_test() {
+        for (from, to) in [
+            (Tile::M9, Tile::M1),
+            (Tile::P3, Tile::P4),
+            (Tile::S9, Tile::S1),
+            (Tile::Z4, Tile::Z1),
+            (Tile::Z7, Tile::Z5),
+        ] {
+            assert_eq!(Tile::new(from).next_with_wrap(), Tile::new(to));
+            assert_eq!(Tile::new(to).prev_with_wrap(), Tile::new(from));
+        }
+        assert_eq!(
+            Tile::new_red(Tile::M5).next_with_wrap(),
+            Tile::new(Tile::M6)
+        );
+        for t in 0..Tile::LEN as TileType {
+            assert_eq!(
+                dora_indicator_to_dora(t),
+                Tile::new(t).next_with_wrap().get()
+            );
+        }
+    }
+
+    #[test]
+    fn suit_test() {
+        assert_eq!(Suit::of(Tile::M9), Some(Suit::Man));
+        assert_eq!(Suit::of(Tile::P1), Some(Suit::Pin));
+        assert_eq!(Suit::of(Tile::S5), Some(Suit::Sou));
+        assert_eq!(Suit::of(Tile::Z7), Some(Suit::Honour));
+        assert_eq!(Suit::of(34), None);
+        for suit in Suit::ALL {
+            assert_eq!(Suit::from_char(suit.to_char()), Some(suit));
+            for t in suit.tile_types() {
+                assert_eq!(Tile::new(t).suit(), suit);
+            }
+        }
+        assert_eq!(Suit::from_char('x'), None);
+        assert_eq!(Suit::Honour.tile_types().count(), 7);
+        assert_eq!(
+            Tile::from_suit_number(Suit::Pin, 7),
+            Some(Tile::new(Tile::P7))
+        );
+        assert_eq!(
+            Tile::from_suit_number(Suit::Honour, 5),
+            Some(Tile::new(Tile::Z5))
+        );
+        assert_eq!(Tile::from_suit_number(Suit::Honour, 8), None);
+        assert_eq!(Tile::from_suit_number(Suit::Sou, 0), None);
+    }
 }
@@ -0,0 +1,201 @@
+//! 牌種ごとの枚数
+//!
+//! `TileSummarize`（`[u32; 34]`）を包み、牌の追加・削除や合計などの操作をまとめる。
+//! 配列として扱う既存の関数にもそのまま渡せるよう、`Deref`で中身の配列を参照できる。
+
+use std::ops::{AddAssign, Deref, DerefMut, Sub, SubAssign};
+
+use crate::tile::{Tile, TileSummarize, TileType};
+
+/// 牌種ごとの枚数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileCounts(TileSummarize);
+
+impl TileCounts {
+    /// 全て0枚の集計を作る
+    pub fn new() -> TileCounts {
+        TileCounts([0; Tile::LEN])
+    }
+
+    /// 牌の枚数を返す（赤ドラは通常牌と同じ牌種として数える）
+    pub fn count(&self, tile: Tile) -> u32 {
+        self.0[tile.get() as usize]
+    }
+
+    /// 牌種の枚数を返す
+    pub fn count_of(&self, tile_type: TileType) -> u32 {
+        self.0[tile_type as usize]
+    }
+
+    /// 牌を1枚加える
+    ///
+    /// この名前と衝突するため、集計どうしの足し算は`Add`ではなく`+=`（`AddAssign`）で行う。
+    pub fn add(&mut self, tile: Tile) {
+        self.0[tile.get() as usize] += 1;
+    }
+
+    /// 牌を1枚取り除く（0枚の場合は何もせず`false`を返す）
+    pub fn remove(&mut self, tile: Tile) -> bool {
+        let count = &mut self.0[tile.get() as usize];
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        true
+    }
+
+    /// 1枚以上ある牌種とその枚数を牌種の順に返す
+    pub fn iter_nonzero(&self) -> impl Iterator<Item = (TileType, u32)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(i, &count)| (i as TileType, count))
+    }
+
+    /// 枚数の合計を返す
+    pub fn total(&self) -> u32 {
+        self.0.iter().sum()
+    }
+
+    /// 中身の配列を返す
+    pub fn into_inner(self) -> TileSummarize {
+        self.0
+    }
+}
+
+impl Default for TileCounts {
+    fn default() -> Self {
+        TileCounts::new()
+    }
+}
+
+impl From<TileSummarize> for TileCounts {
+    fn from(counts: TileSummarize) -> Self {
+        TileCounts(counts)
+    }
+}
+
+impl From<TileCounts> for TileSummarize {
+    fn from(counts: TileCounts) -> Self {
+        counts.0
+    }
+}
+
+impl FromIterator<Tile> for TileCounts {
+    fn from_iter<I: IntoIterator<Item = Tile>>(iter: I) -> Self {
+        let mut counts = TileCounts::new();
+        for tile in iter {
+            counts.add(tile);
+        }
+        counts
+    }
+}
+
+impl<'a> FromIterator<&'a Tile> for TileCounts {
+    fn from_iter<I: IntoIterator<Item = &'a Tile>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Deref for TileCounts {
+    type Target = TileSummarize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TileCounts {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl PartialEq<TileSummarize> for TileCounts {
+    fn eq(&self, other: &TileSummarize) -> bool {
+        self.0 == *other
+    }
+}
+
+impl AddAssign for TileCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+/// 牌種ごとに引く（0枚を下回る分は0枚とする）
+impl SubAssign for TileCounts {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a = a.saturating_sub(b);
+        }
+    }
+}
+
+/// 牌種ごとに引く（0枚を下回る分は0枚とする）
+impl Sub for TileCounts {
+    type Output = TileCounts;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(tiles: &[TileType]) -> TileCounts {
+        tiles.iter().map(|&t| Tile::new(t)).collect()
+    }
+
+    #[test]
+    fn add_remove_and_count() {
+        let mut c = TileCounts::new();
+        c.add(Tile::new(Tile::M1));
+        c.add(Tile::new_red(Tile::P5));
+        c.add(Tile::new(Tile::P5));
+        assert_eq!(c.count(Tile::new(Tile::P5)), 2);
+        assert_eq!(c.count_of(Tile::M1), 1);
+        assert_eq!(c.total(), 3);
+        assert!(c.remove(Tile::new(Tile::M1)));
+        assert!(!c.remove(Tile::new(Tile::M1)));
+        assert_eq!(c.total(), 2);
+    }
+
+    #[test]
+    fn iter_nonzero_in_tile_order() {
+        let c = counts(&[Tile::Z7, Tile::M3, Tile::Z7]);
+        assert_eq!(
+            c.iter_nonzero().collect::<Vec<_>>(),
+            vec![(Tile::M3, 1), (Tile::Z7, 2)]
+        );
+    }
+
+    #[test]
+    fn add_and_sub() {
+        let a = counts(&[Tile::M1, Tile::M2]);
+        let b = counts(&[Tile::M2, Tile::M3]);
+        let mut sum = a;
+        sum += b;
+        assert_eq!(sum.count_of(Tile::M2), 2);
+        assert_eq!(sum.total(), 4);
+        let diff = a - b;
+        assert_eq!(diff, counts(&[Tile::M1]));
+        assert_eq!((b - a).count_of(Tile::M1), 0);
+    }
+
+    #[test]
+    fn converts_to_and_from_array() {
+        let c = counts(&[Tile::S9]);
+        let array: TileSummarize = c.into();
+        assert_eq!(TileCounts::from(array), c);
+        assert_eq!(c, array);
+        assert_eq!(c[Tile::S9 as usize], 1);
+    }
+}
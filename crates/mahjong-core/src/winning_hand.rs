@@ -4,6 +4,12 @@ pub mod checker;
 /// 役の名前
 pub mod name;
 
+/// 役の成立根拠
+pub mod explain;
+
+/// 和了牌ごとの役の有無
+pub mod wait_yaku;
+
 /// 1翻役の判定
 mod check_1_han;
 
@@ -13,11 +19,12 @@ mod check_2_han;
 /// 3翻役の判定
 mod check_3_han;
 
-/// 5翻（満貫）役の判定
-mod check_5_han;
-
 /// 6翻役の判定
 mod check_6_han;
 
 /// 役満の判定
 mod check_yakuman;
+
+/// ローカル役の判定
+#[cfg(feature = "local-yaku")]
+pub mod check_local;
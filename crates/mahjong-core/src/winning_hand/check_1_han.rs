@@ -1,11 +1,14 @@
-use anyhow::Result;
+use crate::error::Result;
 
 use crate::hand::Hand;
 use crate::hand_info::block::BlockProperty;
 use crate::hand_info::hand_analyzer::*;
+use crate::hand_info::identical_sequences::identical_sequences;
+use crate::hand_info::pinfu::is_pinfu;
 use crate::hand_info::status::*;
 use crate::settings::*;
 use crate::tile::Dragon;
+use crate::winning_hand::checker::YakuResult;
 use crate::winning_hand::name::*;
 
 /// 立直
@@ -13,22 +16,22 @@ pub fn check_riichi(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(Kind::Riichi, status.has_claimed_open, settings.display_lang);
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(Kind::Riichi, status.has_claimed_open, settings.display_lang);
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // ダブル立直の場合は通常の立直とは複合しない（ダブル立直が立直を置き換える）
     if status.is_double_riichi {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.has_claimed_riichi {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -37,19 +40,19 @@ pub fn check_fully_concealed_hand(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::FullyConcealedHand,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if !status.has_claimed_open && status.is_self_drawn {
-        return Ok((name, true, 1));
+        return Ok(yaku.established(1));
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 
 /// 一発
@@ -57,41 +60,41 @@ pub fn check_unbroken(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::Unbroken,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    if !check_riichi(hand_analyzer, status, settings)?.1 {
-        return Ok((name, false, 0));
+    if !check_riichi(hand_analyzer, status, settings)?.is_established {
+        return Ok(yaku);
     }
     if status.is_unbroken {
-        return Ok((name, true, 1));
+        return Ok(yaku.established(1));
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 海底撈月
 pub fn check_last_tile_draw(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::LastTileDraw,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.is_last_tile_draw && status.is_self_drawn {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 河底撈魚
@@ -99,19 +102,19 @@ pub fn check_last_tile_claim(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::LastTileClaim,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.is_last_tile_claim && !status.is_self_drawn {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 嶺上開花
@@ -119,39 +122,44 @@ pub fn check_after_a_quad(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::AfterAQuad,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.is_after_a_quad && status.is_self_drawn {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 搶槓
+///
+/// `status.robbed_quad_tile`（加槓された牌）が和了牌と同種である場合のみ成立する。
 pub fn check_robbing_a_quad(
     hand_analyzer: &HandAnalyzer,
+    hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::RobbingAQuad,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    if status.is_robbing_a_quad && !status.is_self_drawn {
-        Ok((name, true, 1))
-    } else {
-        Ok((name, false, 0))
+    if !status.is_robbing_a_quad || status.is_self_drawn {
+        return Ok(yaku);
+    }
+    match (hand.drawn(), status.robbed_quad_tile) {
+        (Some(winning), Some(robbed)) if winning.get() == robbed.get() => Ok(yaku.established(1)),
+        _ => Ok(yaku),
     }
 }
 /// ダブル立直
@@ -159,22 +167,22 @@ pub fn check_double_riichi(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::DoubleRiichi,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.is_double_riichi && status.has_claimed_riichi {
-        Ok((name, true, 2))
+        Ok(yaku.established(2))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 平和
@@ -183,113 +191,63 @@ pub fn check_pinfu(
     raw_hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(Kind::Pinfu, status.has_claimed_open, settings.display_lang);
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(Kind::Pinfu, status.has_claimed_open, settings.display_lang);
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
-    }
-    // 門前でなければ平和は成立しない
-    if status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    // 4つの順子と1つの雀頭で構成されている必要がある
-    if hand_analyzer.sequential3.len() != 4 || hand_analyzer.same2.len() != 1 {
-        return Ok((name, false, 0));
+    if !is_pinfu(hand_analyzer, raw_hand, status) {
+        return Ok(yaku);
     }
-    // 雀頭が役牌でないこと
-    for head in &hand_analyzer.same2 {
-        // 三元牌は不可
-        if head.has_dragon(Dragon::White)?
-            || head.has_dragon(Dragon::Green)?
-            || head.has_dragon(Dragon::Red)?
-        {
-            return Ok((name, false, 0));
-        }
-        // 自風牌は不可
-        if head.has_wind(status.seat_wind)? {
-            return Ok((name, false, 0));
-        }
-        // 場風牌は不可
-        if head.has_wind(status.round_wind)? {
-            return Ok((name, false, 0));
-        }
-    }
-    // 平和は両面待ちのみ成立（辺張・嵌張・単騎は不可）
-    if let Some(winning_tile) = raw_hand.drawn() {
-        let has_open_wait = hand_analyzer
-            .sequential3
-            .iter()
-            .any(|seq| seq.is_two_sided_wait(winning_tile.get()));
-        if !has_open_wait {
-            return Ok((name, false, 0));
-        }
-    }
-    Ok((name, true, 1))
+    Ok(yaku.established(1))
 }
 /// 一盃口
 pub fn check_twin_sequences(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::TwinSequences,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 鳴いていたら一盃口は成立しない
     if status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 順子が2つ以上なければ一盃口はありえない
     if hand_analyzer.sequential3.len() < 2 {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 同一順子ペアの数をカウント（二盃口との区別のため）
-    let mut used = vec![false; hand_analyzer.sequential3.len()];
-    let mut pair_count = 0;
-    for i in 0..hand_analyzer.sequential3.len() {
-        if used[i] {
-            continue;
-        }
-        for j in i + 1..hand_analyzer.sequential3.len() {
-            if used[j] {
-                continue;
-            }
-            if hand_analyzer.sequential3[i] == hand_analyzer.sequential3[j] {
-                used[i] = true;
-                used[j] = true;
-                pair_count += 1;
-                break;
-            }
-        }
-    }
+    let pair_count = identical_sequences(&hand_analyzer.sequential3).pair_count();
     // 二盃口（ペアが2組）の場合は一盃口とは複合しない
     if pair_count == 1 {
-        return Ok((name, true, 1));
+        return Ok(yaku.established(1));
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 断么九
 pub fn check_all_inside(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::AllInside,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 喰いタンなしなら鳴いている時点で抜ける
     if !settings.opened_all_inside && status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     let mut has_1_9_honour = false;
     // 面子
@@ -315,24 +273,24 @@ pub fn check_all_inside(
     }
 
     if has_1_9_honour {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
 
-    Ok((name, true, 1))
+    Ok(yaku.established(1))
 }
 /// 役牌（自風牌）
 pub fn check_value_honour_seat_wind(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ValueHonourSeatWind,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     let mut has_player_wind = false;
     // 刻子
@@ -343,9 +301,9 @@ pub fn check_value_honour_seat_wind(
     }
 
     if has_player_wind {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 役牌（場風牌）
@@ -353,14 +311,14 @@ pub fn check_value_honour_round_wind(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ValueHonourRoundWind,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     let mut has_prevailing_wind = false;
     // 刻子
@@ -371,9 +329,9 @@ pub fn check_value_honour_round_wind(
     }
 
     if has_prevailing_wind {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -398,16 +356,16 @@ pub fn check_value_honour_white_dragon(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ValueHonourWhiteDragon,
         status.has_claimed_open,
         settings.display_lang,
     );
     if check_value_honour_dragons(hand_analyzer, Dragon::White)? {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 役牌（發）
@@ -415,16 +373,16 @@ pub fn check_value_honour_green_dragon(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ValueHonourGreenDragon,
         status.has_claimed_open,
         settings.display_lang,
     );
     if check_value_honour_dragons(hand_analyzer, Dragon::Green)? {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 役牌（中）
@@ -432,16 +390,16 @@ pub fn check_value_honour_red_dragon(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ValueHonourRedDragon,
         status.has_claimed_open,
         settings.display_lang,
     );
     if check_value_honour_dragons(hand_analyzer, Dragon::Red)? {
-        Ok((name, true, 1))
+        Ok(yaku.established(1))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -459,12 +417,28 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_riichi = true;
+        let result = check_riichi(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_riichi(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("立直", true, 1)
         );
     }
     #[test]
+    /// 暗カンがあっても門前なので立直・門前清自摸和が成立する
+    fn test_riichi_with_concealed_kan() {
+        let test = Hand::from("123m45678p22z a9999s 9p");
+        let test_analyzer = HandAnalyzer::new(&test).unwrap();
+        let mut status = Status::new();
+        let settings = Settings::new();
+        status.has_claimed_open = !test.is_concealed();
+        status.has_claimed_riichi = true;
+        status.is_self_drawn = true;
+        let result = check_riichi(&test_analyzer, &status, &settings).unwrap();
+        assert!(result.is_established);
+        let result = check_fully_concealed_hand(&test_analyzer, &status, &settings).unwrap();
+        assert!(result.is_established);
+    }
+    #[test]
     /// 立直に一発が付いた
     fn test_win_by_unbroken() {
         let test_str = "123m45678p999s11z 9p";
@@ -474,8 +448,9 @@ mod tests {
         let settings = Settings::new();
         status.has_claimed_riichi = true;
         status.is_unbroken = true;
+        let result = check_unbroken(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_unbroken(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("一発", true, 1)
         );
     }
@@ -488,8 +463,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.is_self_drawn = true;
+        let result = check_fully_concealed_hand(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_fully_concealed_hand(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("門前清自摸和", true, 1)
         );
     }
@@ -503,8 +479,9 @@ mod tests {
         let settings = Settings::new();
         status.is_self_drawn = true;
         status.has_claimed_open = true;
+        let result = check_fully_concealed_hand(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_fully_concealed_hand(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("門前清自摸和", false, 0)
         );
     }
@@ -519,8 +496,9 @@ mod tests {
         // 喰い断あり鳴きなし
         rules.opened_all_inside = true;
         status.has_claimed_open = false;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", true, 1)
         );
     }
@@ -535,8 +513,9 @@ mod tests {
         // 喰い断あり鳴きなし
         rules.opened_all_inside = true;
         status.has_claimed_open = false;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", false, 0)
         );
     }
@@ -551,8 +530,9 @@ mod tests {
         // 喰い断あり鳴きなし
         rules.opened_all_inside = true;
         status.has_claimed_open = false;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", false, 0)
         );
     }
@@ -567,8 +547,9 @@ mod tests {
         // 喰い断あり鳴きなし
         rules.opened_all_inside = true;
         status.has_claimed_open = false;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", false, 0)
         );
     }
@@ -583,8 +564,9 @@ mod tests {
         // 喰い断あり鳴きあり
         rules.opened_all_inside = true;
         status.has_claimed_open = true;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", true, 1)
         );
     }
@@ -599,8 +581,9 @@ mod tests {
         // 喰い断なし鳴きなし
         rules.opened_all_inside = false;
         status.has_claimed_open = false;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", true, 1)
         );
     }
@@ -615,8 +598,9 @@ mod tests {
         // 喰い断なし鳴きあり（役無し）
         rules.opened_all_inside = false;
         status.has_claimed_open = true;
+        let result = check_all_inside(&test_analyzer, &status, &rules).unwrap();
         assert_eq!(
-            check_all_inside(&test_analyzer, &status, &rules).unwrap(),
+            (result.name, result.is_established, result.han),
             ("断么九", false, 0)
         );
     }
@@ -629,8 +613,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_twin_sequences(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_twin_sequences(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("一盃口", true, 1)
         );
     }
@@ -643,8 +628,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_twin_sequences(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_twin_sequences(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("一盃口", false, 0)
         );
     }
@@ -656,8 +642,9 @@ mod tests {
         let analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_pinfu(&analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_pinfu(&analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("平和", true, 1)
         );
     }
@@ -670,8 +657,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_pinfu(&analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_pinfu(&analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("平和", false, 0)
         );
     }
@@ -683,8 +671,9 @@ mod tests {
         let analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_pinfu(&analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_pinfu(&analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("平和", false, 0)
         );
     }
@@ -696,8 +685,9 @@ mod tests {
         let analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_pinfu(&analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_pinfu(&analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("平和", false, 0)
         );
     }
@@ -710,8 +700,9 @@ mod tests {
         let analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_pinfu(&analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_pinfu(&analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("平和", false, 0)
         );
     }
@@ -725,8 +716,9 @@ mod tests {
         let settings = Settings::new();
         status.seat_wind = Wind::East;
         status.round_wind = Wind::East;
+        let result = check_pinfu(&analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_pinfu(&analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("平和", false, 0)
         );
     }
@@ -742,8 +734,9 @@ mod tests {
         status.round_wind = Wind::East;
         // プレイヤーは南家=`2z`
         status.seat_wind = Wind::South;
+        let result = check_value_honour_seat_wind(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_value_honour_seat_wind(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("役牌（自風牌）", true, 1)
         );
     }
@@ -759,8 +752,9 @@ mod tests {
         status.round_wind = Wind::East;
         // プレイヤーは南家=`2z`
         status.seat_wind = Wind::South;
+        let result = check_value_honour_round_wind(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_value_honour_round_wind(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("役牌（場風牌）", true, 1)
         );
     }
@@ -776,8 +770,9 @@ mod tests {
         status.round_wind = Wind::East;
         // プレイヤーは南家=`2z`
         status.seat_wind = Wind::South;
+        let result = check_value_honour_white_dragon(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_value_honour_white_dragon(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("役牌（白）", true, 1)
         );
     }
@@ -793,8 +788,9 @@ mod tests {
         status.round_wind = Wind::East;
         // プレイヤーは南家=`2z`
         status.seat_wind = Wind::South;
+        let result = check_value_honour_green_dragon(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_value_honour_green_dragon(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("役牌（發）", true, 1)
         );
     }
@@ -810,8 +806,9 @@ mod tests {
         status.round_wind = Wind::East;
         // プレイヤーは南家=`2z`
         status.seat_wind = Wind::South;
+        let result = check_value_honour_red_dragon(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_value_honour_red_dragon(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("役牌（中）", true, 1)
         );
     }
@@ -825,8 +822,9 @@ mod tests {
         let settings = Settings::new();
         status.is_last_tile_draw = true;
         status.is_self_drawn = true;
+        let result = check_last_tile_draw(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_last_tile_draw(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("海底撈月", true, 1)
         );
     }
@@ -840,8 +838,9 @@ mod tests {
         let settings = Settings::new();
         status.is_last_tile_draw = true;
         status.is_self_drawn = false;
+        let result = check_last_tile_draw(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_last_tile_draw(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("海底撈月", false, 0)
         );
     }
@@ -855,8 +854,9 @@ mod tests {
         let settings = Settings::new();
         status.is_last_tile_claim = true;
         status.is_self_drawn = false;
+        let result = check_last_tile_claim(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_last_tile_claim(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("河底撈魚", true, 1)
         );
     }
@@ -870,8 +870,9 @@ mod tests {
         let settings = Settings::new();
         status.is_after_a_quad = true;
         status.is_self_drawn = true;
+        let result = check_after_a_quad(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_after_a_quad(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("嶺上開花", true, 1)
         );
     }
@@ -884,13 +885,31 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.is_robbing_a_quad = true;
+        status.robbed_quad_tile = Some(Tile::new(Tile::P9));
         status.is_self_drawn = false;
+        let result = check_robbing_a_quad(&test_analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_robbing_a_quad(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("搶槓", true, 1)
         );
     }
     #[test]
+    /// 和了牌が加槓された牌と一致しなければ搶槓は成立しない
+    fn test_robbing_a_quad_requires_matching_tile() {
+        let test = Hand::from("123m45678p999s11z 9p");
+        let test_analyzer = HandAnalyzer::new(&test).unwrap();
+        let mut status = Status::new();
+        let settings = Settings::new();
+        status.is_robbing_a_quad = true;
+
+        let result = check_robbing_a_quad(&test_analyzer, &test, &status, &settings).unwrap();
+        assert!(!result.is_established);
+
+        status.robbed_quad_tile = Some(Tile::new(Tile::P3));
+        let result = check_robbing_a_quad(&test_analyzer, &test, &status, &settings).unwrap();
+        assert!(!result.is_established);
+    }
+    #[test]
     /// ダブル立直で和了った
     fn test_win_by_double_riichi() {
         let test_str = "123m45678p999s11z 9p";
@@ -900,8 +919,9 @@ mod tests {
         let settings = Settings::new();
         status.has_claimed_riichi = true;
         status.is_double_riichi = true;
+        let result = check_double_riichi(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_double_riichi(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("ダブル立直", true, 2)
         );
     }
@@ -915,8 +935,9 @@ mod tests {
         let settings = Settings::new();
         status.has_claimed_riichi = false;
         status.is_double_riichi = true;
+        let result = check_double_riichi(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_double_riichi(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("ダブル立直", false, 0)
         );
     }
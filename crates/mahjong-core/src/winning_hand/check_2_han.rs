@@ -1,12 +1,12 @@
-use anyhow::Result;
+use crate::error::Result;
 
 use crate::hand::Hand;
 use crate::hand_info::block::BlockProperty;
 use crate::hand_info::hand_analyzer::*;
-use crate::hand_info::meld::{MeldFrom, MeldType};
 use crate::hand_info::status::*;
 use crate::settings::*;
 use crate::tile::{Dragon, Tile};
+use crate::winning_hand::checker::YakuResult;
 use crate::winning_hand::name::*;
 
 /// 七対子
@@ -14,19 +14,19 @@ pub fn check_seven_pairs(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::SevenPairs,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if hand_analyzer.form == Form::SevenPairs {
-        Ok((name, true, 2))
+        Ok(yaku.established(2))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -35,18 +35,18 @@ pub fn check_mixed_sequences(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::MixedSequences,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 順子が3つ以上なければ三色同順はありえない
     if hand_analyzer.sequential3.len() < 3 {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     for i in 0..hand_analyzer.sequential3.len() {
         for j in (i + 1)..hand_analyzer.sequential3.len() {
@@ -54,47 +54,39 @@ pub fn check_mixed_sequences(
                 let a = hand_analyzer.sequential3[i].get();
                 let b = hand_analyzer.sequential3[j].get();
                 let c = hand_analyzer.sequential3[k].get();
-                // 3つの順子の開始牌が同じ数字（mod 9）で、かつ異なる色であること
-                let a_num = a[0] % 9;
-                let b_num = b[0] % 9;
-                let c_num = c[0] % 9;
-                if a_num == b_num && b_num == c_num {
-                    let a_suit = a[0] / 9;
-                    let b_suit = b[0] / 9;
-                    let c_suit = c[0] / 9;
-                    if a_suit != b_suit
-                        && b_suit != c_suit
-                        && a_suit != c_suit
-                        && a_suit < 3
-                        && b_suit < 3
-                        && c_suit < 3
-                    {
-                        if status.has_claimed_open {
-                            return Ok((name, true, 1));
-                        } else {
-                            return Ok((name, true, 2));
-                        }
+                // 3つの順子の開始牌が同じ数字で、かつ異なる色であること
+                let [a, b, c] = [a[0], b[0], c[0]].map(Tile::new);
+                if a.number() == b.number()
+                    && b.number() == c.number()
+                    && a.suit() != b.suit()
+                    && b.suit() != c.suit()
+                    && a.suit() != c.suit()
+                {
+                    if status.has_claimed_open {
+                        return Ok(yaku.established(1));
+                    } else {
+                        return Ok(yaku.established(2));
                     }
                 }
             }
         }
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 一気通貫
 pub fn check_full_straight(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::FullStraight,
         status.has_claimed_open,
         settings.display_lang,
     );
 
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     let mut m = [false; 3];
     let mut p = [false; 3];
@@ -117,31 +109,31 @@ pub fn check_full_straight(
 
     if (m[0] && m[1] && m[2]) || (p[0] && p[1] && p[2]) || (s[0] && s[1] && s[2]) {
         if status.has_claimed_open {
-            return Ok((name, true, 1));
+            return Ok(yaku.established(1));
         } else {
-            return Ok((name, true, 2));
+            return Ok(yaku.established(2));
         }
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 対々和
 pub fn check_all_triplets(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::AllTriplets,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if hand_analyzer.same3.len() == 4 && hand_analyzer.same2.len() == 1 {
-        return Ok((name, true, 2));
+        return Ok(yaku.established(2));
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 三暗刻
 pub fn check_three_concealed_triplets(
@@ -149,49 +141,25 @@ pub fn check_three_concealed_triplets(
     hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ThreeConcealedTriplets,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
 
-    let mut concealed_triplet_count = hand_analyzer.same3.len();
+    // 副露の刻子とロンで完成した刻子を除いた暗刻の数
+    let concealed_triplet_count = hand_analyzer
+        .concealed_triplets_on_win(hand, status.is_self_drawn)
+        .len();
 
-    for open in hand.melds() {
-        let is_open_triplet = matches!(open.category, MeldType::Pon)
-            || (open.category.is_kan() && open.from != MeldFrom::Myself);
-        if is_open_triplet {
-            concealed_triplet_count = concealed_triplet_count.saturating_sub(1);
-        }
-    }
-
-    if !status.is_self_drawn
-        && let Some(winning_tile) = hand.drawn()
-    {
-        let winning_tile_type = winning_tile.get();
-        let completes_open_triplet = hand.melds().iter().any(|open| {
-            open.tiles[0].get() == winning_tile_type
-                && (matches!(open.category, MeldType::Pon)
-                    || (open.category.is_kan() && open.from != MeldFrom::Myself))
-        });
-        let completes_concealed_triplet = hand_analyzer
-            .same3
-            .iter()
-            .any(|triplet| triplet.get()[0] == winning_tile_type);
-
-        if completes_concealed_triplet && !completes_open_triplet {
-            concealed_triplet_count = concealed_triplet_count.saturating_sub(1);
-        }
-    }
-
-    if concealed_triplet_count >= 3 {
-        Ok((name, true, 2))
+    if concealed_triplet_count == 3 {
+        Ok(yaku.established(2))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 三色同刻
@@ -199,18 +167,18 @@ pub fn check_mixed_triplets(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::MixedTriplets,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 刻子が3つ以上なければ三色同刻はありえない
     if hand_analyzer.same3.len() < 3 {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     for i in 0..hand_analyzer.same3.len() {
         for j in (i + 1)..hand_analyzer.same3.len() {
@@ -218,45 +186,40 @@ pub fn check_mixed_triplets(
                 let a = hand_analyzer.same3[i].get()[0];
                 let b = hand_analyzer.same3[j].get()[0];
                 let c = hand_analyzer.same3[k].get()[0];
-                // 数牌のみ（字牌は三色同刻にならない）
-                if a > Tile::S9 || b > Tile::S9 || c > Tile::S9 {
-                    continue;
-                }
-                // 同じ数字（mod 9）で異なる色であること
-                let a_num = a % 9;
-                let b_num = b % 9;
-                let c_num = c % 9;
-                if a_num == b_num && b_num == c_num {
-                    let a_suit = a / 9;
-                    let b_suit = b / 9;
-                    let c_suit = c / 9;
-                    if a_suit != b_suit && b_suit != c_suit && a_suit != c_suit {
-                        return Ok((name, true, 2));
-                    }
+                // 同じ数字で異なる色であること（字牌は数字を持たないため三色同刻にならない）
+                let [a, b, c] = [a, b, c].map(Tile::new);
+                if a.number().is_some()
+                    && a.number() == b.number()
+                    && b.number() == c.number()
+                    && a.suit() != b.suit()
+                    && b.suit() != c.suit()
+                    && a.suit() != c.suit()
+                {
+                    return Ok(yaku.established(2));
                 }
             }
         }
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 混全帯么九
 pub fn check_common_ends(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::CommonEnds,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
 
     // 混老頭とは複合しないため、必ず順子が含まれる
     if hand_analyzer.sequential3.is_empty() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
 
     let mut no_1_9_honour = false;
@@ -293,31 +256,31 @@ pub fn check_common_ends(
     }
 
     if no_1_9_honour || !has_honour {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.has_claimed_open {
-        return Ok((name, true, 1));
+        return Ok(yaku.established(1));
     }
-    Ok((name, true, 2))
+    Ok(yaku.established(2))
 }
 /// 混老頭
 pub fn check_common_terminals(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::CommonTerminals,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 混老頭は全ての面子・雀頭が么九牌（1,9）または字牌で構成される
     // 順子が含まれていてはいけない
     if !hand_analyzer.sequential3.is_empty() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 字牌が含まれていなければ清老頭であり混老頭にはならない
     let mut has_honour = false;
@@ -330,7 +293,7 @@ pub fn check_common_terminals(
         } else if same.has_1_or_9()? {
             has_terminal = true;
         } else {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
     for head in &hand_analyzer.same2 {
@@ -339,13 +302,13 @@ pub fn check_common_terminals(
         } else if head.has_1_or_9()? {
             has_terminal = true;
         } else {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
     if has_honour && has_terminal {
-        Ok((name, true, 2))
+        Ok(yaku.established(2))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 小三元
@@ -353,14 +316,14 @@ pub fn check_little_dragons(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::LittleDragons,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 小三元: 三元牌のうち2つが刻子、1つが雀頭
     let mut dragon_triplet_count = 0;
@@ -382,9 +345,9 @@ pub fn check_little_dragons(
         }
     }
     if dragon_triplet_count == 2 && dragon_pair {
-        Ok((name, true, 2))
+        Ok(yaku.established(2))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -402,8 +365,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_seven_pairs(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_seven_pairs(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("七対子", true, 2)
         );
     }
@@ -416,8 +380,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_common_ends(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_common_ends(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("混全帯么九", true, 2)
         );
     }
@@ -430,8 +395,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_common_ends(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_common_ends(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("混全帯么九（鳴）", true, 1)
         );
     }
@@ -443,8 +409,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_all_triplets(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_all_triplets(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("対々和", true, 2)
         );
     }
@@ -458,8 +425,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_full_straight(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_full_straight(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("一気通貫", true, 2)
         );
     }
@@ -473,8 +441,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_full_straight(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_full_straight(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("一気通貫（鳴）", true, 1)
         );
     }
@@ -487,8 +456,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_mixed_sequences(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_mixed_sequences(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("三色同順", true, 2)
         );
     }
@@ -501,8 +471,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_mixed_sequences(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_mixed_sequences(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("三色同順（鳴）", true, 1)
         );
     }
@@ -516,6 +487,11 @@ mod tests {
     #[case::open_sequence_tsumo("111m333p999s1z 456s 1z", true, true, ("三暗刻", true, 2))]
     #[case::open_sequence_ron("111m333p999s1z 456s 1z", false, true, ("三暗刻", true, 2))]
     #[case::open_triplet_tsumo("111m333p1z789s 999s 1z", true, true, ("三暗刻", false, 0))]
+    #[case::winning_tile_only_in_chi_ron("11m55p444s777z 123m 1m", false, true, ("三暗刻", false, 0))]
+    #[case::concealed_kan_tsumo("111m333p1z789s a9999s 1z", true, false, ("三暗刻", true, 2))]
+    #[case::sequence_shares_triplet_tile_ron("11123m444p999s55z 1m", false, false, ("三暗刻", true, 2))]
+    #[case::triplets_or_sequences_ron("111222333m456p7s 7s", false, false, ("三暗刻", true, 2))]
+    #[case::four_concealed_tsumo("111333m444s55s77z 5s", true, false, ("三暗刻", false, 0))]
     fn test_three_concealed_triplets(
         #[case] test_str: &str,
         #[case] is_self_drawn: bool,
@@ -529,10 +505,9 @@ mod tests {
         status.is_self_drawn = is_self_drawn;
         status.has_claimed_open = has_claimed_open;
         assert!(test_analyzer.shanten.has_won());
-        assert_eq!(
-            check_three_concealed_triplets(&test_analyzer, &test, &status, &settings).unwrap(),
-            expected
-        );
+        let result =
+            check_three_concealed_triplets(&test_analyzer, &test, &status, &settings).unwrap();
+        assert_eq!((result.name, result.is_established, result.han), expected);
     }
 
     #[test]
@@ -543,8 +518,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_mixed_triplets(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_mixed_triplets(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("三色同刻", true, 2)
         );
     }
@@ -556,8 +532,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_common_terminals(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_common_terminals(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("混老頭", true, 2)
         );
     }
@@ -569,8 +546,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_little_dragons(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_little_dragons(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("小三元", true, 2)
         );
     }
@@ -1,9 +1,12 @@
-use anyhow::Result;
+use crate::error::Result;
 
 use crate::hand_info::block::BlockProperty;
 use crate::hand_info::hand_analyzer::*;
+use crate::hand_info::identical_sequences::identical_sequences;
 use crate::hand_info::status::*;
 use crate::settings::*;
+use crate::tile::Suit;
+use crate::winning_hand::checker::YakuResult;
 use crate::winning_hand::name::*;
 
 /// 二盃口
@@ -11,46 +14,29 @@ pub fn check_double_twin_sequences(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::DoubleTwinSequences,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 門前でなければ二盃口は成立しない
     if status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 順子が4つなければ二盃口はありえない
     if hand_analyzer.sequential3.len() != 4 {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 2組の同じ順子ペアがあるか確認
-    let mut used = [false; 4];
-    let mut pair_count = 0;
-    for i in 0..4 {
-        if used[i] {
-            continue;
-        }
-        for j in (i + 1)..4 {
-            if used[j] {
-                continue;
-            }
-            if hand_analyzer.sequential3[i] == hand_analyzer.sequential3[j] {
-                used[i] = true;
-                used[j] = true;
-                pair_count += 1;
-                break;
-            }
-        }
-    }
+    let pair_count = identical_sequences(&hand_analyzer.sequential3).pair_count();
     if pair_count == 2 {
-        Ok((name, true, 3))
+        Ok(yaku.established(3))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 純全帯么九
@@ -58,18 +44,18 @@ pub fn check_perfect_ends(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::PerfectEnds,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 清老頭とは複合しないため、必ず順子が含まれる
     if hand_analyzer.sequential3.is_empty() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
 
     let mut no_1_9 = false;
@@ -96,12 +82,12 @@ pub fn check_perfect_ends(
     }
 
     if no_1_9 {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     if status.has_claimed_open {
-        Ok((name, true, 2))
+        Ok(yaku.established(2))
     } else {
-        Ok((name, true, 3))
+        Ok(yaku.established(3))
     }
 }
 /// 混一色
@@ -109,74 +95,25 @@ pub fn check_common_flush(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::CommonFlush,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
-    }
-    let mut has_honour = false;
-    let mut has_character = false;
-    let mut has_circle = false;
-    let mut has_bamboo = false;
-
-    for same in &hand_analyzer.same3 {
-        if same.has_honour()? {
-            has_honour = true;
-        }
-        if same.is_character()? {
-            has_character = true;
-        }
-        if same.is_circle()? {
-            has_circle = true;
-        }
-        if same.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-    for seq in &hand_analyzer.sequential3 {
-        if seq.is_character()? {
-            has_character = true;
-        }
-        if seq.is_circle()? {
-            has_circle = true;
-        }
-        if seq.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-    for head in &hand_analyzer.same2 {
-        if head.has_honour()? {
-            has_honour = true;
-        }
-        if head.is_character()? {
-            has_character = true;
-        }
-        if head.is_circle()? {
-            has_circle = true;
-        }
-        if head.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-
-    if !has_honour {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    let suit_count = [has_character, has_circle, has_bamboo]
-        .iter()
-        .filter(|&&x| x)
-        .count();
-    if suit_count != 1 {
-        return Ok((name, false, 0));
+    // 混一色: 字牌と1種類の数牌で構成される
+    let suits = hand_analyzer.block_suits();
+    let suited = suits.iter().filter(|suit| suit.is_suited()).count();
+    if !suits.contains(&Suit::Honour) || suited != 1 {
+        return Ok(yaku);
     }
     if status.has_claimed_open {
-        Ok((name, true, 2))
+        Ok(yaku.established(2))
     } else {
-        Ok((name, true, 3))
+        Ok(yaku.established(3))
     }
 }
 
@@ -196,8 +133,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_perfect_ends(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_perfect_ends(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("純全帯么九", true, 3)
         );
     }
@@ -210,8 +148,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_perfect_ends(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_perfect_ends(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("純全帯么九（鳴）", true, 2)
         );
     }
@@ -228,12 +167,12 @@ mod tests {
         assert!(
             check_common_ends(&test_analyzer, &status, &settings)
                 .unwrap()
-                .1
+                .is_established
         );
         assert!(
             !check_perfect_ends(&test_analyzer, &status, &settings)
                 .unwrap()
-                .1
+                .is_established
         );
     }
     #[test]
@@ -248,12 +187,12 @@ mod tests {
         assert!(
             !check_common_ends(&test_analyzer, &status, &settings)
                 .unwrap()
-                .1
+                .is_established
         );
         assert!(
             check_perfect_ends(&test_analyzer, &status, &settings)
                 .unwrap()
-                .1
+                .is_established
         );
     }
     #[test]
@@ -266,8 +205,9 @@ mod tests {
         let settings = Settings::new();
         status.has_claimed_open = false;
         assert_eq!(test_analyzer.form, Form::Normal);
+        let result = check_double_twin_sequences(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_double_twin_sequences(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("二盃口", true, 3)
         );
     }
@@ -280,8 +220,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_common_flush(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_common_flush(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("混一色（鳴）", true, 2)
         );
     }
@@ -294,8 +235,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_common_flush(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_common_flush(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("混一色", true, 3)
         );
     }
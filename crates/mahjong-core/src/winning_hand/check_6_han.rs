@@ -1,9 +1,9 @@
-use anyhow::Result;
+use crate::error::Result;
 
-use crate::hand_info::block::BlockProperty;
 use crate::hand_info::hand_analyzer::*;
 use crate::hand_info::status::*;
 use crate::settings::*;
+use crate::winning_hand::checker::YakuResult;
 use crate::winning_hand::name::*;
 
 /// 清一色
@@ -11,76 +11,24 @@ pub fn check_perfect_flush(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::PerfectFlush,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 清一色: 1種類の数牌のみで構成される（字牌なし）
-    let mut has_honour = false;
-    let mut has_character = false;
-    let mut has_circle = false;
-    let mut has_bamboo = false;
-
-    for same in &hand_analyzer.same3 {
-        if same.has_honour()? {
-            has_honour = true;
-        }
-        if same.is_character()? {
-            has_character = true;
-        }
-        if same.is_circle()? {
-            has_circle = true;
-        }
-        if same.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-    for seq in &hand_analyzer.sequential3 {
-        if seq.is_character()? {
-            has_character = true;
-        }
-        if seq.is_circle()? {
-            has_circle = true;
-        }
-        if seq.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-    for head in &hand_analyzer.same2 {
-        if head.has_honour()? {
-            has_honour = true;
-        }
-        if head.is_character()? {
-            has_character = true;
-        }
-        if head.is_circle()? {
-            has_circle = true;
-        }
-        if head.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-
-    // 字牌があったら清一色ではない
-    if has_honour {
-        return Ok((name, false, 0));
-    }
-    let suit_count = [has_character, has_circle, has_bamboo]
-        .iter()
-        .filter(|&&x| x)
-        .count();
-    if suit_count != 1 {
-        return Ok((name, false, 0));
+    let suits = hand_analyzer.block_suits();
+    if suits.len() != 1 || !suits[0].is_suited() {
+        return Ok(yaku);
     }
     if status.has_claimed_open {
-        Ok((name, true, 5))
+        Ok(yaku.established(5))
     } else {
-        Ok((name, true, 6))
+        Ok(yaku.established(6))
     }
 }
 
@@ -98,8 +46,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_perfect_flush(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_perfect_flush(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("清一色", true, 6)
         );
     }
@@ -112,8 +61,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = true;
+        let result = check_perfect_flush(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_perfect_flush(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("清一色（鳴）", true, 5)
         );
     }
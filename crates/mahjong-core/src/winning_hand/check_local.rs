@@ -0,0 +1,263 @@
+//! ローカル役の判定
+//!
+//! 標準ルールにない役を、`Settings.local_yaku` で個別に有効にしたときだけ判定する。
+//! `checker::check` から `merge` を通じて呼ばれ、有効な役だけを判定結果に加える。
+
+use std::collections::HashMap;
+
+use crate::error::Result;
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::*;
+use crate::hand_info::identical_sequences::identical_sequences;
+use crate::hand_info::status::*;
+use crate::settings::*;
+use crate::tile::{Tile, TileType};
+use crate::winning_hand::checker::YakuResult;
+use crate::winning_hand::name::*;
+
+/// 有効なローカル役を判定し、判定結果に加える
+///
+/// 一色三順が成立した場合は、その中に含まれる一盃口を数えない。
+/// 四連刻が成立した場合は三連刻を数えない。
+pub fn merge(
+    result: &mut HashMap<Kind, YakuResult>,
+    analyzer: &HandAnalyzer,
+    hand: &Hand,
+    status: &Status,
+    settings: &Settings,
+) -> Result<()> {
+    let local = settings.local_yaku;
+    let four = check_four_consecutive_triplets(analyzer, status, settings)?;
+    if local.four_consecutive_triplets {
+        result.insert(Kind::FourConsecutiveTriplets, four);
+    }
+    if local.three_consecutive_triplets && !(local.four_consecutive_triplets && four.is_established)
+    {
+        result.insert(
+            Kind::ThreeConsecutiveTriplets,
+            check_three_consecutive_triplets(analyzer, status, settings)?,
+        );
+    }
+    if local.big_wheels {
+        result.insert(
+            Kind::BigWheels,
+            check_big_wheels(analyzer, hand, status, settings)?,
+        );
+    }
+    if local.pure_triple_sequences {
+        let yaku = check_pure_triple_sequences(analyzer, status, settings)?;
+        if yaku.is_established {
+            result.insert(
+                Kind::TwinSequences,
+                YakuResult::new(
+                    Kind::TwinSequences,
+                    status.has_claimed_open,
+                    settings.display_lang,
+                ),
+            );
+        }
+        result.insert(Kind::PureTripleSequences, yaku);
+    }
+    Ok(())
+}
+
+/// 同じ色で数字が連続する刻子（槓子を含む）の最大の個数
+fn longest_consecutive_triplets(hand_analyzer: &HandAnalyzer) -> usize {
+    let mut tiles: Vec<TileType> = hand_analyzer
+        .same3
+        .iter()
+        .map(|same| same.get()[0])
+        .filter(|&t| t < Tile::Z1)
+        .collect();
+    tiles.sort_unstable();
+    tiles.dedup();
+
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, &t) in tiles.iter().enumerate() {
+        let continues = i > 0 && tiles[i - 1] + 1 == t && tiles[i - 1] / 9 == t / 9;
+        run = if continues { run + 1 } else { 1 };
+        longest = longest.max(run);
+    }
+    longest
+}
+
+/// 三連刻
+pub fn check_three_consecutive_triplets(
+    hand_analyzer: &HandAnalyzer,
+    status: &Status,
+    settings: &Settings,
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
+        Kind::ThreeConsecutiveTriplets,
+        status.has_claimed_open,
+        settings.display_lang,
+    );
+    if !hand_analyzer.shanten.has_won() {
+        return Ok(yaku);
+    }
+    if longest_consecutive_triplets(hand_analyzer) >= 3 {
+        Ok(yaku.established(2))
+    } else {
+        Ok(yaku)
+    }
+}
+
+/// 四連刻
+pub fn check_four_consecutive_triplets(
+    hand_analyzer: &HandAnalyzer,
+    status: &Status,
+    settings: &Settings,
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
+        Kind::FourConsecutiveTriplets,
+        status.has_claimed_open,
+        settings.display_lang,
+    );
+    if !hand_analyzer.shanten.has_won() {
+        return Ok(yaku);
+    }
+    if longest_consecutive_triplets(hand_analyzer) >= 4 {
+        Ok(yaku.established(13))
+    } else {
+        Ok(yaku)
+    }
+}
+
+/// 大車輪
+///
+/// 面子の取り方によらず、門前の手牌が二筒〜八筒の各2枚であれば成立する。
+pub fn check_big_wheels(
+    hand_analyzer: &HandAnalyzer,
+    hand: &Hand,
+    status: &Status,
+    settings: &Settings,
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
+        Kind::BigWheels,
+        status.has_claimed_open,
+        settings.display_lang,
+    );
+    if !hand_analyzer.shanten.has_won() || status.has_claimed_open || !hand.has_no_melds() {
+        return Ok(yaku);
+    }
+    let mut counts = [0u32; Tile::LEN];
+    for tile in hand.tiles().iter().chain(hand.drawn().iter()) {
+        counts[tile.get() as usize] += 1;
+    }
+    let wheels = (0..Tile::LEN as TileType).all(|t| {
+        counts[t as usize]
+            == if (Tile::P2..=Tile::P8).contains(&t) {
+                2
+            } else {
+                0
+            }
+    });
+    if wheels {
+        Ok(yaku.established(13))
+    } else {
+        Ok(yaku)
+    }
+}
+
+/// 一色三順
+pub fn check_pure_triple_sequences(
+    hand_analyzer: &HandAnalyzer,
+    status: &Status,
+    settings: &Settings,
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
+        Kind::PureTripleSequences,
+        status.has_claimed_open,
+        settings.display_lang,
+    );
+    if !hand_analyzer.shanten.has_won() {
+        return Ok(yaku);
+    }
+    if identical_sequences(&hand_analyzer.sequential3).max_multiplicity < 3 {
+        return Ok(yaku);
+    }
+    if status.has_claimed_open {
+        Ok(yaku.established(2))
+    } else {
+        Ok(yaku.established(3))
+    }
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::winning_hand::checker;
+    use rstest::rstest;
+
+    fn all_local() -> Settings {
+        let mut settings = Settings::new();
+        settings.local_yaku = LocalYaku {
+            three_consecutive_triplets: true,
+            four_consecutive_triplets: true,
+            big_wheels: true,
+            pure_triple_sequences: true,
+        };
+        settings
+    }
+
+    /// いずれかの面子の取り方で成立する役と翻数を返す
+    fn established(hand_str: &str, settings: &Settings) -> Vec<(Kind, u32)> {
+        let hand = Hand::from(hand_str);
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        status.has_claimed_open = !hand.has_no_melds();
+        let mut found = Vec::new();
+        for analyzer in HandAnalyzer::all_decompositions(&hand).unwrap() {
+            for yaku in checker::check(&analyzer, &hand, &status, settings).unwrap() {
+                if yaku.is_established && !found.contains(&(yaku.kind, yaku.han)) {
+                    found.push((yaku.kind, yaku.han));
+                }
+            }
+        }
+        found
+    }
+
+    #[rstest]
+    #[case("333444555m78p11z 9p", Kind::ThreeConsecutiveTriplets, 2)]
+    #[case("444555666777s1z 1z", Kind::FourConsecutiveTriplets, 13)]
+    #[case("2233445566778p 8p", Kind::BigWheels, 13)]
+    #[case("123123123m456p1z 1z", Kind::PureTripleSequences, 3)]
+    #[case("123123m456p1z 123m 1z", Kind::PureTripleSequences, 2)]
+    fn local_yaku_established(#[case] hand_str: &str, #[case] kind: Kind, #[case] han: u32) {
+        assert!(established(hand_str, &all_local()).contains(&(kind, han)));
+        // 設定で無効なら成立しない
+        let found = established(hand_str, &Settings::new());
+        assert!(found.iter().all(|&(k, _)| k != kind));
+    }
+
+    #[test]
+    fn four_consecutive_supersedes_three() {
+        let found = established("444555666777s1z 1z", &all_local());
+        assert!(
+            found
+                .iter()
+                .all(|&(k, _)| k != Kind::ThreeConsecutiveTriplets)
+        );
+    }
+
+    #[test]
+    fn consecutive_triplets_must_share_suit() {
+        // 8m・9m・1p は数字が並んでいても色が違う
+        let found = established("888999m111p456s1z 1z", &all_local());
+        assert!(
+            found
+                .iter()
+                .all(|&(k, _)| k != Kind::ThreeConsecutiveTriplets)
+        );
+    }
+
+    #[test]
+    fn pure_triple_sequences_excludes_twin_sequences() {
+        let found = established("123123123m456p1z 1z", &all_local());
+        assert!(found.contains(&(Kind::PureTripleSequences, 3)));
+        // どの面子の取り方でも一盃口は数えない
+        assert!(found.iter().all(|&(k, _)| k != Kind::TwinSequences));
+    }
+}
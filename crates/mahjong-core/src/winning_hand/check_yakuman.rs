@@ -1,4 +1,4 @@
-use anyhow::Result;
+use crate::error::Result;
 
 use crate::hand::Hand;
 use crate::hand_info::block::BlockProperty;
@@ -6,26 +6,36 @@ use crate::hand_info::hand_analyzer::*;
 use crate::hand_info::status::*;
 use crate::settings::*;
 use crate::tile::{Dragon, Tile, Wind};
+use crate::winning_hand::checker::YakuResult;
 use crate::winning_hand::name::*;
 
 /// 国士無双
 pub fn check_thirteen_orphans(
     hand_analyzer: &HandAnalyzer,
+    _hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::ThirteenOrphans,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    if hand_analyzer.form == Form::ThirteenOrphans {
-        Ok((name, true, 13))
+    if hand_analyzer.form != Form::ThirteenOrphans {
+        return Ok(yaku);
+    }
+    Ok(yaku.established(yakuman_han(hand_analyzer.is_thirteen_wait(), settings)))
+}
+
+/// 役満の翻数を返す（ダブル役満の形で、ダブル役満ありなら26翻）
+fn yakuman_han(is_double: bool, settings: &Settings) -> u32 {
+    if is_double && settings.double_yakuman {
+        26
     } else {
-        Ok((name, false, 0))
+        13
     }
 }
 fn is_four_concealed_triplets_pair_wait(hand_analyzer: &HandAnalyzer, hand: &Hand) -> bool {
@@ -43,26 +53,28 @@ pub fn check_four_concealed_triplets(
     hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::FourConcealedTriplets,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    if status.has_claimed_open
-        || hand_analyzer.same3.len() != 4
-        || is_four_concealed_triplets_pair_wait(hand_analyzer, hand)
-    {
-        return Ok((name, false, 0));
+    if status.has_claimed_open || is_four_concealed_triplets_pair_wait(hand_analyzer, hand) {
+        return Ok(yaku);
     }
 
-    if status.is_self_drawn {
-        Ok((name, true, 13))
+    // ロンで刻子が完成した場合は明刻となり、対々和・三暗刻に格下げされる
+    if hand_analyzer
+        .concealed_triplets_on_win(hand, status.is_self_drawn)
+        .len()
+        == 4
+    {
+        Ok(yaku.established(13))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -72,23 +84,29 @@ pub fn check_four_concealed_triplets_pair_wait(
     hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::FourConcealedTripletsPairWait,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    if status.has_claimed_open || hand_analyzer.same3.len() != 4 {
-        return Ok((name, false, 0));
+    // 単騎待ちはロン和了でも4つの刻子がすべて暗刻のまま
+    if status.has_claimed_open
+        || hand_analyzer
+            .concealed_triplets_on_win(hand, status.is_self_drawn)
+            .len()
+            != 4
+    {
+        return Ok(yaku);
     }
 
     if is_four_concealed_triplets_pair_wait(hand_analyzer, hand) {
-        Ok((name, true, 13))
+        Ok(yaku.established(yakuman_han(true, settings)))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 大三元
@@ -96,14 +114,14 @@ pub fn check_big_dragons(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::BigDragons,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 大三元: 三元牌（白・發・中）の3つすべてが刻子
     let mut dragon_count = 0;
@@ -116,9 +134,9 @@ pub fn check_big_dragons(
         }
     }
     if dragon_count == 3 {
-        Ok((name, true, 13))
+        Ok(yaku.established(13))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 小四喜
@@ -126,14 +144,14 @@ pub fn check_little_winds(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::LittleWinds,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 小四喜: 風牌のうち3つが刻子、1つが雀頭
     let mut wind_triplet_count = 0;
@@ -157,9 +175,9 @@ pub fn check_little_winds(
         }
     }
     if wind_triplet_count == 3 && wind_pair {
-        Ok((name, true, 13))
+        Ok(yaku.established(13))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 大四喜
@@ -167,14 +185,14 @@ pub fn check_big_winds(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::BigWinds,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 大四喜: 風牌4つすべてが刻子
     let mut wind_triplet_count = 0;
@@ -188,9 +206,9 @@ pub fn check_big_winds(
         }
     }
     if wind_triplet_count == 4 {
-        Ok((name, true, 13))
+        Ok(yaku.established(yakuman_han(true, settings)))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 字一色
@@ -198,83 +216,84 @@ pub fn check_all_honours(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::AllHonours,
         status.has_claimed_open,
         settings.display_lang,
     );
-    if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+    // 国士無双の形は面子・対子を持たないため、牌の構成では判定しない
+    if !hand_analyzer.shanten.has_won() || hand_analyzer.form == Form::ThirteenOrphans {
+        return Ok(yaku);
     }
     // 字一色: すべての牌が字牌で構成される
     for same in &hand_analyzer.same3 {
         if !same.has_honour()? {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
     for head in &hand_analyzer.same2 {
         if !head.has_honour()? {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
     // 順子があったら字一色ではない
     if !hand_analyzer.sequential3.is_empty() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
-    // 七対子形の場合もチェック（same2が7つの場合）
-    if hand_analyzer.form == Form::SevenPairs {
-        for head in &hand_analyzer.same2 {
-            if !head.has_honour()? {
-                return Ok((name, false, 0));
-            }
-        }
+    // 七対子形の場合もチェック
+    if let Some(info) = hand_analyzer.seven_pairs_info()
+        && !info.pairs.iter().all(|&pair| Tile::new(pair).is_honour())
+    {
+        return Ok(yaku);
     }
-    Ok((name, true, 13))
+    Ok(yaku.established(13))
 }
 /// 清老頭
 pub fn check_perfect_terminals(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::PerfectTerminals,
         status.has_claimed_open,
         settings.display_lang,
     );
-    if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+    // 国士無双の形は面子・対子を持たないため、牌の構成では判定しない
+    if !hand_analyzer.shanten.has_won() || hand_analyzer.form == Form::ThirteenOrphans {
+        return Ok(yaku);
     }
     // 清老頭: すべての牌が数牌の1と9のみで構成される（字牌なし・順子なし）
     if !hand_analyzer.sequential3.is_empty() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     for same in &hand_analyzer.same3 {
         if !same.has_1_or_9()? || same.has_honour()? {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
     for head in &hand_analyzer.same2 {
         if !head.has_1_or_9()? || head.has_honour()? {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
-    Ok((name, true, 13))
+    Ok(yaku.established(13))
 }
 /// 緑一色
 pub fn check_all_green(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::AllGreen,
         status.has_claimed_open,
         settings.display_lang,
     );
-    if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+    // 国士無双の形は面子・対子を持たないため、牌の構成では判定しない
+    if !hand_analyzer.shanten.has_won() || hand_analyzer.form == Form::ThirteenOrphans {
+        return Ok(yaku);
     }
     // 緑一色: 2s, 3s, 4s, 6s, 8s, 6z（發）のみで構成される
     let is_green_tile = |t: u32| -> bool {
@@ -285,108 +304,52 @@ pub fn check_all_green(
     };
     for same in &hand_analyzer.same3 {
         if !is_green_tile(same.get()[0]) {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
     for seq in &hand_analyzer.sequential3 {
         let tiles = seq.get();
         for t in &tiles {
             if !is_green_tile(*t) {
-                return Ok((name, false, 0));
+                return Ok(yaku);
             }
         }
     }
     for head in &hand_analyzer.same2 {
         if !is_green_tile(head.get()[0]) {
-            return Ok((name, false, 0));
+            return Ok(yaku);
         }
     }
-    Ok((name, true, 13))
+    Ok(yaku.established(13))
 }
 /// 九蓮宝燈
 pub fn check_nine_gates(
     hand_analyzer: &HandAnalyzer,
+    hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::NineGates,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 九蓮宝燈: 門前で同一種の数牌のみで、1112345678999+同種1枚の形
     if status.has_claimed_open {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 全ブロックが同じ種類の数牌であること
-    let mut has_character = false;
-    let mut has_circle = false;
-    let mut has_bamboo = false;
-    let mut has_honour = false;
-
-    for same in &hand_analyzer.same3 {
-        if same.is_character()? {
-            has_character = true;
-        }
-        if same.is_circle()? {
-            has_circle = true;
-        }
-        if same.is_bamboo()? {
-            has_bamboo = true;
-        }
-        if same.has_honour()? {
-            has_honour = true;
-        }
-    }
-    for seq in &hand_analyzer.sequential3 {
-        if seq.is_character()? {
-            has_character = true;
-        }
-        if seq.is_circle()? {
-            has_circle = true;
-        }
-        if seq.is_bamboo()? {
-            has_bamboo = true;
-        }
-    }
-    for head in &hand_analyzer.same2 {
-        if head.is_character()? {
-            has_character = true;
-        }
-        if head.is_circle()? {
-            has_circle = true;
-        }
-        if head.is_bamboo()? {
-            has_bamboo = true;
-        }
-        if head.has_honour()? {
-            has_honour = true;
-        }
-    }
-
-    if has_honour {
-        return Ok((name, false, 0));
-    }
-    let suit_count = [has_character, has_circle, has_bamboo]
-        .iter()
-        .filter(|&&x| x)
-        .count();
-    if suit_count != 1 {
-        return Ok((name, false, 0));
+    let suits = hand_analyzer.block_suits();
+    if suits.len() != 1 || !suits[0].is_suited() {
+        return Ok(yaku);
     }
 
     // 牌の数を集計して九蓮宝燈のパターンかチェック
     // 基本形: 1が3枚以上, 2~8が各1枚以上, 9が3枚以上
-    let offset = if has_character {
-        0
-    } else if has_circle {
-        9
-    } else {
-        18
-    };
+    let offset = suits[0].first_tile();
     let mut counts = [0u32; 9];
     for same in &hand_analyzer.same3 {
         let t = same.get()[0];
@@ -421,30 +384,38 @@ pub fn check_nine_gates(
     {
         let total: u32 = counts.iter().sum();
         if total == 14 {
-            return Ok((name, true, 13));
+            // 純正九蓮宝燈: 和了牌を除く13枚が 1112345678999 の形（九面待ち）
+            let mut concealed = [0u32; 9];
+            for tile in hand.tiles() {
+                if (offset..offset + 9).contains(&tile.get()) {
+                    concealed[(tile.get() - offset) as usize] += 1;
+                }
+            }
+            let is_pure = hand.drawn().is_some() && concealed == [3, 1, 1, 1, 1, 1, 1, 1, 3];
+            return Ok(yaku.established(yakuman_han(is_pure, settings)));
         }
     }
-    Ok((name, false, 0))
+    Ok(yaku)
 }
 /// 四槓子
 pub fn check_four_quads(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::FourQuads,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 四槓子: 4つの槓子を持っている
     if status.kan_count == 4 {
-        Ok((name, true, 13))
+        Ok(yaku.established(13))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 天和
@@ -452,21 +423,21 @@ pub fn check_blessing_of_heaven(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::BlessingOfHeaven,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 天和: 親の配牌時点で和了している（第一ツモ・親・自摸）
     if status.is_dealer && status.is_first_turn && status.is_self_drawn && !status.has_claimed_open
     {
-        Ok((name, true, 13))
+        Ok(yaku.established(13))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 /// 地和
@@ -474,21 +445,21 @@ pub fn check_blessing_of_earth(
     hand_analyzer: &HandAnalyzer,
     status: &Status,
     settings: &Settings,
-) -> Result<(&'static str, bool, u32)> {
-    let name = get(
+) -> Result<YakuResult> {
+    let yaku = YakuResult::new(
         Kind::BlessingOfEarth,
         status.has_claimed_open,
         settings.display_lang,
     );
     if !hand_analyzer.shanten.has_won() {
-        return Ok((name, false, 0));
+        return Ok(yaku);
     }
     // 地和: 子の第一ツモで和了している（第一ツモ・子・自摸）
     if !status.is_dealer && status.is_first_turn && status.is_self_drawn && !status.has_claimed_open
     {
-        Ok((name, true, 13))
+        Ok(yaku.established(13))
     } else {
-        Ok((name, false, 0))
+        Ok(yaku)
     }
 }
 
@@ -507,8 +478,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_thirteen_orphans(&test_analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_thirteen_orphans(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("国士無双", true, 13)
         );
     }
@@ -519,6 +491,8 @@ mod tests {
     #[case::shanpon_tsumo("111333m444s55s77z 5s", true, ("四暗刻単騎待ち", false, 0), ("四暗刻", true, 13), false)]
     #[case::shanpon_ron("111333m444s55s77z 5s", false, ("四暗刻単騎待ち", false, 0), ("四暗刻", false, 0), false)]
     #[case::open_tanki_tsumo("111333m444s1777z 1z", true, ("四暗刻単騎待ち", false, 0), ("四暗刻", false, 0), true)]
+    #[case::pon_meld_tsumo("111333m1777z 444s 1z", true, ("四暗刻単騎待ち", false, 0), ("四暗刻", false, 0), false)]
+    #[case::concealed_kan_tanki_tsumo("111333m1777z a4444s 1z", true, ("四暗刻単騎待ち", true, 13), ("四暗刻", false, 0), false)]
     /// 四暗刻と四暗刻単騎待ちの振り分けを確認する
     fn test_four_concealed_triplets(
         #[case] test_str: &str,
@@ -534,13 +508,17 @@ mod tests {
         status.is_self_drawn = is_self_drawn;
         status.has_claimed_open = has_claimed_open;
         assert!(test_analyzer.shanten.has_won());
-        assert_eq!(
+        let result =
             check_four_concealed_triplets_pair_wait(&test_analyzer, &test, &status, &settings)
-                .unwrap(),
+                .unwrap();
+        assert_eq!(
+            (result.name, result.is_established, result.han),
             expected_single_wait
         );
+        let result =
+            check_four_concealed_triplets(&test_analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_four_concealed_triplets(&test_analyzer, &test, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             expected_four_concealed_triplets
         );
     }
@@ -552,8 +530,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_big_dragons(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_big_dragons(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("大三元", true, 13)
         );
     }
@@ -565,8 +544,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_little_winds(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_little_winds(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("小四喜", true, 13)
         );
     }
@@ -578,8 +558,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_big_winds(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_big_winds(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("大四喜", true, 13)
         );
     }
@@ -591,8 +572,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_all_honours(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_all_honours(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("字一色", true, 13)
         );
     }
@@ -604,8 +586,9 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_perfect_terminals(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_perfect_terminals(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("清老頭", true, 13)
         );
     }
@@ -617,11 +600,46 @@ mod tests {
         let test_analyzer = HandAnalyzer::new(&test).unwrap();
         let status = Status::new();
         let settings = Settings::new();
+        let result = check_all_green(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_all_green(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("緑一色", true, 13)
         );
     }
+    #[rstest]
+    #[case::thirteen_sided("19m19p19s1234567z 1m", Kind::ThirteenOrphans, 26)]
+    #[case::thirteen_orphans_single_wait("119m19p19s123456z 7z", Kind::ThirteenOrphans, 13)]
+    #[case::pure_nine_gates("1112345678999m 5m", Kind::NineGates, 26)]
+    #[case::nine_gates("1112345678899m 9m", Kind::NineGates, 13)]
+    #[case::big_winds("5m111222333444z 5m", Kind::BigWinds, 26)]
+    #[case::four_concealed_pair_wait(
+        "111m222p333s5m444z 5m",
+        Kind::FourConcealedTripletsPairWait,
+        26
+    )]
+    /// ダブル役満ありの場合、ダブル役満の形だけが26翻になる
+    fn test_double_yakuman(#[case] hand_str: &str, #[case] kind: Kind, #[case] han: u32) {
+        let test = Hand::from(hand_str);
+        let test_analyzer = HandAnalyzer::new(&test).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        let mut settings = Settings::new();
+        settings.double_yakuman = true;
+        let result =
+            crate::winning_hand::checker::check(&test_analyzer, &test, &status, &settings).unwrap();
+        let yaku = crate::winning_hand::checker::find(&result, kind).unwrap();
+        assert!(yaku.is_established);
+        assert_eq!(yaku.han, han);
+        assert_eq!(yaku.yakuman_multiplier, han / 13);
+
+        // ダブル役満なしでは常に13翻
+        settings.double_yakuman = false;
+        let result =
+            crate::winning_hand::checker::check(&test_analyzer, &test, &status, &settings).unwrap();
+        let yaku = crate::winning_hand::checker::find(&result, kind).unwrap();
+        assert_eq!(yaku.han, 13);
+    }
+
     #[test]
     /// 九蓮宝燈で和了った
     fn test_win_by_nine_gates() {
@@ -631,8 +649,9 @@ mod tests {
         let mut status = Status::new();
         let settings = Settings::new();
         status.has_claimed_open = false;
+        let result = check_nine_gates(&test_analyzer, &test, &status, &settings).unwrap();
         assert_eq!(
-            check_nine_gates(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("九蓮宝燈", true, 13)
         );
     }
@@ -646,8 +665,9 @@ mod tests {
         let settings = Settings::new();
         status.kan_count = 4;
         status.is_self_drawn = true;
+        let result = check_four_quads(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_four_quads(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("四槓子", true, 13)
         );
     }
@@ -662,8 +682,9 @@ mod tests {
         status.is_dealer = true;
         status.is_first_turn = true;
         status.is_self_drawn = true;
+        let result = check_blessing_of_heaven(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_blessing_of_heaven(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("天和", true, 13)
         );
     }
@@ -678,8 +699,9 @@ mod tests {
         status.is_dealer = false;
         status.is_first_turn = true;
         status.is_self_drawn = true;
+        let result = check_blessing_of_heaven(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_blessing_of_heaven(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("天和", false, 0)
         );
     }
@@ -694,8 +716,9 @@ mod tests {
         status.is_dealer = false;
         status.is_first_turn = true;
         status.is_self_drawn = true;
+        let result = check_blessing_of_earth(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_blessing_of_earth(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("地和", true, 13)
         );
     }
@@ -710,8 +733,9 @@ mod tests {
         status.is_dealer = true;
         status.is_first_turn = true;
         status.is_self_drawn = true;
+        let result = check_blessing_of_earth(&test_analyzer, &status, &settings).unwrap();
         assert_eq!(
-            check_blessing_of_earth(&test_analyzer, &status, &settings).unwrap(),
+            (result.name, result.is_established, result.han),
             ("地和", false, 0)
         );
     }
@@ -1,5 +1,7 @@
-use anyhow::Result;
+use crate::error::Result;
 /// 役を判定する
+#[cfg(feature = "serde")]
+use serde::Serialize;
 use std::collections::HashMap;
 use strum::{EnumCount, IntoEnumIterator};
 
@@ -10,20 +12,69 @@ use crate::settings::*;
 use crate::winning_hand::check_1_han::*;
 use crate::winning_hand::check_2_han::*;
 use crate::winning_hand::check_3_han::*;
-use crate::winning_hand::check_5_han::*;
 use crate::winning_hand::check_6_han::*;
 use crate::winning_hand::check_yakuman::*;
 use crate::winning_hand::name::*;
 
+/// 1つの役の判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct YakuResult {
+    /// 役
+    pub kind: Kind,
+    /// 表示名（鳴きの有無・表示言語に応じたもの）
+    pub name: &'static str,
+    /// 成立しているか
+    pub is_established: bool,
+    /// 翻数（成立していなければ0、役満は13、ダブル役満は26）
+    pub han: u32,
+    /// 役満か
+    pub is_yakuman: bool,
+    /// 役満の倍数（役満でなければ0、役満は1、ダブル役満は2）
+    pub yakuman_multiplier: u32,
+}
+
+impl YakuResult {
+    /// 不成立の判定結果を作る
+    pub fn new(kind: Kind, has_openned: bool, lang: Lang) -> YakuResult {
+        YakuResult {
+            kind,
+            name: get(kind, has_openned, lang),
+            is_established: false,
+            han: 0,
+            is_yakuman: false,
+            yakuman_multiplier: 0,
+        }
+    }
+
+    /// 指定した翻数で成立した判定結果を返す
+    pub fn established(self, han: u32) -> YakuResult {
+        YakuResult {
+            is_established: true,
+            han,
+            is_yakuman: han >= 13,
+            yakuman_multiplier: han / 13,
+            ..self
+        }
+    }
+}
+
+/// すべての役を判定する
+///
+/// 成立していない役も含め、`Kind` の定義順に並べた判定結果を返す。
+/// 並び順は実行ごとに変わらないため、そのまま表示に使える。
 pub fn check(
     analyzer: &HandAnalyzer,
     hand: &Hand,
     status: &Status,
     settings: &Settings,
-) -> Result<HashMap<Kind, (&'static str, bool, u32)>> {
+) -> Result<Vec<YakuResult>> {
     let mut result = HashMap::with_capacity(Kind::COUNT);
     for hand_kind in Kind::iter() {
-        result.insert(hand_kind, ("Unknown", false, 0));
+        result.insert(
+            hand_kind,
+            YakuResult::new(hand_kind, status.has_claimed_open, settings.display_lang),
+        );
     }
 
     // 立直
@@ -33,11 +84,7 @@ pub fn check(
         Kind::SevenPairs,
         check_seven_pairs(analyzer, status, settings)?,
     );
-    // 流し満貫
-    result.insert(
-        Kind::NagashiMangan,
-        check_nagashi_mangan(analyzer, status, settings)?,
-    );
+    // 流し満貫は和了形を必要としないため、ここでは判定しない（`board::draw_outcome` で扱う）
     // 門前清自摸和
     result.insert(
         Kind::FullyConcealedHand,
@@ -63,7 +110,7 @@ pub fn check(
     // 搶槓
     result.insert(
         Kind::RobbingAQuad,
-        check_robbing_a_quad(analyzer, status, settings)?,
+        check_robbing_a_quad(analyzer, hand, status, settings)?,
     );
     // ダブル立直
     result.insert(
@@ -170,7 +217,7 @@ pub fn check(
     // 国士無双
     result.insert(
         Kind::ThirteenOrphans,
-        check_thirteen_orphans(analyzer, status, settings)?,
+        check_thirteen_orphans(analyzer, hand, status, settings)?,
     );
     // 四暗刻単騎待ち
     result.insert(
@@ -209,7 +256,7 @@ pub fn check(
     // 九蓮宝燈
     result.insert(
         Kind::NineGates,
-        check_nine_gates(analyzer, status, settings)?,
+        check_nine_gates(analyzer, hand, status, settings)?,
     );
     // 四槓子
     result.insert(
@@ -227,9 +274,59 @@ pub fn check(
         check_blessing_of_earth(analyzer, status, settings)?,
     );
 
+    // ローカル役（設定で有効なもののみ）
+    #[cfg(feature = "local-yaku")]
+    crate::winning_hand::check_local::merge(&mut result, analyzer, hand, status, settings)?;
+
+    let mut result: Vec<YakuResult> = result.into_values().collect();
+    result.sort_by_key(|yaku| yaku.kind);
     Ok(result)
 }
 
+/// 判定結果から指定した役を探す
+pub fn find(result: &[YakuResult], kind: Kind) -> Option<&YakuResult> {
+    result.iter().find(|yaku| yaku.kind == kind)
+}
+
 /// ユニットテスト
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaku_result_established() {
+        let yaku = YakuResult::new(Kind::BigDragons, false, Lang::Ja);
+        assert!(!yaku.is_established);
+        assert_eq!(yaku.han, 0);
+        let yaku = yaku.established(13);
+        assert_eq!(yaku.name, "大三元");
+        assert!(yaku.is_established);
+        assert!(yaku.is_yakuman);
+        assert_eq!(yaku.yakuman_multiplier, 1);
+        assert_eq!(yaku.established(26).yakuman_multiplier, 2);
+    }
+
+    #[test]
+    fn test_check_keys_by_kind() {
+        let hand = Hand::from("123m456p789s11z55z 5z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let result = check(&analyzer, &hand, &Status::new(), &Settings::new()).unwrap();
+        assert_eq!(result.len(), Kind::COUNT);
+        assert!(result.windows(2).all(|w| w[0].kind < w[1].kind));
+        assert!(
+            find(&result, Kind::ValueHonourWhiteDragon)
+                .unwrap()
+                .is_established
+        );
+    }
+
+    #[test]
+    fn test_check_does_not_establish_nagashi_mangan() {
+        let hand = Hand::from("123m456p789s11z55z 5z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_nagashi_mangan = true;
+        let result = check(&analyzer, &hand, &status, &Settings::new()).unwrap();
+        assert!(!find(&result, Kind::NagashiMangan).unwrap().is_established);
+    }
+}
@@ -0,0 +1,329 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::HandAnalyzer;
+use crate::hand_info::identical_sequences::identical_sequences;
+use crate::hand_info::meld::MeldType;
+use crate::hand_info::status::Status;
+use crate::tile::{Dragon, Tile, TileType, Wind};
+use crate::winning_hand::name::Kind;
+
+/// 役が成立した根拠となる面子・対子・牌
+///
+/// UI で和了形の該当部分を強調表示したり、学習者に役の成立理由を示したりするために用いる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct YakuEvidence {
+    /// 役
+    pub kind: Kind,
+    /// 役を構成する面子・対子（牌種の並び、昇順）。状況役（立直・門前清自摸和など）では空
+    pub blocks: Vec<Vec<TileType>>,
+    /// 役に関わる牌種（重複なし・昇順）
+    pub tiles: Vec<TileType>,
+}
+
+impl YakuEvidence {
+    fn from_blocks(kind: Kind, mut blocks: Vec<Vec<TileType>>) -> YakuEvidence {
+        blocks.sort();
+        let mut tiles: Vec<TileType> = blocks.iter().flatten().copied().collect();
+        tiles.sort_unstable();
+        tiles.dedup();
+        YakuEvidence {
+            kind,
+            blocks,
+            tiles,
+        }
+    }
+}
+
+/// 和了形のうち、指定した役の根拠となる面子・牌を返す
+///
+/// `analyzer` は和了形の解析結果（`calculate_score`に渡したものと同じ）。
+/// 手牌全体で成立する役（断么九・清一色など）は全ての面子・対子を、
+/// 状況役は空の根拠を返す。
+pub fn explain_yaku(
+    analyzer: &HandAnalyzer,
+    hand: &Hand,
+    status: &Status,
+    kind: Kind,
+) -> YakuEvidence {
+    let sequences: Vec<[TileType; 3]> = analyzer.sequential3.iter().map(|s| s.get()).collect();
+    let triplets: Vec<[TileType; 3]> = analyzer.same3.iter().map(|s| s.get()).collect();
+    let pairs: Vec<[TileType; 2]> = analyzer.same2.iter().map(|s| s.get()).collect();
+
+    let blocks = match kind {
+        Kind::Pinfu
+        | Kind::AllInside
+        | Kind::CommonEnds
+        | Kind::PerfectEnds
+        | Kind::CommonTerminals
+        | Kind::CommonFlush
+        | Kind::PerfectFlush
+        | Kind::AllTriplets
+        | Kind::SevenPairs
+        | Kind::AllHonours
+        | Kind::PerfectTerminals
+        | Kind::AllGreen
+        | Kind::NineGates
+        | Kind::FourConcealedTriplets
+        | Kind::FourConcealedTripletsPairWait => all_blocks(&sequences, &triplets, &pairs),
+        Kind::ThirteenOrphans => {
+            let mut tiles: Vec<TileType> = hand.tiles().iter().map(|t| t.get()).collect();
+            tiles.extend(hand.drawn().map(|t| t.get()));
+            tiles.sort_unstable();
+            tiles.dedup();
+            return YakuEvidence {
+                kind,
+                blocks: Vec::new(),
+                tiles,
+            };
+        }
+        Kind::TwinSequences | Kind::DoubleTwinSequences => identical_sequence_blocks(&sequences),
+        Kind::MixedSequences => mixed_sequences(&sequences),
+        Kind::FullStraight => full_straight(&sequences),
+        Kind::MixedTriplets => mixed_triplets(&triplets),
+        Kind::ThreeConcealedTriplets => concealed_triplets(&triplets, hand, status),
+        Kind::ValueHonourSeatWind => honour_triplets(&triplets, |t| {
+            Wind::is_tile_type(t) == Some(status.seat_wind)
+        }),
+        Kind::ValueHonourRoundWind => honour_triplets(&triplets, |t| {
+            Wind::is_tile_type(t) == Some(status.round_wind)
+        }),
+        Kind::ValueHonourWhiteDragon => honour_triplets(&triplets, |t| {
+            Dragon::is_tile_type(t) == Some(Dragon::White)
+        }),
+        Kind::ValueHonourGreenDragon => honour_triplets(&triplets, |t| {
+            Dragon::is_tile_type(t) == Some(Dragon::Green)
+        }),
+        Kind::ValueHonourRedDragon => {
+            honour_triplets(&triplets, |t| Dragon::is_tile_type(t) == Some(Dragon::Red))
+        }
+        Kind::LittleDragons | Kind::BigDragons => {
+            honour_blocks(&triplets, &pairs, |t| Dragon::is_tile_type(t).is_some())
+        }
+        Kind::LittleWinds | Kind::BigWinds => {
+            honour_blocks(&triplets, &pairs, |t| Wind::is_tile_type(t).is_some())
+        }
+        Kind::FourQuads => hand
+            .melds()
+            .iter()
+            .filter(|m| m.category.is_kan())
+            .map(|m| m.tiles.iter().map(|t| t.get()).collect())
+            .collect(),
+        _ => Vec::new(),
+    };
+    YakuEvidence::from_blocks(kind, blocks)
+}
+
+fn all_blocks(
+    sequences: &[[TileType; 3]],
+    triplets: &[[TileType; 3]],
+    pairs: &[[TileType; 2]],
+) -> Vec<Vec<TileType>> {
+    sequences
+        .iter()
+        .chain(triplets)
+        .map(|b| b.to_vec())
+        .chain(pairs.iter().map(|p| p.to_vec()))
+        .collect()
+}
+
+/// 同じ順子の組（一盃口は1組、二盃口は2組）
+fn identical_sequence_blocks(sequences: &[[TileType; 3]]) -> Vec<Vec<TileType>> {
+    identical_sequences(sequences)
+        .pairs
+        .iter()
+        .flat_map(|s| [s.to_vec(), s.to_vec()])
+        .collect()
+}
+
+/// 三色同順を構成する3つの順子
+fn mixed_sequences(sequences: &[[TileType; 3]]) -> Vec<Vec<TileType>> {
+    for start in 0..7 {
+        let found: Vec<[TileType; 3]> = (0..3)
+            .filter_map(|suit| sequences.iter().find(|s| s[0] == suit * 9 + start).copied())
+            .collect();
+        if found.len() == 3 {
+            return found.iter().map(|s| s.to_vec()).collect();
+        }
+    }
+    Vec::new()
+}
+
+/// 一気通貫を構成する3つの順子
+fn full_straight(sequences: &[[TileType; 3]]) -> Vec<Vec<TileType>> {
+    for suit in 0..3 {
+        let found: Vec<[TileType; 3]> = [0, 3, 6]
+            .iter()
+            .filter_map(|&start| sequences.iter().find(|s| s[0] == suit * 9 + start).copied())
+            .collect();
+        if found.len() == 3 {
+            return found.iter().map(|s| s.to_vec()).collect();
+        }
+    }
+    Vec::new()
+}
+
+/// 三色同刻を構成する3つの刻子
+fn mixed_triplets(triplets: &[[TileType; 3]]) -> Vec<Vec<TileType>> {
+    for num in 1..=9 {
+        let found: Vec<[TileType; 3]> = triplets
+            .iter()
+            .filter(|t| Tile::new(t[0]).number() == Some(num))
+            .copied()
+            .collect();
+        if found.len() == 3 {
+            return found.iter().map(|t| t.to_vec()).collect();
+        }
+    }
+    Vec::new()
+}
+
+/// 暗刻（ポン・明槓した刻子と、ロンで完成した刻子を除く）
+///
+/// 三暗刻の判定と同じく、ロン和了では和了牌の刻子を明刻として扱う。
+fn concealed_triplets(
+    triplets: &[[TileType; 3]],
+    hand: &Hand,
+    status: &Status,
+) -> Vec<Vec<TileType>> {
+    let mut open: Vec<TileType> = hand
+        .melds()
+        .iter()
+        .filter(|m| m.category != MeldType::Chi && !m.is_concealed())
+        .map(|m| m.tiles[0].get())
+        .collect();
+    if !status.is_self_drawn
+        && let Some(winning_tile) = hand.drawn()
+        && !open.contains(&winning_tile.get())
+    {
+        open.push(winning_tile.get());
+    }
+
+    let mut blocks = Vec::new();
+    for triplet in triplets {
+        if let Some(pos) = open.iter().position(|&t| t == triplet[0]) {
+            open.remove(pos);
+            continue;
+        }
+        blocks.push(triplet.to_vec());
+    }
+    blocks
+}
+
+/// 条件を満たす字牌の刻子
+fn honour_triplets(
+    triplets: &[[TileType; 3]],
+    is_target: impl Fn(TileType) -> bool,
+) -> Vec<Vec<TileType>> {
+    triplets
+        .iter()
+        .filter(|t| is_target(t[0]))
+        .map(|t| t.to_vec())
+        .collect()
+}
+
+/// 条件を満たす字牌の刻子と雀頭
+fn honour_blocks(
+    triplets: &[[TileType; 3]],
+    pairs: &[[TileType; 2]],
+    is_target: impl Fn(TileType) -> bool,
+) -> Vec<Vec<TileType>> {
+    let mut blocks = honour_triplets(triplets, &is_target);
+    blocks.extend(pairs.iter().filter(|p| is_target(p[0])).map(|p| p.to_vec()));
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::score::calculate_score;
+    use crate::settings::Settings;
+
+    fn evidence(hand_str: &str, status: &Status, kind: Kind) -> YakuEvidence {
+        let hand = Hand::from(hand_str);
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        explain_yaku(&analyzer, &hand, status, kind)
+    }
+
+    #[test]
+    fn mixed_sequences_test() {
+        let e = evidence(
+            "234m234p234s678m5z 5z",
+            &Status::new(),
+            Kind::MixedSequences,
+        );
+        assert_eq!(
+            e.blocks,
+            vec![
+                vec![Tile::M2, Tile::M3, Tile::M4],
+                vec![Tile::P2, Tile::P3, Tile::P4],
+                vec![Tile::S2, Tile::S3, Tile::S4],
+            ]
+        );
+    }
+
+    #[test]
+    fn full_straight_test() {
+        let e = evidence("123456789p11z23s 4s", &Status::new(), Kind::FullStraight);
+        assert_eq!(e.blocks.len(), 3);
+        assert_eq!(e.tiles, (Tile::P1..=Tile::P9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn value_honour_test() {
+        let e = evidence(
+            "123m456p789s55z77z 7z",
+            &Status::new(),
+            Kind::ValueHonourRedDragon,
+        );
+        assert_eq!(e.blocks, vec![vec![Tile::Z7; 3]]);
+        // 状況役は根拠を持たない
+        let e = evidence("123m456p789s55z77z 7z", &Status::new(), Kind::Riichi);
+        assert!(e.blocks.is_empty());
+    }
+
+    #[test]
+    fn three_concealed_triplets_excludes_ron_triplet() {
+        let mut status = Status::new();
+        status.is_self_drawn = false;
+        let e = evidence(
+            "111m222p456s33s99m 9m",
+            &status,
+            Kind::ThreeConcealedTriplets,
+        );
+        // ロンで完成した 9m は暗刻に含めない
+        assert_eq!(e.blocks, vec![vec![Tile::M1; 3], vec![Tile::P2; 3]]);
+
+        status.is_self_drawn = true;
+        let e = evidence(
+            "111m222p456s33s99m 9m",
+            &status,
+            Kind::ThreeConcealedTriplets,
+        );
+        assert_eq!(e.blocks.len(), 3);
+    }
+
+    #[test]
+    fn yaku_evidence_from_score_result() {
+        let hand = Hand::from("234m234p234s678m5z 5z");
+        let analyzer = HandAnalyzer::new(&hand).unwrap();
+        let mut status = Status::new();
+        status.is_self_drawn = true;
+        let score = calculate_score(&analyzer, &hand, &status, &Settings::new())
+            .unwrap()
+            .unwrap();
+        let evidence = score.yaku_evidence(&analyzer, &hand, &status);
+        let mixed = evidence
+            .iter()
+            .find(|e| e.kind == Kind::MixedSequences)
+            .unwrap();
+        assert_eq!(mixed.blocks.len(), 3);
+        let tsumo = evidence
+            .iter()
+            .find(|e| e.kind == Kind::FullyConcealedHand)
+            .unwrap();
+        assert!(tsumo.tiles.is_empty());
+    }
+}
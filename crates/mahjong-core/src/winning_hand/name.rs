@@ -1,3 +1,4 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 
@@ -5,6 +6,7 @@ use crate::settings::Lang;
 
 /// 和了時の手牌の形態
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Form {
     /// 七対子
     SevenPairs,
@@ -18,20 +20,8 @@ pub enum Form {
 ///
 /// 英語名は WRC Rules 2025 に準拠する（docs/glossary.md を参照）
 /// ここでの定義順で同翻役のリザルト画面の役の表示順も決定する
-#[derive(
-    Debug,
-    Clone,
-    Copy,
-    PartialEq,
-    Eq,
-    Hash,
-    PartialOrd,
-    Ord,
-    EnumCountMacro,
-    EnumIter,
-    Serialize,
-    Deserialize,
-)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumCountMacro, EnumIter)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Kind {
     /// 立直
     Riichi,
@@ -119,6 +109,14 @@ pub enum Kind {
     BlessingOfHeaven,
     /// 地和
     BlessingOfEarth,
+    /// 三連刻（ローカル役）
+    ThreeConsecutiveTriplets,
+    /// 一色三順（ローカル役）
+    PureTripleSequences,
+    /// 四連刻（ローカル役）
+    FourConsecutiveTriplets,
+    /// 大車輪（ローカル役）
+    BigWheels,
 }
 
 /// 和了役の名前を返す
@@ -259,6 +257,16 @@ fn get_en(hand_kind: Kind, has_openned: bool) -> &'static str {
         Kind::BlessingOfHeaven => "Blessing of Heaven",
         // 地和
         Kind::BlessingOfEarth => "Blessing of Earth",
+        // 三連刻
+        Kind::ThreeConsecutiveTriplets => "Three Consecutive Triplets",
+        // 一色三順
+        Kind::PureTripleSequences => {
+            openned_name!("Pure Triple Sequences", has_openned, Lang::En)
+        }
+        // 四連刻
+        Kind::FourConsecutiveTriplets => "Four Consecutive Triplets",
+        // 大車輪
+        Kind::BigWheels => "Big Wheels",
     }
 }
 
@@ -362,6 +370,14 @@ fn get_ja(hand_kind: Kind, has_openned: bool) -> &'static str {
         Kind::BlessingOfHeaven => "天和",
         // 地和
         Kind::BlessingOfEarth => "地和",
+        // 三連刻
+        Kind::ThreeConsecutiveTriplets => "三連刻",
+        // 一色三順
+        Kind::PureTripleSequences => openned_name!("一色三順", has_openned, Lang::Ja),
+        // 四連刻
+        Kind::FourConsecutiveTriplets => "四連刻",
+        // 大車輪
+        Kind::BigWheels => "大車輪",
     }
 }
 
@@ -432,6 +448,10 @@ mod tests {
             (Kind::FourQuads, "Four Quads"),
             (Kind::BlessingOfHeaven, "Blessing of Heaven"),
             (Kind::BlessingOfEarth, "Blessing of Earth"),
+            (Kind::ThreeConsecutiveTriplets, "Three Consecutive Triplets"),
+            (Kind::PureTripleSequences, "Pure Triple Sequences"),
+            (Kind::FourConsecutiveTriplets, "Four Consecutive Triplets"),
+            (Kind::BigWheels, "Big Wheels"),
         ];
         for (kind, expected) in cases {
             let label = format!("{kind:?}");
@@ -450,6 +470,7 @@ mod tests {
             (Kind::PerfectEnds, "Perfect Ends (Open)"),
             (Kind::CommonFlush, "Common Flush (Open)"),
             (Kind::PerfectFlush, "Perfect Flush (Open)"),
+            (Kind::PureTripleSequences, "Pure Triple Sequences (Open)"),
         ];
         for (kind, expected) in cases {
             let label = format!("{kind:?}");
@@ -556,6 +577,10 @@ mod tests {
             (Kind::FourQuads, "四槓子"),
             (Kind::BlessingOfHeaven, "天和"),
             (Kind::BlessingOfEarth, "地和"),
+            (Kind::ThreeConsecutiveTriplets, "三連刻"),
+            (Kind::PureTripleSequences, "一色三順"),
+            (Kind::FourConsecutiveTriplets, "四連刻"),
+            (Kind::BigWheels, "大車輪"),
         ];
         for (kind, expected) in cases {
             let label = format!("{kind:?}");
@@ -574,6 +599,7 @@ mod tests {
             (Kind::PerfectEnds, "純全帯么九（鳴）"),
             (Kind::CommonFlush, "混一色（鳴）"),
             (Kind::PerfectFlush, "清一色（鳴）"),
+            (Kind::PureTripleSequences, "一色三順（鳴）"),
         ];
         for (kind, expected) in cases {
             let label = format!("{kind:?}");
@@ -0,0 +1,141 @@
+//! 聴牌している手牌について、和了牌ごとに成立する役を求める
+//!
+//! 役のない待ち（形式聴牌）をUIで警告する用途を想定する。
+//! 和了牌を仮にツモ牌として置き、ロン和了・ツモ和了それぞれについて `checker::check` で判定する。
+//! ドラは役ではないため含めない。
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::hand::Hand;
+use crate::hand_info::hand_analyzer::HandAnalyzer;
+use crate::hand_info::status::Status;
+use crate::settings::Settings;
+use crate::tile::Tile;
+use crate::winning_hand::checker;
+use crate::winning_hand::name::Kind;
+
+/// 1つの和了牌について成立する役
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct WaitYaku {
+    /// 和了牌
+    pub tile: Tile,
+    /// ロン和了で成立する役と翻数（役の定義順）
+    pub ron: Vec<(Kind, u32)>,
+    /// ツモ和了で成立する役と翻数（役の定義順）
+    pub tsumo: Vec<(Kind, u32)>,
+}
+
+impl WaitYaku {
+    /// ロン和了できるか（役があるか）
+    pub fn can_ron(&self) -> bool {
+        !self.ron.is_empty()
+    }
+
+    /// ツモ和了できるか（役があるか）
+    pub fn can_tsumo(&self) -> bool {
+        !self.tsumo.is_empty()
+    }
+
+    /// ロンでもツモでも役がないか
+    pub fn is_yakuless(&self) -> bool {
+        !self.can_ron() && !self.can_tsumo()
+    }
+}
+
+/// 聴牌している手牌の和了牌ごとに、成立する役を返す（和了牌の昇順）
+///
+/// - `hand`: ツモ牌のない聴牌形の手牌
+/// - `status`: 局の状態（`is_self_drawn` は和了方法ごとに上書きする）
+/// - `settings`: ルール設定
+///
+/// 面子の取り方が複数ある場合は、翻数の合計が最も大きくなる取り方の役を返す。
+/// 聴牌していない手牌では空を返す。
+pub fn yaku_per_wait(hand: &Hand, status: &Status, settings: &Settings) -> Result<Vec<WaitYaku>> {
+    let mut result = Vec::new();
+    for tile in HandAnalyzer::waits(hand) {
+        let mut completed = hand.clone();
+        completed.set_drawn(Some(tile));
+        result.push(WaitYaku {
+            tile,
+            ron: best_yaku(&completed, status, false, settings)?,
+            tsumo: best_yaku(&completed, status, true, settings)?,
+        });
+    }
+    Ok(result)
+}
+
+/// 和了形の手牌について、翻数の合計が最も大きい面子の取り方で成立する役を返す
+fn best_yaku(
+    hand: &Hand,
+    status: &Status,
+    is_self_drawn: bool,
+    settings: &Settings,
+) -> Result<Vec<(Kind, u32)>> {
+    let mut status = status.clone();
+    status.is_self_drawn = is_self_drawn;
+
+    let mut best: Vec<(Kind, u32)> = Vec::new();
+    for analyzer in HandAnalyzer::all_decompositions(hand)? {
+        if !analyzer.shanten.has_won() {
+            continue;
+        }
+        let yaku = checker::check(&analyzer, hand, &status, settings)?;
+        let mut list: Vec<(Kind, u32)> = yaku
+            .iter()
+            .filter(|y| y.is_established && y.han > 0)
+            .map(|y| (y.kind, y.han))
+            .collect();
+        list.sort();
+        let han = |list: &[(Kind, u32)]| list.iter().map(|&(_, h)| h).sum::<u32>();
+        if han(&list) > han(&best) {
+            best = list;
+        }
+    }
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Wind;
+
+    fn status() -> Status {
+        let mut status = Status::new();
+        status.seat_wind = Wind::South;
+        status.round_wind = Wind::East;
+        status
+    }
+
+    #[test]
+    fn test_yaku_per_wait_yakuless_ron() {
+        // 123m 456p 789s 東東 北北 のシャンポン待ち（東は場風、北は客風）
+        let hand = Hand::from("123m456p789s1144z");
+        let waits = yaku_per_wait(&hand, &status(), &Settings::new()).unwrap();
+        assert_eq!(waits.len(), 2);
+
+        // 東: 場風の役牌が成立
+        let east = &waits[0];
+        assert_eq!(east.tile, Tile::new(Tile::Z1));
+        assert!(east.ron.contains(&(Kind::ValueHonourRoundWind, 1)));
+
+        // 北: ロンでは役がなく、ツモなら門前清自摸和
+        let north = &waits[1];
+        assert_eq!(north.tile, Tile::new(Tile::Z4));
+        assert!(!north.can_ron());
+        assert_eq!(north.tsumo, vec![(Kind::FullyConcealedHand, 1)]);
+        assert!(!north.is_yakuless());
+    }
+
+    #[test]
+    fn test_yaku_per_wait_not_tenpai() {
+        let hand = Hand::from("147m258p369s1234z");
+        assert!(
+            yaku_per_wait(&hand, &status(), &Settings::new())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
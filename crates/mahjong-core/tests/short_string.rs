@@ -0,0 +1,123 @@
+//! 手牌の省略表記（`Hand::to_short_string`）の性質テスト
+//!
+//! 副露・赤5・ツモ牌の有無を含む任意の手牌について、省略表記を `Hand::from` で
+//! 読み戻すと元の手牌に戻ることを確認する。
+
+use mahjong_core::hand::Hand;
+use mahjong_core::hand_info::meld::{Meld, MeldFrom, MeldType};
+use mahjong_core::tile::{Tile, TileType};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// 5の牌なら赤ドラにもする
+fn tile(tile_type: TileType, red: bool) -> Tile {
+    if red && matches!(tile_type, Tile::M5 | Tile::P5 | Tile::S5) {
+        Tile::new_red(tile_type)
+    } else {
+        Tile::new(tile_type)
+    }
+}
+
+fn from_other() -> impl Strategy<Value = MeldFrom> {
+    prop_oneof![
+        Just(MeldFrom::Previous),
+        Just(MeldFrom::Opposite),
+        Just(MeldFrom::Following),
+    ]
+}
+
+/// 手牌文字列で表せる副露（チー・ポン・暗カン・大明カン）
+fn meld() -> impl Strategy<Value = Meld> {
+    prop_oneof![
+        (0..3u32, 0..7u32, 0..3usize).prop_map(|(suit, start, called)| {
+            let tiles: Vec<Tile> = (0..3).map(|i| Tile::new(suit * 9 + start + i)).collect();
+            Meld {
+                called_tile: Some(tiles[called]),
+                tiles,
+                category: MeldType::Chi,
+                from: MeldFrom::Previous,
+            }
+        }),
+        (0..34u32, from_other()).prop_map(|(t, from)| Meld {
+            tiles: vec![Tile::new(t); 3],
+            category: MeldType::Pon,
+            from,
+            called_tile: Some(Tile::new(t)),
+        }),
+        (0..34u32).prop_map(|t| Meld {
+            tiles: vec![Tile::new(t); 3],
+            category: MeldType::Kan,
+            from: MeldFrom::Myself,
+            called_tile: None,
+        }),
+        (0..34u32, from_other()).prop_map(|(t, from)| Meld {
+            tiles: vec![Tile::new(t); 3],
+            category: MeldType::Kan,
+            from,
+            called_tile: Some(Tile::new(t)),
+        }),
+    ]
+}
+
+/// 副露を除いて13枚（ツモ牌があれば14枚）の、同じ牌が4枚以下の手牌
+fn hand() -> impl Strategy<Value = Hand> {
+    (
+        vec(meld(), 0..=4),
+        vec((0..34u32, any::<bool>()), 14),
+        any::<bool>(),
+    )
+        .prop_map(|(melds, tiles, has_drawn)| {
+            let len = 13 - 3 * melds.len() + usize::from(has_drawn);
+            let mut tiles: Vec<Tile> = tiles[..len].iter().map(|&(t, r)| tile(t, r)).collect();
+            let drawn = if has_drawn { tiles.pop() } else { None };
+            Hand::new_with_melds(tiles, melds, drawn)
+        })
+        .prop_filter("at most four copies of a tile", |hand| {
+            hand.validate().is_ok()
+        })
+}
+
+proptest! {
+    #[test]
+    fn short_string_round_trips(hand in hand()) {
+        let text = hand.to_short_string();
+        let restored = Hand::from(&text);
+
+        let mut expected = hand.tiles().to_vec();
+        expected.sort();
+        let mut actual = restored.tiles().to_vec();
+        actual.sort();
+        prop_assert_eq!(actual, expected, "{}", text);
+        prop_assert_eq!(restored.drawn(), hand.drawn(), "{}", text);
+        prop_assert_eq!(restored.melds().len(), hand.melds().len(), "{}", text);
+        for (a, b) in restored.melds().iter().zip(hand.melds()) {
+            let mut a_tiles = a.expanded_tiles();
+            a_tiles.sort();
+            let mut b_tiles = b.expanded_tiles();
+            b_tiles.sort();
+            prop_assert_eq!(a_tiles, b_tiles, "{}", text);
+            prop_assert_eq!(a.category, b.category, "{}", text);
+            prop_assert_eq!(a.from, b.from, "{}", text);
+            prop_assert_eq!(a.called_tile, b.called_tile, "{}", text);
+        }
+        prop_assert_eq!(restored.to_short_string(), text);
+    }
+}
+
+#[test]
+fn single_tile_and_unsorted_input() {
+    let hand = Hand::new(vec![Tile::new(Tile::Z1)], None);
+    assert_eq!(hand.to_short_string(), "1z");
+
+    let hand = Hand::new(
+        vec![
+            Tile::new(Tile::S3),
+            Tile::new(Tile::M1),
+            Tile::new_red(Tile::P5),
+            Tile::new(Tile::M2),
+            Tile::new(Tile::S1),
+        ],
+        Some(Tile::new_red(Tile::M5)),
+    );
+    assert_eq!(hand.to_short_string(), "12m0p13s 0m");
+}
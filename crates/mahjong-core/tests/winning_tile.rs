@@ -0,0 +1,85 @@
+//! 和了牌を指定した解析（`HandAnalyzer::new_with_winning_tile`）の結合テスト
+//!
+//! 七対子にも二盃口にも取れる手などで、和了形・待ち・符が同じ解釈から求まることを確認する。
+
+use mahjong_core::hand::Hand;
+use mahjong_core::hand_info::hand_analyzer::HandAnalyzer;
+use mahjong_core::hand_info::status::Status;
+use mahjong_core::hand_info::wait::WaitShape;
+use mahjong_core::settings::Settings;
+use mahjong_core::tile::{Tile, TileType};
+use mahjong_core::winning_hand::name::Form;
+use rstest::rstest;
+
+fn status(is_self_drawn: bool) -> Status {
+    let builder = Status::builder();
+    let builder = if is_self_drawn {
+        builder.tsumo()
+    } else {
+        builder.ron()
+    };
+    builder.build().unwrap()
+}
+
+#[rstest]
+// 二盃口・七対子の単騎: 通常形の単騎待ちとして符を付ける
+#[case("112233m445566p7s", Tile::S7, false, Form::Normal, vec![WaitShape::Tanki], 40)]
+#[case("112233m445566p7s", Tile::S7, true, Form::Normal, vec![WaitShape::Tanki], 30)]
+// 二盃口・七対子に取れる両面待ち: 平和形として符を付ける
+#[case("223344m45566p77s", Tile::P4, false, Form::Normal, vec![WaitShape::Ryanmen], 30)]
+#[case("223344m45566p77s", Tile::P4, true, Form::Normal, vec![WaitShape::Ryanmen], 20)]
+// 七対子にしか取れない手
+#[case("1133m5577p99s1122z", Tile::Z2, false, Form::SevenPairs, vec![WaitShape::SevenPairs], 25)]
+// 双碰待ちのロン（明刻として扱う）
+#[case("123m456p789s2233s", Tile::S2, false, Form::Normal, vec![WaitShape::Shanpon], 40)]
+// 国士無双十三面待ち（役満のため符はない）
+#[case(
+    "19m19p19s1234567z",
+    Tile::M1,
+    true,
+    Form::ThirteenOrphans,
+    vec![WaitShape::ThirteenOrphansThirteenSided],
+    0
+)]
+fn resolves_form_wait_and_fu_together(
+    #[case] hand: &str,
+    #[case] winning_tile: TileType,
+    #[case] is_self_drawn: bool,
+    #[case] form: Form,
+    #[case] wait_shapes: Vec<WaitShape>,
+    #[case] fu: u32,
+) {
+    let win = HandAnalyzer::new_with_winning_tile(
+        &Hand::from(hand),
+        Tile::new(winning_tile),
+        &status(is_self_drawn),
+        &Settings::new(),
+    )
+    .unwrap();
+    assert_eq!(win.analyzer.form, form);
+    assert_eq!(win.wait_shapes, wait_shapes);
+    assert_eq!(win.fu.total, fu);
+    assert_eq!(win.hand.drawn(), Some(Tile::new(winning_tile)));
+}
+
+#[test]
+fn rejects_hand_with_drawn_tile() {
+    let result = HandAnalyzer::new_with_winning_tile(
+        &Hand::from("112233m445566p7s 7s"),
+        Tile::new(Tile::S7),
+        &status(false),
+        &Settings::new(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn rejects_tile_that_does_not_complete_the_hand() {
+    let result = HandAnalyzer::new_with_winning_tile(
+        &Hand::from("112233m445566p7s"),
+        Tile::new(Tile::S8),
+        &status(false),
+        &Settings::new(),
+    );
+    assert!(result.is_err());
+}
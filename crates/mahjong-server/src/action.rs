@@ -1 +1,57 @@
-// プレイヤーのアクション定義
+//! プレイヤーのアクション定義
+//!
+//! 局の状態から求めた「ある座席がいま取れる行動」（`Round::legal_actions`）を表す。
+//! UI や CPU はここから選んだ行動を `ClientAction` に変換して送ればよく、
+//! 打牌・リーチ・カン・鳴きの可否を自前で判定する必要はない。
+
+use mahjong_core::tile::{Tile, TileType};
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::ClientAction;
+
+/// 合法な行動
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// 打牌（Noneならツモ切り）
+    Discard(Option<Tile>),
+    /// リーチ宣言と宣言牌の打牌（Noneならツモ切りリーチ）
+    Riichi(Option<Tile>),
+    /// ツモ和了
+    Tsumo,
+    /// 暗カン（カンする牌の種類）
+    Ankan(TileType),
+    /// 加カン（カンする牌の種類）
+    Kakan(TileType),
+    /// 九種九牌の選択（true=流局, false=続行）
+    NineTerminals { declare: bool },
+    /// ロン和了
+    Ron,
+    /// ポン（手牌から使う2枚）
+    Pon([Tile; 2]),
+    /// チー（手牌から使う2枚）
+    Chi([Tile; 2]),
+    /// 大明カン（カンする牌の種類）
+    Daiminkan(TileType),
+    /// 鳴き・ロンをしない
+    Pass,
+}
+
+impl From<Action> for ClientAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Discard(tile) => ClientAction::Discard { tile },
+            Action::Riichi(tile) => ClientAction::Riichi { tile },
+            Action::Tsumo => ClientAction::Tsumo,
+            Action::Ankan(tile_type) | Action::Kakan(tile_type) | Action::Daiminkan(tile_type) => {
+                ClientAction::Kan {
+                    tile_index: tile_type as usize,
+                }
+            }
+            Action::NineTerminals { declare } => ClientAction::NineTerminals { declare },
+            Action::Ron => ClientAction::Ron,
+            Action::Pon(tiles) => ClientAction::Pon { tiles },
+            Action::Chi(tiles) => ClientAction::Chi { tiles },
+            Action::Pass => ClientAction::Pass,
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! 外部エージェント（ボット）向けのプレイヤー抽象
+//!
+//! 各座席を [`Bot`] トレイトのオブジェクトとして渡すと、[`run_round`]・[`run_game`] が
+//! 行動を求めるたびに [`GameView`] を作って `choose_action` を呼び、局・半荘を進める。
+//! [`GameView`] には自分の手牌と公開情報（河・副露・点数・ドラ表示牌）だけを入れ、
+//! 他家の手牌や牌山は渡さないため、ボットが不正に情報を得ることはできない。
+//!
+//! 行動を future で返すボットは、`tokio` フィーチャーの
+//! [`async_driver`](crate::async_driver) で `AsyncBot` として座席に割り当てる。
+
+use mahjong_core::hand::Hand;
+use mahjong_core::hand_info::meld::Meld;
+use mahjong_core::tile::{Tile, Wind};
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::cpu::baseline::BaselineBot;
+use crate::player::Discard;
+use crate::round::{Round, TurnPhase};
+use crate::table::Table;
+
+/// 1つの座席から見た局面
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameView {
+    /// 自分の座席（0-3）
+    pub seat: usize,
+    /// 自分の自風
+    pub seat_wind: Wind,
+    /// 場風
+    pub round_wind: Wind,
+    /// 親の座席
+    pub dealer: usize,
+    /// 手番の座席
+    pub current_player: usize,
+    /// 自分の手牌（ツモ牌・副露を含む）
+    pub hand: Hand,
+    /// 各座席の副露
+    pub melds: [Vec<Meld>; 4],
+    /// 各座席の河
+    pub discards: [Vec<Discard>; 4],
+    /// 各座席がリーチしているか
+    pub riichi: [bool; 4],
+    /// 各座席の持ち点
+    pub scores: [i32; 4],
+    /// 公開済みのドラ表示牌
+    pub dora_indicators: Vec<Tile>,
+    /// 山の残り枚数
+    pub remaining_tiles: usize,
+    /// 本場数
+    pub honba: usize,
+    /// 供託リーチ棒の本数
+    pub riichi_sticks: usize,
+    /// いま取れる行動
+    pub legal_actions: Vec<Action>,
+}
+
+/// 行動を決めるボット
+pub trait Bot {
+    /// 行動が求められたときに呼ばれ、`view.legal_actions` の中から1つを返す
+    ///
+    /// 合法でない行動を返した場合は既定の行動（ツモ切り/パス/続行）で代替する。
+    fn choose_action(&mut self, view: &GameView) -> Action;
+}
+
+impl Bot for BaselineBot {
+    fn choose_action(&mut self, view: &GameView) -> Action {
+        BaselineBot::choose_action(
+            self,
+            &view.hand,
+            view.seat_wind,
+            view.round_wind,
+            &view.legal_actions,
+        )
+        .unwrap_or_else(|| default_action(&view.legal_actions))
+    }
+}
+
+impl Round {
+    /// 指定した座席から見た局面を返す（他家の手牌は含まない）
+    pub fn view(&self, seat: usize) -> GameView {
+        GameView {
+            seat,
+            seat_wind: self.players[seat].seat_wind,
+            round_wind: self.round_wind,
+            dealer: self.dealer,
+            current_player: self.current_player,
+            hand: self.players[seat].hand.clone(),
+            melds: std::array::from_fn(|i| self.players[i].hand.melds().to_vec()),
+            discards: std::array::from_fn(|i| self.players[i].discards.clone()),
+            riichi: std::array::from_fn(|i| self.players[i].is_riichi()),
+            scores: self.get_scores(),
+            dora_indicators: self.wall.dora_indicators(),
+            remaining_tiles: self.wall.remaining(),
+            honba: self.honba,
+            riichi_sticks: self.riichi_sticks,
+            legal_actions: self.legal_actions(seat),
+        }
+    }
+}
+
+/// 合法でない行動を返されたときの既定の行動（ツモ切り/パス/続行）
+fn default_action(legal: &[Action]) -> Action {
+    [
+        Action::Discard(None),
+        Action::Pass,
+        Action::NineTerminals { declare: false },
+    ]
+    .into_iter()
+    .find(|a| legal.contains(a))
+    .or_else(|| legal.first().cloned())
+    .unwrap_or(Action::Pass)
+}
+
+/// 次に行動を求める座席と、その座席から見た局面を返す
+///
+/// ツモは自動で行う。局が終わっていれば`None`を返す。
+fn next_decision(table: &mut Table) -> Option<GameView> {
+    loop {
+        let round = table.current_round_mut()?;
+        if round.is_over() {
+            return None;
+        }
+        if round.phase == TurnPhase::Draw {
+            round.do_draw();
+            continue;
+        }
+        let seat = (0..4).find(|&seat| !round.legal_actions(seat).is_empty())?;
+        return Some(round.view(seat));
+    }
+}
+
+/// 選ばれた行動を適用する（受け付けられなければ既定の行動で代替する）
+///
+/// 既定の行動も受け付けられなかった場合は`false`を返す。
+fn apply_decision(table: &mut Table, view: &GameView, action: Action) -> bool {
+    if view.legal_actions.contains(&action) && table.handle_action(view.seat, action.clone().into())
+    {
+        return true;
+    }
+    let fallback = default_action(&view.legal_actions);
+    fallback != action && table.handle_action(view.seat, fallback.into())
+}
+
+/// 進行中の局を4人のボットで終局まで進める
+///
+/// 局の後処理（`Table::finish_round`）は行わない。
+/// 選ばれた行動も既定の行動も受け付けられず局を進められなくなった場合は、
+/// 無限ループを避けるためにその場で止めて`false`を返す。
+pub fn run_round(table: &mut Table, players: &mut [Box<dyn Bot>; 4]) -> bool {
+    while let Some(view) = next_decision(table) {
+        let action = players[view.seat].choose_action(&view);
+        let accepted = apply_decision(table, &view, action);
+        table.drain_events();
+        if !accepted {
+            return false;
+        }
+    }
+    true
+}
+
+/// 半荘を4人のボットで終了まで進める
+///
+/// 局を進められなくなった場合（[`run_round`]）は途中で止めて`false`を返す。
+pub fn run_game(table: &mut Table, players: &mut [Box<dyn Bot>; 4]) -> bool {
+    while !table.is_game_over {
+        table.start_round();
+        if !run_round(table, players) {
+            return false;
+        }
+        table.finish_round();
+    }
+    true
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::GameSettings;
+    use mahjong_core::settings::Settings;
+
+    fn baseline_players() -> [Box<dyn Bot>; 4] {
+        std::array::from_fn(|_| Box::new(BaselineBot::new(Settings::new())) as Box<dyn Bot>)
+    }
+
+    /// 常に合法でない行動を返すプレイヤー
+    struct Stubborn;
+
+    impl Bot for Stubborn {
+        fn choose_action(&mut self, _view: &GameView) -> Action {
+            Action::Kakan(0)
+        }
+    }
+
+    #[test]
+    fn view_hides_other_hands() {
+        let mut round =
+            Round::new_with_seed(1, Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+        round.do_draw();
+        let view = round.view(0);
+        assert_eq!(view.hand.tiles(), round.players[0].hand.tiles());
+        assert!(view.hand.drawn().is_some());
+        assert!(!view.legal_actions.is_empty());
+        // 他家の視点では手番の行動はなく、自分の手牌しか見えない
+        let other = round.view(1);
+        assert!(other.legal_actions.is_empty());
+        assert_eq!(other.hand.tiles(), round.players[1].hand.tiles());
+        let json = serde_json::to_string(&other).unwrap();
+        assert!(!json.contains("\"wall\""));
+    }
+
+    #[test]
+    fn run_game_with_baseline_bots() {
+        let mut table = Table::new(GameSettings::default());
+        assert!(run_game(&mut table, &mut baseline_players()));
+        assert!(table.is_game_over);
+        assert!(table.round.is_none());
+    }
+
+    #[test]
+    fn illegal_actions_fall_back_to_default() {
+        let mut table = Table::new(GameSettings::default());
+        table.start_round_with_seed(9);
+        let mut players: [Box<dyn Bot>; 4] =
+            std::array::from_fn(|_| Box::new(Stubborn) as Box<dyn Bot>);
+        assert!(run_round(&mut table, &mut players));
+        assert!(table.current_round().unwrap().is_over());
+    }
+
+    #[test]
+    fn rejected_fallback_is_reported() {
+        let mut table = Table::new(GameSettings::default());
+        table.start_round_with_seed(9);
+        table.current_round_mut().unwrap().do_draw();
+        // 手番でない座席の局面では既定の行動（パス）も受け付けられない
+        let view = table.current_round().unwrap().view(1);
+        assert!(!apply_decision(&mut table, &view, Action::Kakan(0)));
+    }
+}
@@ -0,0 +1,370 @@
+//! 非同期ゲームドライバー（`tokio` フィーチャー）
+//!
+//! [`GameDriver`] をラップし、CPU以外の座席の行動を future として待つゲームループ。
+//! ネットワーク越しのプレイヤーや思考に時間のかかるボットを、
+//! ゲームごとに OS スレッドを占有せずに同卓させるために使う。
+//!
+//! CPU が操作する座席は従来どおり [`GameDriver`] のイベントポンプが即時に処理し、
+//! [`AsyncAgent`] を割り当てた座席は行動が求められるたびに `decide` を await する。
+//! [`set_action_timeout`](AsyncGameDriver::set_action_timeout) で持ち時間を設定すると、
+//! 期限までに行動を返さなかった座席は既定の行動（ツモ切り/パス/続行）で進める。
+//!
+//! イベント列ではなく局面（[`GameView`]）だけを見て行動するボットは、
+//! [`AsyncBot`] として [`set_bot`](AsyncGameDriver::set_bot) で割り当てる。
+//! 同期的に行動を決めるボットの対局には [`agent`](crate::agent) のゲームループを使う。
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use crate::action::Action;
+use crate::agent::GameView;
+use crate::driver::GameDriver;
+use crate::protocol::{ClientAction, ServerEvent};
+
+/// エージェントが返す行動の future
+pub type ActionFuture<'a> = Pin<Box<dyn Future<Output = ClientAction> + Send + 'a>>;
+
+/// ボットが返す行動の future
+pub type BotFuture<'a> = Pin<Box<dyn Future<Output = Action> + Send + 'a>>;
+
+/// 非同期に行動を決めるエージェント
+pub trait AsyncAgent: Send {
+    /// 座席宛てのイベントを受け取る
+    fn observe(&mut self, events: &[ServerEvent]);
+
+    /// 座席に行動が求められたときに呼ばれ、行動を返す
+    ///
+    /// `deadline` は行動の期限（持ち時間を設定していない場合は None）。
+    /// 期限を過ぎた場合や無効な行動を返した場合は、既定の行動（ツモ切り/パス/続行）で代替する。
+    fn decide(&mut self, deadline: Option<Instant>) -> ActionFuture<'_>;
+
+    /// 期限切れで既定の行動が適用されたときに呼ばれる
+    fn on_timeout(&mut self) {}
+}
+
+/// 局面を見て非同期に行動を決めるボット
+pub trait AsyncBot: Send {
+    /// 行動が求められたときに呼ばれ、`view.legal_actions` の中から1つを返す
+    ///
+    /// 期限を過ぎた場合や合法でない行動を返した場合は、既定の行動（ツモ切り/パス/続行）で代替する。
+    fn choose_action<'a>(&'a mut self, view: &'a GameView) -> BotFuture<'a>;
+}
+
+/// 座席を操作するもの
+enum Controller {
+    /// イベント列を受け取って `ClientAction` を返すエージェント
+    Agent(Box<dyn AsyncAgent>),
+    /// 局面を見て `Action` を返すボット
+    Bot(Box<dyn AsyncBot>),
+}
+
+/// 行動の決定を待つ future（`None` なら既定の行動で進める）
+type DecisionFuture<'a> = Pin<Box<dyn Future<Output = Option<ClientAction>> + Send + 'a>>;
+
+/// 非同期ゲームドライバー
+pub struct AsyncGameDriver {
+    driver: GameDriver,
+    /// 各座席のエージェント・ボット（None の座席は CPU または既定の行動で進める）
+    agents: [Option<Controller>; 4],
+    /// 1回の行動の持ち時間（None なら無制限）
+    action_timeout: Option<Duration>,
+}
+
+impl AsyncGameDriver {
+    /// CPU の割り当てなどを済ませたドライバーから作成する
+    pub fn new(driver: GameDriver) -> Self {
+        AsyncGameDriver {
+            driver,
+            agents: [None, None, None, None],
+            action_timeout: None,
+        }
+    }
+
+    /// 1回の行動の持ち時間を設定する
+    pub fn set_action_timeout(&mut self, timeout: Duration) {
+        self.action_timeout = Some(timeout);
+    }
+
+    /// 指定した座席にエージェントを割り当てる
+    pub fn set_agent(&mut self, seat: usize, agent: Box<dyn AsyncAgent>) {
+        if seat < 4 {
+            self.agents[seat] = Some(Controller::Agent(agent));
+        }
+    }
+
+    /// 指定した座席にボットを割り当てる
+    pub fn set_bot(&mut self, seat: usize, bot: Box<dyn AsyncBot>) {
+        if seat < 4 {
+            self.agents[seat] = Some(Controller::Bot(bot));
+        }
+    }
+
+    /// ドライバーへの参照を取得する
+    pub fn driver(&self) -> &GameDriver {
+        &self.driver
+    }
+
+    /// ドライバーへの可変参照を取得する
+    pub fn driver_mut(&mut self) -> &mut GameDriver {
+        &mut self.driver
+    }
+
+    /// ゲームを開始し、終了するまで進める
+    pub async fn run_game(&mut self) {
+        self.driver.start_game();
+        self.run_rounds().await;
+    }
+
+    /// シード値を指定してゲームを開始し、終了するまで進める（テスト・再現用）
+    pub async fn run_game_with_seed(&mut self, seed: u64) {
+        self.driver.start_game_with_seed(seed);
+        self.run_rounds().await;
+    }
+
+    async fn run_rounds(&mut self) {
+        loop {
+            self.run_round().await;
+            if self.driver.is_game_over() {
+                return;
+            }
+            self.driver.next_round();
+            if self.driver.is_game_over() {
+                return;
+            }
+        }
+    }
+
+    /// 現在の局が終了するまで進める
+    pub async fn run_round(&mut self) {
+        loop {
+            self.driver.run_until_blocked();
+            self.deliver_events();
+            if self.driver.is_round_over() {
+                return;
+            }
+
+            let Some(seat) = self.driver.pending_action_seats().first().copied() else {
+                if !self.driver.needs_tick() {
+                    return;
+                }
+                self.driver.tick();
+                continue;
+            };
+
+            let accepted = match self.decide(seat).await {
+                Some(action) => self.driver.handle_action(seat, action),
+                None => false,
+            };
+            if !accepted {
+                self.driver.force_default_action(seat);
+            }
+
+            // 1手ごとにランタイムへ制御を返し、他のゲームを進められるようにする
+            tokio::task::yield_now().await;
+        }
+    }
+
+    /// 座席のエージェント・ボットに行動を求め、持ち時間内に返された行動を返す
+    ///
+    /// 割り当てがない座席・期限切れ・ボットの合法でない行動では`None`を返す。
+    async fn decide(&mut self, seat: usize) -> Option<ClientAction> {
+        let deadline = self.action_timeout.map(|timeout| Instant::now() + timeout);
+        let view = match self.agents[seat] {
+            Some(Controller::Bot(_)) => Some(self.driver.table().current_round()?.view(seat)),
+            _ => None,
+        };
+        let decision: DecisionFuture<'_> = match &mut self.agents[seat] {
+            Some(Controller::Agent(agent)) => {
+                Box::pin(async move { Some(agent.decide(deadline).await) })
+            }
+            Some(Controller::Bot(bot)) => {
+                let view = view.as_ref()?;
+                Box::pin(async move {
+                    let action = bot.choose_action(view).await;
+                    view.legal_actions.contains(&action).then(|| action.into())
+                })
+            }
+            None => return None,
+        };
+        let Some(timeout) = self.action_timeout else {
+            return decision.await;
+        };
+        match tokio::time::timeout(timeout, decision).await {
+            Ok(action) => action,
+            Err(_) => {
+                if let Some(Controller::Agent(agent)) = &mut self.agents[seat] {
+                    agent.on_timeout();
+                }
+                None
+            }
+        }
+    }
+
+    /// エージェントを割り当てた座席にイベントを配信する
+    ///
+    /// ボットは行動のたびに局面を受け取るため、ボットの座席のイベントは読み捨てる。
+    fn deliver_events(&mut self) {
+        for (seat, controller) in self.agents.iter_mut().enumerate() {
+            match controller {
+                Some(Controller::Agent(agent)) => {
+                    let events = self.driver.drain_events(seat);
+                    if !events.is_empty() {
+                        agent.observe(&events);
+                    }
+                }
+                Some(Controller::Bot(_)) => {
+                    self.driver.drain_events(seat);
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::cpu::personalities::default_cpu_configs;
+    use crate::table::GameSettings;
+
+    /// 常にツモ切り・パスするエージェント
+    struct TsumogiriAgent {
+        last_call: bool,
+        decisions: Arc<AtomicUsize>,
+    }
+
+    impl AsyncAgent for TsumogiriAgent {
+        fn observe(&mut self, events: &[ServerEvent]) {
+            for event in events {
+                match event {
+                    ServerEvent::CallAvailable { .. } => self.last_call = true,
+                    ServerEvent::TileDrawn { .. } => self.last_call = false,
+                    _ => {}
+                }
+            }
+        }
+
+        fn decide(&mut self, _deadline: Option<Instant>) -> ActionFuture<'_> {
+            self.decisions.fetch_add(1, Ordering::SeqCst);
+            let action = if self.last_call {
+                ClientAction::Pass
+            } else {
+                ClientAction::Discard { tile: None }
+            };
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                action
+            })
+        }
+    }
+
+    fn driver_with_agent(decisions: Arc<AtomicUsize>) -> AsyncGameDriver {
+        let mut driver = GameDriver::new(GameSettings::default());
+        for (i, config) in default_cpu_configs().into_iter().enumerate().skip(1) {
+            driver.set_cpu(i, config);
+        }
+        let mut driver = AsyncGameDriver::new(driver);
+        driver.set_agent(
+            0,
+            Box::new(TsumogiriAgent {
+                last_call: false,
+                decisions,
+            }),
+        );
+        driver
+    }
+
+    /// エージェントの行動を await しながら局が最後まで進むことを確認
+    #[tokio::test]
+    async fn test_round_completes_with_async_agent() {
+        let decisions = Arc::new(AtomicUsize::new(0));
+        let mut driver = driver_with_agent(decisions.clone());
+        driver.driver_mut().start_game_with_seed(42);
+        driver.run_round().await;
+
+        assert!(driver.driver().is_round_over());
+        assert!(decisions.load(Ordering::SeqCst) > 0);
+    }
+
+    /// エージェントを割り当てない人間席は既定の行動で進むことを確認
+    #[tokio::test]
+    async fn test_seat_without_agent_uses_default_action() {
+        let mut driver = GameDriver::new(GameSettings::default());
+        for (i, config) in default_cpu_configs().into_iter().enumerate().skip(1) {
+            driver.set_cpu(i, config);
+        }
+        let mut driver = AsyncGameDriver::new(driver);
+        driver.driver_mut().start_game_with_seed(7);
+        driver.run_round().await;
+        assert!(driver.driver().is_round_over());
+    }
+
+    /// 行動を返さないエージェント
+    struct SilentAgent {
+        timeouts: Arc<AtomicUsize>,
+    }
+
+    impl AsyncAgent for SilentAgent {
+        fn observe(&mut self, _events: &[ServerEvent]) {}
+
+        fn decide(&mut self, deadline: Option<Instant>) -> ActionFuture<'_> {
+            assert!(deadline.is_some());
+            Box::pin(std::future::pending())
+        }
+
+        fn on_timeout(&mut self) {
+            self.timeouts.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// 常に合法手の先頭を選ぶボット
+    struct FirstLegalBot;
+
+    impl AsyncBot for FirstLegalBot {
+        fn choose_action<'a>(&'a mut self, view: &'a GameView) -> BotFuture<'a> {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                view.legal_actions[0].clone()
+            })
+        }
+    }
+
+    /// 4人ともボットの卓で、局面を渡しながら局が最後まで進むことを確認
+    #[tokio::test]
+    async fn test_round_completes_with_async_bots() {
+        let mut driver = AsyncGameDriver::new(GameDriver::new(GameSettings::default()));
+        for seat in 0..4 {
+            driver.set_bot(seat, Box::new(FirstLegalBot));
+        }
+        driver.driver_mut().start_game_with_seed(4);
+        driver.run_round().await;
+        assert!(driver.driver().is_round_over());
+    }
+
+    /// 持ち時間切れの座席は既定の行動で進み、局が終了することを確認
+    #[tokio::test]
+    async fn test_timeout_applies_default_action() {
+        let mut driver = GameDriver::new(GameSettings::default());
+        for (i, config) in default_cpu_configs().into_iter().enumerate().skip(1) {
+            driver.set_cpu(i, config);
+        }
+        let mut driver = AsyncGameDriver::new(driver);
+        let timeouts = Arc::new(AtomicUsize::new(0));
+        driver.set_agent(
+            0,
+            Box::new(SilentAgent {
+                timeouts: timeouts.clone(),
+            }),
+        );
+        driver.set_action_timeout(Duration::from_millis(1));
+        driver.driver_mut().start_game_with_seed(42);
+        driver.run_round().await;
+
+        assert!(driver.driver().is_round_over());
+        assert!(timeouts.load(Ordering::SeqCst) > 0);
+    }
+}
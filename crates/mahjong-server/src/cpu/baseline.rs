@@ -0,0 +1,243 @@
+//! 基準となる単純な CPU
+//!
+//! 合法な行動（`Round::legal_actions`）の中から、次の方針で1つを選ぶ。
+//! 強さの比較の基準や、外部のエージェントを作る際の出発点に使う。
+//!
+//! - 和了できるときは必ず和了る。九種九牌は流局にする
+//! - 聴牌したらリーチする（門前の手に役を付ける）。宣言牌は受け入れ枚数が最も多いもの
+//! - 打牌は向聴数が最小、受け入れ枚数が最大になる牌（聴牌していれば聴牌を崩さない）
+//! - 役牌の対子はポンする。対子・刻子が4組以上あれば対々和を狙ってポンする
+//! - チー・カンはしない
+
+use mahjong_core::hand::Hand;
+use mahjong_core::hand_info::discard_eval::best_discards_with_settings;
+use mahjong_core::hand_info::meld::MeldType;
+use mahjong_core::settings::Settings;
+use mahjong_core::tile::{Tile, TileType, Wind};
+
+use crate::action::Action;
+
+use super::client::is_yakuhai;
+
+/// 対々和を狙ってポンする対子・刻子の組数
+const TOITOI_MIN_SETS: usize = 4;
+
+/// 基準となる単純な CPU
+#[derive(Debug, Clone)]
+pub struct BaselineBot {
+    /// ルール設定（受け入れ枚数の計算に使う）
+    settings: Settings,
+}
+
+impl BaselineBot {
+    /// ルール設定を指定して作成する
+    pub fn new(settings: Settings) -> Self {
+        BaselineBot { settings }
+    }
+
+    /// 合法な行動の中から1つを選ぶ（合法な行動がなければ`None`）
+    ///
+    /// - `hand`: 自分の手牌（ツモ牌・副露を含む）
+    /// - `legal`: いま取れる行動
+    pub fn choose_action(
+        &self,
+        hand: &Hand,
+        seat_wind: Wind,
+        round_wind: Wind,
+        legal: &[Action],
+    ) -> Option<Action> {
+        for action in [
+            Action::Tsumo,
+            Action::Ron,
+            Action::NineTerminals { declare: true },
+        ] {
+            if legal.contains(&action) {
+                return Some(action);
+            }
+        }
+
+        let riichi: Vec<Option<Tile>> = legal
+            .iter()
+            .filter_map(|a| match a {
+                Action::Riichi(tile) => Some(*tile),
+                _ => None,
+            })
+            .collect();
+        if let Some(tile) = self.best_discard(hand, &riichi) {
+            return Some(Action::Riichi(tile));
+        }
+
+        let pon = legal.iter().find(|a| match a {
+            Action::Pon(tiles) => should_pon(hand, tiles[0].get(), seat_wind, round_wind),
+            _ => false,
+        });
+        if let Some(pon) = pon {
+            return Some(pon.clone());
+        }
+        if legal.contains(&Action::Pass) {
+            return Some(Action::Pass);
+        }
+
+        let discards: Vec<Option<Tile>> = legal
+            .iter()
+            .filter_map(|a| match a {
+                Action::Discard(tile) => Some(*tile),
+                _ => None,
+            })
+            .collect();
+        if let Some(tile) = self.best_discard(hand, &discards) {
+            return Some(Action::Discard(tile));
+        }
+
+        legal.first().cloned()
+    }
+
+    /// 候補の打牌（Noneならツモ切り）のうち、打牌後の向聴数が最小・受け入れ枚数が最大のもの
+    fn best_discard(&self, hand: &Hand, candidates: &[Option<Tile>]) -> Option<Option<Tile>> {
+        if candidates.is_empty() {
+            return None;
+        }
+        // 鳴いた直後はツモ牌がないため、手牌の1枚をツモ牌とみなして評価する
+        let mut eval_hand = hand.clone();
+        if eval_hand.drawn().is_none() {
+            let last = eval_hand.tiles_mut().pop();
+            eval_hand.set_drawn(last);
+        }
+        let evals = best_discards_with_settings(&eval_hand, &self.settings).unwrap_or_default();
+        evals
+            .iter()
+            .find_map(|eval| {
+                if hand.drawn() == Some(eval.tile) && candidates.contains(&None) {
+                    Some(None)
+                } else if candidates.contains(&Some(eval.tile)) {
+                    Some(Some(eval.tile))
+                } else {
+                    None
+                }
+            })
+            .or_else(|| candidates.first().copied())
+    }
+}
+
+/// 捨てられた牌をポンするか
+fn should_pon(hand: &Hand, tile_type: TileType, seat_wind: Wind, round_wind: Wind) -> bool {
+    if is_yakuhai(tile_type, seat_wind, round_wind) {
+        return true;
+    }
+    if hand.melds().iter().any(|m| m.category == MeldType::Chi) {
+        return false;
+    }
+    let mut counts = [0u8; Tile::LEN];
+    for tile in hand.tiles() {
+        counts[tile.get() as usize] += 1;
+    }
+    let sets = counts.iter().filter(|&&n| n >= 2).count() + hand.melds().len();
+    sets >= TOITOI_MIN_SETS
+}
+
+/// ユニットテスト
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{GameSettings, Table};
+
+    fn bot() -> BaselineBot {
+        BaselineBot::new(Settings::new())
+    }
+
+    /// 手番の合法な打牌（手牌の各牌とツモ切り）
+    fn discards(hand: &Hand) -> Vec<Action> {
+        let mut actions: Vec<Action> = hand
+            .tiles()
+            .iter()
+            .map(|&t| Action::Discard(Some(t)))
+            .collect();
+        actions.dedup();
+        actions.push(Action::Discard(None));
+        actions
+    }
+
+    #[test]
+    fn takes_win_when_available() {
+        let hand = Hand::from("123m456p789s1122z 2z");
+        let mut legal = discards(&hand);
+        legal.push(Action::Tsumo);
+        assert_eq!(
+            bot().choose_action(&hand, Wind::South, Wind::East, &legal),
+            Some(Action::Tsumo)
+        );
+    }
+
+    #[test]
+    fn keeps_tenpai_when_discarding() {
+        let hand = Hand::from("123m456p789s1125z 2z");
+        let legal = discards(&hand);
+        assert_eq!(
+            bot().choose_action(&hand, Wind::South, Wind::East, &legal),
+            Some(Action::Discard(Some(Tile::new(Tile::Z5))))
+        );
+    }
+
+    #[test]
+    fn declares_riichi_when_tenpai() {
+        let hand = Hand::from("123m123p123s45z67m 8m");
+        let mut legal = discards(&hand);
+        legal.push(Action::Riichi(Some(Tile::new(Tile::Z4))));
+        legal.push(Action::Riichi(Some(Tile::new(Tile::Z5))));
+        assert!(matches!(
+            bot().choose_action(&hand, Wind::South, Wind::East, &legal),
+            Some(Action::Riichi(Some(_)))
+        ));
+    }
+
+    #[test]
+    fn pons_yakuhai_and_toitoi_shapes_only() {
+        let pon = |tile: TileType| vec![Action::Pon([Tile::new(tile); 2]), Action::Pass];
+
+        let yakuhai = Hand::from("123m456p789s1155z");
+        assert_eq!(
+            bot().choose_action(&yakuhai, Wind::South, Wind::East, &pon(Tile::Z5)),
+            Some(Action::Pon([Tile::new(Tile::Z5); 2]))
+        );
+        // 客風の対子はポンしない
+        assert_eq!(
+            bot().choose_action(&yakuhai, Wind::South, Wind::East, &pon(Tile::Z4)),
+            Some(Action::Pass)
+        );
+
+        let toitoi = Hand::from("11m22p33s44m55667z");
+        assert_eq!(
+            bot().choose_action(&toitoi, Wind::South, Wind::East, &pon(Tile::M1)),
+            Some(Action::Pon([Tile::new(Tile::M1); 2]))
+        );
+    }
+
+    #[test]
+    fn four_bots_finish_a_round() {
+        let mut table = Table::new(GameSettings::default());
+        table.start_round_with_seed(5);
+        for _ in 0..1000 {
+            let round = table.current_round_mut().unwrap();
+            if round.is_over() {
+                return;
+            }
+            if round.phase == crate::round::TurnPhase::Draw {
+                round.do_draw();
+                continue;
+            }
+            let seat = (0..4)
+                .find(|&seat| !round.legal_actions(seat).is_empty())
+                .unwrap();
+            let action = bot()
+                .choose_action(
+                    &round.players[seat].hand,
+                    round.players[seat].seat_wind,
+                    round.round_wind,
+                    &round.legal_actions(seat),
+                )
+                .unwrap();
+            assert!(table.handle_action(seat, action.into()));
+        }
+        panic!("round did not finish");
+    }
+}
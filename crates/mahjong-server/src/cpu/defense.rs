@@ -3,9 +3,11 @@
 //! 牌の安全度を評価する。現物・筋・壁・字牌・端牌の判定に加え、
 //! 他家の脅威（リーチ・副露・染め手・役満気配）を統合的に扱う。
 
-use mahjong_core::tile::{Tile, TileType, dora_indicator_to_dora};
+use mahjong_core::hand_info::defense;
+use mahjong_core::tile::{Tile, TileSummarize, TileType, dora_indicator_to_dora};
 
 use super::client::{CpuConfig, CpuLevel, is_yakuhai};
+use super::river::read_river;
 use super::state::CpuGameState;
 
 /// 国士無双の構成牌（么九牌・字牌の全13種）
@@ -59,8 +61,17 @@ pub fn evaluate_safety(tile: Tile, state: &CpuGameState, config: &CpuConfig) ->
             continue;
         };
 
-        let safety =
+        let mut safety =
             evaluate_safety_against_threat(tile, &state.all_discards[i], state, &threat, strict);
+
+        // 中以上: 河読み（跨ぎ筋・筋引っかけ・序盤の字牌切り）で危険度を補正する
+        if strict {
+            let reading = read_river(&state.all_discards[i], &state.discard_is_tsumogiri[i]);
+            let weight = reading.weight(tile.get());
+            if weight != 1.0 {
+                safety = 1.0 - ((1.0 - safety) * weight).min(1.0);
+            }
+        }
         min_safety = min_safety.min(safety);
     }
 
@@ -252,6 +263,7 @@ fn evaluate_safety_against_threat(
     }
 
     let visible_counts = state.visible_tile_counts();
+    let visible: TileSummarize = std::array::from_fn(|i| visible_counts[i] as u32);
     let mut base: f64;
 
     if tt >= 27 {
@@ -272,10 +284,12 @@ fn evaluate_safety_against_threat(
         {
             base = base.min(0.22);
         }
-    } else if is_suji(tt, opponent_discards) {
-        // 4. 筋（suji）判定
+    } else if defense::is_suji(tt, opponent_discards)
+        || defense::is_half_suji(tt, opponent_discards)
+    {
+        // 4. 筋（suji）判定。4〜6は片筋でも筋として扱う
         base = 0.75;
-    } else if is_kabe(tt, &visible_counts) {
+    } else if defense::is_no_chance(tt, &visible) {
         // 5. 壁（ノーチャンス）判定
         base = 0.7;
     } else {
@@ -289,7 +303,7 @@ fn evaluate_safety_against_threat(
         };
 
         // #175（中以上）: ワンチャンス（順子の材料が残り1枚以下）はやや安全寄り
-        if strict && is_one_chance(tt, &visible_counts) {
+        if strict && defense::is_one_chance(tt, &visible) {
             base = base.max(0.5);
         }
     }
@@ -334,174 +348,6 @@ fn is_dora_or_neighbor(tile_type: TileType, state: &CpuGameState) -> bool {
     false
 }
 
-/// 筋（suji）で安全かどうか判定する
-///
-/// 例: 相手が4mを捨てている → 1m, 7m は筋で比較的安全
-///     相手が5mを捨てている → 2m, 8m は筋
-///     相手が6mを捨てている → 3m, 9m は筋
-fn is_suji(tile_type: TileType, opponent_discards: &[Tile]) -> bool {
-    if tile_type >= 27 {
-        return false; // 字牌に筋はない
-    }
-
-    let suit_start = (tile_type / 9) * 9;
-    let num = tile_type - suit_start; // 0-8
-
-    // 筋のペア: (1,4), (2,5), (3,6), (4,7), (5,8), (6,9)
-    // numは0-indexed: (0,3), (1,4), (2,5), (3,6), (4,7), (5,8)
-    let suji_partner = match num {
-        0 => Some(suit_start + 3), // 1 → 4
-        1 => Some(suit_start + 4), // 2 → 5
-        2 => Some(suit_start + 5), // 3 → 6
-        3 => {
-            // 4 → 1 or 7
-            if opponent_discards.iter().any(|d| d.get() == suit_start)
-                || opponent_discards.iter().any(|d| d.get() == suit_start + 6)
-            {
-                return true;
-            }
-            return false;
-        }
-        4 => {
-            // 5 → 2 or 8
-            if opponent_discards.iter().any(|d| d.get() == suit_start + 1)
-                || opponent_discards.iter().any(|d| d.get() == suit_start + 7)
-            {
-                return true;
-            }
-            return false;
-        }
-        5 => {
-            // 6 → 3 or 9
-            if opponent_discards.iter().any(|d| d.get() == suit_start + 2)
-                || opponent_discards.iter().any(|d| d.get() == suit_start + 8)
-            {
-                return true;
-            }
-            return false;
-        }
-        6 => Some(suit_start + 3), // 7 → 4
-        7 => Some(suit_start + 4), // 8 → 5
-        8 => Some(suit_start + 5), // 9 → 6
-        _ => None,
-    };
-
-    if let Some(partner) = suji_partner {
-        opponent_discards.iter().any(|d| d.get() == partner)
-    } else {
-        false
-    }
-}
-
-/// 壁（kabe）で安全かどうか判定する
-///
-/// ある牌種が場に全て見えている（残り0枚）場合、
-/// その牌を含む順子が成立しないため、隣接牌の危険度が下がる。
-fn is_kabe(tile_type: TileType, visible_counts: &[u8; 34]) -> bool {
-    is_blocked(tile_type, visible_counts, 4)
-}
-
-/// ワンチャンス（順子の材料が残り1枚以下）か（#175）
-///
-/// 壁ほどではないが、両面待ちで当たる可能性が低い。
-fn is_one_chance(tile_type: TileType, visible_counts: &[u8; 34]) -> bool {
-    is_blocked(tile_type, visible_counts, 3)
-}
-
-/// 順子の材料が min_visible 枚以上見えていて成立しにくいか（壁判定の一般化）
-///
-/// min_visible=4 でノーチャンス（壁）、3 でワンチャンス相当になる。
-fn is_blocked(tile_type: TileType, visible_counts: &[u8; 34], min_visible: u8) -> bool {
-    if tile_type >= 27 {
-        return false; // 字牌に壁はない
-    }
-
-    let suit_start = (tile_type / 9) * 9;
-    let num = tile_type - suit_start; // 0-8
-
-    // この牌を含みうる順子の構成牌を確認
-    // 例: 5m(num=4) → 345m, 456m, 567m の構成牌 3,4,6,7 のいずれかが壁なら安全寄り
-    let mut blocked_patterns = 0;
-    let total_patterns;
-
-    match num {
-        0 => {
-            // 1: 123 のみ。2か3が壁なら安全
-            total_patterns = 1;
-            if visible_counts[(suit_start + 1) as usize] >= min_visible
-                || visible_counts[(suit_start + 2) as usize] >= min_visible
-            {
-                blocked_patterns = 1;
-            }
-        }
-        1 => {
-            // 2: 123, 234。
-            total_patterns = 2;
-            if visible_counts[suit_start as usize] >= min_visible
-                || visible_counts[(suit_start + 2) as usize] >= min_visible
-            {
-                blocked_patterns += 1;
-            }
-            if visible_counts[(suit_start + 2) as usize] >= min_visible
-                || visible_counts[(suit_start + 3) as usize] >= min_visible
-            {
-                blocked_patterns += 1;
-            }
-        }
-        7 => {
-            // 8: 789, 678
-            total_patterns = 2;
-            if visible_counts[(suit_start + 8) as usize] >= min_visible
-                || visible_counts[(suit_start + 6) as usize] >= min_visible
-            {
-                blocked_patterns += 1;
-            }
-            if visible_counts[(suit_start + 6) as usize] >= min_visible
-                || visible_counts[(suit_start + 5) as usize] >= min_visible
-            {
-                blocked_patterns += 1;
-            }
-        }
-        8 => {
-            // 9: 789 のみ。7か8が壁なら安全
-            total_patterns = 1;
-            if visible_counts[(suit_start + 6) as usize] >= min_visible
-                || visible_counts[(suit_start + 7) as usize] >= min_visible
-            {
-                blocked_patterns = 1;
-            }
-        }
-        _ => {
-            // 3-7: 3パターン
-            total_patterns = 3;
-            // 前方の順子
-            if num >= 2
-                && (visible_counts[(suit_start + num - 2) as usize] >= min_visible
-                    || visible_counts[(suit_start + num - 1) as usize] >= min_visible)
-            {
-                blocked_patterns += 1;
-            }
-            // 中央の順子
-            if (1..=7).contains(&num)
-                && (visible_counts[(suit_start + num - 1) as usize] >= min_visible
-                    || visible_counts[(suit_start + num + 1) as usize] >= min_visible)
-            {
-                blocked_patterns += 1;
-            }
-            // 後方の順子
-            if num <= 6
-                && (visible_counts[(suit_start + num + 1) as usize] >= min_visible
-                    || visible_counts[(suit_start + num + 2) as usize] >= min_visible)
-            {
-                blocked_patterns += 1;
-            }
-        }
-    }
-
-    // 全パターンが壁でブロックされていれば安全
-    blocked_patterns > 0 && blocked_patterns >= total_patterns
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -531,23 +377,6 @@ mod tests {
         assert_eq!(safety, 1.0);
     }
 
-    #[test]
-    fn test_suji_basic() {
-        // 4mが捨てられている → 1m は筋で安全
-        let discards = vec![Tile::new(Tile::M4)];
-        assert!(is_suji(Tile::M1, &discards));
-        assert!(is_suji(Tile::M7, &discards));
-        assert!(!is_suji(Tile::M5, &discards));
-    }
-
-    #[test]
-    fn test_suji_middle() {
-        // 5mが捨てられている → 2m, 8m は筋
-        let discards = vec![Tile::new(Tile::M5)];
-        assert!(is_suji(Tile::M2, &discards));
-        assert!(is_suji(Tile::M8, &discards));
-    }
-
     #[test]
     fn test_honour_tile_safety() {
         let state = CpuGameState::new();
@@ -870,6 +699,21 @@ mod tests {
         assert_eq!(safety, 1.0);
     }
 
+    #[test]
+    fn test_suji_trap_less_safe_when_strict() {
+        // 手出しの 4m の筋 1m は、最後の手出しが 4m だと筋引っかけを警戒する
+        let mut state = CpuGameState::new();
+        state.my_seat_wind = Wind::East;
+        state.player_riichi[1] = true;
+        state.all_discards[1] = vec![Tile::new(Tile::M4), Tile::new(Tile::P9)];
+        state.discard_is_tsumogiri[1] = vec![false, true];
+        let trap = evaluate_safety(Tile::new(Tile::M1), &state, &test_config());
+
+        state.discard_is_tsumogiri[1] = vec![false, false];
+        let plain_suji = evaluate_safety(Tile::new(Tile::M1), &state, &test_config());
+        assert!(trap < plain_suji, "trap={trap}, plain={plain_suji}");
+    }
+
     #[test]
     fn test_evaluate_safety_multiple_riichi_takes_min() {
         // 複数リーチ者がいる場合、最小の安全度を返す
@@ -882,72 +726,6 @@ mod tests {
         assert!(safety < 1.0);
     }
 
-    // --- is_suji: 未カバーの num パターン ---
-
-    #[test]
-    fn test_suji_3m_when_6m_discarded() {
-        // 6m捨て → 3m, 9m は筋
-        let discards = vec![Tile::new(Tile::M6)];
-        assert!(is_suji(Tile::M3, &discards));
-        assert!(is_suji(Tile::M9, &discards));
-    }
-
-    #[test]
-    fn test_suji_4m_when_1m_or_7m_discarded() {
-        // 1m または 7m が捨てられている → 4m は筋
-        assert!(is_suji(Tile::M4, &[Tile::new(Tile::M1)]));
-        assert!(is_suji(Tile::M4, &[Tile::new(Tile::M7)]));
-        assert!(!is_suji(Tile::M4, &[Tile::new(Tile::M2)]));
-    }
-
-    #[test]
-    fn test_suji_5m_when_2m_or_8m_discarded() {
-        // 2m または 8m が捨てられている → 5m は筋
-        assert!(is_suji(Tile::M5, &[Tile::new(Tile::M2)]));
-        assert!(is_suji(Tile::M5, &[Tile::new(Tile::M8)]));
-        assert!(!is_suji(Tile::M5, &[Tile::new(Tile::M1)]));
-    }
-
-    #[test]
-    fn test_suji_6m_when_3m_or_9m_discarded() {
-        // 3m または 9m が捨てられている → 6m は筋
-        assert!(is_suji(Tile::M6, &[Tile::new(Tile::M3)]));
-        assert!(is_suji(Tile::M6, &[Tile::new(Tile::M9)]));
-        assert!(!is_suji(Tile::M6, &[Tile::new(Tile::M2)]));
-    }
-
-    #[test]
-    fn test_suji_pin_suit() {
-        // 筋は別の色でも成立し、異なる色の捨て牌は無効
-        let discards = vec![Tile::new(Tile::P4)];
-        assert!(is_suji(Tile::P1, &discards));
-        assert!(is_suji(Tile::P7, &discards));
-        assert!(!is_suji(Tile::M1, &discards)); // 万子には無関係
-    }
-
-    #[test]
-    fn test_suji_sou_suit() {
-        // 索子でも同様
-        let discards = vec![Tile::new(Tile::S5)];
-        assert!(is_suji(Tile::S2, &discards));
-        assert!(is_suji(Tile::S8, &discards));
-    }
-
-    #[test]
-    fn test_suji_honour_tile_returns_false() {
-        // 字牌に筋はない
-        let discards = vec![Tile::new(Tile::M4)];
-        assert!(!is_suji(Tile::Z1, &discards));
-        assert!(!is_suji(Tile::Z7, &discards));
-    }
-
-    #[test]
-    fn test_suji_no_partner_in_discards() {
-        // 筋パートナーが捨てられていなければ false
-        let discards = vec![Tile::new(Tile::M3)];
-        assert!(!is_suji(Tile::M1, &discards)); // M1 のパートナーは M4
-    }
-
     // --- evaluate_safety_against_player: 各安全度の返り値 ---
 
     #[test]
@@ -959,6 +737,15 @@ mod tests {
         assert_eq!(safety, 0.75);
     }
 
+    #[test]
+    fn test_half_suji_safety_value() {
+        // 4〜6は片側の筋だけでも筋と同じ安全度
+        let discards = vec![Tile::new(Tile::M1)];
+        let state = CpuGameState::new();
+        let safety = evaluate_safety_against_player(Tile::new(Tile::M4), &discards, &state);
+        assert_eq!(safety, 0.75);
+    }
+
     #[test]
     fn test_kabe_safety_value() {
         // 壁牌の安全度は 0.70
@@ -1078,86 +865,4 @@ mod tests {
             );
         }
     }
-
-    // --- is_kabe ---
-
-    #[test]
-    fn test_kabe_1m_when_2m_exhausted() {
-        // 2m が4枚見えている → 1m の唯一の順子(123m)がブロック → 壁
-        let mut counts = [0u8; 34];
-        counts[Tile::M2 as usize] = 4;
-        assert!(is_kabe(Tile::M1, &counts));
-    }
-
-    #[test]
-    fn test_kabe_1m_not_blocked() {
-        // 壁牌がなければ false
-        let counts = [0u8; 34];
-        assert!(!is_kabe(Tile::M1, &counts));
-    }
-
-    #[test]
-    fn test_kabe_9m_when_8m_exhausted() {
-        // 8m が4枚見えている → 9m の唯一の順子(789m)がブロック → 壁
-        let mut counts = [0u8; 34];
-        counts[Tile::M8 as usize] = 4;
-        assert!(is_kabe(Tile::M9, &counts));
-    }
-
-    #[test]
-    fn test_kabe_2m_fully_blocked() {
-        // 3m が4枚見えている → 123m と 234m の両方がブロック → 壁
-        let mut counts = [0u8; 34];
-        counts[Tile::M3 as usize] = 4;
-        assert!(is_kabe(Tile::M2, &counts));
-    }
-
-    #[test]
-    fn test_kabe_2m_partially_blocked() {
-        // 1m のみ4枚 → 123m はブロックされるが 234m はブロックされない → false
-        let mut counts = [0u8; 34];
-        counts[Tile::M1 as usize] = 4;
-        assert!(!is_kabe(Tile::M2, &counts));
-    }
-
-    #[test]
-    fn test_kabe_8m_fully_blocked() {
-        // 7m が4枚見えている → 789m と 678m の両方がブロック → 壁
-        let mut counts = [0u8; 34];
-        counts[Tile::M7 as usize] = 4;
-        assert!(is_kabe(Tile::M8, &counts));
-    }
-
-    #[test]
-    fn test_kabe_middle_5m_fully_blocked() {
-        // 4m + 6m が4枚見えている → 345m / 456m / 567m 全てブロック → 壁
-        let mut counts = [0u8; 34];
-        counts[Tile::M4 as usize] = 4;
-        counts[Tile::M6 as usize] = 4;
-        assert!(is_kabe(Tile::M5, &counts));
-    }
-
-    #[test]
-    fn test_kabe_middle_5m_partially_blocked() {
-        // 4m のみ4枚 → 345m と 456m はブロックされるが 567m はブロックされない → false
-        let mut counts = [0u8; 34];
-        counts[Tile::M4 as usize] = 4;
-        assert!(!is_kabe(Tile::M5, &counts));
-    }
-
-    #[test]
-    fn test_kabe_pin_suit() {
-        // 別のスートでも壁判定が成立する
-        let mut counts = [0u8; 34];
-        counts[Tile::P2 as usize] = 4;
-        assert!(is_kabe(Tile::P1, &counts));
-    }
-
-    #[test]
-    fn test_kabe_honour_tile_returns_false() {
-        // 字牌に壁はない
-        let mut counts = [0u8; 34];
-        counts[Tile::Z1 as usize] = 4;
-        assert!(!is_kabe(Tile::Z1, &counts));
-    }
 }
@@ -387,8 +387,8 @@ mod tests {
     #[test]
     fn test_get_yakuhai_types_all_winds() {
         // 各風の組み合わせでクラッシュしない
-        for seat in [Wind::East, Wind::South, Wind::West, Wind::North] {
-            for prev in [Wind::East, Wind::South, Wind::West, Wind::North] {
+        for seat in Wind::ALL {
+            for prev in Wind::ALL {
                 let types = get_yakuhai_types(seat, prev);
                 assert!(types.len() >= 4); // 三元牌3枚 + 場風 + 自風(重複除く)
             }
@@ -2,10 +2,13 @@
 //!
 //! CPUはプレイヤーと同じプロトコル（ServerEvent / ClientAction）で
 //! サーバとやり取りする。サーバ内部に直接アクセスしない。
+//! `baseline` は例外で、合法な行動の一覧（`Round::legal_actions`）から選ぶだけの基準用の CPU。
 
+pub mod baseline;
 pub mod client;
 pub mod defense;
 pub mod evaluator;
 pub mod heuristics;
 pub mod personalities;
+pub mod river;
 pub mod state;
@@ -0,0 +1,148 @@
+//! 河読み
+//!
+//! 他家の捨て牌の並び（序盤の字牌切り・手出しとツモ切り）から待ちの傾向を推定し、
+//! 牌種ごとの危険度の重みとして守備ロジックに渡す。
+//! 現物・筋・壁といった牌単位の判定は `defense` 側で行い、ここでは河の読みによる
+//! 補正だけを扱う。
+
+use mahjong_core::tile::{Tile, TileType};
+
+/// 序盤とみなす捨て牌の枚数
+const EARLY_DISCARDS: usize = 6;
+/// 序盤に字牌をこの枚数以上切っていれば、中張牌中心の手とみなす
+const EARLY_HONOURS_THRESHOLD: usize = 3;
+/// 序盤に字牌を多く切った相手に対する中張牌（3〜7）の重み
+const EARLY_HONOURS_MIDDLE_WEIGHT: f64 = 1.15;
+/// 最後の手出し牌の跨ぎ筋の重み
+const MATAGI_WEIGHT: f64 = 1.3;
+/// 最後の手出し牌の筋（筋引っかけ）の重み
+const SUJI_TRAP_WEIGHT: f64 = 1.4;
+
+/// 1人分の河読みの結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiverReading {
+    /// 牌種ごとの危険度の重み（1.0=補正なし、0.0=現物、1.0超で危険寄り）
+    pub danger: [f64; 34],
+    /// 序盤に字牌を多く切っているか
+    pub early_honours: bool,
+    /// 最後の手出し牌（ツモ切りの情報がなければ最後の捨て牌）
+    pub last_tedashi: Option<Tile>,
+}
+
+impl RiverReading {
+    /// 牌種の危険度の重みを返す
+    pub fn weight(&self, tile_type: TileType) -> f64 {
+        self.danger[tile_type as usize]
+    }
+}
+
+/// 捨て牌の並びから危険度の重みを推定する
+///
+/// `tsumogiri` は `discards` と同じ並びのツモ切りフラグ。
+/// 足りない分（牌譜にツモ切りの情報がない場合など）は手出しとして扱う。
+pub fn read_river(discards: &[Tile], tsumogiri: &[bool]) -> RiverReading {
+    let mut danger = [1.0f64; 34];
+
+    let early_honours = discards
+        .iter()
+        .take(EARLY_DISCARDS)
+        .filter(|t| t.get() >= Tile::Z1)
+        .count()
+        >= EARLY_HONOURS_THRESHOLD;
+    if early_honours {
+        for (tile_type, weight) in danger.iter_mut().enumerate().take(Tile::Z1 as usize) {
+            if (2..=6).contains(&(tile_type % 9)) {
+                *weight *= EARLY_HONOURS_MIDDLE_WEIGHT;
+            }
+        }
+    }
+
+    let last_tedashi = discards
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(i, _)| !tsumogiri.get(*i).copied().unwrap_or(false))
+        .map(|(_, &t)| t);
+    if let Some(tile) = last_tedashi
+        && tile.get() < Tile::Z1
+    {
+        let tile_type = tile.get();
+        let suit_start = tile_type / 9 * 9;
+        let num = (tile_type % 9) as i32;
+        let mut apply = |offset: i32, weight: f64| {
+            let n = num + offset;
+            if (0..9).contains(&n) {
+                danger[(suit_start + n as u32) as usize] *= weight;
+            }
+        };
+        // 跨ぎ筋: 両面の片割れを切った可能性（例: 5 を切って 46 / 47 待ち）
+        for offset in [-2, -1, 1, 2] {
+            apply(offset, MATAGI_WEIGHT);
+        }
+        // 筋引っかけ: 手出しの筋を安全そうに見せた愚形待ち
+        for offset in [-3, 3] {
+            apply(offset, SUJI_TRAP_WEIGHT);
+        }
+    }
+
+    // 現物はフリテンになるため当たらない
+    for tile in discards {
+        danger[tile.get() as usize] = 0.0;
+    }
+
+    RiverReading {
+        danger,
+        early_honours,
+        last_tedashi,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(types: &[TileType]) -> Vec<Tile> {
+        types.iter().map(|&t| Tile::new(t)).collect()
+    }
+
+    #[test]
+    fn test_genbutsu_has_zero_weight() {
+        let reading = read_river(&tiles(&[Tile::M1, Tile::P9]), &[]);
+        assert_eq!(reading.weight(Tile::M1), 0.0);
+        assert_eq!(reading.weight(Tile::P9), 0.0);
+        assert_eq!(reading.weight(Tile::Z1), 1.0);
+    }
+
+    #[test]
+    fn test_early_honours_raise_middle_tiles() {
+        let discards = tiles(&[Tile::Z1, Tile::Z2, Tile::Z6, Tile::M9]);
+        let reading = read_river(&discards, &[]);
+        assert!(reading.early_honours);
+        assert!(reading.weight(Tile::P5) > 1.0);
+        assert_eq!(reading.weight(Tile::P1), 1.0);
+    }
+
+    #[test]
+    fn test_last_tedashi_skips_tsumogiri() {
+        // 5m を手出しした後に 1z をツモ切り
+        let discards = tiles(&[Tile::M5, Tile::Z1]);
+        let reading = read_river(&discards, &[false, true]);
+        assert_eq!(reading.last_tedashi, Some(Tile::new(Tile::M5)));
+        // 跨ぎ筋（3m・4m・6m・7m）と筋引っかけ（2m・8m）が危険寄りになる
+        assert_eq!(reading.weight(Tile::M4), MATAGI_WEIGHT);
+        assert_eq!(reading.weight(Tile::M7), MATAGI_WEIGHT);
+        assert_eq!(reading.weight(Tile::M2), SUJI_TRAP_WEIGHT);
+        assert_eq!(reading.weight(Tile::M8), SUJI_TRAP_WEIGHT);
+        assert_eq!(reading.weight(Tile::M1), 1.0);
+        assert_eq!(reading.weight(Tile::P4), 1.0);
+    }
+
+    #[test]
+    fn test_missing_tsumogiri_info_treated_as_tedashi() {
+        let discards = tiles(&[Tile::M5, Tile::S1]);
+        let reading = read_river(&discards, &[]);
+        assert_eq!(reading.last_tedashi, Some(Tile::new(Tile::S1)));
+        assert_eq!(reading.weight(Tile::S2), MATAGI_WEIGHT);
+        assert_eq!(reading.weight(Tile::S4), SUJI_TRAP_WEIGHT);
+    }
+}
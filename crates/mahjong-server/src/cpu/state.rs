@@ -34,6 +34,8 @@ pub struct CpuGameState {
     pub scores: [i32; 4],
     /// 各プレイヤーの捨て牌（風のインデックス順: 東=0, 南=1, 西=2, 北=3）
     pub all_discards: [Vec<Tile>; 4],
+    /// 各捨て牌がツモ切りだったか（`all_discards` と同じ並び）
+    pub discard_is_tsumogiri: [Vec<bool>; 4],
     /// 鳴かれて副露側にも現れる捨て牌
     called_discards: Vec<Tile>,
     /// 各プレイヤーのリーチ状態
@@ -81,6 +83,7 @@ impl CpuGameState {
             is_furiten: false,
             scores: [0; 4],
             all_discards: [Vec::new(), Vec::new(), Vec::new(), Vec::new()],
+            discard_is_tsumogiri: [Vec::new(), Vec::new(), Vec::new(), Vec::new()],
             called_discards: Vec::new(),
             player_riichi: [false; 4],
             player_melds: [Vec::new(), Vec::new(), Vec::new(), Vec::new()],
@@ -133,6 +136,7 @@ impl CpuGameState {
                 self.is_furiten = false;
                 self.scores = *scores;
                 self.all_discards = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+                self.discard_is_tsumogiri = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
                 self.called_discards.clear();
                 self.player_riichi = [false; 4];
                 self.player_melds = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
@@ -177,6 +181,7 @@ impl CpuGameState {
             } => {
                 let idx = Self::wind_to_index(*player);
                 self.all_discards[idx].push(*tile);
+                self.discard_is_tsumogiri[idx].push(*is_tsumogiri);
 
                 // 自分が捨てた場合、手牌を正しく更新する
                 if *player == self.my_seat_wind {
@@ -817,7 +822,7 @@ mod tests {
             scores: [35000, 15000, 25000, 25000],
             yaku_list: Vec::new(),
             han: 1,
-            fu: 30,
+            fu: Some(30),
             score_points: 1000,
             rank: ScoreRank::Normal,
             has_opened: false,
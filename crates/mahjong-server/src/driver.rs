@@ -56,6 +56,8 @@ pub struct GameDriver {
     action_delay: Option<f64>,
     /// 思考待ち中のCPUアクション（イベント処理単位のFIFO）
     pending_cpu_batches: VecDeque<PendingCpuActionBatch>,
+    /// 卓が生成した全座席分のイベントの記録（無効なら None）
+    event_log: Option<Vec<(usize, ServerEvent)>>,
 }
 
 impl GameDriver {
@@ -67,6 +69,7 @@ impl GameDriver {
             event_buffers: [const { Vec::new() }; 4],
             action_delay: None,
             pending_cpu_batches: VecDeque::new(),
+            event_log: None,
         }
     }
 
@@ -124,6 +127,19 @@ impl GameDriver {
         self.action_delay = Some(seconds);
     }
 
+    /// 卓が生成した全座席分のイベントの記録を開始する
+    ///
+    /// 記録したイベントは [`event_log`](Self::event_log) で取得でき、
+    /// [`write_kifu`](crate::kifu::write_kifu) で牌譜に書き出せる。
+    pub fn enable_event_log(&mut self) {
+        self.event_log.get_or_insert_with(Vec::new);
+    }
+
+    /// 記録したイベント（座席, イベント）を返す（記録していなければ空）
+    pub fn event_log(&self) -> &[(usize, ServerEvent)] {
+        self.event_log.as_deref().unwrap_or(&[])
+    }
+
     /// 卓への参照を取得する
     pub fn table(&self) -> &Table {
         &self.table
@@ -415,6 +431,7 @@ impl GameDriver {
                 break;
             }
 
+            self.record_events(&all_events);
             self.buffer_human_events(&all_events);
 
             // CPUプレイヤーにイベントを配信してアクションを収集
@@ -443,6 +460,7 @@ impl GameDriver {
             return cpu_acted;
         }
 
+        self.record_events(&all_events);
         self.buffer_human_events(&all_events);
 
         // CPUプレイヤーにイベントを配信してアクションを収集
@@ -452,6 +470,13 @@ impl GameDriver {
         cpu_acted
     }
 
+    /// イベントの記録が有効なら、全座席分のイベントを記録する
+    fn record_events(&mut self, events: &[(usize, ServerEvent)]) {
+        if let Some(log) = self.event_log.as_mut() {
+            log.extend_from_slice(events);
+        }
+    }
+
     /// 人間に紐づく座席のイベントをバッファに追加する
     ///
     /// 純粋なCPU席（[`set_cpu`](Self::set_cpu)）以外、すなわち人間席
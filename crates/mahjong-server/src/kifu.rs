@@ -0,0 +1,320 @@
+//! 牌譜（テキスト形式）の書き出し
+//!
+//! 卓が生成したイベント列（座席, イベント）から、局ごとの配牌・ツモと打牌・鳴き・
+//! 結果（役と点数）を人が読める形式で書き出す。
+//! 全員に送られる公開イベントは座席0宛てのものだけを使い、
+//! 配牌とツモは各座席宛ての非公開イベントから復元する。
+
+use std::fmt::Write;
+
+use mahjong_core::settings::Lang;
+use mahjong_core::tile::{Tile, Wind};
+
+use crate::protocol::{CallType, DrawReason, ServerEvent};
+
+/// イベント列から牌譜を書き出す
+///
+/// `events` は [`GameDriver::event_log`](crate::driver::GameDriver::event_log) などで
+/// 記録した、卓が生成した全座席分のイベント。
+pub fn write_kifu(events: &[(usize, ServerEvent)], lang: Lang) -> String {
+    let mut out = String::new();
+    // 各座席の自風（局ごとに GameStarted で更新する）
+    let mut winds = [Wind::East, Wind::South, Wind::West, Wind::North];
+
+    for (seat, event) in events {
+        let seat = *seat;
+        match event {
+            ServerEvent::GameStarted {
+                seat_wind,
+                hand,
+                round_wind,
+                dora_indicators,
+                round_number,
+                honba,
+                riichi_sticks,
+                ..
+            } => {
+                winds[seat] = *seat_wind;
+                if seat == 0 {
+                    if !out.is_empty() {
+                        out.push('\n');
+                    }
+                    let _ = writeln!(
+                        out,
+                        "{}",
+                        round_header(*round_wind, *round_number, *honba, *riichi_sticks, lang)
+                    );
+                    let _ = writeln!(
+                        out,
+                        "{} {}",
+                        label(lang, "ドラ表示牌:", "Dora indicators:"),
+                        tiles_text(dora_indicators)
+                    );
+                }
+                let _ = writeln!(
+                    out,
+                    "{} {} {}",
+                    seat_wind.name(lang),
+                    label(lang, "配牌", "deal"),
+                    tiles_text(hand)
+                );
+            }
+            ServerEvent::TileDrawn { tile, .. } => {
+                let _ = writeln!(
+                    out,
+                    "{} {} {tile}",
+                    winds[seat].name(lang),
+                    label(lang, "ツモ", "draws")
+                );
+            }
+            _ if seat != 0 => {}
+            ServerEvent::TileDiscarded {
+                player,
+                tile,
+                is_tsumogiri,
+            } => {
+                let tsumogiri = if *is_tsumogiri {
+                    label(lang, "（ツモ切り）", " (tsumogiri)")
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "{} {} {tile}{tsumogiri}",
+                    player.name(lang),
+                    label(lang, "打", "discards")
+                );
+            }
+            ServerEvent::PlayerCalled {
+                player,
+                call_type,
+                called_tile,
+                tiles,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "{} {} {called_tile} [{}]",
+                    player.name(lang),
+                    call_name(call_type, lang),
+                    tiles_text(tiles)
+                );
+            }
+            ServerEvent::PlayerRiichi { player, .. } => {
+                let _ = writeln!(
+                    out,
+                    "{} {}",
+                    player.name(lang),
+                    label(lang, "リーチ", "declares riichi")
+                );
+            }
+            ServerEvent::DoraIndicatorsUpdated { dora_indicators } => {
+                let _ = writeln!(
+                    out,
+                    "{} {}",
+                    label(lang, "ドラ表示牌:", "Dora indicators:"),
+                    tiles_text(dora_indicators)
+                );
+            }
+            ServerEvent::RoundWon {
+                winner,
+                loser,
+                winning_tile,
+                yaku_list,
+                han,
+                fu,
+                score_points,
+                rank,
+                has_opened,
+                uradora_indicators,
+                ..
+            } => {
+                let how = match loser {
+                    Some(loser) => match lang {
+                        Lang::Ja => format!("ロン {winning_tile}（放銃: {}）", loser.name(lang)),
+                        Lang::En => format!("Ron on {winning_tile} from {}", loser.name(lang)),
+                    },
+                    None => format!("{} {winning_tile}", label(lang, "ツモ", "Tsumo on")),
+                };
+                let _ = writeln!(
+                    out,
+                    "{} {} {how}",
+                    label(lang, "和了:", "Win:"),
+                    winner.name(lang)
+                );
+                if !uradora_indicators.is_empty() {
+                    let _ = writeln!(
+                        out,
+                        "{} {}",
+                        label(lang, "裏ドラ表示牌:", "Ura dora indicators:"),
+                        tiles_text(uradora_indicators)
+                    );
+                }
+                let yaku: Vec<String> = yaku_list
+                    .iter()
+                    .map(|(item, han)| format!("{} {han}", item.name(*has_opened, lang)))
+                    .collect();
+                let _ = writeln!(out, "  {}", yaku.join(", "));
+                let rank_name = rank.name(lang);
+                let rank_text = if rank_name.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {rank_name}")
+                };
+                let _ = match (lang, fu) {
+                    (Lang::Ja, Some(fu)) => {
+                        writeln!(out, "  {han}翻{fu}符{rank_text} {score_points}点")
+                    }
+                    (Lang::Ja, None) => writeln!(out, "  {han}翻{rank_text} {score_points}点"),
+                    (Lang::En, Some(fu)) => {
+                        writeln!(out, "  {han} han {fu} fu{rank_text}, {score_points} points")
+                    }
+                    (Lang::En, None) => {
+                        writeln!(out, "  {han} han{rank_text}, {score_points} points")
+                    }
+                };
+            }
+            ServerEvent::RoundDraw { reason, tenpai, .. } => {
+                let _ = write!(
+                    out,
+                    "{} {}",
+                    label(lang, "流局:", "Draw:"),
+                    draw_reason_name(reason, lang)
+                );
+                if matches!(reason, DrawReason::Exhaustive) {
+                    let names: Vec<&str> = tenpai.iter().map(|w| w.name(lang)).collect();
+                    let _ = write!(
+                        out,
+                        " {} {}",
+                        label(lang, "聴牌:", "tenpai:"),
+                        if names.is_empty() {
+                            label(lang, "なし", "none").to_string()
+                        } else {
+                            names.join(" ")
+                        }
+                    );
+                }
+                out.push('\n');
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// 言語に応じた文字列を返す
+fn label(lang: Lang, ja: &'static str, en: &'static str) -> &'static str {
+    match lang {
+        Lang::Ja => ja,
+        Lang::En => en,
+    }
+}
+
+/// 局の見出しを返す（例: `東1局 0本場 供託0` / `East 1, honba 0, riichi sticks 0`）
+fn round_header(
+    round_wind: Wind,
+    round_number: usize,
+    honba: usize,
+    riichi_sticks: usize,
+    lang: Lang,
+) -> String {
+    let kyoku = round_number % 4 + 1;
+    match lang {
+        Lang::Ja => format!(
+            "{}{kyoku}局 {honba}本場 供託{riichi_sticks}",
+            round_wind.name(lang)
+        ),
+        Lang::En => format!(
+            "{} {kyoku}, honba {honba}, riichi sticks {riichi_sticks}",
+            round_wind.name(lang)
+        ),
+    }
+}
+
+fn tiles_text(tiles: &[Tile]) -> String {
+    tiles.iter().map(|t| t.to_string()).collect()
+}
+
+fn call_name(call_type: &CallType, lang: Lang) -> &'static str {
+    match call_type {
+        CallType::Ron => label(lang, "ロン", "ron"),
+        CallType::Pon => label(lang, "ポン", "pon"),
+        CallType::Chi => label(lang, "チー", "chi"),
+        CallType::Ankan => label(lang, "暗槓", "closed kan"),
+        CallType::Daiminkan => label(lang, "大明槓", "open kan"),
+        CallType::Kakan => label(lang, "加槓", "added kan"),
+    }
+}
+
+fn draw_reason_name(reason: &DrawReason, lang: Lang) -> &'static str {
+    match reason {
+        DrawReason::Exhaustive => label(lang, "荒牌流局", "exhaustive draw"),
+        DrawReason::FourWinds => label(lang, "四風連打", "four winds"),
+        DrawReason::FourRiichi => label(lang, "四家立直", "four riichi"),
+        DrawReason::NineTerminals => label(lang, "九種九牌", "nine terminals"),
+        DrawReason::FourKans => label(lang, "四槓散了", "four kans"),
+        DrawReason::TripleRon => label(lang, "三家和", "triple ron"),
+        DrawReason::DoubleRon => label(lang, "ダブロン流局", "double ron"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::personalities::default_cpu_configs;
+    use crate::driver::GameDriver;
+    use crate::table::GameSettings;
+
+    fn played_round_log() -> Vec<(usize, ServerEvent)> {
+        let mut driver = GameDriver::new(GameSettings::default());
+        let configs = default_cpu_configs();
+        for seat in 0..4 {
+            driver.set_cpu(seat, configs[seat % configs.len()].clone());
+        }
+        driver.enable_event_log();
+        driver.start_game_with_seed(42);
+        driver.run_until_blocked();
+        assert!(driver.is_round_over());
+        driver.event_log().to_vec()
+    }
+
+    #[test]
+    fn test_write_kifu_ja() {
+        let kifu = write_kifu(&played_round_log(), Lang::Ja);
+        assert!(kifu.starts_with("東1局 0本場 供託0\n"));
+        assert_eq!(kifu.matches("配牌").count(), 4);
+        assert!(kifu.contains("東 ツモ "));
+        assert!(kifu.contains(" 打 "));
+        assert!(kifu.contains("和了:") || kifu.contains("流局:"));
+    }
+
+    #[test]
+    fn test_write_kifu_en() {
+        let kifu = write_kifu(&played_round_log(), Lang::En);
+        assert!(kifu.starts_with("East 1, honba 0, riichi sticks 0\n"));
+        assert!(kifu.contains("East draws "));
+        assert!(kifu.contains("Win:") || kifu.contains("Draw:"));
+    }
+
+    #[test]
+    fn test_write_kifu_result_line() {
+        let events = vec![(
+            0,
+            ServerEvent::RoundDraw {
+                scores: [25000; 4],
+                reason: DrawReason::Exhaustive,
+                tenpai: vec![Wind::East, Wind::West],
+                riichi_sticks: 0,
+                player_hands: Vec::new(),
+                declarer: None,
+            },
+        )];
+        assert_eq!(
+            write_kifu(&events, Lang::Ja),
+            "流局: 荒牌流局 聴牌: 東 西\n"
+        );
+        assert_eq!(
+            write_kifu(&events, Lang::En),
+            "Draw: exhaustive draw tenpai: East West\n"
+        );
+    }
+}
@@ -1,6 +1,10 @@
 pub mod action;
+pub mod agent;
+#[cfg(feature = "tokio")]
+pub mod async_driver;
 pub mod cpu;
 pub mod driver;
+pub mod kifu;
 pub mod player;
 pub mod protocol;
 pub mod round;
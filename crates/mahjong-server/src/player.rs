@@ -2,8 +2,12 @@
 //!
 //! 各プレイヤーの手牌、捨て牌、点数、リーチ状態などを管理する。
 
+use mahjong_core::board::actions;
+use mahjong_core::error::Result;
 use mahjong_core::hand::Hand;
-use mahjong_core::hand_info::meld::{Meld, MeldFrom, MeldType};
+use mahjong_core::hand_info::calls;
+use mahjong_core::hand_info::meld::MeldFrom;
+use mahjong_core::hand_info::status::Status;
 use mahjong_core::tile::{Tile, TileType, Wind};
 use serde::{Deserialize, Serialize};
 
@@ -19,16 +23,10 @@ pub struct Player {
     pub discards: Vec<Discard>,
     /// 持ち点
     pub score: i32,
-    /// リーチしているか
-    pub is_riichi: bool,
-    /// ダブルリーチか
-    pub is_double_riichi: bool,
-    /// 一発が有効か
-    pub is_ippatsu: bool,
-    /// 第一ツモか（天和・地和判定用）
-    pub is_first_turn: bool,
-    /// 副露によって一巡目が中断されたか
-    pub first_turn_interrupted: bool,
+    /// 立直・ダブル立直・一発・第一ツモの状態
+    ///
+    /// `Status::on_riichi_declared` / `on_turn_passed` / `on_call_made` で遷移させる。
+    pub status: Status,
     /// リーチ後フリテン（リーチ後にロン見逃し → 局終了まで永続）
     pub is_riichi_furiten: bool,
     /// 同巡フリテン（ロン見逃し → 自分のツモ番で解除）
@@ -55,16 +53,16 @@ impl Player {
     /// 新しいプレイヤーを作成する
     pub fn new(seat_wind: Wind, tiles: Vec<Tile>, initial_score: i32) -> Self {
         let hand = Hand::new(tiles, None);
+        let mut status = Status::new();
+        status.seat_wind = seat_wind;
+        status.is_dealer = seat_wind == Wind::East;
+        status.is_first_turn = true;
         Player {
             seat_wind,
             hand,
             discards: Vec::new(),
             score: initial_score,
-            is_riichi: false,
-            is_double_riichi: false,
-            is_ippatsu: false,
-            is_first_turn: true,
-            first_turn_interrupted: false,
+            status,
             is_riichi_furiten: false,
             is_temporary_furiten: false,
             forbidden_discards: Vec::new(),
@@ -83,6 +81,11 @@ impl Player {
         self.forbidden_discards = tile_types;
     }
 
+    /// 喰い替え禁止で次の打牌に限り捨てられない牌種を返す
+    pub fn forbidden_discards(&self) -> &[TileType] {
+        &self.forbidden_discards
+    }
+
     /// 指定牌が喰い替え禁止により打牌できないかを返す
     pub fn is_swap_call_forbidden(&self, tile: Tile) -> bool {
         self.forbidden_discards.contains(&tile.get())
@@ -92,6 +95,22 @@ impl Player {
     /// tile が Some(牌) なら手牌からその牌を探して捨てる（手出し）
     /// tile が None ならツモ切り
     pub fn try_discard(&mut self, tile: Option<Tile>) -> Option<Tile> {
+        let discarded = self.take_discard(tile)?;
+        self.status.on_turn_passed();
+        Some(discarded)
+    }
+
+    /// 立直宣言牌を捨てて立直する
+    ///
+    /// 宣言牌の打牌では一発・第一ツモを消さず、`declare_riichi` で立直を宣言する。
+    pub fn try_discard_riichi(&mut self, tile: Option<Tile>) -> Option<Tile> {
+        let discarded = self.take_discard(tile)?;
+        self.declare_riichi();
+        Some(discarded)
+    }
+
+    /// 手牌から指定牌を取り除いて河に置く（状態の遷移は呼び出し側で行う）
+    fn take_discard(&mut self, tile: Option<Tile>) -> Option<Tile> {
         // 喰い替え禁止: 鳴き直後に禁止された牌種は捨てられない
         if let Some(target) = tile
             && self.is_swap_call_forbidden(target)
@@ -133,8 +152,6 @@ impl Player {
             is_called: false,
         });
 
-        self.is_ippatsu = false;
-        self.is_first_turn = false;
         // 鳴き直後の打牌が完了したので喰い替え制限を解除する
         self.forbidden_discards.clear();
 
@@ -164,19 +181,21 @@ impl Player {
 
     /// 門前（鳴いていない）かどうか
     pub fn is_menzen(&self) -> bool {
-        self.hand.melds().iter().all(|o| {
-            // 暗カンは門前扱い
-            o.from == MeldFrom::Myself
-        })
+        // 暗カンは門前扱い
+        self.hand.is_concealed()
+    }
+
+    /// リーチしているか
+    pub fn is_riichi(&self) -> bool {
+        self.status.has_claimed_riichi
     }
 
     /// リーチ宣言を行う
-    pub fn declare_riichi(&mut self, is_double: bool) {
-        self.is_riichi = true;
-        self.is_ippatsu = true;
-        if is_double {
-            self.is_double_riichi = true;
-        }
+    ///
+    /// 一発を有効にし、第一ツモで鳴きが入っていなければダブルリーチとする。
+    /// 宣言牌の打牌と合わせて行う場合は `try_discard_riichi` を使う。
+    pub fn declare_riichi(&mut self) {
+        self.status.on_riichi_declared();
         // リーチ棒代を引く
         self.score -= 1000;
     }
@@ -185,13 +204,7 @@ impl Player {
 
     /// ポン可能か判定する
     pub fn can_pon(&self, tile: Tile) -> bool {
-        let count = self
-            .hand
-            .tiles()
-            .iter()
-            .filter(|t| t.get() == tile.get())
-            .count();
-        count >= 2
+        !calls::pon_options(&self.hand, tile).is_empty()
     }
 
     /// ポン可能な手牌の組み合わせを返す
@@ -199,141 +212,39 @@ impl Player {
     /// 通常は1通りだが、手牌に赤ドラと通常牌の両方がある場合は
     /// 「赤ドラを含む刻子」と「赤ドラを含まない刻子」の2通りを返す。
     pub fn pon_options(&self, tile: Tile) -> Vec<[Tile; 2]> {
-        let tiles_of_type: Vec<Tile> = self
-            .hand
-            .tiles()
-            .iter()
-            .filter(|t| t.get() == tile.get())
-            .cloned()
-            .collect();
-
-        if tiles_of_type.len() < 2 {
-            return vec![];
-        }
-
-        // (赤ドラを含むペアか否か) で重複を除いた組み合わせを列挙する
-        let mut has_with_red = false;
-        let mut has_without_red = false;
-        let mut options = vec![];
-
-        for i in 0..tiles_of_type.len() {
-            for j in (i + 1)..tiles_of_type.len() {
-                let includes_red = tiles_of_type[i].is_red_dora() || tiles_of_type[j].is_red_dora();
-                if includes_red && !has_with_red {
-                    has_with_red = true;
-                    options.push([tiles_of_type[i], tiles_of_type[j]]);
-                } else if !includes_red && !has_without_red {
-                    has_without_red = true;
-                    options.push([tiles_of_type[i], tiles_of_type[j]]);
-                }
-            }
-        }
-
-        options
+        calls::pon_options(&self.hand, tile)
     }
 
     /// チー可能な組み合わせを返す
     ///
-    /// 各要素は [TileType; 2] で、手牌から使う2枚の牌の種類を表す。
-    /// 字牌はチー不可。
+    /// 各要素は手牌から使う2枚の牌を表す。字牌はチー不可。
     pub fn chi_options(&self, tile: Tile) -> Vec<[Tile; 2]> {
-        if tile.is_honour() {
-            return vec![];
-        }
-
-        let tt = tile.get();
-        let tiles = self.hand.tiles();
-        let mut options = vec![];
-
-        // 同じスーツの範囲を計算
-        let suit_start = (tt / 9) * 9;
-        let suit_end = suit_start + 9;
-
-        // パターン (a, b) に対して、手牌から実際の牌インスタンスを列挙して選択肢を追加する。
-        // 同じ牌種に赤ドラと通常牌の両方がある場合は別の選択肢として追加する。
-        let mut add_pattern = |a: TileType, b: TileType| {
-            let tiles_a: Vec<Tile> = tiles.iter().filter(|t| t.get() == a).cloned().collect();
-            let tiles_b: Vec<Tile> = tiles.iter().filter(|t| t.get() == b).cloned().collect();
-            if tiles_a.is_empty() || tiles_b.is_empty() {
-                return;
-            }
-            // (赤ドラか否か, 赤ドラか否か) の組み合わせで重複を除く
-            let mut seen = std::collections::HashSet::new();
-            for ta in &tiles_a {
-                for tb in &tiles_b {
-                    let key = (ta.is_red_dora(), tb.is_red_dora());
-                    if seen.insert(key) {
-                        options.push([*ta, *tb]);
-                    }
-                }
-            }
-        };
-
-        // パターン1: [tt-2, tt-1] + tt （例: 鳴く牌が3m, 手牌に1m2mがある）
-        if tt >= suit_start + 2 {
-            add_pattern(tt - 2, tt - 1);
-        }
-
-        // パターン2: [tt-1, tt+1] + tt （例: 鳴く牌が5m, 手牌に4m6mがある）
-        if tt > suit_start && tt + 1 < suit_end {
-            add_pattern(tt - 1, tt + 1);
-        }
-
-        // パターン3: [tt+1, tt+2] + tt （例: 鳴く牌が1m, 手牌に2m3mがある）
-        if tt + 2 < suit_end {
-            add_pattern(tt + 1, tt + 2);
-        }
-
-        options
+        calls::chi_options(&self.hand, tile)
     }
 
     /// 大明カン可能か判定する
     pub fn can_daiminkan(&self, tile: Tile) -> bool {
-        let count = self
-            .hand
-            .tiles()
-            .iter()
-            .filter(|t| t.get() == tile.get())
-            .count();
-        count >= 3
+        calls::can_daiminkan(&self.hand, tile)
     }
 
     /// 暗カン可能な牌種一覧を返す
+    ///
+    /// リーチ後はツモ牌で揃えた、待ちの変わらない暗カンのみを返す。
     pub fn ankan_options(&self) -> Vec<TileType> {
-        let mut counts = [0u8; Tile::LEN];
-        for tile in self.hand.tiles() {
-            counts[tile.get() as usize] += 1;
-        }
-        if let Some(drawn) = self.hand.drawn() {
-            counts[drawn.get() as usize] += 1;
+        let options = calls::ankan_options(&self.hand);
+        if !self.is_riichi() {
+            return options;
         }
-
-        counts
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, &count)| (count == 4).then_some(idx as TileType))
+        let waits = self.hand.winning_tile_types();
+        options
+            .into_iter()
+            .filter(|&tile_type| actions::can_ankan_after_riichi(&self.hand, &waits, tile_type))
             .collect()
     }
 
     /// 加カン可能な牌種一覧を返す
     pub fn kakan_options(&self) -> Vec<TileType> {
-        let mut counts = [0u8; Tile::LEN];
-        for tile in self.hand.tiles() {
-            counts[tile.get() as usize] += 1;
-        }
-        if let Some(drawn) = self.hand.drawn() {
-            counts[drawn.get() as usize] += 1;
-        }
-
-        self.hand
-            .melds()
-            .iter()
-            .filter(|open| open.category == MeldType::Pon)
-            .filter_map(|open| {
-                let tile_type = open.tiles[0].get();
-                (counts[tile_type as usize] >= 1).then_some(tile_type)
-            })
-            .collect()
+        calls::kakan_options(&self.hand)
     }
 
     /// 加カンで追加する実際の牌を返す（赤ドラも区別する）
@@ -380,187 +291,46 @@ impl Player {
     /// ポンを実行する
     ///
     /// 手牌から同じ種類の牌2枚を取り除き、鳴いた牌と合わせて副露に追加する。
-    pub fn do_pon(&mut self, called_tile: Tile, hand_tiles: [Tile; 2], from: MeldFrom) {
-        let mut indices: Vec<usize> = Vec::new();
-        for &target in &hand_tiles {
-            for (i, t) in self.hand.tiles().iter().enumerate() {
-                if *t == target && !indices.contains(&i) {
-                    indices.push(i);
-                    break;
-                }
-            }
-        }
-
-        let t1 = self.hand.tiles()[indices[0]];
-        let t2 = self.hand.tiles()[indices[1]];
-
-        self.hand.remove_tiles_by_indices(&mut indices);
-
-        self.hand.add_meld(Meld {
-            tiles: vec![t1, t2, called_tile],
-            category: MeldType::Pon,
-            from,
-            called_tile: Some(called_tile),
-        });
-
-        self.is_first_turn = false;
-        self.is_ippatsu = false;
+    /// 必要な牌が手牌になければエラーを返し、手牌は変更しない（以下の鳴きも同様）。
+    pub fn do_pon(
+        &mut self,
+        called_tile: Tile,
+        hand_tiles: [Tile; 2],
+        from: MeldFrom,
+    ) -> Result<()> {
+        self.hand.apply_pon(called_tile, hand_tiles, from)?;
+        self.status.on_call_made();
+        Ok(())
     }
 
     /// チーを実行する
     ///
     /// 手牌から指定種類の牌2枚を取り除き、鳴いた牌と合わせて副露に追加する。
-    pub fn do_chi(&mut self, called_tile: Tile, hand_tiles: [Tile; 2]) {
-        let mut indices: Vec<usize> = Vec::new();
-        for &target in &hand_tiles {
-            for (i, t) in self.hand.tiles().iter().enumerate() {
-                if *t == target && !indices.contains(&i) {
-                    indices.push(i);
-                    break;
-                }
-            }
-        }
-
-        let t1 = self.hand.tiles()[indices[0]];
-        let t2 = self.hand.tiles()[indices[1]];
-
-        self.hand.remove_tiles_by_indices(&mut indices);
-
-        // 順子の牌をソートして副露に追加
-        let mut chi_tiles = [t1, t2, called_tile];
-        chi_tiles.sort();
-
-        self.hand.add_meld(Meld {
-            tiles: chi_tiles.to_vec(),
-            category: MeldType::Chi,
-            from: MeldFrom::Previous, // チーは常に上家から
-            called_tile: Some(called_tile),
-        });
-
-        self.is_first_turn = false;
-        self.is_ippatsu = false;
+    pub fn do_chi(&mut self, called_tile: Tile, hand_tiles: [Tile; 2]) -> Result<()> {
+        self.hand.apply_chi(called_tile, hand_tiles)?;
+        self.status.on_call_made();
+        Ok(())
     }
 
     /// 大明カンを実行する
-    pub fn do_daiminkan(&mut self, called_tile: Tile, from: MeldFrom) {
-        let tt = called_tile.get();
-        let mut indices: Vec<usize> = Vec::new();
-        for (i, t) in self.hand.tiles().iter().enumerate() {
-            if t.get() == tt && indices.len() < 3 {
-                indices.push(i);
-            }
-        }
-        assert_eq!(indices.len(), 3, "大明カンに必要な3枚がありません");
-
-        let t1 = self.hand.tiles()[indices[0]];
-        let t2 = self.hand.tiles()[indices[1]];
-        let t3 = self.hand.tiles()[indices[2]];
-
-        self.hand.remove_tiles_by_indices(&mut indices);
-        self.hand.add_meld(Meld {
-            tiles: vec![t1, t2, t3],
-            category: MeldType::Kan,
-            from,
-            called_tile: Some(called_tile),
-        });
-
-        self.is_first_turn = false;
-        self.is_ippatsu = false;
+    pub fn do_daiminkan(&mut self, called_tile: Tile, from: MeldFrom) -> Result<()> {
+        self.hand.apply_daiminkan(called_tile, from)?;
+        self.status.on_call_made();
+        Ok(())
     }
 
     /// 暗カンを実行する
-    pub fn do_ankan(&mut self, tile_type: TileType) {
-        let mut indices: Vec<usize> = Vec::new();
-        for (i, t) in self.hand.tiles().iter().enumerate() {
-            if t.get() == tile_type {
-                indices.push(i);
-            }
-        }
-
-        let drawn = self.hand.drawn();
-        let drawn_matches = drawn.map(|t| t.get() == tile_type).unwrap_or(false);
-        assert_eq!(
-            indices.len() + usize::from(drawn_matches),
-            4,
-            "暗カンに必要な4枚が揃っていません"
-        );
-
-        let mut kan_tiles: Vec<Tile> = indices.iter().map(|&idx| self.hand.tiles()[idx]).collect();
-
-        // カン牌を先に除去する。ツモ牌を手牌に戻してソートすると
-        // indices が指す位置がずれて誤った牌を削除してしまうため。
-        self.hand.remove_tiles_by_indices(&mut indices);
-
-        if drawn_matches {
-            kan_tiles.push(drawn.unwrap());
-        } else if let Some(d) = drawn {
-            // ツモ牌がカン牌でない場合、手牌に戻す（嶺上ツモで上書きされないよう）
-            self.hand.tiles_mut().push(d);
-            self.hand.sort();
-        }
-        self.hand.set_drawn(None);
-
-        let stored_tiles = Self::stored_kan_tiles(kan_tiles);
-
-        self.hand.add_meld(Meld {
-            tiles: stored_tiles,
-            category: MeldType::Kan,
-            from: MeldFrom::Myself,
-            called_tile: None,
-        });
-
-        self.is_first_turn = false;
-        self.is_ippatsu = false;
+    pub fn do_ankan(&mut self, tile_type: TileType) -> Result<()> {
+        self.hand.apply_ankan(tile_type)?;
+        self.status.on_call_made();
+        Ok(())
     }
 
     /// 加カンを実行する
-    pub fn do_kakan(&mut self, tile_type: TileType) {
-        let drawn_matches = self
-            .hand
-            .drawn()
-            .map(|t| t.get() == tile_type)
-            .unwrap_or(false);
-        let added_tile = if drawn_matches {
-            let tile = self.hand.drawn().expect("加カンに必要なツモ牌がありません");
-            self.hand.set_drawn(None);
-            tile
-        } else {
-            let idx = self
-                .hand
-                .tiles()
-                .iter()
-                .position(|t| t.get() == tile_type)
-                .expect("加カンに必要な牌が手牌にありません");
-            let tile = self.hand.tiles_mut().remove(idx);
-
-            if let Some(drawn_tile) = self.hand.drawn() {
-                self.hand.tiles_mut().push(drawn_tile);
-                self.hand.sort();
-                self.hand.set_drawn(None);
-            }
-            tile
-        };
-
-        let open = self
-            .hand
-            .melds_mut()
-            .iter_mut()
-            .find(|open| open.category == MeldType::Pon && open.tiles[0].get() == tile_type)
-            .expect("加カン対象のポンがありません");
-        open.category = MeldType::Kakan;
-        open.called_tile = Some(added_tile);
-
-        self.is_first_turn = false;
-        self.is_ippatsu = false;
-    }
-
-    fn stored_kan_tiles(mut kan_tiles: Vec<Tile>) -> Vec<Tile> {
-        let mut stored = Vec::with_capacity(3);
-        if let Some(red_pos) = kan_tiles.iter().position(|tile| tile.is_red_dora()) {
-            stored.push(kan_tiles.remove(red_pos));
-        }
-        stored.extend(kan_tiles.into_iter().take(3 - stored.len()));
-        stored
+    pub fn do_kakan(&mut self, tile_type: TileType) -> Result<()> {
+        self.hand.apply_kakan(tile_type)?;
+        self.status.on_call_made();
+        Ok(())
     }
 
     /// 手牌に含まれる槓子の数を返す
@@ -586,6 +356,7 @@ impl Player {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mahjong_core::hand_info::meld::MeldType;
     use mahjong_core::tile::Tile;
 
     fn make_test_tiles() -> Vec<Tile> {
@@ -614,7 +385,7 @@ mod tests {
         assert_eq!(player.score, 25000);
         assert_eq!(player.hand.tiles().len(), 13);
         assert!(player.discards.is_empty());
-        assert!(!player.is_riichi);
+        assert!(!player.is_riichi());
         assert!(player.is_dealer());
     }
 
@@ -655,14 +426,32 @@ mod tests {
     fn test_riichi_declaration() {
         let mut player = Player::new(Wind::East, make_test_tiles(), 25000);
 
-        player.declare_riichi(false);
-        assert!(player.is_riichi);
-        assert!(!player.is_double_riichi);
-        assert!(player.is_ippatsu);
+        // 第一ツモの宣言牌ならダブルリーチ
+        player.draw(Tile::new(Tile::Z5));
+        assert!(player.try_discard_riichi(None).is_some());
+        assert!(player.is_riichi());
+        assert!(player.status.is_double_riichi);
+        assert!(player.status.is_unbroken);
         assert_eq!(player.score, 24000); // 1000点引かれる
 
-        player.declare_riichi(true);
-        assert!(player.is_double_riichi);
+        // 次の打牌で一発が消える
+        player.draw(Tile::new(Tile::Z6));
+        player.tsumogiri();
+        assert!(!player.status.is_unbroken);
+        assert!(player.status.is_double_riichi);
+    }
+
+    #[test]
+    fn test_riichi_declaration_after_first_turn() {
+        let mut player = Player::new(Wind::East, make_test_tiles(), 25000);
+        player.draw(Tile::new(Tile::Z5));
+        player.tsumogiri();
+
+        player.draw(Tile::new(Tile::Z6));
+        assert!(player.try_discard_riichi(None).is_some());
+        assert!(player.is_riichi());
+        assert!(!player.status.is_double_riichi);
+        assert!(player.status.is_unbroken);
     }
 
     #[test]
@@ -765,11 +554,13 @@ mod tests {
         let mut player = Player::new(Wind::South, tiles, 25000);
         let called = Tile::new(Tile::M1);
 
-        player.do_pon(
-            called,
-            [Tile::new(Tile::M1), Tile::new(Tile::M1)],
-            MeldFrom::Previous,
-        );
+        player
+            .do_pon(
+                called,
+                [Tile::new(Tile::M1), Tile::new(Tile::M1)],
+                MeldFrom::Previous,
+            )
+            .unwrap();
 
         // 手牌が11枚になること（13 - 2 = 11）
         assert_eq!(player.hand.tiles().len(), 11);
@@ -800,7 +591,9 @@ mod tests {
         let mut player = Player::new(Wind::South, tiles, 25000);
         let called = Tile::new(Tile::M4);
 
-        player.do_chi(called, [Tile::new(Tile::M3), Tile::new(Tile::M5)]);
+        player
+            .do_chi(called, [Tile::new(Tile::M3), Tile::new(Tile::M5)])
+            .unwrap();
 
         // 手牌が11枚になること
         assert_eq!(player.hand.tiles().len(), 11);
@@ -842,12 +635,37 @@ mod tests {
         assert_eq!(player.ankan_options(), vec![Tile::M1]);
     }
 
+    #[test]
+    fn test_ankan_options_after_riichi() {
+        // 111m の刻子をカンしても 1z 単騎のまま
+        let mut player = Player::new(
+            Wind::East,
+            Hand::from("111m234p567s789m1z").tiles().to_vec(),
+            25000,
+        );
+        player.status.has_claimed_riichi = true;
+        player.draw(Tile::new(Tile::M1));
+        assert_eq!(player.ankan_options(), vec![Tile::M1]);
+
+        // 3334m の 3m をカンすると 2-4-5m 待ちが 4m 単騎に変わる
+        let mut player = Player::new(
+            Wind::East,
+            Hand::from("3334m456p789s111z").tiles().to_vec(),
+            25000,
+        );
+        player.status.has_claimed_riichi = true;
+        player.draw(Tile::new(Tile::M3));
+        assert!(player.ankan_options().is_empty());
+    }
+
     #[test]
     fn test_do_daiminkan() {
         let hand = Hand::from("111m234p567s789m1z");
         let mut player = Player::new(Wind::South, hand.tiles().to_vec(), 25000);
 
-        player.do_daiminkan(Tile::new(Tile::M1), MeldFrom::Previous);
+        player
+            .do_daiminkan(Tile::new(Tile::M1), MeldFrom::Previous)
+            .unwrap();
 
         assert_eq!(player.hand.tiles().len(), 10);
         assert_eq!(player.hand.melds().len(), 1);
@@ -855,13 +673,25 @@ mod tests {
         assert!(!player.is_menzen());
     }
 
+    #[test]
+    fn test_do_ankan_without_four_tiles_is_error() {
+        let hand = Hand::from("111m234p567s789m1z 2m");
+        let mut player = Player::new(Wind::South, hand.tiles().to_vec(), 25000);
+        player.draw(hand.drawn().unwrap());
+
+        assert!(player.do_ankan(Tile::M1).is_err());
+        // 失敗した場合は手牌も一発などの状態も変わらない
+        assert_eq!(player.hand.tiles().len(), 13);
+        assert!(player.hand.melds().is_empty());
+    }
+
     #[test]
     fn test_do_ankan() {
         let hand = Hand::from("111m234p567s789m1z 1m");
         let mut player = Player::new(Wind::South, hand.tiles().to_vec(), 25000);
         player.draw(hand.drawn().unwrap());
 
-        player.do_ankan(Tile::M1);
+        player.do_ankan(Tile::M1).unwrap();
 
         assert_eq!(player.hand.tiles().len(), 10);
         assert!(player.hand.drawn().is_none());
@@ -890,7 +720,7 @@ mod tests {
         let mut player = Player::new(Wind::South, tiles, 25000);
         player.draw(Tile::new_red(Tile::M5));
 
-        player.do_ankan(Tile::M5);
+        player.do_ankan(Tile::M5).unwrap();
 
         assert!(
             player.hand.melds()[0]
@@ -909,7 +739,7 @@ mod tests {
         let mut player = Player::new(Wind::South, hand.tiles().to_vec(), 25000);
         player.draw(hand.drawn().unwrap());
 
-        player.do_ankan(Tile::S9);
+        player.do_ankan(Tile::S9).unwrap();
 
         assert_eq!(player.hand.tiles().len(), 10);
         assert!(player.hand.drawn().is_none());
@@ -940,7 +770,7 @@ mod tests {
         let mut player = Player::new(Wind::South, vec![], 25000);
         player.hand = Hand::from("234p567s789m1z 111m 1m");
 
-        player.do_kakan(Tile::M1);
+        player.do_kakan(Tile::M1).unwrap();
 
         assert_eq!(player.hand.tiles().len(), 10);
         assert!(player.hand.drawn().is_none());
@@ -954,7 +784,7 @@ mod tests {
         let mut player = Player::new(Wind::South, vec![], 25000);
         player.hand = Hand::from("127m234p567s1z 111m 9s");
 
-        player.do_kakan(Tile::M1);
+        player.do_kakan(Tile::M1).unwrap();
 
         assert!(player.hand.drawn().is_none());
         assert_eq!(player.hand.tiles().len(), 10);
@@ -969,7 +799,7 @@ mod tests {
         player.hand = Hand::from("234p567s789m1z 555m");
         player.draw(Tile::new_red(Tile::M5));
 
-        player.do_kakan(Tile::M5);
+        player.do_kakan(Tile::M5).unwrap();
 
         assert_eq!(player.hand.melds()[0].category, MeldType::Kakan);
         assert_eq!(
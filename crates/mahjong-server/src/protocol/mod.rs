@@ -24,6 +24,8 @@ pub enum DrawReason {
     FourKans,
     /// 三家和
     TripleRon,
+    /// 複数同時ロンによる流局（`MultipleRon::AbortiveDraw` のときの2人ロン）
+    DoubleRon,
 }
 
 /// 鳴きの種類
@@ -191,8 +193,8 @@ pub enum ServerEvent {
         yaku_list: Vec<(ScoreItem, u32)>,
         /// 翻数
         han: u32,
-        /// 符
-        fu: u32,
+        /// 符（役満は `None`）
+        fu: Option<u32>,
         /// 和了者が得た点数
         score_points: i32,
         /// 点数等級（満貫、跳満など。通常は `ScoreRank::Normal`）
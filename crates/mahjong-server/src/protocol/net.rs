@@ -12,7 +12,7 @@ use crate::cpu::client::{CpuConfig, CpuLevel, CpuPersonality};
 ///
 /// 互換性のない変更を入れる際にインクリメントする。
 /// `Hello` で照合し、不一致なら `ErrorCode::VersionMismatch` で切断する。
-pub const PROTOCOL_VERSION: u32 = 2;
+pub const PROTOCOL_VERSION: u32 = 3;
 
 /// CPUの強さ・性格の指定
 ///
@@ -403,7 +403,7 @@ mod tests {
                 (ScoreItem::Dora(DoraLabel::RedDora), 1),
             ],
             han: 5,
-            fu: 40,
+            fu: Some(40),
             score_points: 8000,
             rank: ScoreRank::Mangan,
             has_opened: true,
@@ -5,22 +5,31 @@
 
 #[cfg(debug_assertions)]
 mod diagnostics;
+pub mod replay;
+pub mod scenario;
 #[cfg(test)]
 mod test_helpers;
 
+use mahjong_core::board;
+use mahjong_core::board::{abortive_draw, draw_outcome};
+use mahjong_core::error::MahjongError;
 use mahjong_core::hand_info::hand_analyzer;
-use mahjong_core::settings::Settings;
+use mahjong_core::scoring::payment::Pao;
+use mahjong_core::settings::{KanDoraTiming, MultipleRon, Settings};
 use mahjong_core::tile::{Tile, TileType, Wind};
+use serde::{Deserialize, Serialize};
 
+use crate::action::Action;
 use crate::player::Player;
 use crate::protocol::{
     AvailableCall, CallType, DrawReason, MeldTiles, PlayerHandInfo, ServerEvent,
 };
 use crate::scoring;
 use crate::wall::Wall;
+use replay::{Deal, GameEvent};
 
 /// リーチ棒1本の点数
-const RIICHI_STICK_VALUE: i32 = 1000;
+pub(crate) const RIICHI_STICK_VALUE: i32 = 1000;
 /// リーチ宣言に必要な最低持ち点
 const RIICHI_MIN_SCORE: i32 = 1000;
 
@@ -118,8 +127,12 @@ pub struct Round {
     pub call_state: Option<CallState>,
     /// 直前のツモが嶺上牌か
     pub last_draw_was_dead_wall: bool,
+    /// 打牌後にめくる明槓の新ドラの枚数（`KanDoraTiming::AfterDiscard` のとき）
+    pub pending_kan_dora: usize,
     /// ゲーム設定
     pub settings: Settings,
+    /// 配牌からの操作記録（`Round::replay` で同じ局面を再構築できる）
+    log: Vec<GameEvent>,
 }
 
 impl Round {
@@ -140,7 +153,7 @@ impl Round {
         settings: Settings,
     ) -> Self {
         Self::with_wall(
-            Wall::new(),
+            Wall::new_with_red_fives(settings.red_fives, None),
             round_wind,
             dealer,
             initial_scores,
@@ -168,7 +181,7 @@ impl Round {
         settings: Settings,
     ) -> Self {
         Self::with_wall(
-            Wall::new_with_seed(seed),
+            Wall::new_with_red_fives(settings.red_fives, Some(seed)),
             round_wind,
             dealer,
             initial_scores,
@@ -193,15 +206,21 @@ impl Round {
         total_rounds: usize,
         settings: Settings,
     ) -> Self {
+        let log = vec![GameEvent::Deal(Box::new(Deal {
+            wall: wall.ordered_tiles(),
+            round_wind,
+            dealer,
+            scores: initial_scores,
+            honba,
+            riichi_sticks,
+            round_number,
+            total_rounds,
+            settings: settings.clone(),
+        }))];
         let dealt = wall.deal();
 
         // 座席の風を割り当て: dealer=東, 反時計回りに南西北
-        let winds = [
-            Wind::from_index((4 - dealer) % 4),
-            Wind::from_index((1 + 4 - dealer) % 4),
-            Wind::from_index((2 + 4 - dealer) % 4),
-            Wind::from_index((3 + 4 - dealer) % 4),
-        ];
+        let winds = board::seat_winds(dealer);
 
         let players = [
             Player::new(winds[0], dealt[0].clone(), initial_scores[0]),
@@ -244,7 +263,9 @@ impl Round {
             events,
             call_state: None,
             last_draw_was_dead_wall: false,
+            pending_kan_dora: 0,
             settings,
+            log,
         }
     }
 
@@ -312,14 +333,26 @@ impl Round {
 
         // 牌山が空なら流局
         if self.wall.is_empty() {
+            self.log.push(GameEvent::Draw {
+                seat: self.current_player,
+                tile: None,
+            });
             self.do_exhaustive_draw();
             return true;
         }
 
         let Some(tile) = self.wall.draw() else {
+            self.log.push(GameEvent::Draw {
+                seat: self.current_player,
+                tile: None,
+            });
             self.do_exhaustive_draw();
             return true;
         };
+        self.log.push(GameEvent::Draw {
+            seat: self.current_player,
+            tile: Some(tile),
+        });
         self.players[self.current_player].draw(tile);
         self.last_draw_was_dead_wall = false;
         self.phase = TurnPhase::WaitForDiscard;
@@ -350,6 +383,10 @@ impl Round {
         let Some(discarded) = self.players[self.current_player].try_discard(tile) else {
             return false;
         };
+        self.log.push(GameEvent::Discard {
+            seat: self.current_player,
+            tile,
+        });
 
         // 一発フラグは try_discard() 内で解除済み。
         // リーチ宣言牌の打牌は do_riichi() が別途処理し、そこでフラグを復元する。
@@ -380,6 +417,9 @@ impl Round {
             ));
         }
 
+        // 明槓の新ドラは打牌後にめくる（ロンでは新ドラが乗る）
+        self.reveal_pending_kan_dora();
+
         // 鳴き候補をチェック
         let call_state = self.check_available_calls(discarded, discarder);
         let has_any_calls = call_state.available_calls.iter().any(|c| !c.is_empty());
@@ -443,7 +483,7 @@ impl Round {
             }
 
             // リーチ中は鳴き不可
-            if player.is_riichi {
+            if player.is_riichi() {
                 if !available_calls[i].is_empty() {
                     responded[i] = false;
                 }
@@ -566,6 +606,10 @@ impl Round {
         }
 
         call_state.responded[player_idx] = true;
+        self.log.push(GameEvent::Call {
+            seat: player_idx,
+            response,
+        });
 
         // 全員応答済みなら解決
         if call_state.responded.iter().all(|&r| r) {
@@ -588,7 +632,7 @@ impl Round {
             let declared_ron = call_state.ron_declared.contains(&i);
 
             if had_ron && !declared_ron {
-                if self.players[i].is_riichi {
+                if self.players[i].is_riichi() {
                     // リーチ中 → リーチ後フリテン（局終了まで永続）
                     self.players[i].is_riichi_furiten = true;
                 } else {
@@ -610,14 +654,19 @@ impl Round {
             let mut sorted_winners = call_state.ron_declared.clone();
             sorted_winners.sort_by_key(|&p| (p + 4 - discarder) % 4);
 
-            if ron_count >= 3 && self.settings.triple_ron_draw {
-                // 三家和流局（最優先）
-                self.declare_special_draw(DrawReason::TripleRon, None);
+            if abortive_draw::is_multiple_ron_draw(ron_count, &self.settings) {
+                // 三家和・複数同時ロンによる流局（最優先）
+                let reason = if ron_count >= 3 {
+                    DrawReason::TripleRon
+                } else {
+                    DrawReason::DoubleRon
+                };
+                self.declare_special_draw(reason, None);
                 return;
             }
 
             // 複数同時ロンが有効かつ2人以上: 全員和了
-            let winners = if ron_count >= 2 && self.settings.multiple_ron {
+            let winners = if ron_count >= 2 && self.settings.multiple_ron == MultipleRon::AllWin {
                 sorted_winners
             } else {
                 // 上家取り: 最優先の1人のみ和了
@@ -688,17 +737,14 @@ impl Round {
         struct WinnerData {
             winner: usize,
             score_result: mahjong_core::scoring::score::ScoreResult,
-            deltas: [i32; 4],
             uradora_indicators: Vec<Tile>,
             score_points: i32,
         }
 
-        // 打順が最も早い和了者を rank=0 として本場・供託ボーナスの基準にする
-        let mut winner_data: Vec<WinnerData> = Vec::new();
-
-        for (rank, &winner) in winners.iter().enumerate() {
-            let honba_for_this = if rank == 0 { self.honba } else { 0 };
+        let mut ron_winners: Vec<scoring::RonWinner> = Vec::new();
+        let mut uradora_by_winner: Vec<Vec<Tile>> = Vec::new();
 
+        for &winner in &winners {
             let win_result = scoring::check_ron_with_flags_and_settings(
                 &self.players[winner],
                 winning_tile,
@@ -716,11 +762,7 @@ impl Round {
                 continue;
             };
 
-            let uradora_indicators = if self.players[winner].is_riichi {
-                self.wall.uradora_indicators()
-            } else {
-                vec![]
-            };
+            let uradora_indicators = self.uradora_for(winner);
 
             scoring::add_dora_to_score(
                 &mut score_result,
@@ -728,53 +770,50 @@ impl Round {
                 Some(winning_tile),
                 &dora_indicators,
                 &uradora_indicators,
+                &self.settings,
             );
 
-            let winner_is_dealer = self.players[winner].is_dealer();
-            let deltas = scoring::calculate_ron_score_deltas(
-                winner,
-                loser,
-                &score_result,
-                winner_is_dealer,
-                honba_for_this,
-            );
-
-            // 供託棒は打順最優先の和了者（winner_data の先頭）のみ取得
-            let riichi_bonus = if winner_data.is_empty() {
-                (riichi_sticks as i32) * RIICHI_STICK_VALUE
-            } else {
-                0
-            };
-            let score_points = deltas[winner] + riichi_bonus;
-
-            winner_data.push(WinnerData {
-                winner,
+            ron_winners.push(scoring::RonWinner {
+                seat: winner,
                 score_result,
-                deltas,
-                uradora_indicators,
-                score_points,
+                is_dealer: self.players[winner].is_dealer(),
+                pao: Pao::from_hand(&self.players[winner].hand, winner),
             });
+            uradora_by_winner.push(uradora_indicators);
         }
 
         // 安全のため: 和了成立者が0人ならフェーズを進めて返す
-        if winner_data.is_empty() {
+        if ron_winners.is_empty() {
             self.current_player = (loser + 1) % 4;
             self.phase = TurnPhase::Draw;
             return;
         }
 
-        // 全スコアデルタを合算して適用
-        for wd in &winner_data {
-            for i in 0..4 {
-                self.players[i].score += wd.deltas[i];
-            }
-        }
-        // 供託棒は打順最優先の和了者に付与
-        if riichi_sticks > 0 {
-            self.players[winner_data[0].winner].score +=
-                (riichi_sticks as i32) * RIICHI_STICK_VALUE;
-            self.riichi_sticks = 0;
+        // 本場・供託棒は打順最優先の和了者のみ取得する
+        let settlement = scoring::score_multiple_winners(
+            &ron_winners,
+            loser,
+            self.honba,
+            riichi_sticks,
+            &self.settings,
+        );
+        for (player, delta) in self.players.iter_mut().zip(settlement.deltas) {
+            player.score += delta;
         }
+        self.riichi_sticks = 0;
+
+        // winners は打順優先順なので、精算結果と和了者の並びは一致する
+        let winner_data: Vec<WinnerData> = ron_winners
+            .into_iter()
+            .zip(uradora_by_winner)
+            .zip(&settlement.payouts)
+            .map(|((ron_winner, uradora_indicators), payout)| WinnerData {
+                winner: payout.seat,
+                score_result: ron_winner.score_result,
+                uradora_indicators,
+                score_points: payout.points,
+            })
+            .collect();
 
         if !is_robbing_a_quad {
             self.mark_last_discard_as_called(loser);
@@ -830,7 +869,10 @@ impl Round {
         hand_tile_types: [Tile; 2],
     ) {
         let from = Player::meld_from_relative(caller, discarder);
-        self.players[caller].do_pon(called_tile, hand_tile_types, from);
+        if let Err(err) = self.players[caller].do_pon(called_tile, hand_tile_types, from) {
+            self.abandon_call(caller, Some(discarder), "pon", err);
+            return;
+        }
 
         // 捨て牌を「鳴かれた」としてマーク
         self.mark_last_discard_as_called(discarder);
@@ -877,7 +919,10 @@ impl Round {
     /// 大明カンを実行する
     fn execute_daiminkan(&mut self, caller: usize, discarder: usize, called_tile: Tile) {
         let from = Player::meld_from_relative(caller, discarder);
-        self.players[caller].do_daiminkan(called_tile, from);
+        if let Err(err) = self.players[caller].do_daiminkan(called_tile, from) {
+            self.abandon_call(caller, Some(discarder), "daiminkan", err);
+            return;
+        }
 
         self.mark_last_discard_as_called(discarder);
         self.invalidate_first_turn_flags();
@@ -905,7 +950,7 @@ impl Round {
             },
         ));
 
-        self.reveal_new_dora_indicator();
+        self.reveal_kan_dora(true);
         self.current_player = caller;
         self.draw_after_kan(caller);
     }
@@ -918,7 +963,10 @@ impl Round {
         called_tile: Tile,
         hand_tile_types: [Tile; 2],
     ) {
-        self.players[caller].do_chi(called_tile, hand_tile_types);
+        if let Err(err) = self.players[caller].do_chi(called_tile, hand_tile_types) {
+            self.abandon_call(caller, Some(discarder), "chi", err);
+            return;
+        }
 
         // 捨て牌を「鳴かれた」としてマーク
         self.mark_last_discard_as_called(discarder);
@@ -964,20 +1012,48 @@ impl Round {
 
     /// チー・ポン直後の喰い替え禁止牌を、設定が有効なら当該プレイヤーに設定する
     fn apply_swap_call_restriction(&mut self, caller: usize) {
-        if !self.settings.forbid_swap_calling {
-            return;
-        }
         let forbidden = self.players[caller]
             .hand
             .melds()
             .last()
-            .map(|meld| meld.forbidden_swap_tiles())
+            .map(|meld| meld.forbidden_discards(self.settings.kuikae))
             .unwrap_or_default();
         self.players[caller].set_forbidden_discards(forbidden);
     }
 
+    /// 手牌に必要な牌がなく鳴きを実行できなかった場合に、鳴きを取り消して局を進める
+    ///
+    /// 鳴きは宣言時に検証しているため通常は起こらない。デバッグビルドでは診断ログに残したうえで、
+    /// 捨て牌に対する鳴きは全員パスとして、加カン（`discarder` が `None`）は
+    /// カンせずに打牌を待つ状態として続ける。
+    fn abandon_call(
+        &mut self,
+        caller: usize,
+        discarder: Option<usize>,
+        call: &str,
+        err: MahjongError,
+    ) {
+        if cfg!(debug_assertions) {
+            eprintln!("[call-reject] {call} by player {caller}: {err}");
+        }
+        match discarder {
+            Some(discarder) => {
+                self.current_player = (discarder + 1) % 4;
+                self.phase = TurnPhase::Draw;
+                self.check_special_draws();
+            }
+            None => {
+                self.current_player = caller;
+                self.phase = TurnPhase::WaitForDiscard;
+            }
+        }
+    }
+
     fn execute_kakan(&mut self, caller: usize, tile_type: TileType) {
-        self.players[caller].do_kakan(tile_type);
+        if let Err(err) = self.players[caller].do_kakan(tile_type) {
+            self.abandon_call(caller, None, "kakan", err);
+            return;
+        }
         self.invalidate_first_turn_flags();
 
         let caller_wind = self.players[caller].seat_wind;
@@ -1013,7 +1089,7 @@ impl Round {
             },
         ));
 
-        self.reveal_new_dora_indicator();
+        self.reveal_kan_dora(true);
         self.draw_after_kan(caller);
     }
 
@@ -1088,8 +1164,14 @@ impl Round {
         }
 
         let player_idx = self.current_player;
-        if self.players[player_idx].is_riichi {
-            return false;
+
+        // リーチ後は待ちの変わらない暗カンのみ（クライアントからの不正な宣言もここで弾く）
+        let player = &self.players[player_idx];
+        if player.is_riichi() {
+            let waits = player.hand.winning_tile_types();
+            if !board::actions::can_ankan_after_riichi(&player.hand, &waits, tile_type) {
+                return false;
+            }
         }
 
         // 場全体で4回カン済みなら追加のカン不可
@@ -1101,11 +1183,21 @@ impl Round {
             .ankan_options()
             .contains(&tile_type)
         {
-            self.players[player_idx].do_ankan(tile_type);
+            if self.players[player_idx].do_ankan(tile_type).is_err() {
+                return false;
+            }
+            self.log.push(GameEvent::Kan {
+                seat: player_idx,
+                tile_type,
+            });
         } else if self.players[player_idx]
             .kakan_options()
             .contains(&tile_type)
         {
+            self.log.push(GameEvent::Kan {
+                seat: player_idx,
+                tile_type,
+            });
             self.check_kakan_ron_and_resolve(player_idx, tile_type);
             return true;
         } else {
@@ -1138,7 +1230,7 @@ impl Round {
             },
         ));
 
-        self.reveal_new_dora_indicator();
+        self.reveal_kan_dora(false);
         self.draw_after_kan(player_idx);
         true
     }
@@ -1150,12 +1242,42 @@ impl Round {
         }
     }
 
-    /// 鳴き・カンなどにより全プレイヤーの一発フラグと
-    /// 第1巡フラグ（四風連打の判定用）を無効化する
+    /// 鳴き・カンなどにより全プレイヤーの一発と第一ツモ（天和・地和・ダブルリーチの条件）を無効化する
     fn invalidate_first_turn_flags(&mut self) {
         for player in &mut self.players {
-            player.is_ippatsu = false;
-            player.first_turn_interrupted = true;
+            player.status.on_call_made();
+        }
+    }
+
+    /// カンの新ドラをめくる
+    ///
+    /// 明槓（`is_open`）かつ `KanDoraTiming::AfterDiscard` の場合は次の打牌まで保留する。
+    /// それ以外は、保留中の新ドラをめくってから今回の新ドラをめくる。
+    fn reveal_kan_dora(&mut self, is_open: bool) {
+        self.reveal_pending_kan_dora();
+        if is_open && self.settings.kan_dora_timing == KanDoraTiming::AfterDiscard {
+            self.pending_kan_dora += 1;
+        } else {
+            self.reveal_new_dora_indicator();
+        }
+    }
+
+    /// 保留中の明槓の新ドラをめくる
+    fn reveal_pending_kan_dora(&mut self) {
+        while self.pending_kan_dora > 0 {
+            self.pending_kan_dora -= 1;
+            self.reveal_new_dora_indicator();
+        }
+    }
+
+    /// 和了者が数える裏ドラ表示牌を返す
+    ///
+    /// `settings.ura_dora_requires_riichi` が有効なら立直していない和了者は数えない。
+    fn uradora_for(&self, winner: usize) -> Vec<Tile> {
+        if self.players[winner].is_riichi() || !self.settings.ura_dora_requires_riichi {
+            self.wall.uradora_indicators()
+        } else {
+            vec![]
         }
     }
 
@@ -1281,7 +1403,7 @@ impl Round {
             }
         };
 
-        if player.is_riichi {
+        if player.is_riichi() {
             log_reject(format_args!("reason=already_riichi player={player_idx}"));
             return false;
         }
@@ -1340,29 +1462,17 @@ impl Round {
             return false;
         }
 
-        // ダブルリーチ判定（第一ツモかつ副露による中断なし）
-        let is_double = self.players[player_idx].is_first_turn
-            && !self.players[player_idx].first_turn_interrupted;
-
-        // リーチ宣言
-        self.players[player_idx].declare_riichi(is_double);
-        self.riichi_sticks += 1;
-
-        // リーチ宣言牌を打牌
-        // （declare_riichi内でippatsu=trueが設定されるが、
-        //   直後のdiscardでippatsu=falseにされてしまう。
-        //   これを防ぐため、一時的にippatsuを保護する）
+        // リーチ宣言牌を打牌して宣言する
+        // （第一ツモで鳴きが入っていなければダブルリーチ。判定は Status::on_riichi_declared が行う）
         let is_tsumogiri = tile.is_none();
-        let Some(discarded) = self.players[player_idx].try_discard(tile) else {
-            self.players[player_idx].is_riichi = false;
-            self.players[player_idx].is_double_riichi = false;
-            self.players[player_idx].is_ippatsu = false;
-            self.players[player_idx].score += RIICHI_STICK_VALUE;
-            self.riichi_sticks = self.riichi_sticks.saturating_sub(1);
+        let Some(discarded) = self.players[player_idx].try_discard_riichi(tile) else {
             return false;
         };
-        // リーチ宣言直後の打牌なのでippatsuを復元
-        self.players[player_idx].is_ippatsu = true;
+        self.log.push(GameEvent::Riichi {
+            seat: player_idx,
+            tile,
+        });
+        self.riichi_sticks += 1;
 
         // 打牌をリーチ宣言牌としてマーク
         if let Some(last_discard) = self.players[player_idx].discards.last_mut() {
@@ -1389,11 +1499,16 @@ impl Round {
     }
 
     /// 現在のプレイヤーがツモ和了できるか判定する
+    ///
+    /// 鳴いた直後などツモ牌が無い場合は和了形でもツモ和了できない。
     pub fn can_tsumo(&self) -> bool {
         if self.phase != TurnPhase::WaitForDiscard {
             return false;
         }
         let player = &self.players[self.current_player];
+        if player.hand.drawn().is_none() {
+            return false;
+        }
         let is_last_tile = self.wall.is_empty();
         let result = scoring::check_win_with_settings(
             player,
@@ -1406,6 +1521,93 @@ impl Round {
         result.is_win
     }
 
+    /// 指定した座席がいま取れる行動を全て返す
+    ///
+    /// - 打牌待ちの手番プレイヤー: 打牌・リーチ・ツモ和了・暗カン・加カン
+    ///   （リーチ中はツモ切りとツモ和了のみ。喰い替え禁止の牌は打牌に含めない）
+    /// - 九種九牌の選択中の手番プレイヤー: 流局の宣言か続行
+    /// - 鳴き待ちで未応答のプレイヤー: ロン・ポン・大明カン・チーとパス
+    ///
+    /// それ以外の座席・フェーズでは空を返す。
+    pub fn legal_actions(&self, seat: usize) -> Vec<Action> {
+        match self.phase {
+            TurnPhase::WaitForDiscard if seat == self.current_player => self.turn_actions(seat),
+            TurnPhase::WaitForNineTerminals if seat == self.current_player => vec![
+                Action::NineTerminals { declare: true },
+                Action::NineTerminals { declare: false },
+            ],
+            TurnPhase::WaitForCalls => self.call_actions(seat),
+            _ => Vec::new(),
+        }
+    }
+
+    /// 手番プレイヤーの打牌待ちでの行動
+    fn turn_actions(&self, seat: usize) -> Vec<Action> {
+        let player = &self.players[seat];
+        let mut actions = Vec::new();
+        if self.can_tsumo() {
+            actions.push(Action::Tsumo);
+        }
+
+        let has_drawn = player.hand.drawn().is_some();
+        let mut discards: Vec<Option<Tile>> = Vec::new();
+        if !player.is_riichi() {
+            for &tile in player.hand.tiles() {
+                if !player.is_swap_call_forbidden(tile) && !discards.contains(&Some(tile)) {
+                    discards.push(Some(tile));
+                }
+            }
+        }
+        if has_drawn {
+            discards.push(None);
+        }
+
+        if self.can_player_riichi(seat) {
+            actions.extend(
+                discards
+                    .iter()
+                    .filter(|&&tile| self.can_player_riichi_with_discard(seat, tile))
+                    .map(|&tile| Action::Riichi(tile)),
+            );
+        }
+
+        // リーチ後の暗カンは ankan_options が待ちの変わらないものに絞る
+        if self.total_kan_count() < 4 {
+            actions.extend(player.ankan_options().into_iter().map(Action::Ankan));
+            actions.extend(player.kakan_options().into_iter().map(Action::Kakan));
+        }
+
+        actions.extend(discards.into_iter().map(Action::Discard));
+        actions
+    }
+
+    /// 鳴き待ちで未応答のプレイヤーの行動
+    fn call_actions(&self, seat: usize) -> Vec<Action> {
+        let Some(call_state) = &self.call_state else {
+            return Vec::new();
+        };
+        if call_state.responded[seat] {
+            return Vec::new();
+        }
+        let mut actions = Vec::new();
+        for call in &call_state.available_calls[seat] {
+            match call {
+                AvailableCall::Ron => actions.push(Action::Ron),
+                AvailableCall::Pon { options } => {
+                    actions.extend(options.iter().map(|&tiles| Action::Pon(tiles)))
+                }
+                AvailableCall::Daiminkan => {
+                    actions.push(Action::Daiminkan(call_state.discarded_tile.get()))
+                }
+                AvailableCall::Chi { options } => {
+                    actions.extend(options.iter().map(|&tiles| Action::Chi(tiles)))
+                }
+            }
+        }
+        actions.push(Action::Pass);
+        actions
+    }
+
     /// ツモ和了を実行する
     /// 点数移動を行い、局を終了させる
     pub fn do_tsumo(&mut self) -> bool {
@@ -1435,21 +1637,19 @@ impl Round {
         let Some(winning_tile) = self.players[winner].hand.drawn() else {
             return false;
         };
+        self.log.push(GameEvent::Tsumo { seat: winner });
         let winner_is_dealer = self.players[winner].is_dealer();
 
         // ドラ・赤ドラ・裏ドラを加算
         let dora_indicators = self.wall.dora_indicators();
-        let uradora_indicators = if self.players[winner].is_riichi {
-            self.wall.uradora_indicators()
-        } else {
-            vec![]
-        };
+        let uradora_indicators = self.uradora_for(winner);
         scoring::add_dora_to_score(
             &mut score_result,
             &self.players[winner].hand,
             None,
             &dora_indicators,
             &uradora_indicators,
+            &self.settings,
         );
 
         // 点数移動を計算
@@ -1459,6 +1659,7 @@ impl Round {
             winner_is_dealer,
             self.dealer,
             self.honba,
+            Pao::from_hand(&self.players[winner].hand, winner),
         );
         let riichi_sticks = self.riichi_sticks;
 
@@ -1537,44 +1738,33 @@ impl Round {
         self.phase == TurnPhase::RoundOver
     }
 
-    /// 荒牌流局を処理する（ノーテン罰符を含む）
+    /// 荒牌流局を処理する（ノーテン罰符・流し満貫を含む）
     fn do_exhaustive_draw(&mut self) {
-        // テンパイ判定
-        let mut tenpai_players = Vec::new();
-        let mut noten_players = Vec::new();
-
-        for i in 0..4 {
-            if scoring::is_ready(&self.players[i]) {
-                tenpai_players.push(i);
-            } else {
-                noten_players.push(i);
-            }
-        }
-
-        // ノーテン罰符の計算（テンパイ者と非テンパイ者がいる場合のみ）
-        if !tenpai_players.is_empty() && !noten_players.is_empty() {
-            let total_penalty = 3000i32;
-            let tenpai_count = tenpai_players.len() as i32;
-            let noten_count = noten_players.len() as i32;
-
-            let gain_each = total_penalty / tenpai_count;
-            let loss_each = total_penalty / noten_count;
-
-            for &i in &tenpai_players {
-                self.players[i].score += gain_each;
-            }
-            for &i in &noten_players {
-                self.players[i].score -= loss_each;
-            }
+        // テンパイ判定と流し満貫の判定（流し満貫がなければノーテン罰符で精算）
+        let discards: Vec<Vec<Tile>> = self
+            .players
+            .iter()
+            .map(|p| p.discards.iter().map(|d| d.tile).collect())
+            .collect();
+        let seats: [draw_outcome::DrawSeat; 4] = std::array::from_fn(|i| draw_outcome::DrawSeat {
+            hand: &self.players[i].hand,
+            discards: &discards[i],
+            discard_called: self.players[i].discards.iter().any(|d| d.is_called),
+        });
+        let outcome =
+            draw_outcome::draw_outcome(&seats, self.dealer, self.honba as u32, &self.settings);
+        for (player, delta) in self.players.iter_mut().zip(outcome.deltas) {
+            player.score += delta;
         }
 
         let scores = self.get_scores();
-        let tenpai_winds: Vec<Wind> = tenpai_players
+        let tenpai_winds: Vec<Wind> = outcome
+            .tenpai
             .iter()
             .map(|&i| self.players[i].seat_wind)
             .collect();
 
-        let dealer_tenpai = tenpai_players.contains(&self.dealer);
+        let dealer_tenpai = outcome.tenpai.contains(&self.dealer);
         let player_hands = self.build_player_hands();
 
         self.phase = TurnPhase::RoundOver;
@@ -1614,33 +1804,30 @@ impl Round {
     /// 条件: 各プレイヤーがちょうど1枚ずつ捨てており、
     /// 全て同じ風牌で、鳴きが発生していない
     fn check_four_winds_draw(&self) -> bool {
-        // 全プレイヤーがちょうど1枚捨てていること
-        for player in &self.players {
-            if player.discards.len() != 1 {
-                return false;
-            }
-            // 鳴かれていたら不成立
-            if player.discards[0].is_called {
-                return false;
-            }
-        }
-
-        // 全て同じ風牌であること
-        let first_tile = self.players[0].discards[0].tile;
-        if !first_tile.is_wind() {
-            return false;
-        }
-
-        self.players
+        let discards: Vec<Vec<Tile>> = self
+            .players
             .iter()
-            .all(|p| p.discards[0].tile.get() == first_tile.get())
+            .map(|p| p.discards.iter().map(|d| d.tile).collect())
+            .collect();
+        let any_call = self
+            .players
+            .iter()
+            .any(|p| p.discards.iter().any(|d| d.is_called));
+        abortive_draw::is_four_winds_draw(
+            std::array::from_fn(|i| discards[i].as_slice()),
+            any_call,
+            &self.settings,
+        )
     }
 
     /// 四家立直を判定する
     ///
     /// 条件: 全4プレイヤーがリーチ宣言済み
     fn check_four_riichi_draw(&self) -> bool {
-        self.players.iter().all(|p| p.is_riichi)
+        abortive_draw::is_four_riichi_draw(
+            std::array::from_fn(|i| self.players[i].is_riichi()),
+            &self.settings,
+        )
     }
 
     /// 九種九牌の宣言条件を判定する
@@ -1649,22 +1836,11 @@ impl Round {
     /// 手牌＋ツモ牌に9種類以上のヤオ九牌（老頭牌・字牌）がある
     fn check_nine_terminals(&self) -> bool {
         let player = &self.players[self.current_player];
-        // 初回ツモのみ（捨牌済みなら宣言不可）
-        if !player.discards.is_empty() {
-            return false;
-        }
-        let mut tile_types = std::collections::HashSet::new();
-        for tile in player.hand.tiles() {
-            if tile.is_1_9_honour() {
-                tile_types.insert(tile.get());
-            }
-        }
-        if let Some(tile) = player.hand.drawn()
-            && tile.is_1_9_honour()
-        {
-            tile_types.insert(tile.get());
-        }
-        tile_types.len() >= 9
+        abortive_draw::can_declare_nine_terminals(
+            &player.hand,
+            player.discards.is_empty(),
+            &self.settings,
+        )
     }
 
     /// 九種九牌の宣言を処理する
@@ -1678,6 +1854,10 @@ impl Round {
         if self.current_player != player_idx {
             return false;
         }
+        self.log.push(GameEvent::NineTerminals {
+            seat: player_idx,
+            declare,
+        });
         if declare {
             let declarer_wind = self.players[player_idx].seat_wind;
             self.declare_special_draw(DrawReason::NineTerminals, Some(declarer_wind));
@@ -1717,11 +1897,10 @@ impl Round {
     /// 条件: 場全体で4回カンが成立し、かつ2人以上がカンしている
     /// （1人が4回カンした場合は四槓子の可能性があるため続行）
     fn check_four_kans_draw(&self) -> bool {
-        if self.total_kan_count() < 4 {
-            return false;
-        }
-        let players_with_kan = self.players.iter().filter(|p| p.kan_count() > 0).count();
-        players_with_kan >= 2
+        abortive_draw::is_four_kans_draw(
+            std::array::from_fn(|i| self.players[i].kan_count()),
+            &self.settings,
+        )
     }
 
     /// 特殊流局を宣言する
@@ -1748,7 +1927,7 @@ impl Round {
 }
 
 /// 鳴き応答の種類
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CallResponse {
     /// ロン
     Ron,
@@ -0,0 +1,124 @@
+//! 局の操作記録と再生
+//!
+//! 局に加えた操作（配牌・ツモ・打牌・リーチ・鳴き応答・カン・和了）を順に記録し、
+//! 同じ操作を配牌から適用し直して同じ局面を再構築する。
+//! 不具合の再現や一手戻し（末尾を除いた記録の再生）、外部の牌譜形式との変換に使う。
+//!
+//! 記録は配牌から始まるため、`ScenarioBuilder` で途中から作った局は再生できない。
+
+use anyhow::{Result, anyhow};
+use mahjong_core::settings::Settings;
+use mahjong_core::tile::{Tile, TileType, Wind};
+use serde::{Deserialize, Serialize};
+
+use super::{CallResponse, Round};
+use crate::wall::Wall;
+
+/// 配牌時の局の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deal {
+    /// 配牌前の牌山（ツモ順に並べ、末尾14枚が王牌）
+    pub wall: Vec<Tile>,
+    /// 場風
+    pub round_wind: Wind,
+    /// 親の座席
+    pub dealer: usize,
+    /// 各座席の持ち点
+    pub scores: [i32; 4],
+    /// 本場数
+    pub honba: usize,
+    /// 供託リーチ棒の本数
+    pub riichi_sticks: usize,
+    /// 局番号（0-based）
+    pub round_number: usize,
+    /// ゲーム全体の局数
+    pub total_rounds: usize,
+    /// ルール設定
+    pub settings: Settings,
+}
+
+/// 局に加えた操作
+///
+/// 和了はツモ和了（`Tsumo`）とロンの応答（`Call` の `CallResponse::Ron`）として記録する。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// 配牌
+    Deal(Box<Deal>),
+    /// ツモ（牌山が尽きて流局した場合は `None`）
+    Draw { seat: usize, tile: Option<Tile> },
+    /// 打牌（Noneならツモ切り）
+    Discard { seat: usize, tile: Option<Tile> },
+    /// リーチ宣言と宣言牌の打牌（Noneならツモ切りリーチ）
+    Riichi { seat: usize, tile: Option<Tile> },
+    /// 打牌・加カンに対する鳴き応答
+    Call { seat: usize, response: CallResponse },
+    /// 暗カン・加カン
+    Kan { seat: usize, tile_type: TileType },
+    /// ツモ和了
+    Tsumo { seat: usize },
+    /// 九種九牌の選択
+    NineTerminals { seat: usize, declare: bool },
+}
+
+impl Round {
+    /// 配牌からの操作記録を返す
+    pub fn event_log(&self) -> &[GameEvent] {
+        &self.log
+    }
+
+    /// 操作記録を配牌から適用し直して局を再構築する
+    ///
+    /// 記録の先頭は配牌でなければならない。適用できない操作があった場合や、
+    /// ツモ牌が記録と食い違った場合はエラーを返す。
+    /// 通知イベントは配牌からの分が全て溜まった状態になる。
+    pub fn replay(events: &[GameEvent]) -> Result<Round> {
+        let Some((GameEvent::Deal(deal), rest)) = events.split_first() else {
+            return Err(anyhow!("event log must start with a deal"));
+        };
+        let deal = deal.as_ref().clone();
+        let mut round = Round::with_wall(
+            Wall::from_shuffled(deal.wall),
+            deal.round_wind,
+            deal.dealer,
+            deal.scores,
+            deal.honba,
+            deal.riichi_sticks,
+            deal.round_number,
+            deal.total_rounds,
+            deal.settings,
+        );
+
+        for (index, event) in rest.iter().enumerate() {
+            if !round.apply(event) {
+                return Err(anyhow!(
+                    "event #{} cannot be applied: {:?}",
+                    index + 1,
+                    event
+                ));
+            }
+        }
+        Ok(round)
+    }
+
+    /// 記録された操作を1つ適用する
+    fn apply(&mut self, event: &GameEvent) -> bool {
+        match *event {
+            GameEvent::Deal(_) => false,
+            GameEvent::Draw { seat, tile } => {
+                seat == self.current_player
+                    && self.do_draw()
+                    && self.players[seat].hand.drawn() == tile
+            }
+            GameEvent::Discard { seat, tile } => {
+                seat == self.current_player && self.do_discard(tile)
+            }
+            GameEvent::Riichi { seat, tile } => seat == self.current_player && self.do_riichi(tile),
+            GameEvent::Call { seat, ref response } => self.respond_to_call(seat, response.clone()),
+            GameEvent::Kan { seat, tile_type } => {
+                seat == self.current_player && self.do_kan(tile_type)
+            }
+            GameEvent::Tsumo { seat } => seat == self.current_player && self.do_tsumo(),
+            GameEvent::NineTerminals { seat, declare } => self.do_nine_terminals(seat, declare),
+        }
+    }
+}
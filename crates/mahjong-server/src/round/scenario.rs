@@ -0,0 +1,766 @@
+//! 任意の局面からの局の構築
+//!
+//! 配牌を経ずに、手牌・河・残りの牌山・ドラ表示牌・点数を指定して局を作る。
+//! テストケースや不具合報告の再現、何切る問題などのパズル用途に使う。
+//! 指定しなかった牌（手牌・牌山・王牌）は、使われていない牌から補う。
+//!
+//! 進行中の局は`Round::snapshot`で`RoundSnapshot`に保存でき、
+//! `ScenarioBuilder::from_snapshot`で同じ局面から再開できる（クラッシュからの復旧や検討用）。
+//! 保存形式は王牌の並びや一発・フリテンなど各座席の状態も含むため、再開した局は保存時と同じように進む。
+
+use anyhow::{Result, anyhow};
+use mahjong_core::hand::Hand;
+use mahjong_core::hand_info::meld::Meld;
+use mahjong_core::hand_info::status::Status;
+use mahjong_core::settings::Settings;
+use mahjong_core::tile::{Tile, TileType, Wind};
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use super::{Round, TurnPhase};
+use crate::player::{Discard, Player};
+use crate::protocol::ServerEvent;
+use crate::wall::Wall;
+
+/// 局面を指定して局を構築するビルダー
+///
+/// ```
+/// use mahjong_core::hand::Hand;
+/// use mahjong_server::round::scenario::ScenarioBuilder;
+///
+/// let round = ScenarioBuilder::new()
+///     .hand(0, Hand::from("123m456p789s1122z 2z"))
+///     .build()
+///     .unwrap();
+/// assert!(round.can_tsumo());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScenarioBuilder {
+    round_wind: Wind,
+    dealer: usize,
+    current_player: Option<usize>,
+    hands: [Option<Hand>; 4],
+    rivers: [Vec<Discard>; 4],
+    riichi: [bool; 4],
+    player_states: [Option<PlayerSnapshot>; 4],
+    wall: Option<Vec<Tile>>,
+    dead_wall: Option<Vec<Tile>>,
+    dora_indicators: Vec<Tile>,
+    uradora_indicators: Vec<Tile>,
+    pending_kan_dora: usize,
+    last_draw_was_dead_wall: bool,
+    scores: [i32; 4],
+    honba: usize,
+    riichi_sticks: usize,
+    round_number: usize,
+    total_rounds: usize,
+    settings: Settings,
+    seed: u64,
+}
+
+impl Default for ScenarioBuilder {
+    fn default() -> Self {
+        ScenarioBuilder::new()
+    }
+}
+
+impl ScenarioBuilder {
+    /// 東1局・親は座席0・持ち点25000点の局面から始める
+    pub fn new() -> Self {
+        ScenarioBuilder {
+            round_wind: Wind::East,
+            dealer: 0,
+            current_player: None,
+            hands: [None, None, None, None],
+            rivers: [const { Vec::new() }; 4],
+            riichi: [false; 4],
+            player_states: [const { None }; 4],
+            wall: None,
+            dead_wall: None,
+            dora_indicators: Vec::new(),
+            uradora_indicators: Vec::new(),
+            pending_kan_dora: 0,
+            last_draw_was_dead_wall: false,
+            scores: [25000; 4],
+            honba: 0,
+            riichi_sticks: 0,
+            round_number: 0,
+            total_rounds: 4,
+            settings: Settings::new(),
+            seed: 0,
+        }
+    }
+
+    /// 場風を指定する
+    pub fn round_wind(mut self, wind: Wind) -> Self {
+        self.round_wind = wind;
+        self
+    }
+
+    /// 親の座席を指定する
+    pub fn dealer(mut self, seat: usize) -> Self {
+        self.dealer = seat % 4;
+        self
+    }
+
+    /// 手番の座席を指定する（省略時は親）
+    ///
+    /// 手番の手牌にツモ牌があれば打牌待ち、なければツモから始まる。
+    pub fn current_player(mut self, seat: usize) -> Self {
+        self.current_player = Some(seat % 4);
+        self
+    }
+
+    /// 座席の手牌（副露を含む）を指定する
+    pub fn hand(mut self, seat: usize, hand: Hand) -> Self {
+        self.hands[seat % 4] = Some(hand);
+        self
+    }
+
+    /// 座席の河（捨て牌）を指定する
+    pub fn river(mut self, seat: usize, tiles: Vec<Tile>) -> Self {
+        self.rivers[seat % 4] = tiles
+            .into_iter()
+            .map(|tile| Discard {
+                tile,
+                is_tsumogiri: false,
+                is_riichi_declaration: false,
+                is_called: false,
+            })
+            .collect();
+        self
+    }
+
+    /// 座席の河を、ツモ切り・リーチ宣言牌・鳴かれたかの情報つきで指定する
+    pub fn river_discards(mut self, seat: usize, discards: Vec<Discard>) -> Self {
+        self.rivers[seat % 4] = discards;
+        self
+    }
+
+    /// 座席をリーチ済みにする
+    pub fn riichi(mut self, seat: usize) -> Self {
+        self.riichi[seat % 4] = true;
+        self
+    }
+
+    /// 座席の立直・一発・第一ツモ・フリテン・喰い替え禁止の状態を指定する
+    ///
+    /// 指定した場合は`riichi`の指定より優先する。
+    pub fn player_state(mut self, seat: usize, state: PlayerSnapshot) -> Self {
+        self.player_states[seat % 4] = Some(state);
+        self
+    }
+
+    /// 残りの牌山（ツモ順）を指定する
+    pub fn wall(mut self, tiles: Vec<Tile>) -> Self {
+        self.wall = Some(tiles);
+        self
+    }
+
+    /// 王牌を並び順のまま指定する（残りの嶺上牌に続けて、ドラ表示牌・裏ドラ表示牌を交互に10枚）
+    ///
+    /// 指定した場合、ドラ表示牌は公開枚数分の一致確認にのみ使い、裏ドラ表示牌は指定できない。
+    pub fn dead_wall(mut self, tiles: Vec<Tile>) -> Self {
+        self.dead_wall = Some(tiles);
+        self
+    }
+
+    /// ドラ表示牌（1〜5枚）を指定する
+    pub fn dora_indicators(mut self, tiles: Vec<Tile>) -> Self {
+        self.dora_indicators = tiles;
+        self
+    }
+
+    /// 裏ドラ表示牌を指定する
+    pub fn uradora_indicators(mut self, tiles: Vec<Tile>) -> Self {
+        self.uradora_indicators = tiles;
+        self
+    }
+
+    /// めくる前の明槓の新ドラの枚数を指定する（`KanDoraTiming::AfterDiscard` 用）
+    ///
+    /// 次の打牌の後に、この枚数だけ新ドラをめくる。
+    pub fn pending_kan_dora(mut self, count: usize) -> Self {
+        self.pending_kan_dora = count;
+        self
+    }
+
+    /// 直前のツモが嶺上牌かを指定する（嶺上開花の判定用）
+    pub fn last_draw_was_dead_wall(mut self, value: bool) -> Self {
+        self.last_draw_was_dead_wall = value;
+        self
+    }
+
+    /// 各座席の持ち点を指定する
+    pub fn scores(mut self, scores: [i32; 4]) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    /// 本場数を指定する
+    pub fn honba(mut self, honba: usize) -> Self {
+        self.honba = honba;
+        self
+    }
+
+    /// 供託リーチ棒の本数を指定する
+    pub fn riichi_sticks(mut self, sticks: usize) -> Self {
+        self.riichi_sticks = sticks;
+        self
+    }
+
+    /// 局番号と総局数（`GameStarted` イベントの表示用）を指定する
+    pub fn round_number(mut self, round_number: usize, total_rounds: usize) -> Self {
+        self.round_number = round_number;
+        self.total_rounds = total_rounds;
+        self
+    }
+
+    /// ルール設定を指定する
+    pub fn settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// 指定しなかった牌を補うときのシャッフルのシード値を指定する
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 保存した局面から再開するビルダーを作る
+    ///
+    /// 局番号・総局数は含まれないため、必要なら`round_number`で指定する。
+    pub fn from_snapshot(snapshot: RoundSnapshot) -> Self {
+        let mut builder = ScenarioBuilder::new()
+            .round_wind(snapshot.round_wind)
+            .dealer(snapshot.dealer)
+            .current_player(snapshot.current_player)
+            .wall(snapshot.wall)
+            .dead_wall(snapshot.dead_wall)
+            .dora_indicators(snapshot.dora_indicators)
+            .pending_kan_dora(snapshot.pending_kan_dora)
+            .last_draw_was_dead_wall(snapshot.last_draw_was_dead_wall)
+            .scores(snapshot.scores)
+            .honba(snapshot.honba)
+            .riichi_sticks(snapshot.riichi_sticks)
+            .settings(snapshot.settings);
+        let seats = snapshot.hands.into_iter().zip(snapshot.rivers);
+        for (seat, ((hand, river), state)) in seats.zip(snapshot.players).enumerate() {
+            builder = builder
+                .hand(seat, hand.into())
+                .river_discards(seat, river)
+                .player_state(seat, state);
+        }
+        builder
+    }
+
+    /// 局を構築する
+    ///
+    /// 同じ牌が5枚以上使われている場合や、ドラ表示牌が多すぎる場合、
+    /// 指定した王牌とドラ表示牌が食い違う場合はエラーを返す。
+    pub fn build(self) -> Result<Round> {
+        if self.dora_indicators.len().max(1) + self.pending_kan_dora > 5
+            || self.uradora_indicators.len() > 5
+        {
+            return Err(anyhow!("too many dora indicators"));
+        }
+        if let Some(dead_wall) = &self.dead_wall {
+            if !(10..=14).contains(&dead_wall.len()) {
+                return Err(anyhow!("the dead wall must hold 10 to 14 tiles"));
+            }
+            if !self.uradora_indicators.is_empty() {
+                return Err(anyhow!(
+                    "uradora indicators cannot be given together with the dead wall"
+                ));
+            }
+            let revealed: Vec<Tile> = dead_wall[dead_wall.len() - 10..]
+                .iter()
+                .step_by(2)
+                .take(self.dora_indicators.len())
+                .copied()
+                .collect();
+            if revealed != self.dora_indicators {
+                return Err(anyhow!("dora indicators do not match the dead wall"));
+            }
+        }
+
+        let mut pool = Wall::create_tiles(self.settings.red_fives);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(self.seed);
+        pool.shuffle(&mut rng);
+
+        // 指定された牌を未使用の牌から取り除く
+        let dead_wall_tiles: Vec<Tile> = match &self.dead_wall {
+            Some(tiles) => tiles.clone(),
+            None => self
+                .dora_indicators
+                .iter()
+                .chain(self.uradora_indicators.iter())
+                .copied()
+                .collect(),
+        };
+        let specified = self
+            .hands
+            .iter()
+            .flatten()
+            .flat_map(|hand| {
+                let mut tiles = hand.tiles().to_vec();
+                tiles.extend(hand.melds().iter().flat_map(|meld| meld.expanded_tiles()));
+                tiles.extend(hand.nuki());
+                tiles.extend(hand.drawn());
+                tiles
+            })
+            .chain(self.rivers.iter().flatten().map(|discard| discard.tile))
+            .chain(self.wall.iter().flatten().copied())
+            .chain(dead_wall_tiles);
+        for tile in specified {
+            take_tile(&mut pool, tile)?;
+        }
+
+        let current_player = self.current_player.unwrap_or(self.dealer);
+        let mut hands = self.hands.clone();
+        for hand in hands.iter_mut() {
+            if hand.is_none() {
+                let tiles = take_tiles(&mut pool, 13)?;
+                *hand = Some(Hand::new(tiles, None));
+            }
+        }
+
+        // 王牌: [嶺上0-3, ドラ表示0, 裏ドラ表示0, ドラ表示1, 裏ドラ表示1, ...]
+        let indicator_count = self.dora_indicators.len().max(1);
+        let (rinshan, indicators) = match self.dead_wall {
+            Some(mut rinshan) => {
+                let indicators = rinshan.split_off(rinshan.len() - 10);
+                (rinshan, indicators)
+            }
+            None => {
+                // 嶺上牌はカンの回数だけツモ済みとする
+                let kans = self
+                    .hands
+                    .iter()
+                    .flatten()
+                    .flat_map(|hand| hand.melds())
+                    .filter(|meld| meld.category.is_kan())
+                    .count();
+                let rinshan = take_tiles(&mut pool, 4 - kans.min(4))?;
+                let mut indicators = Vec::with_capacity(10);
+                for i in 0..5 {
+                    let dora = match self.dora_indicators.get(i) {
+                        Some(&tile) => tile,
+                        None => take_tiles(&mut pool, 1)?[0],
+                    };
+                    let uradora = match self.uradora_indicators.get(i) {
+                        Some(&tile) => tile,
+                        None => take_tiles(&mut pool, 1)?[0],
+                    };
+                    indicators.push(dora);
+                    indicators.push(uradora);
+                }
+                (rinshan, indicators)
+            }
+        };
+
+        let live = match self.wall {
+            Some(tiles) => tiles,
+            None => std::mem::take(&mut pool),
+        };
+        let mut wall = Wall::from_parts(live, rinshan, indicators);
+        for _ in 1..indicator_count {
+            wall.add_dora_indicator();
+        }
+
+        let players: [Player; 4] = std::array::from_fn(|i| {
+            let wind = Wind::from_seat(self.dealer, i);
+            let mut player = Player::new(wind, Vec::new(), self.scores[i]);
+            player.hand = hands[i]
+                .take()
+                .unwrap_or_else(|| Hand::new(Vec::new(), None));
+            player.discards = self.rivers[i].clone();
+            player.status.has_claimed_riichi = self.riichi[i];
+            player.status.is_first_turn =
+                player.discards.is_empty() && player.hand.melds().is_empty();
+            if let Some(state) = &self.player_states[i] {
+                player.status = state.status.clone();
+                player.is_riichi_furiten = state.is_riichi_furiten;
+                player.is_temporary_furiten = state.is_temporary_furiten;
+                player.set_forbidden_discards(state.forbidden_discards.clone());
+            }
+            player
+        });
+
+        let phase = if players[current_player].hand.drawn().is_some() {
+            TurnPhase::WaitForDiscard
+        } else {
+            TurnPhase::Draw
+        };
+
+        let dora_indicators = wall.dora_indicators();
+        let events = players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                (
+                    i,
+                    ServerEvent::GameStarted {
+                        seat_wind: player.seat_wind,
+                        hand: player.hand.tiles().to_vec(),
+                        scores: self.scores,
+                        round_wind: self.round_wind,
+                        dora_indicators: dora_indicators.clone(),
+                        round_number: self.round_number,
+                        total_rounds: self.total_rounds,
+                        honba: self.honba,
+                        riichi_sticks: self.riichi_sticks,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(Round {
+            wall,
+            players,
+            round_wind: self.round_wind,
+            dealer: self.dealer,
+            current_player,
+            honba: self.honba,
+            riichi_sticks: self.riichi_sticks,
+            phase,
+            result: None,
+            events,
+            call_state: None,
+            last_draw_was_dead_wall: self.last_draw_was_dead_wall,
+            pending_kan_dora: self.pending_kan_dora,
+            settings: self.settings,
+            log: Vec::new(),
+        })
+    }
+}
+
+/// 保存用の手牌（手牌・副露・抜きドラ・ツモ牌）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandSnapshot {
+    /// 手牌
+    pub tiles: Vec<Tile>,
+    /// 副露
+    pub melds: Vec<Meld>,
+    /// 抜きドラ
+    #[serde(default)]
+    pub nuki: Vec<Tile>,
+    /// ツモ牌
+    pub drawn: Option<Tile>,
+}
+
+impl From<&Hand> for HandSnapshot {
+    fn from(hand: &Hand) -> Self {
+        HandSnapshot {
+            tiles: hand.tiles().to_vec(),
+            melds: hand.melds().to_vec(),
+            nuki: hand.nuki().to_vec(),
+            drawn: hand.drawn(),
+        }
+    }
+}
+
+impl From<HandSnapshot> for Hand {
+    fn from(snapshot: HandSnapshot) -> Self {
+        let mut hand = Hand::new_with_melds(snapshot.tiles, snapshot.melds, snapshot.drawn);
+        for tile in snapshot.nuki {
+            hand.add_nuki(tile);
+        }
+        hand
+    }
+}
+
+/// 保存用のプレイヤーの状態（立直・一発・第一ツモ・フリテン・喰い替え禁止）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    /// 立直・ダブル立直・一発・第一ツモの状態
+    pub status: Status,
+    /// リーチ後フリテン
+    pub is_riichi_furiten: bool,
+    /// 同巡フリテン
+    pub is_temporary_furiten: bool,
+    /// 喰い替え禁止により、直後の打牌で捨てられない牌種
+    pub forbidden_discards: Vec<TileType>,
+}
+
+impl From<&Player> for PlayerSnapshot {
+    fn from(player: &Player) -> Self {
+        PlayerSnapshot {
+            status: player.status.clone(),
+            is_riichi_furiten: player.is_riichi_furiten,
+            is_temporary_furiten: player.is_temporary_furiten,
+            forbidden_discards: player.forbidden_discards().to_vec(),
+        }
+    }
+}
+
+/// 進行中の局の保存形式
+///
+/// ツモ前・打牌待ちの局面を表す。牌山・王牌（嶺上牌・裏ドラ表示牌を含む）を並び順のまま、
+/// 各座席の一発・フリテンなどの状態とあわせて保存するため、再開後のツモや裏ドラは保存時と変わらない。
+/// 局番号・総局数と、保存時点で未配信だった通知は含まない。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundSnapshot {
+    /// 場風
+    pub round_wind: Wind,
+    /// 親の座席
+    pub dealer: usize,
+    /// 手番の座席
+    pub current_player: usize,
+    /// 各座席の手牌
+    pub hands: [HandSnapshot; 4],
+    /// 各座席の河
+    pub rivers: [Vec<Discard>; 4],
+    /// 各座席の立直・一発・フリテンなどの状態
+    pub players: [PlayerSnapshot; 4],
+    /// 残りの牌山（ツモ順）
+    pub wall: Vec<Tile>,
+    /// 王牌（残りの嶺上牌に続けて、ドラ表示牌・裏ドラ表示牌を交互に10枚）
+    pub dead_wall: Vec<Tile>,
+    /// 公開済みのドラ表示牌
+    pub dora_indicators: Vec<Tile>,
+    /// めくる前の明槓の新ドラの枚数（`KanDoraTiming::AfterDiscard` で次の打牌後にめくる）
+    #[serde(default)]
+    pub pending_kan_dora: usize,
+    /// 直前のツモが嶺上牌か
+    pub last_draw_was_dead_wall: bool,
+    /// 各座席の持ち点
+    pub scores: [i32; 4],
+    /// 本場数
+    pub honba: usize,
+    /// 供託リーチ棒の本数
+    pub riichi_sticks: usize,
+    /// ルール設定
+    pub settings: Settings,
+}
+
+impl Round {
+    /// 現在の局面を保存形式で返す
+    ///
+    /// ツモ前・打牌待ち以外（鳴き待ちや局終了後など）はエラーを返す。
+    pub fn snapshot(&self) -> Result<RoundSnapshot> {
+        if !matches!(self.phase, TurnPhase::Draw | TurnPhase::WaitForDiscard) {
+            return Err(anyhow!(
+                "round can only be saved before a draw or discard: {:?}",
+                self.phase
+            ));
+        }
+        Ok(RoundSnapshot {
+            round_wind: self.round_wind,
+            dealer: self.dealer,
+            current_player: self.current_player,
+            hands: std::array::from_fn(|i| HandSnapshot::from(&self.players[i].hand)),
+            rivers: std::array::from_fn(|i| self.players[i].discards.clone()),
+            players: std::array::from_fn(|i| PlayerSnapshot::from(&self.players[i])),
+            wall: self.wall.live_tiles(),
+            dead_wall: self.wall.remaining_dead_wall(),
+            dora_indicators: self.wall.dora_indicators(),
+            pending_kan_dora: self.pending_kan_dora,
+            last_draw_was_dead_wall: self.last_draw_was_dead_wall,
+            scores: self.get_scores(),
+            honba: self.honba,
+            riichi_sticks: self.riichi_sticks,
+            settings: self.settings.clone(),
+        })
+    }
+}
+
+/// 未使用の牌から指定した牌を1枚取り除く
+///
+/// 赤ドラの区別が一致する牌がなければ、同じ種類の牌で代用する。
+fn take_tile(pool: &mut Vec<Tile>, tile: Tile) -> Result<()> {
+    let index = pool
+        .iter()
+        .position(|t| *t == tile)
+        .or_else(|| pool.iter().position(|t| t.get() == tile.get()))
+        .ok_or_else(|| anyhow!("more than 4 copies of {tile} in scenario"))?;
+    pool.swap_remove(index);
+    Ok(())
+}
+
+/// 未使用の牌から先頭の`count`枚を取り出す
+fn take_tiles(pool: &mut Vec<Tile>, count: usize) -> Result<Vec<Tile>> {
+    if pool.len() < count {
+        return Err(anyhow!("not enough tiles left to fill the scenario"));
+    }
+    Ok(pool.drain(..count).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round::RoundResult;
+
+    /// 指定した手牌・ツモ牌で打牌待ちから始まり、ツモ和了できることを確認
+    #[test]
+    fn test_build_tsumo_position() {
+        let mut round = ScenarioBuilder::new()
+            .dealer(1)
+            .current_player(2)
+            .hand(2, Hand::from("123m456p789s1122z 2z"))
+            .river(2, vec![Tile::new(Tile::Z7)])
+            .build()
+            .unwrap();
+
+        assert_eq!(round.phase, TurnPhase::WaitForDiscard);
+        assert_eq!(round.players[1].seat_wind, Wind::East);
+        assert_eq!(round.players[2].seat_wind, Wind::South);
+        assert_eq!(round.players[2].discards.len(), 1);
+        for i in [0, 1, 3] {
+            assert_eq!(round.players[i].hand.tiles().len(), 13);
+        }
+        assert!(round.can_tsumo());
+        assert!(round.do_tsumo());
+        assert!(matches!(
+            round.result,
+            Some(RoundResult::Tsumo { winner: 2, .. })
+        ));
+    }
+
+    /// 牌山とドラ表示牌の指定が反映されることを確認
+    #[test]
+    fn test_build_wall_and_dora() {
+        let wall = vec![Tile::new(Tile::Z1), Tile::new(Tile::Z2)];
+        let indicators = vec![Tile::new(Tile::M1), Tile::new(Tile::P1)];
+        let mut round = ScenarioBuilder::new()
+            .wall(wall)
+            .dora_indicators(indicators.clone())
+            .uradora_indicators(vec![Tile::new(Tile::S9)])
+            .scores([30000, 20000, 25000, 25000])
+            .honba(2)
+            .riichi_sticks(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(round.wall.remaining(), 2);
+        assert_eq!(round.wall.dora_indicators(), indicators);
+        assert_eq!(round.wall.uradora_indicators()[0], Tile::new(Tile::S9));
+        assert_eq!(round.get_scores(), [30000, 20000, 25000, 25000]);
+        assert_eq!(round.honba, 2);
+        assert_eq!(round.phase, TurnPhase::Draw);
+
+        assert!(round.do_draw());
+        assert_eq!(round.players[0].hand.drawn(), Some(Tile::new(Tile::Z1)));
+    }
+
+    /// 保存した局面を JSON 経由で復元すると、同じ局面から再開できることを確認
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut round = ScenarioBuilder::new()
+            .hand(0, Hand::from("123m456p789s1122z"))
+            .seed(7)
+            .build()
+            .unwrap();
+        assert!(round.do_draw());
+        assert!(round.do_discard(None));
+        round.call_state = None;
+        round.current_player = 1;
+        round.phase = TurnPhase::Draw;
+        assert!(round.do_draw());
+
+        let json = serde_json::to_string(&round.snapshot().unwrap()).unwrap();
+        let snapshot: RoundSnapshot = serde_json::from_str(&json).unwrap();
+        let restored = ScenarioBuilder::from_snapshot(snapshot).build().unwrap();
+
+        assert_eq!(restored.phase, TurnPhase::WaitForDiscard);
+        assert_eq!(restored.current_player, 1);
+        assert_eq!(restored.wall.live_tiles(), round.wall.live_tiles());
+        assert_eq!(
+            restored.wall.dora_indicators(),
+            round.wall.dora_indicators()
+        );
+        for seat in 0..4 {
+            let (a, b) = (&restored.players[seat], &round.players[seat]);
+            assert_eq!(a.hand.tiles(), b.hand.tiles());
+            assert_eq!(a.hand.drawn(), b.hand.drawn());
+            let river = |p: &Player| p.discards.iter().map(|d| d.tile).collect::<Vec<_>>();
+            assert_eq!(river(a), river(b));
+        }
+        assert_eq!(
+            restored.players[0].discards[0].is_tsumogiri,
+            round.players[0].discards[0].is_tsumogiri
+        );
+    }
+
+    /// 王牌の並びと各座席の一発・フリテン・喰い替え禁止の状態も復元されることを確認
+    #[test]
+    fn test_snapshot_keeps_dead_wall_and_player_flags() {
+        let mut round =
+            Round::new_with_seed(3, Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+        assert!(round.do_draw());
+        round.players[0].status.has_claimed_riichi = true;
+        round.players[0].status.is_unbroken = true;
+        round.players[0].status.is_double_riichi = true;
+        round.players[1].is_riichi_furiten = true;
+        round.players[2].is_temporary_furiten = true;
+        round.players[3].set_forbidden_discards(vec![Tile::M3]);
+        round.last_draw_was_dead_wall = true;
+
+        let json = serde_json::to_string(&round.snapshot().unwrap()).unwrap();
+        let snapshot: RoundSnapshot = serde_json::from_str(&json).unwrap();
+        let mut restored = ScenarioBuilder::from_snapshot(snapshot).build().unwrap();
+
+        assert_eq!(restored.wall.live_tiles(), round.wall.live_tiles());
+        assert_eq!(
+            restored.wall.remaining_dead_wall(),
+            round.wall.remaining_dead_wall()
+        );
+        assert_eq!(
+            restored.wall.uradora_indicators(),
+            round.wall.uradora_indicators()
+        );
+        assert_eq!(restored.wall.draw_rinshan(), round.wall.draw_rinshan());
+        assert_eq!(
+            restored.wall.add_dora_indicator(),
+            round.wall.add_dora_indicator()
+        );
+        assert!(restored.last_draw_was_dead_wall);
+        for seat in 0..4 {
+            let (a, b) = (&restored.players[seat], &round.players[seat]);
+            assert_eq!(a.status.has_claimed_riichi, b.status.has_claimed_riichi);
+            assert_eq!(a.status.is_unbroken, b.status.is_unbroken);
+            assert_eq!(a.status.is_double_riichi, b.status.is_double_riichi);
+            assert_eq!(a.status.is_first_turn, b.status.is_first_turn);
+            assert_eq!(a.is_riichi_furiten, b.is_riichi_furiten);
+            assert_eq!(a.is_temporary_furiten, b.is_temporary_furiten);
+            assert_eq!(a.forbidden_discards(), b.forbidden_discards());
+        }
+    }
+
+    /// 指定した王牌と食い違うドラ表示牌はエラーになることを確認
+    #[test]
+    fn test_build_rejects_dora_not_in_dead_wall() {
+        let round = ScenarioBuilder::new().build().unwrap();
+        let dead_wall = round.wall.remaining_dead_wall();
+        let wrong = if dead_wall[4] == Tile::new(Tile::Z7) {
+            Tile::new(Tile::Z6)
+        } else {
+            Tile::new(Tile::Z7)
+        };
+        let result = ScenarioBuilder::new()
+            .dead_wall(dead_wall)
+            .dora_indicators(vec![wrong])
+            .build();
+        assert!(result.is_err());
+    }
+
+    /// 鳴き待ちの局面は保存できないことを確認
+    #[test]
+    fn test_snapshot_rejects_pending_calls() {
+        let mut round = ScenarioBuilder::new().build().unwrap();
+        round.phase = TurnPhase::WaitForCalls;
+        assert!(round.snapshot().is_err());
+    }
+
+    /// 同じ牌を5枚以上指定するとエラーになることを確認
+    #[test]
+    fn test_build_rejects_fifth_copy() {
+        let result = ScenarioBuilder::new()
+            .hand(0, Hand::from("1111m456p789s123z"))
+            .river(1, vec![Tile::new(Tile::M1)])
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -166,6 +166,53 @@ fn test_round_scores() {
     assert_eq!(scores, [25000, 30000, 20000, 25000]);
 }
 
+#[test]
+fn test_exhaustive_draw_pays_nagashi_mangan() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 1, 0, 0, 4, Settings::new());
+    let river = |s: &str, is_called: bool| -> Vec<crate::player::Discard> {
+        Hand::from(s)
+            .tiles()
+            .iter()
+            .map(|&tile| crate::player::Discard {
+                tile,
+                is_tsumogiri: true,
+                is_riichi_declaration: false,
+                is_called,
+            })
+            .collect()
+    };
+    // プレイヤー1のみ聴牌、プレイヤー2は么九牌のみを捨てている
+    // プレイヤー3も么九牌のみだが、捨て牌を鳴かれているので不成立
+    for i in 0..4 {
+        round.players[i].hand = Hand::from("147m258p369s1234z");
+    }
+    round.players[1].hand = Hand::from("123m456p789s1122z");
+    round.players[0].discards = river("5m5p", false);
+    round.players[1].discards = river("5m5p", false);
+    round.players[2].discards = river("19m19p1z", false);
+    round.players[3].discards = river("19m19p1z", true);
+    round.drain_events();
+
+    round.do_exhaustive_draw();
+
+    assert_eq!(round.get_scores(), [20900, 22900, 33300, 22900]);
+    assert!(matches!(
+        round.result,
+        Some(RoundResult::ExhaustiveDraw {
+            dealer_tenpai: false
+        })
+    ));
+    let events = round.drain_events();
+    assert!(events.iter().any(|(_, e)| matches!(
+        e,
+        ServerEvent::RoundDraw {
+            reason: DrawReason::Exhaustive,
+            tenpai,
+            ..
+        } if tenpai == &vec![Wind::South]
+    )));
+}
+
 #[test]
 fn test_round_events_on_start() {
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
@@ -317,11 +364,11 @@ fn test_do_riichi_requires_tenpai_after_discard() {
     round.drain_events();
 
     assert!(!round.do_riichi(None));
-    assert!(!round.players[0].is_riichi);
+    assert!(!round.players[0].is_riichi());
     assert_eq!(round.players[0].hand.drawn(), Some(Tile::new(Tile::M8)));
 
     assert!(round.do_riichi(Some(Tile::new(Tile::Z4))));
-    assert!(round.players[0].is_riichi);
+    assert!(round.players[0].is_riichi());
 }
 
 #[test]
@@ -394,6 +441,80 @@ fn test_do_kakan_draws_rinshan_and_reveals_dora() {
     assert_eq!(round.wall.dora_indicators().len(), 2);
 }
 
+#[test]
+fn test_big_dragons_tsumo_is_paid_by_liable_player() {
+    // 3つ目の三元牌（中）を下家（座席2）からポンしているため、ツモでも座席2が全額を支払う
+    let mut round = scenario::ScenarioBuilder::new()
+        .current_player(1)
+        .hand(
+            1,
+            mahjong_core::hand::Hand::from("123m1p 5'55z 66'6z 777'z 1p"),
+        )
+        .build()
+        .unwrap();
+    let before = round.get_scores();
+    assert!(round.do_tsumo());
+    let after = round.get_scores();
+    let deltas: Vec<i32> = (0..4).map(|i| after[i] - before[i]).collect();
+    assert_eq!(deltas, [0, 32000, -32000, 0]);
+}
+
+#[test]
+fn test_kakan_dora_after_discard() {
+    let mut settings = Settings::new();
+    settings.kan_dora_timing = KanDoraTiming::AfterDiscard;
+    let mut round = scenario::ScenarioBuilder::new()
+        .hand(0, mahjong_core::hand::Hand::from("234p567s789m1z 111m 1m"))
+        .settings(settings)
+        .build()
+        .unwrap();
+    assert_eq!(round.phase, TurnPhase::WaitForDiscard);
+    round.drain_events();
+
+    // 加槓の新ドラは嶺上ツモの時点ではまだめくらない
+    assert!(round.do_kan(Tile::M1));
+    assert_eq!(round.wall.dora_indicators().len(), 1);
+
+    // 新ドラをめくる前の局面も保存・再開できる
+    let snapshot = round.snapshot().unwrap();
+    assert_eq!(snapshot.pending_kan_dora, 1);
+    let mut restored = scenario::ScenarioBuilder::from_snapshot(snapshot)
+        .build()
+        .unwrap();
+    assert_eq!(restored.pending_kan_dora, 1);
+    assert!(restored.do_discard(None));
+    assert_eq!(restored.wall.dora_indicators().len(), 2);
+
+    // 打牌後にめくる
+    assert!(round.do_discard(None));
+    assert_eq!(round.wall.dora_indicators().len(), 2);
+    assert_eq!(round.pending_kan_dora, 0);
+}
+
+#[test]
+fn test_ankan_dora_is_immediate_even_after_discard_timing() {
+    let mut settings = Settings::new();
+    settings.kan_dora_timing = KanDoraTiming::AfterDiscard;
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
+    let seat_wind = round.players[0].seat_wind;
+    let hand = mahjong_core::hand::Hand::from("111m234p567s789m 1m");
+    round.players[0] = Player::new(seat_wind, hand.tiles().to_vec(), 25000);
+    round.players[0].draw(hand.drawn().unwrap());
+    round.current_player = 0;
+    round.phase = TurnPhase::WaitForDiscard;
+
+    assert!(round.do_kan(Tile::M1));
+    assert_eq!(round.wall.dora_indicators().len(), 2);
+}
+
+#[test]
+fn test_uradora_without_riichi_setting() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    assert!(round.uradora_for(0).is_empty());
+    round.settings.ura_dora_requires_riichi = false;
+    assert_eq!(round.uradora_for(0), round.wall.uradora_indicators());
+}
+
 #[test]
 fn test_do_kakan_keeps_unrelated_drawn_tile_in_hand() {
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
@@ -482,7 +603,7 @@ fn test_riichi_furiten_set_on_ron_pass() {
     let seat1 = round.players[1].seat_wind;
     let hand1 = mahjong_core::hand::Hand::from("123m456p789s1122z");
     round.players[1] = Player::new(seat1, hand1.tiles().to_vec(), 25000);
-    round.players[1].is_riichi = true;
+    round.players[1].status.has_claimed_riichi = true;
 
     let call_state = round.check_available_calls(Tile::new(Tile::Z1), 0);
     assert!(
@@ -517,7 +638,7 @@ fn test_riichi_furiten_persists_after_draw() {
 
     // リーチ後フリテンを設定
     round.players[1].is_riichi_furiten = true;
-    round.players[1].is_riichi = true;
+    round.players[1].status.has_claimed_riichi = true;
 
     // プレイヤー1がツモ
     round.current_player = 1;
@@ -594,7 +715,7 @@ fn test_riichi_with_specific_tenpai_hand() {
     round.players[0].hand.set_drawn(Some(Tile::new(Tile::M8)));
 
     // 前提条件チェック
-    assert!(!round.players[0].is_riichi, "should not be in riichi");
+    assert!(!round.players[0].is_riichi(), "should not be in riichi");
     assert!(round.players[0].is_menzen(), "should be menzen");
     assert!(round.players[0].score >= 1000, "should have >= 1000 score");
     assert!(round.wall.remaining() >= 1, "wall should have tiles");
@@ -922,11 +1043,11 @@ fn test_triple_ron_draw_enabled() {
 
 #[test]
 fn test_triple_ron_draw_takes_priority_over_multiple_ron() {
-    // triple_ron_draw=true かつ multiple_ron=true の両方が有効な場合、
+    // triple_ron_draw=true かつ multiple_ron=AllWin の両方が有効な場合、
     // 三家和流局が優先されてトリロン（全員和了）にはならないことを明示的に確認する
     let mut settings = Settings::new();
     settings.triple_ron_draw = true;
-    settings.multiple_ron = true;
+    settings.multiple_ron = MultipleRon::AllWin;
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
     setup_triple_ron(&mut round);
     round.drain_events();
@@ -953,10 +1074,10 @@ fn test_triple_ron_draw_takes_priority_over_multiple_ron() {
 
 #[test]
 fn test_triple_ron_draw_disabled_multiple_ron_disabled_picks_winner() {
-    // triple_ron_draw=false, multiple_ron=false の場合は上家取り（頭ハネ）の1人ロン
+    // triple_ron_draw=false, multiple_ron=HeadBump の場合は上家取り（頭ハネ）の1人ロン
     let mut settings = Settings::new();
     settings.triple_ron_draw = false;
-    settings.multiple_ron = false;
+    settings.multiple_ron = MultipleRon::HeadBump;
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
     setup_triple_ron(&mut round);
     round.drain_events();
@@ -968,7 +1089,7 @@ fn test_triple_ron_draw_disabled_multiple_ron_disabled_picks_winner() {
     assert!(round.respond_to_call(2, CallResponse::Ron));
     assert!(round.respond_to_call(3, CallResponse::Ron));
 
-    // multiple_ron=false → 上家（プレイヤー1）が優先してロン
+    // multiple_ron=HeadBump → 上家（プレイヤー1）が優先してロン
     assert_eq!(round.phase, TurnPhase::RoundOver);
     match &round.result {
         Some(RoundResult::Ron { winners, loser, .. }) => {
@@ -984,7 +1105,7 @@ fn test_two_ron_no_draw() {
     // 2人ロンは三家和流局にならない（triple_ron_draw=true でも2人なら流局しない）
     let mut settings = Settings::new();
     settings.triple_ron_draw = true;
-    // multiple_ron=true（デフォルト）なので両方和了
+    // multiple_ron=AllWin（デフォルト）なので両方和了
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
     setup_triple_ron(&mut round);
     round.drain_events();
@@ -1009,9 +1130,9 @@ fn test_two_ron_no_draw() {
 
 #[test]
 fn test_two_ron_disabled_picks_winner() {
-    // multiple_ron=false の場合は上家取り（頭ハネ）の1人ロン
+    // multiple_ron=HeadBump の場合は上家取り（頭ハネ）の1人ロン
     let mut settings = Settings::new();
-    settings.multiple_ron = false;
+    settings.multiple_ron = MultipleRon::HeadBump;
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
     setup_triple_ron(&mut round);
     round.drain_events();
@@ -1023,7 +1144,7 @@ fn test_two_ron_disabled_picks_winner() {
     assert!(round.respond_to_call(2, CallResponse::Ron));
     assert!(round.respond_to_call(3, CallResponse::Pass));
 
-    // multiple_ron=false → 上家（プレイヤー1）のみロン
+    // multiple_ron=HeadBump → 上家（プレイヤー1）のみロン
     assert_eq!(round.phase, TurnPhase::RoundOver);
     match &round.result {
         Some(RoundResult::Ron { winners, loser, .. }) => {
@@ -1034,9 +1155,35 @@ fn test_two_ron_disabled_picks_winner() {
     }
 }
 
+#[test]
+fn test_double_ron_abortive_draw() {
+    // multiple_ron=AbortiveDraw: 2人ロンで流局
+    let mut settings = Settings::new();
+    settings.multiple_ron = MultipleRon::AbortiveDraw;
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
+    setup_triple_ron(&mut round);
+    round.drain_events();
+
+    assert!(round.do_discard(None));
+    assert!(round.respond_to_call(1, CallResponse::Ron));
+    assert!(round.respond_to_call(2, CallResponse::Ron));
+    assert!(round.respond_to_call(3, CallResponse::Pass));
+
+    assert_eq!(round.phase, TurnPhase::RoundOver);
+    assert!(matches!(round.result, Some(RoundResult::SpecialDraw)));
+    let events = round.drain_events();
+    assert!(events.iter().any(|(_, e)| matches!(
+        e,
+        ServerEvent::RoundDraw {
+            reason: DrawReason::DoubleRon,
+            ..
+        }
+    )));
+}
+
 #[test]
 fn test_double_ron_both_win() {
-    // multiple_ron=true（デフォルト）: 2人ロンで両方和了
+    // multiple_ron=AllWin（デフォルト）: 2人ロンで両方和了
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
     setup_triple_ron(&mut round);
     round.drain_events();
@@ -1059,9 +1206,9 @@ fn test_double_ron_both_win() {
 
 #[test]
 fn test_triple_ron_all_win() {
-    // multiple_ron=true かつ triple_ron_draw=false: 3人ロンで全員和了
+    // multiple_ron=AllWin かつ triple_ron_draw=false: 3人ロンで全員和了
     let mut settings = Settings::new();
-    settings.multiple_ron = true;
+    settings.multiple_ron = MultipleRon::AllWin;
     settings.triple_ron_draw = false;
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
     setup_triple_ron(&mut round);
@@ -1243,6 +1390,7 @@ fn test_auto_pass_cpu_skips_human_player() {
 // ===== 喰い替え禁止（#247） =====
 
 use mahjong_core::hand::Hand;
+use mahjong_core::settings::Kuikae;
 
 #[test]
 fn test_swap_calling_forbids_genbutsu_after_chi() {
@@ -1265,6 +1413,26 @@ fn test_swap_calling_forbids_genbutsu_after_chi() {
     assert!(round.do_discard(Some(Tile::new(Tile::P2))));
 }
 
+#[test]
+fn test_ippatsu_broken_by_other_players_call() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    round.players[2].declare_riichi();
+    assert!(round.players[2].status.is_unbroken);
+    round.players[1].hand = Hand::from("345m234567p678s1z");
+
+    round.execute_chi(
+        1,
+        0,
+        Tile::new(Tile::M3),
+        [Tile::new(Tile::M4), Tile::new(Tile::M5)],
+    );
+
+    assert!(round.players[2].is_riichi());
+    assert!(!round.players[2].status.is_unbroken);
+    // 鳴きが入ったので、以降は天和・地和・ダブルリーチの条件を満たさない
+    assert!(round.players.iter().all(|p| !p.status.is_first_turn));
+}
+
 #[test]
 fn test_swap_calling_forbids_suji_after_chi() {
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
@@ -1303,10 +1471,28 @@ fn test_swap_calling_forbids_genbutsu_after_pon() {
     assert!(round.do_discard(Some(Tile::new(Tile::P2))));
 }
 
+#[test]
+fn test_no_tsumo_right_after_pon() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    // 7z をポンすると残りの手牌だけで和了形になるが、ツモ牌が無いのでツモ和了はできない
+    round.players[1].hand = Hand::from("111s234567p66m77z");
+
+    round.execute_pon(
+        1,
+        0,
+        Tile::new(Tile::Z7),
+        [Tile::new(Tile::Z7), Tile::new(Tile::Z7)],
+    );
+
+    assert!(!round.can_tsumo());
+    assert!(!round.legal_actions(1).contains(&Action::Tsumo));
+    assert!(!round.do_tsumo());
+}
+
 #[test]
 fn test_swap_calling_disabled_allows_genbutsu_discard() {
     let settings = Settings {
-        forbid_swap_calling: false,
+        kuikae: Kuikae::Allowed,
         ..Settings::new()
     };
     let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
@@ -1322,3 +1508,230 @@ fn test_swap_calling_disabled_allows_genbutsu_discard() {
     // 設定で喰い替え禁止を無効化している場合は、スジ牌でも打牌できる
     assert!(round.do_discard(Some(Tile::new(Tile::M6))));
 }
+
+#[test]
+fn test_kuikae_only_same_tile_allows_suji_discard() {
+    let settings = Settings {
+        kuikae: Kuikae::OnlySameTile,
+        ..Settings::new()
+    };
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, settings);
+    round.players[1].hand = Hand::from("3456m234567p67s1z");
+
+    round.execute_chi(
+        1,
+        0,
+        Tile::new(Tile::M3),
+        [Tile::new(Tile::M4), Tile::new(Tile::M5)],
+    );
+
+    // 現物喰い替えのみ禁止の場合、鳴いた 3m は捨てられないがスジの 6m は捨てられる
+    assert!(!round.do_discard(Some(Tile::new(Tile::M3))));
+    assert!(round.do_discard(Some(Tile::new(Tile::M6))));
+}
+
+#[test]
+fn test_legal_actions_on_own_turn() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    let seat_wind = round.players[0].seat_wind;
+    let hand = mahjong_core::hand::Hand::from("123m123p123s45z67m 8m");
+    round.players[0] = Player::new(seat_wind, hand.tiles().to_vec(), 25000);
+    round.players[0].draw(hand.drawn().unwrap());
+    round.phase = TurnPhase::WaitForDiscard;
+    round.current_player = 0;
+
+    let actions = round.legal_actions(0);
+    assert!(actions.contains(&Action::Discard(None)));
+    assert!(actions.contains(&Action::Discard(Some(Tile::new(Tile::M1)))));
+    // 4z・5z 切りなら聴牌するが、ツモ切りでは聴牌しない
+    assert!(actions.contains(&Action::Riichi(Some(Tile::new(Tile::Z4)))));
+    assert!(actions.contains(&Action::Riichi(Some(Tile::new(Tile::Z5)))));
+    assert!(!actions.contains(&Action::Riichi(None)));
+    assert!(!actions.contains(&Action::Tsumo));
+    // 同じ牌は1つにまとめる
+    let m1_discards = actions
+        .iter()
+        .filter(|a| **a == Action::Discard(Some(Tile::new(Tile::M1))))
+        .count();
+    assert_eq!(m1_discards, 1);
+
+    // 手番でない座席は何もできない
+    assert!(round.legal_actions(1).is_empty());
+}
+
+#[test]
+fn test_legal_actions_in_riichi_only_tsumogiri_and_tsumo() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    let seat_wind = round.players[0].seat_wind;
+    let hand = mahjong_core::hand::Hand::from("111m234p567s789m5z 5z");
+    round.players[0] = Player::new(seat_wind, hand.tiles().to_vec(), 25000);
+    round.players[0].declare_riichi();
+    round.players[0].draw(hand.drawn().unwrap());
+    round.phase = TurnPhase::WaitForDiscard;
+    round.current_player = 0;
+
+    assert_eq!(
+        round.legal_actions(0),
+        vec![Action::Tsumo, Action::Discard(None)]
+    );
+}
+
+#[test]
+fn test_do_kan_rejects_wait_changing_ankan_after_riichi() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    let seat_wind = round.players[0].seat_wind;
+    // 3m をカンすると 2-4-5m 待ちが 4m 単騎に変わる
+    let hand = mahjong_core::hand::Hand::from("3334m456p789s111z 3m");
+    round.players[0] = Player::new(seat_wind, hand.tiles().to_vec(), 25000);
+    round.players[0].declare_riichi();
+    round.players[0].draw(hand.drawn().unwrap());
+    round.phase = TurnPhase::WaitForDiscard;
+    round.current_player = 0;
+
+    assert!(!round.legal_actions(0).contains(&Action::Ankan(Tile::M3)));
+    // 合法手に無いカンを直接宣言しても受け付けない
+    assert!(!round.do_kan(Tile::M3));
+    assert!(round.players[0].hand.melds().is_empty());
+}
+
+#[test]
+fn test_legal_actions_include_ankan_and_are_accepted() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    let seat_wind = round.players[0].seat_wind;
+    let hand = mahjong_core::hand::Hand::from("111m234p567s789m 1m");
+    round.players[0] = Player::new(seat_wind, hand.tiles().to_vec(), 25000);
+    round.players[0].draw(hand.drawn().unwrap());
+    round.current_player = 0;
+    round.phase = TurnPhase::WaitForDiscard;
+
+    let actions = round.legal_actions(0);
+    assert!(actions.contains(&Action::Ankan(Tile::M1)));
+    assert!(!actions.iter().any(|a| matches!(a, Action::Kakan(_))));
+    assert!(round.do_kan(Tile::M1));
+}
+
+#[test]
+fn test_legal_actions_exclude_swap_call_discards() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    round.players[1].hand = Hand::from("345m234567p678s1z");
+    round.execute_chi(
+        1,
+        0,
+        Tile::new(Tile::M3),
+        [Tile::new(Tile::M4), Tile::new(Tile::M5)],
+    );
+
+    let actions = round.legal_actions(1);
+    assert!(!actions.contains(&Action::Discard(Some(Tile::new(Tile::M3)))));
+    assert!(actions.contains(&Action::Discard(Some(Tile::new(Tile::P2)))));
+    // 鳴いた直後はツモ牌がないのでツモ切りはできない
+    assert!(!actions.contains(&Action::Discard(None)));
+}
+
+#[test]
+fn test_legal_actions_for_calls() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    let seat_wind = round.players[1].seat_wind;
+    let hand = mahjong_core::hand::Hand::from("111m234p567s789m");
+    round.players[1] = Player::new(seat_wind, hand.tiles().to_vec(), 25000);
+    round.call_state = Some(round.check_available_calls(Tile::new(Tile::M1), 0));
+    round.phase = TurnPhase::WaitForCalls;
+
+    let actions = round.legal_actions(1);
+    assert!(actions.contains(&Action::Pon([Tile::new(Tile::M1); 2])));
+    assert!(actions.contains(&Action::Daiminkan(Tile::M1)));
+    assert_eq!(actions.last(), Some(&Action::Pass));
+    assert!(round.legal_actions(0).is_empty());
+
+    assert!(round.respond_to_call(1, CallResponse::Pass));
+    assert!(round.legal_actions(1).is_empty());
+}
+
+#[test]
+fn test_legal_actions_for_nine_terminals() {
+    let mut round = Round::new(Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    setup_nine_terminals_hand(&mut round, 0);
+    round.phase = TurnPhase::WaitForNineTerminals;
+
+    assert_eq!(
+        round.legal_actions(0),
+        vec![
+            Action::NineTerminals { declare: true },
+            Action::NineTerminals { declare: false },
+        ]
+    );
+}
+
+/// 再生した局が元の局と同じ局面かを確認する
+fn assert_same_round(a: &Round, b: &Round) {
+    assert_eq!(a.phase, b.phase);
+    assert_eq!(a.current_player, b.current_player);
+    assert_eq!(a.get_scores(), b.get_scores());
+    assert_eq!(a.riichi_sticks, b.riichi_sticks);
+    assert_eq!(a.wall.live_tiles(), b.wall.live_tiles());
+    assert_eq!(a.wall.dora_indicators(), b.wall.dora_indicators());
+    for seat in 0..4 {
+        assert_eq!(a.players[seat].hand.tiles(), b.players[seat].hand.tiles());
+        assert_eq!(a.players[seat].hand.drawn(), b.players[seat].hand.drawn());
+        assert_eq!(
+            a.players[seat].hand.melds().len(),
+            b.players[seat].hand.melds().len()
+        );
+        let rivers =
+            |r: &Round| -> Vec<Tile> { r.players[seat].discards.iter().map(|d| d.tile).collect() };
+        assert_eq!(rivers(a), rivers(b));
+    }
+}
+
+#[test]
+fn test_replay_reconstructs_finished_round() {
+    let mut round = Round::new_with_seed(7, Wind::East, 0, [25000; 4], 1, 1, 0, 4, Settings::new());
+    round.play_to_end();
+
+    let log = round.event_log();
+    assert!(matches!(log[0], replay::GameEvent::Deal(_)));
+    let replayed = Round::replay(log).unwrap();
+    assert_same_round(&round, &replayed);
+    assert_eq!(replayed.event_log().len(), log.len());
+}
+
+#[test]
+fn test_replay_prefix_undoes_last_action() {
+    let mut round =
+        Round::new_with_seed(42, Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    round.do_draw();
+    let log_before_discard = round.event_log().to_vec();
+    assert!(round.do_discard(None));
+
+    let undone = Round::replay(&log_before_discard).unwrap();
+    assert_eq!(undone.phase, TurnPhase::WaitForDiscard);
+    assert!(undone.players[0].hand.drawn().is_some());
+    assert!(undone.players[0].discards.is_empty());
+}
+
+#[test]
+fn test_replay_log_survives_serde_round_trip() {
+    let mut round =
+        Round::new_with_seed(3, Wind::South, 2, [25000; 4], 0, 0, 5, 8, Settings::new());
+    round.play_to_end();
+
+    let json = serde_json::to_string(round.event_log()).unwrap();
+    let log: Vec<replay::GameEvent> = serde_json::from_str(&json).unwrap();
+    assert_same_round(&round, &Round::replay(&log).unwrap());
+}
+
+#[test]
+fn test_replay_rejects_invalid_logs() {
+    assert!(Round::replay(&[]).is_err());
+
+    let mut round =
+        Round::new_with_seed(42, Wind::East, 0, [25000; 4], 0, 0, 0, 4, Settings::new());
+    round.do_draw();
+    let mut log = round.event_log().to_vec();
+    // 手番でない座席の打牌は適用できない
+    log.push(replay::GameEvent::Discard {
+        seat: 2,
+        tile: None,
+    });
+    assert!(Round::replay(&log).is_err());
+}
@@ -6,15 +6,16 @@
 
 use mahjong_core::hand::Hand;
 use mahjong_core::hand_info::hand_analyzer::{self, HandAnalyzer};
-use mahjong_core::hand_info::status::Status;
+use mahjong_core::scoring::payment::{self, Pao, WinMethod};
 use mahjong_core::scoring::score::{
-    DoraLabel, ScoreItem, ScoreResult, calculate_base_points, calculate_score, determine_rank,
+    DoraSummary, ScoreItem, ScoreResult, calculate_base_points, calculate_score, determine_rank,
     round_up_to_100,
 };
-use mahjong_core::settings::Settings;
+use mahjong_core::settings::{MultipleRon, Settings};
 use mahjong_core::tile::{Tile, TileType, Wind, dora_indicator_to_dora};
 
 use crate::player::Player;
+use crate::round::RIICHI_STICK_VALUE;
 
 /// 和了判定の結果
 #[derive(Debug)]
@@ -77,17 +78,13 @@ pub fn check_win_with_settings(
         };
     }
 
-    // Status を構築
-    let mut status = Status::new();
+    // プレイヤーの Status（立直・一発・第一ツモ）に和了時の状態を加える
+    let mut status = player.status.clone();
     status.is_self_drawn = is_tsumo;
     status.seat_wind = player.seat_wind;
     status.round_wind = round_wind;
-    status.has_claimed_riichi = player.is_riichi;
-    status.is_double_riichi = player.is_double_riichi;
-    status.is_unbroken = player.is_ippatsu;
     status.has_claimed_open = !player.is_menzen();
     status.is_dealer = player.is_dealer();
-    status.is_first_turn = player.is_first_turn;
     status.is_last_tile_draw = is_last_tile && is_tsumo;
     status.is_last_tile_claim = is_last_tile && !is_tsumo;
     status.is_after_a_quad = is_after_a_quad;
@@ -174,20 +171,17 @@ pub fn check_ron_with_flags_and_settings(
         };
     }
 
-    // Status を構築（ロンなので is_self_drawn = false）
-    let mut status = Status::new();
+    // プレイヤーの Status に和了時の状態を加える（ロンなので is_self_drawn = false）
+    let mut status = player.status.clone();
     status.is_self_drawn = false;
     status.seat_wind = player.seat_wind;
     status.round_wind = round_wind;
-    status.has_claimed_riichi = player.is_riichi;
-    status.is_double_riichi = player.is_double_riichi;
-    status.is_unbroken = player.is_ippatsu;
     status.has_claimed_open = !player.is_menzen();
     status.is_dealer = player.is_dealer();
-    status.is_first_turn = player.is_first_turn;
     status.is_last_tile_draw = false;
     status.is_last_tile_claim = is_last_tile && !is_robbing_a_quad;
     status.is_robbing_a_quad = is_robbing_a_quad;
+    status.robbed_quad_tile = is_robbing_a_quad.then_some(discarded_tile);
     status.kan_count = player.kan_count() as u32;
 
     match calculate_score(&analyzer, &hand, &status, settings) {
@@ -227,7 +221,9 @@ pub fn get_waiting_tiles(player: &Player) -> Vec<TileType> {
 /// - `winner_is_dealer`: 和了プレイヤーが親かどうか
 /// - `dealer_idx`: 親のプレイヤーインデックス (0-3)
 /// - `honba`: 本場数
+/// - `pao`: 責任払い（包）があれば、その対象と責任者
 ///
+/// 供託リーチ棒は含まない（呼び出し側で和了者に加算する）。
 /// 戻り値: 各プレイヤーの点数変動 (正=増加、負=減少)。合計は必ず0。
 pub fn calculate_tsumo_score_deltas(
     winner: usize,
@@ -235,44 +231,18 @@ pub fn calculate_tsumo_score_deltas(
     winner_is_dealer: bool,
     dealer_idx: usize,
     honba: usize,
+    pao: Option<Pao>,
 ) -> [i32; 4] {
-    let mut deltas = [0i32; 4];
-    let honba_bonus = honba as i32 * 100;
-
-    if winner_is_dealer {
-        // 親ツモ: 各子が dealer_tsumo_all + 本場ボーナス を支払う
-        let each_pay = score_result.dealer_tsumo_all as i32 + honba_bonus;
-        for (i, delta) in deltas.iter_mut().enumerate() {
-            if i == winner {
-                *delta = each_pay * 3;
-            } else {
-                *delta = -each_pay;
-            }
-        }
-    } else {
-        // 子ツモ: 親が non_dealer_tsumo_dealer、他の子が non_dealer_tsumo_non_dealer を支払う
-        let dealer_pay = score_result.non_dealer_tsumo_dealer as i32 + honba_bonus;
-        let non_dealer_pay = score_result.non_dealer_tsumo_non_dealer as i32 + honba_bonus;
-        let mut total_gain = 0i32;
-        for (i, delta) in deltas.iter_mut().enumerate() {
-            if i == winner {
-                continue;
-            }
-            if i == dealer_idx {
-                *delta = -dealer_pay;
-                total_gain += dealer_pay;
-            } else {
-                *delta = -non_dealer_pay;
-                total_gain += non_dealer_pay;
-            }
-        }
-        deltas[winner] = total_gain;
+    if let Some(pao) = pao {
+        let score = with_honba(score_result, honba);
+        return payment::pao_payment_deltas(&score, winner, WinMethod::Tsumo, dealer_idx, pao);
     }
-
-    deltas
+    score_result
+        .payments(winner_is_dealer, true, honba as u32, 0)
+        .tsumo_deltas(winner, dealer_idx)
 }
 
-/// 和了結果にドラ・赤ドラ・裏ドラの翻を加算する
+/// 和了結果にドラ・カンドラ・赤ドラ・裏ドラの翻を加算する
 ///
 /// 役判定後の点数計算結果にドラ関連の翻を追加し、
 /// 翻数・等級・支払い額を再計算する。
@@ -282,12 +252,14 @@ pub fn calculate_tsumo_score_deltas(
 /// * `extra_tile` - ロン和了の場合の和了牌（手牌に含まれていないため別途指定）
 /// * `dora_indicators` - ドラ表示牌
 /// * `uradora_indicators` - 裏ドラ表示牌（リーチ時のみ非空）
+/// * `settings` - ルール設定（数え役満の有無）
 pub fn add_dora_to_score(
     score_result: &mut ScoreResult,
     hand: &Hand,
     extra_tile: Option<Tile>,
     dora_indicators: &[Tile],
     uradora_indicators: &[Tile],
+    settings: &Settings,
 ) {
     // 役満の場合はドラを加算しない
     if score_result.yaku_list.iter().any(|(_, h)| *h >= 13) {
@@ -306,24 +278,30 @@ pub fn add_dora_to_score(
         all_tiles.extend(open.expanded_tiles());
     }
 
-    // ドラ表示牌からドラ牌を計算してカウント
-    let mut dora_count: u32 = 0;
-    for indicator in dora_indicators {
-        let dora_type = dora_indicator_to_dora(indicator.get());
-        dora_count += all_tiles.iter().filter(|t| t.get() == dora_type).count() as u32;
-    }
-
-    // 裏ドラ表示牌からドラ牌を計算してカウント
-    let mut uradora_count: u32 = 0;
-    for indicator in uradora_indicators {
-        let dora_type = dora_indicator_to_dora(indicator.get());
-        uradora_count += all_tiles.iter().filter(|t| t.get() == dora_type).count() as u32;
-    }
+    let count_dora = |indicators: &[Tile]| -> u32 {
+        indicators
+            .iter()
+            .map(|indicator| {
+                let dora_type = dora_indicator_to_dora(indicator.get());
+                all_tiles.iter().filter(|t| t.get() == dora_type).count() as u32
+            })
+            .sum()
+    };
 
-    // 赤ドラをカウント
-    let red_dora_count = all_tiles.iter().filter(|t| t.is_red_dora()).count() as u32;
+    // 最初のドラ表示牌は表ドラ、以降はカンで追加されたカンドラとして数える
+    let (first, kan) = dora_indicators.split_at(dora_indicators.len().min(1));
+    // 抜きドラは mahjong-core の点数計算で加算済み
+    let counted = score_result.dora_summary;
+    let summary = DoraSummary {
+        dora: count_dora(first),
+        kan_dora: count_dora(kan),
+        ura_dora: count_dora(uradora_indicators),
+        red_dora: all_tiles.iter().filter(|t| t.is_red_dora()).count() as u32,
+        nuki_dora: counted.nuki_dora,
+    };
+    score_result.dora_summary = summary;
 
-    let extra_han = dora_count + uradora_count + red_dora_count;
+    let extra_han = summary.total() - counted.total();
     if extra_han == 0 {
         return;
     }
@@ -333,30 +311,20 @@ pub fn add_dora_to_score(
     score_result.han = new_han;
 
     // 等級・点数を再計算
-    score_result.rank = determine_rank(new_han, score_result.fu, false);
-    let base_points = calculate_base_points(new_han, score_result.fu, score_result.rank);
+    let fu = score_result.fu.unwrap_or(0);
+    score_result.rank = determine_rank(new_han, fu, false, settings);
+    let base_points = calculate_base_points(new_han, fu, score_result.rank);
     score_result.dealer_ron = round_up_to_100(base_points * 6);
     score_result.dealer_tsumo_all = round_up_to_100(base_points * 2);
     score_result.non_dealer_ron = round_up_to_100(base_points * 4);
     score_result.non_dealer_tsumo_dealer = round_up_to_100(base_points * 2);
     score_result.non_dealer_tsumo_non_dealer = round_up_to_100(base_points);
 
-    // ドラ・赤ドラ・裏ドラをこの順で末尾に追加
-    if dora_count > 0 {
-        score_result
-            .yaku_list
-            .push((ScoreItem::Dora(DoraLabel::Dora), dora_count));
-    }
-    if red_dora_count > 0 {
-        score_result
-            .yaku_list
-            .push((ScoreItem::Dora(DoraLabel::RedDora), red_dora_count));
-    }
-    if uradora_count > 0 {
-        score_result
-            .yaku_list
-            .push((ScoreItem::Dora(DoraLabel::UraDora), uradora_count));
-    }
+    // ドラ・カンドラ・赤ドラ・抜きドラ・裏ドラをこの順で末尾に並べ直す
+    score_result
+        .yaku_list
+        .retain(|(item, _)| !matches!(item, ScoreItem::Dora(_)));
+    score_result.yaku_list.extend(summary.items());
 }
 
 /// プレイヤーがテンパイしているか判定する（13枚の手牌で）
@@ -371,7 +339,9 @@ pub fn is_ready(player: &Player) -> bool {
 /// - `score_result`: 点数計算の結果
 /// - `winner_is_dealer`: 和了プレイヤーが親かどうか
 /// - `honba`: 本場数
+/// - `pao`: 責任払い（包）があれば、その対象と責任者
 ///
+/// 供託リーチ棒は含まない（呼び出し側で和了者に加算する）。
 /// 戻り値: 各プレイヤーの点数変動 (正=増加、負=減少)。合計は必ず0。
 pub fn calculate_ron_score_deltas(
     winner: usize,
@@ -379,20 +349,127 @@ pub fn calculate_ron_score_deltas(
     score_result: &ScoreResult,
     winner_is_dealer: bool,
     honba: usize,
+    pao: Option<Pao>,
 ) -> [i32; 4] {
-    let mut deltas = [0i32; 4];
-    let honba_bonus = honba as i32 * 300; // ロンは本場1本場につき300点
+    if let Some(pao) = pao {
+        // ロンの支払いは和了者が親かどうかだけで決まるため、親の座席はそれに合わせて仮に決める
+        let dealer = if winner_is_dealer {
+            winner
+        } else {
+            (winner + 1) % 4
+        };
+        let score = with_honba(score_result, honba);
+        return payment::pao_payment_deltas(&score, winner, WinMethod::Ron { loser }, dealer, pao);
+    }
+    score_result
+        .payments(winner_is_dealer, false, honba as u32, 0)
+        .ron_deltas(winner, loser)
+}
 
-    let ron_points = if winner_is_dealer {
-        score_result.dealer_ron as i32
-    } else {
-        score_result.non_dealer_ron as i32
-    };
+/// 本場を設定し、供託を含めない点数計算の結果を返す（供託は呼び出し側で和了者に加算する）
+fn with_honba(score_result: &ScoreResult, honba: usize) -> ScoreResult {
+    ScoreResult {
+        honba: honba as u32,
+        riichi_sticks: 0,
+        ..score_result.clone()
+    }
+}
 
-    deltas[winner] = ron_points + honba_bonus;
-    deltas[loser] = -(ron_points + honba_bonus);
+/// 荒牌流局時のノーテン罰符の点数移動を計算する
+///
+/// - `tenpai_players`: 聴牌しているプレイヤーのインデックス
+///
+/// 聴牌者と不聴者がいる場合のみ、不聴者が合計3000点を聴牌者に支払う。
+/// 戻り値: 各プレイヤーの点数変動 (正=増加、負=減少)。合計は必ず0。
+pub fn calculate_tenpai_payment_deltas(tenpai_players: &[usize]) -> [i32; 4] {
+    payment::tenpai_payment_deltas(tenpai_players)
+}
+
+/// 同じ打牌に対するロン和了者1人分の情報
+#[derive(Debug)]
+pub struct RonWinner {
+    /// 和了プレイヤーのインデックス (0-3)
+    pub seat: usize,
+    /// 点数計算の結果（ドラ加算済み）
+    pub score_result: ScoreResult,
+    /// 和了プレイヤーが親かどうか
+    pub is_dealer: bool,
+    /// 責任払い（包）
+    pub pao: Option<Pao>,
+}
 
-    deltas
+/// 和了者1人分の精算結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RonPayout {
+    /// 和了プレイヤーのインデックス (0-3)
+    pub seat: usize,
+    /// この和了による点数変動（本場を含み、供託棒は含まない）。合計は0。
+    pub deltas: [i32; 4],
+    /// 受け取った供託棒の点数
+    pub riichi_bonus: i32,
+    /// 和了者の獲得点数（本場・供託棒を含む）
+    pub points: i32,
+}
+
+/// 1つの打牌に対するロンの精算結果
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RonSettlement {
+    /// 和了が認められた和了者ごとの精算結果（打順優先順）
+    pub payouts: Vec<RonPayout>,
+    /// 全員分を合算した各プレイヤーの点数変動（供託棒を含む）
+    pub deltas: [i32; 4],
+}
+
+/// 同じ打牌に対する1人以上のロン和了をまとめて精算する
+///
+/// - `winners`: ロン宣言したプレイヤー（順不同）
+/// - `discarder`: 放銃プレイヤーのインデックス (0-3)
+/// - `honba`: 本場数
+/// - `riichi_sticks`: 供託リーチ棒の本数
+///
+/// 和了者は打順優先順（下家→対面→上家）に並べ、`settings.multiple_ron` が全員和了でなければ
+/// 先頭の1人のみ和了を認める（頭ハネ）。本場と供託棒は打順最優先の和了者のみが受け取る。
+/// 三家和・複数同時ロンによる流局の判定は呼び出し側で行う。
+pub fn score_multiple_winners(
+    winners: &[RonWinner],
+    discarder: usize,
+    honba: usize,
+    riichi_sticks: usize,
+    settings: &Settings,
+) -> RonSettlement {
+    let mut sorted: Vec<&RonWinner> = winners.iter().collect();
+    sorted.sort_by_key(|w| (w.seat + 4 - discarder) % 4);
+    if settings.multiple_ron != MultipleRon::AllWin {
+        sorted.truncate(1);
+    }
+
+    let mut settlement = RonSettlement::default();
+    for (rank, winner) in sorted.into_iter().enumerate() {
+        let (honba_for_this, riichi_bonus) = if rank == 0 {
+            (honba, riichi_sticks as i32 * RIICHI_STICK_VALUE)
+        } else {
+            (0, 0)
+        };
+        let deltas = calculate_ron_score_deltas(
+            winner.seat,
+            discarder,
+            &winner.score_result,
+            winner.is_dealer,
+            honba_for_this,
+            winner.pao,
+        );
+        for (total, delta) in settlement.deltas.iter_mut().zip(deltas) {
+            *total += delta;
+        }
+        settlement.deltas[winner.seat] += riichi_bonus;
+        settlement.payouts.push(RonPayout {
+            seat: winner.seat,
+            deltas,
+            riichi_bonus,
+            points: deltas[winner.seat] + riichi_bonus,
+        });
+    }
+    settlement
 }
 
 #[cfg(test)]
@@ -408,7 +485,7 @@ mod tests {
     fn make_mangan_score() -> ScoreResult {
         ScoreResult {
             han: 5,
-            fu: 30,
+            fu: Some(30),
             rank: ScoreRank::Mangan,
             dealer_ron: 12000,
             dealer_tsumo_all: 4000,
@@ -424,13 +501,16 @@ mod tests {
                     fu: 20,
                 }],
             },
+            dora_summary: DoraSummary::default(),
+            honba: 0,
+            riichi_sticks: 0,
         }
     }
 
     #[test]
     fn test_tsumo_dealer_mangan() {
         let score = make_mangan_score();
-        let deltas = calculate_tsumo_score_deltas(0, &score, true, 0, 0);
+        let deltas = calculate_tsumo_score_deltas(0, &score, true, 0, 0, None);
         assert_eq!(deltas[0], 12000); // 4000 * 3
         assert_eq!(deltas[1], -4000);
         assert_eq!(deltas[2], -4000);
@@ -441,7 +521,7 @@ mod tests {
     #[test]
     fn test_tsumo_non_dealer_mangan() {
         let score = make_mangan_score();
-        let deltas = calculate_tsumo_score_deltas(1, &score, false, 0, 0);
+        let deltas = calculate_tsumo_score_deltas(1, &score, false, 0, 0, None);
         assert_eq!(deltas[0], -4000); // 親
         assert_eq!(deltas[1], 8000); // 和了者: 4000+2000+2000
         assert_eq!(deltas[2], -2000); // 子
@@ -453,7 +533,7 @@ mod tests {
     fn test_tsumo_with_honba() {
         let score = make_mangan_score();
         // 2本場: 各プレイヤーの支払いに100*2=200点加算
-        let deltas = calculate_tsumo_score_deltas(0, &score, true, 0, 2);
+        let deltas = calculate_tsumo_score_deltas(0, &score, true, 0, 2, None);
         assert_eq!(deltas[1], -4200); // 4000+200
         assert_eq!(deltas[2], -4200);
         assert_eq!(deltas[3], -4200);
@@ -464,7 +544,7 @@ mod tests {
     #[test]
     fn test_ron_dealer_mangan() {
         let score = make_mangan_score();
-        let deltas = calculate_ron_score_deltas(0, 2, &score, true, 0);
+        let deltas = calculate_ron_score_deltas(0, 2, &score, true, 0, None);
         assert_eq!(deltas[0], 12000);
         assert_eq!(deltas[2], -12000);
         assert_eq!(deltas[1], 0);
@@ -475,7 +555,7 @@ mod tests {
     #[test]
     fn test_ron_non_dealer_mangan() {
         let score = make_mangan_score();
-        let deltas = calculate_ron_score_deltas(1, 3, &score, false, 0);
+        let deltas = calculate_ron_score_deltas(1, 3, &score, false, 0, None);
         assert_eq!(deltas[1], 8000);
         assert_eq!(deltas[3], -8000);
         assert_eq!(deltas.iter().sum::<i32>(), 0);
@@ -485,12 +565,78 @@ mod tests {
     fn test_ron_with_honba() {
         let score = make_mangan_score();
         // 3本場: 300*3=900点加算
-        let deltas = calculate_ron_score_deltas(1, 3, &score, false, 3);
+        let deltas = calculate_ron_score_deltas(1, 3, &score, false, 3, None);
         assert_eq!(deltas[1], 8900);
         assert_eq!(deltas[3], -8900);
         assert_eq!(deltas.iter().sum::<i32>(), 0);
     }
 
+    #[test]
+    fn test_tenpai_payment_deltas() {
+        assert_eq!(
+            calculate_tenpai_payment_deltas(&[1]),
+            [-1000, 3000, -1000, -1000]
+        );
+        assert_eq!(
+            calculate_tenpai_payment_deltas(&[0, 2]),
+            [1500, -1500, 1500, -1500]
+        );
+        assert_eq!(calculate_tenpai_payment_deltas(&[]), [0; 4]);
+        assert_eq!(calculate_tenpai_payment_deltas(&[0, 1, 2, 3]), [0; 4]);
+    }
+
+    #[test]
+    fn test_score_multiple_winners_double_ron() {
+        let winners = [
+            RonWinner {
+                seat: 0,
+                score_result: make_mangan_score(),
+                is_dealer: true,
+                pao: None,
+            },
+            RonWinner {
+                seat: 3,
+                score_result: make_mangan_score(),
+                is_dealer: false,
+                pao: None,
+            },
+        ];
+        // 座席2の打牌: 打順は座席3 → 座席0
+        let settlement = score_multiple_winners(&winners, 2, 1, 2, &Settings::new());
+
+        assert_eq!(settlement.payouts.len(), 2);
+        assert_eq!(settlement.payouts[0].seat, 3);
+        assert_eq!(settlement.payouts[0].points, 8300 + 2000);
+        assert_eq!(settlement.payouts[1].seat, 0);
+        assert_eq!(settlement.payouts[1].points, 12000);
+        assert_eq!(settlement.deltas, [12000, 0, -20300, 10300]);
+    }
+
+    #[test]
+    fn test_score_multiple_winners_head_bump() {
+        let winners = [
+            RonWinner {
+                seat: 0,
+                score_result: make_mangan_score(),
+                is_dealer: true,
+                pao: None,
+            },
+            RonWinner {
+                seat: 3,
+                score_result: make_mangan_score(),
+                is_dealer: false,
+                pao: None,
+            },
+        ];
+        let mut settings = Settings::new();
+        settings.multiple_ron = MultipleRon::HeadBump;
+        let settlement = score_multiple_winners(&winners, 2, 1, 1, &settings);
+
+        assert_eq!(settlement.payouts.len(), 1);
+        assert_eq!(settlement.payouts[0].seat, 3);
+        assert_eq!(settlement.deltas, [0, 0, -8300, 9300]);
+    }
+
     #[test]
     fn test_check_win_non_winning_hand() {
         let tiles = vec![
@@ -659,7 +805,7 @@ mod tests {
         };
         let mut score = ScoreResult {
             han: 1,
-            fu: 30,
+            fu: Some(30),
             rank: ScoreRank::Normal,
             dealer_ron: 1500,
             dealer_tsumo_all: 500,
@@ -669,6 +815,9 @@ mod tests {
             yaku_list: vec![(ScoreItem::Yaku(Kind::AllInside), 1)],
             has_opened: false,
             fu_result,
+            dora_summary: DoraSummary::default(),
+            honba: 0,
+            riichi_sticks: 0,
         };
 
         // 手牌にM2（ドラ）・赤M5（赤ドラ）・S7（裏ドラ対象）を含む
@@ -702,6 +851,7 @@ mod tests {
             None,
             &dora_indicators,
             &uradora_indicators,
+            &Settings::new(),
         );
 
         assert_eq!(score.yaku_list.len(), 4);
@@ -709,6 +859,51 @@ mod tests {
         assert_eq!(score.yaku_list[1], (ScoreItem::Dora(DoraLabel::Dora), 1));
         assert_eq!(score.yaku_list[2], (ScoreItem::Dora(DoraLabel::RedDora), 1));
         assert_eq!(score.yaku_list[3], (ScoreItem::Dora(DoraLabel::UraDora), 1));
+        assert_eq!(
+            score.dora_summary,
+            DoraSummary {
+                dora: 1,
+                kan_dora: 0,
+                ura_dora: 1,
+                red_dora: 1,
+                nuki_dora: 0,
+            }
+        );
+    }
+
+    /// 2枚目以降のドラ表示牌はカンドラとして別に数える
+    #[test]
+    fn test_add_dora_separates_kan_dora() {
+        let mut score = make_mangan_score();
+        score.han = 1;
+        let hand = Hand::from("234m234p234s678m7s");
+
+        // 表ドラ M2（1枚）、カンドラ P2・S2（各1枚）
+        let dora_indicators = vec![
+            Tile::new(Tile::M1),
+            Tile::new(Tile::P1),
+            Tile::new(Tile::S1),
+        ];
+        add_dora_to_score(
+            &mut score,
+            &hand,
+            None,
+            &dora_indicators,
+            &[],
+            &Settings::new(),
+        );
+
+        assert_eq!(score.han, 4);
+        assert_eq!(score.dora_summary.dora, 1);
+        assert_eq!(score.dora_summary.kan_dora, 2);
+        assert_eq!(score.dora_summary.total(), 3);
+        assert_eq!(
+            &score.yaku_list[score.yaku_list.len() - 2..],
+            &[
+                (ScoreItem::Dora(DoraLabel::Dora), 1),
+                (ScoreItem::Dora(DoraLabel::KanDora), 2),
+            ]
+        );
     }
 
     #[test]
@@ -722,7 +917,7 @@ mod tests {
         };
         let mut score = ScoreResult {
             han: 1,
-            fu: 30,
+            fu: Some(30),
             rank: ScoreRank::Normal,
             dealer_ron: 1500,
             dealer_tsumo_all: 500,
@@ -732,6 +927,9 @@ mod tests {
             yaku_list: vec![(ScoreItem::Yaku(Kind::Riichi), 1)],
             has_opened: false,
             fu_result,
+            dora_summary: DoraSummary::default(),
+            honba: 0,
+            riichi_sticks: 0,
         };
         let hand = Hand::new_with_melds(
             vec![],
@@ -744,7 +942,7 @@ mod tests {
             None,
         );
 
-        add_dora_to_score(&mut score, &hand, None, &[], &[]);
+        add_dora_to_score(&mut score, &hand, None, &[], &[], &Settings::new());
 
         assert_eq!(
             score.yaku_list.last(),
@@ -764,7 +962,7 @@ mod tests {
         };
         let mut score = ScoreResult {
             han: 1,
-            fu: 30,
+            fu: Some(30),
             rank: ScoreRank::Normal,
             dealer_ron: 1500,
             dealer_tsumo_all: 500,
@@ -774,6 +972,9 @@ mod tests {
             yaku_list: vec![(ScoreItem::Yaku(Kind::Riichi), 1)],
             has_opened: false,
             fu_result,
+            dora_summary: DoraSummary::default(),
+            honba: 0,
+            riichi_sticks: 0,
         };
         let hand = Hand::new_with_melds(
             vec![],
@@ -790,7 +991,7 @@ mod tests {
             None,
         );
 
-        add_dora_to_score(&mut score, &hand, None, &[], &[]);
+        add_dora_to_score(&mut score, &hand, None, &[], &[], &Settings::new());
 
         assert_eq!(
             score.yaku_list.last(),
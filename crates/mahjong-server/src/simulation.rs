@@ -9,17 +9,24 @@
 //!   席ローテーションし、統計は席ではなくCPU設定ごとに集計する。
 //! - `CpuConfig::without_heuristics()` のCPUを混ぜることで、
 //!   定石導入前後のA/B比較が同一卓でできる。
+//! - 和了の役・翻・符・等級の分布（[`YakuStats`]）も集計し、
+//!   実戦の統計と比較してエンジンの妥当性を確認できる。
 //!
 //! 実行例:
 //! ```sh
 //! cargo run -p mahjong-server --release --example cpu_simulation -- 100 42
 //! ```
 
+use std::collections::BTreeMap;
 use std::fmt;
 
 use mahjong_core::hand_info::hand_analyzer::calc_shanten_number;
+use mahjong_core::scoring::score::{ScoreItem, ScoreRank};
+use mahjong_core::settings::Lang;
+use mahjong_core::winning_hand::name::{self, Kind};
 
 use crate::cpu::client::{CpuClient, CpuConfig, CpuLevel, CpuPersonality};
+use crate::protocol::ServerEvent;
 use crate::round::{RoundResult, TurnPhase};
 use crate::table::{GameSettings, Table};
 
@@ -120,6 +127,127 @@ pub struct SimulationStats {
     pub exhaustive_draws: u32,
     /// 途中流局の回数
     pub special_draws: u32,
+    /// 和了の役・翻・符・等級の分布
+    pub yaku: YakuStats,
+}
+
+/// 和了の役・翻・符・等級の分布
+///
+/// シミュレーションのほか、牌譜などの `ServerEvent::RoundWon` を
+/// [`record_event`](Self::record_event) に渡して集計することもできる。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct YakuStats {
+    /// 和了数
+    pub wins: u32,
+    /// 役ごとの出現回数
+    pub yaku_counts: BTreeMap<Kind, u32>,
+    /// 翻数の合計（平均算出用）
+    pub total_han: u64,
+    /// 符の合計（平均算出用。役満を除く）
+    pub total_fu: u64,
+    /// 符を持つ和了の数（役満を除く）
+    pub fu_wins: u32,
+    /// 等級ごとの回数（[通常, 満貫, 跳満, 倍満, 三倍満, 役満]）
+    pub rank_counts: [u32; 6],
+}
+
+impl YakuStats {
+    /// 和了1回分を記録する
+    pub fn record_win(
+        &mut self,
+        yaku_list: &[(ScoreItem, u32)],
+        han: u32,
+        fu: Option<u32>,
+        rank: ScoreRank,
+    ) {
+        self.wins += 1;
+        for (item, _) in yaku_list {
+            if let ScoreItem::Yaku(kind) = item {
+                *self.yaku_counts.entry(*kind).or_default() += 1;
+            }
+        }
+        self.total_han += han as u64;
+        if let Some(fu) = fu {
+            self.total_fu += fu as u64;
+            self.fu_wins += 1;
+        }
+        self.rank_counts[rank_index(rank)] += 1;
+    }
+
+    /// 和了イベントであれば記録する（それ以外のイベントは無視する）
+    pub fn record_event(&mut self, event: &ServerEvent) {
+        if let ServerEvent::RoundWon {
+            yaku_list,
+            han,
+            fu,
+            rank,
+            ..
+        } = event
+        {
+            self.record_win(yaku_list, *han, *fu, *rank);
+        }
+    }
+
+    /// 役の出現率（和了あたり）を返す
+    pub fn frequency(&self, kind: Kind) -> f64 {
+        if self.wins == 0 {
+            return 0.0;
+        }
+        self.yaku_counts.get(&kind).copied().unwrap_or(0) as f64 / self.wins as f64
+    }
+
+    /// 平均翻数
+    pub fn average_han(&self) -> f64 {
+        if self.wins == 0 {
+            return 0.0;
+        }
+        self.total_han as f64 / self.wins as f64
+    }
+
+    /// 平均符（役満を除く）
+    pub fn average_fu(&self) -> f64 {
+        if self.fu_wins == 0 {
+            return 0.0;
+        }
+        self.total_fu as f64 / self.fu_wins as f64
+    }
+}
+
+/// 等級を `YakuStats::rank_counts` のインデックスに変換する
+fn rank_index(rank: ScoreRank) -> usize {
+    match rank {
+        ScoreRank::Normal => 0,
+        ScoreRank::Mangan => 1,
+        ScoreRank::Haneman => 2,
+        ScoreRank::Baiman => 3,
+        ScoreRank::Sanbaiman => 4,
+        ScoreRank::Yakuman | ScoreRank::MultipleYakuman(_) => 5,
+    }
+}
+
+impl fmt::Display for YakuStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [normal, mangan, haneman, baiman, sanbaiman, yakuman] = self.rank_counts;
+        writeln!(
+            f,
+            "wins: {}, avg han: {:.2}, avg fu: {:.1}, ranks: {normal}-{mangan}-{haneman}-{baiman}-{sanbaiman}-{yakuman}",
+            self.wins,
+            self.average_han(),
+            self.average_fu(),
+        )?;
+        let mut counts: Vec<(&Kind, &u32)> = self.yaku_counts.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        for (kind, count) in counts {
+            writeln!(
+                f,
+                "{:<32} {:>6} {:>5.1}%",
+                name::get(*kind, false, Lang::En),
+                count,
+                self.frequency(*kind) * 100.0
+            )?;
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for SimulationStats {
@@ -165,7 +293,7 @@ impl fmt::Display for SimulationStats {
                 },
             )?;
         }
-        Ok(())
+        write!(f, "{}", self.yaku)
     }
 }
 
@@ -205,6 +333,7 @@ pub fn run_simulation(config: &SimulationConfig) -> Result<SimulationStats, Stri
         rounds: 0,
         exhaustive_draws: 0,
         special_draws: 0,
+        yaku: YakuStats::default(),
     };
 
     for game in 0..config.games {
@@ -223,7 +352,7 @@ pub fn run_simulation(config: &SimulationConfig) -> Result<SimulationStats, Stri
             round_serial += 1;
 
             table.start_round_with_seed(seed);
-            play_round(&mut table, &mut cpus)
+            play_round(&mut table, &mut cpus, &mut stats.yaku)
                 .map_err(|e| format!("game {game}, round {round_serial}: {e}"))?;
 
             collect_round_stats(&table, &config_for_seat, &mut stats)?;
@@ -247,12 +376,16 @@ pub fn run_simulation(config: &SimulationConfig) -> Result<SimulationStats, Stri
 }
 
 /// 1局を最後まで進行させる
-fn play_round(table: &mut Table, cpus: &mut [CpuClient; 4]) -> Result<(), String> {
+fn play_round(
+    table: &mut Table,
+    cpus: &mut [CpuClient; 4],
+    yaku: &mut YakuStats,
+) -> Result<(), String> {
     // 直近の拒否されたアクション（スタック診断用）
     let mut rejected_log: Vec<String> = Vec::new();
 
     // 局開始イベント（GameStarted など）を配信
-    process_events(table, cpus, &mut rejected_log);
+    process_events(table, cpus, &mut rejected_log, yaku);
 
     for _ in 0..MAX_STEPS_PER_ROUND {
         let phase = {
@@ -272,7 +405,7 @@ fn play_round(table: &mut Table, cpus: &mut [CpuClient; 4]) -> Result<(), String
                 .do_draw();
         }
 
-        process_events(table, cpus, &mut rejected_log);
+        process_events(table, cpus, &mut rejected_log, yaku);
     }
 
     let detail = table
@@ -286,7 +419,7 @@ fn play_round(table: &mut Table, cpus: &mut [CpuClient; 4]) -> Result<(), String
                 player.hand.tiles(),
                 player.hand.drawn(),
                 player.hand.melds().len(),
-                player.is_riichi,
+                player.is_riichi(),
             )
         })
         .unwrap_or_else(|| "round missing".to_string());
@@ -300,13 +433,23 @@ fn play_round(table: &mut Table, cpus: &mut [CpuClient; 4]) -> Result<(), String
 /// アクションが新たなイベントを生成しうるため、イベントが尽きるまでループする。
 /// 拒否されたアクションは診断用に `rejected_log` へ記録する
 /// （鳴きの競合などで正当に拒否される場合もあるため、エラーにはしない）。
-fn process_events(table: &mut Table, cpus: &mut [CpuClient; 4], rejected_log: &mut Vec<String>) {
+/// 和了イベントは全席に同じ内容が届くため、座席0宛てのものだけを `yaku` に記録する。
+fn process_events(
+    table: &mut Table,
+    cpus: &mut [CpuClient; 4],
+    rejected_log: &mut Vec<String>,
+    yaku: &mut YakuStats,
+) {
     loop {
         let events = table.drain_events();
         if events.is_empty() {
             break;
         }
 
+        for (_, event) in events.iter().filter(|(player_idx, _)| *player_idx == 0) {
+            yaku.record_event(event);
+        }
+
         let mut actions = Vec::new();
         for (player_idx, event) in &events {
             if let Some(action) = cpus[*player_idx].handle_event(event) {
@@ -367,7 +510,7 @@ fn collect_round_stats(
 
     for (seat, player) in round.players.iter().enumerate() {
         let cpu_stats = &mut stats.per_cpu[config_for_seat[seat]];
-        if player.is_riichi {
+        if player.is_riichi() {
             cpu_stats.riichi_count += 1;
         }
         cpu_stats.meld_count += player.hand.melds().len() as u32;
@@ -411,6 +554,44 @@ mod tests {
         // 最終持ち点の合計は ゲーム数 × 初期持ち点 × 4 から供託分を引いた値以下
         let total_score: i64 = stats.per_cpu.iter().map(|c| c.total_final_score).sum();
         assert!(total_score <= stats.games as i64 * 25000 * 4);
+
+        // 役統計の和了数は和了の集計と一致する
+        let total_wins: u32 = stats.per_cpu.iter().map(|c| c.total_wins()).sum();
+        assert_eq!(stats.yaku.wins, total_wins);
+        assert_eq!(stats.yaku.rank_counts.iter().sum::<u32>(), total_wins);
+    }
+
+    #[test]
+    fn test_yaku_stats_records_wins() {
+        let mut yaku = YakuStats::default();
+        yaku.record_win(
+            &[
+                (ScoreItem::Yaku(Kind::Riichi), 1),
+                (ScoreItem::Yaku(Kind::Pinfu), 1),
+            ],
+            2,
+            Some(30),
+            ScoreRank::Normal,
+        );
+        yaku.record_win(
+            &[(ScoreItem::Yaku(Kind::Riichi), 1)],
+            5,
+            Some(40),
+            ScoreRank::Mangan,
+        );
+        yaku.record_win(
+            &[(ScoreItem::Yaku(Kind::BigDragons), 13)],
+            13,
+            None,
+            ScoreRank::Yakuman,
+        );
+
+        assert_eq!(yaku.wins, 3);
+        assert_eq!(yaku.frequency(Kind::Pinfu), 1.0 / 3.0);
+        assert_eq!(yaku.average_han(), 20.0 / 3.0);
+        // 役満は平均符に含めない
+        assert_eq!(yaku.average_fu(), 35.0);
+        assert_eq!(yaku.rank_counts, [1, 1, 0, 0, 0, 1]);
     }
 
     #[test]
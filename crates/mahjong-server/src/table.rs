@@ -3,14 +3,19 @@
 //! 半荘（東風戦/東南戦）を通した状態を管理する。
 //! 局の生成・進行・終了判定を行う。
 
-use mahjong_core::settings::Settings;
+use anyhow::Result;
+use mahjong_core::board;
+use mahjong_core::settings::{LeftoverRiichiSticks, Renchan, Settings};
 use mahjong_core::tile::{Tile, Wind};
+use serde::{Deserialize, Serialize};
 
 use crate::protocol::{ClientAction, ServerEvent};
-use crate::round::{CallResponse, Round, RoundResult, TurnPhase};
+use crate::round::replay::GameEvent;
+use crate::round::scenario::{RoundSnapshot, ScenarioBuilder};
+use crate::round::{CallResponse, RIICHI_STICK_VALUE, Round, RoundResult, TurnPhase};
 
 /// ゲームの設定
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSettings {
     /// 初期持ち点
     pub initial_score: i32,
@@ -52,6 +57,43 @@ pub struct Table {
     pub is_game_over: bool,
 }
 
+/// 卓の保存形式（`Table::snapshot`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSnapshot {
+    /// ゲーム設定
+    pub settings: GameSettings,
+    /// 進行中の局（局間なら`None`）
+    pub round: Option<SavedRound>,
+    /// 場風
+    pub round_wind: Wind,
+    /// 局番号（0-based）
+    pub round_number: usize,
+    /// 本場数
+    pub honba: usize,
+    /// 場に出ている供託リーチ棒の本数
+    pub riichi_sticks: usize,
+    /// 親のプレイヤーインデックス（0-3）
+    pub dealer: usize,
+    /// 各プレイヤーの点数
+    pub scores: [i32; 4],
+    /// ゲームが終了したか
+    pub is_game_over: bool,
+}
+
+/// 進行中の局の保存形式
+///
+/// 配牌からの操作記録がある局は記録として保存し、復元時に再生するため、
+/// 牌山の残り・王牌・手牌・河・鳴き待ちの状態まで保存時と同じになる。
+/// `ScenarioBuilder`で作った局など操作記録がない局は`RoundSnapshot`として保存する。
+/// こちらも王牌の並びや一発・フリテンなどの状態を含むため、鳴き待ち以外は保存時と同じ局面に戻る。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SavedRound {
+    /// 配牌からの操作記録
+    Log(Vec<GameEvent>),
+    /// 局面そのもの（ツモ前・打牌待ちのみ）
+    State(Box<RoundSnapshot>),
+}
+
 impl Table {
     /// 新しい卓を作成する
     pub fn new(settings: GameSettings) -> Self {
@@ -107,6 +149,62 @@ impl Table {
         self.round = Some(round);
     }
 
+    /// 卓の状態を保存形式で返す
+    ///
+    /// 操作記録がない局は`Round::snapshot`で保存するため、ツモ前・打牌待ち以外ではエラーを返す。
+    pub fn snapshot(&self) -> Result<TableSnapshot> {
+        let round = match &self.round {
+            Some(round) if round.event_log().is_empty() => {
+                Some(SavedRound::State(Box::new(round.snapshot()?)))
+            }
+            Some(round) => Some(SavedRound::Log(round.event_log().to_vec())),
+            None => None,
+        };
+        Ok(TableSnapshot {
+            settings: self.settings.clone(),
+            round,
+            round_wind: self.round_wind,
+            round_number: self.round_number,
+            honba: self.honba,
+            riichi_sticks: self.riichi_sticks,
+            dealer: self.dealer,
+            scores: self.scores,
+            is_game_over: self.is_game_over,
+        })
+    }
+
+    /// 保存形式から卓を復元する
+    ///
+    /// 進行中の局は操作記録の再生、または局面からの再構築で復元する。
+    /// 保存時点で未配信だった通知は復元しない。
+    pub fn restore(snapshot: TableSnapshot) -> Result<Table> {
+        let total_rounds = snapshot.settings.round_count as usize * 4;
+        let round = match snapshot.round {
+            Some(SavedRound::Log(log)) => Some(Round::replay(&log)?),
+            Some(SavedRound::State(state)) => Some(
+                ScenarioBuilder::from_snapshot(*state)
+                    .round_number(snapshot.round_number, total_rounds)
+                    .build()?,
+            ),
+            None => None,
+        };
+        let round = round.map(|mut round| {
+            round.drain_events();
+            round
+        });
+        Ok(Table {
+            settings: snapshot.settings,
+            round,
+            round_wind: snapshot.round_wind,
+            round_number: snapshot.round_number,
+            honba: snapshot.honba,
+            riichi_sticks: snapshot.riichi_sticks,
+            dealer: snapshot.dealer,
+            scores: snapshot.scores,
+            is_game_over: snapshot.is_game_over,
+        })
+    }
+
     /// 現在の局への参照を取得する
     pub fn current_round(&self) -> Option<&Round> {
         self.round.as_ref()
@@ -221,19 +319,18 @@ impl Table {
         // 誰かが箱割れしていたらその時点でゲーム終了（0点は許容）
         if self.scores.iter().any(|&score| score < 0) {
             self.is_game_over = true;
+            self.settle_leftover_riichi_sticks();
             self.round = None;
             return;
         }
 
+        let renchan = self.settings.rules.renchan;
         match result {
             Some(RoundResult::ExhaustiveDraw { dealer_tenpai }) => {
                 self.honba += 1;
-                if dealer_tenpai {
-                    // 親がテンパイなら連荘（親交代しない、局も進めない）
-                } else {
-                    // 親がノーテンなら親交代して局を進める
-                    self.dealer = (self.dealer + 1) % 4;
-                    self.advance_round_number();
+                if !(dealer_tenpai && renchan == Renchan::WinOrTenpai) {
+                    // 連荘しない場合は親交代して局を進める
+                    self.pass_dealer();
                 }
             }
             Some(RoundResult::SpecialDraw) => {
@@ -241,30 +338,58 @@ impl Table {
                 self.honba += 1;
             }
             Some(RoundResult::Tsumo { winner, .. }) => {
-                if winner == self.dealer {
-                    self.honba += 1;
-                } else {
-                    self.honba = 0;
-                    self.dealer = (self.dealer + 1) % 4;
-                    self.advance_round_number();
-                }
+                self.finish_win(winner == self.dealer && renchan != Renchan::Never);
             }
             Some(RoundResult::Ron { winners, .. }) => {
                 // 和了者の中に親がいれば連荘（1人ロンでも複数ロンでも共通）
-                if winners.contains(&self.dealer) {
-                    self.honba += 1;
-                } else {
-                    self.honba = 0;
-                    self.dealer = (self.dealer + 1) % 4;
-                    self.advance_round_number();
-                }
+                self.finish_win(winners.contains(&self.dealer) && renchan != Renchan::Never);
             }
             None => {}
         }
 
+        if self.is_game_over {
+            self.settle_leftover_riichi_sticks();
+        }
         self.round = None;
     }
 
+    /// 終局時に残った供託リーチ棒を設定に従って精算する
+    ///
+    /// 流局で持ち越された供託は次の和了者が受け取るが、和了がないまま終局した場合は
+    /// ここで扱いを決める。
+    fn settle_leftover_riichi_sticks(&mut self) {
+        if self.riichi_sticks == 0 {
+            return;
+        }
+        match self.settings.rules.leftover_riichi_sticks {
+            LeftoverRiichiSticks::TopPlayer => {
+                // 同点の場合は起家（座席0）に近い座席を優先する
+                let top = (0..4)
+                    .max_by_key(|&seat| (self.scores[seat], std::cmp::Reverse(seat)))
+                    .unwrap_or(0);
+                self.scores[top] += self.riichi_sticks as i32 * RIICHI_STICK_VALUE;
+            }
+            LeftoverRiichiSticks::Forfeited => {}
+        }
+        self.riichi_sticks = 0;
+    }
+
+    /// 和了で局が終了したときの本場・親を更新する
+    fn finish_win(&mut self, is_renchan: bool) {
+        if is_renchan {
+            self.honba += 1;
+        } else {
+            self.honba = 0;
+            self.pass_dealer();
+        }
+    }
+
+    /// 親を次の座席に移して局を進める
+    fn pass_dealer(&mut self) {
+        self.dealer = (self.dealer + 1) % 4;
+        self.advance_round_number();
+    }
+
     /// 局番号を進める
     fn advance_round_number(&mut self) {
         self.round_number += 1;
@@ -273,7 +398,7 @@ impl Table {
         }
 
         // 場風を更新
-        self.round_wind = Wind::from_index(self.round_number / 4);
+        self.round_wind = board::round_wind(self.round_number);
     }
 }
 
@@ -336,6 +461,47 @@ mod tests {
         assert_eq!(table.riichi_sticks, 3);
     }
 
+    /// オーラスを流局で終え、供託が残った状態で終局させる
+    fn finish_last_round_with_sticks(table: &mut Table, scores: [i32; 4], sticks: usize) {
+        table.round_number = 3;
+        table.dealer = 3;
+        table.start_round();
+
+        let round = table.current_round_mut().unwrap();
+        for (player, score) in round.players.iter_mut().zip(scores) {
+            player.score = score;
+        }
+        round.riichi_sticks = sticks;
+        round.phase = TurnPhase::RoundOver;
+        round.result = Some(RoundResult::ExhaustiveDraw {
+            dealer_tenpai: false,
+        });
+
+        table.finish_round();
+        assert!(table.is_game_over);
+    }
+
+    #[test]
+    fn test_table_leftover_riichi_sticks_go_to_top_player() {
+        let mut table = Table::new(GameSettings::default());
+        finish_last_round_with_sticks(&mut table, [24000, 30000, 30000, 14000], 2);
+
+        // 同点の場合は起家に近い座席がトップ
+        assert_eq!(table.scores, [24000, 32000, 30000, 14000]);
+        assert_eq!(table.riichi_sticks, 0);
+    }
+
+    #[test]
+    fn test_table_leftover_riichi_sticks_forfeited() {
+        let mut settings = GameSettings::default();
+        settings.rules.leftover_riichi_sticks = LeftoverRiichiSticks::Forfeited;
+        let mut table = Table::new(settings);
+        finish_last_round_with_sticks(&mut table, [24000, 30000, 30000, 14000], 2);
+
+        assert_eq!(table.scores, [24000, 30000, 30000, 14000]);
+        assert_eq!(table.riichi_sticks, 0);
+    }
+
     #[test]
     fn test_table_handle_discard() {
         let mut table = Table::new(GameSettings::default());
@@ -503,6 +669,47 @@ mod tests {
         assert!(!table.is_game_over);
     }
 
+    #[test]
+    fn test_table_finish_round_win_only_renchan_passes_dealer_on_draw() {
+        let mut settings = GameSettings::default();
+        settings.rules.renchan = Renchan::WinOnly;
+        let mut table = Table::new(settings);
+        table.start_round();
+
+        let round = table.current_round_mut().unwrap();
+        round.phase = TurnPhase::RoundOver;
+        round.result = Some(RoundResult::ExhaustiveDraw {
+            dealer_tenpai: true,
+        });
+
+        table.finish_round();
+
+        assert_eq!(table.honba, 1);
+        assert_eq!(table.dealer, 1);
+        assert_eq!(table.round_number, 1);
+    }
+
+    #[test]
+    fn test_table_finish_round_never_renchan_passes_dealer_on_win() {
+        let mut settings = GameSettings::default();
+        settings.rules.renchan = Renchan::Never;
+        let mut table = Table::new(settings);
+        table.start_round();
+
+        let round = table.current_round_mut().unwrap();
+        round.phase = TurnPhase::RoundOver;
+        round.result = Some(RoundResult::Tsumo {
+            winner: 0,
+            winning_tile: Tile::new(Tile::M1),
+        });
+
+        table.finish_round();
+
+        assert_eq!(table.honba, 0);
+        assert_eq!(table.dealer, 1);
+        assert_eq!(table.round_number, 1);
+    }
+
     #[test]
     fn test_table_finish_round_special_draw_keeps_round_number() {
         let mut table = Table::new(GameSettings::default());
@@ -694,4 +901,86 @@ mod tests {
             Some(RoundResult::SpecialDraw)
         ));
     }
+
+    #[test]
+    fn test_table_snapshot_restores_round_in_progress() {
+        let mut table = Table::new(GameSettings::default());
+        table.honba = 2;
+        table.riichi_sticks = 1;
+        table.start_round_with_seed(11);
+        let round = table.current_round_mut().unwrap();
+        for _ in 0..10 {
+            round.advance_auto_player();
+            round.auto_pass_cpu(4);
+        }
+        round.do_draw();
+
+        let json = serde_json::to_string(&table.snapshot().unwrap()).unwrap();
+        let restored = Table::restore(serde_json::from_str(&json).unwrap()).unwrap();
+
+        assert_eq!(restored.honba, 2);
+        assert_eq!(restored.riichi_sticks, 1);
+        assert_eq!(restored.scores, table.scores);
+        let (a, b) = (
+            table.current_round().unwrap(),
+            restored.current_round().unwrap(),
+        );
+        assert_eq!(a.phase, b.phase);
+        assert_eq!(a.current_player, b.current_player);
+        assert_eq!(a.wall.live_tiles(), b.wall.live_tiles());
+        for seat in 0..4 {
+            assert_eq!(a.players[seat].hand.tiles(), b.players[seat].hand.tiles());
+            assert_eq!(a.players[seat].hand.drawn(), b.players[seat].hand.drawn());
+            assert_eq!(
+                a.players[seat].discards.len(),
+                b.players[seat].discards.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_table_snapshot_between_rounds() {
+        let mut table = Table::new(GameSettings::default());
+        table.start_round();
+        table.current_round_mut().unwrap().play_to_end();
+        table.finish_round();
+
+        let restored = Table::restore(table.snapshot().unwrap()).unwrap();
+        assert!(restored.round.is_none());
+        assert_eq!(restored.honba, table.honba);
+        assert_eq!(restored.dealer, table.dealer);
+        assert_eq!(restored.round_number, table.round_number);
+    }
+
+    #[test]
+    fn test_table_snapshot_saves_round_without_log_as_state() {
+        let mut table = Table::new(GameSettings::default());
+        let mut round = ScenarioBuilder::new()
+            .hand(0, mahjong_core::hand::Hand::from("123m456p789s1122z 3z"))
+            .riichi(1)
+            .build()
+            .unwrap();
+        round.players[1].status.is_unbroken = true;
+        round.players[2].is_riichi_furiten = true;
+        table.round = Some(round);
+
+        let snapshot = table.snapshot().unwrap();
+        assert!(matches!(snapshot.round, Some(SavedRound::State(_))));
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored = Table::restore(serde_json::from_str(&json).unwrap()).unwrap();
+        let (a, b) = (
+            table.current_round().unwrap(),
+            restored.current_round().unwrap(),
+        );
+        assert_eq!(a.phase, b.phase);
+        assert_eq!(a.players[0].hand.tiles(), b.players[0].hand.tiles());
+        assert_eq!(a.players[0].hand.drawn(), b.players[0].hand.drawn());
+        assert_eq!(a.wall.live_tiles(), b.wall.live_tiles());
+        assert_eq!(a.wall.remaining_dead_wall(), b.wall.remaining_dead_wall());
+        assert_eq!(a.wall.uradora_indicators(), b.wall.uradora_indicators());
+        assert!(b.players[1].is_riichi());
+        assert!(b.players[1].status.is_unbroken);
+        assert!(b.players[2].is_riichi_furiten);
+    }
 }
@@ -21,17 +21,28 @@ pub struct Wall {
 }
 
 impl Wall {
-    /// 136枚の牌を生成する（赤ドラ3枚含む）
-    fn create_all_tiles() -> Vec<Tile> {
+    /// テスト用：136枚の牌を生成する（赤ドラ3枚含む）
+    #[cfg(test)]
+    pub(crate) fn create_all_tiles() -> Vec<Tile> {
+        Self::create_tiles([1, 1, 1])
+    }
+
+    /// 136枚の牌を生成する
+    ///
+    /// `red_fives` は萬子・筒子・索子の五のうち赤ドラにする枚数（各4枚まで）。
+    pub(crate) fn create_tiles(red_fives: [u8; 3]) -> Vec<Tile> {
         let mut tiles = Vec::with_capacity(136);
 
         for tile_type in 0..Tile::LEN as TileType {
+            let red = match tile_type {
+                Tile::M5 => red_fives[0],
+                Tile::P5 => red_fives[1],
+                Tile::S5 => red_fives[2],
+                _ => 0,
+            };
             for copy in 0..4u8 {
-                // 赤ドラ: 5m, 5p, 5s の各1枚目を赤にする
-                let is_red = copy == 0
-                    && (tile_type == Tile::M5 || tile_type == Tile::P5 || tile_type == Tile::S5);
-
-                if is_red {
+                // 赤ドラ: 5m, 5p, 5s の先頭から指定枚数を赤にする
+                if copy < red {
                     tiles.push(Tile::new_red(tile_type));
                 } else {
                     tiles.push(Tile::new(tile_type));
@@ -44,19 +55,26 @@ impl Wall {
 
     /// 牌山を生成してシャッフルする
     pub fn new() -> Self {
-        let mut tiles = Self::create_all_tiles();
-        tiles.shuffle(&mut rand::rng());
-        Self::from_shuffled(tiles)
+        Self::new_with_red_fives([1, 1, 1], None)
     }
 
     /// 固定シードで牌山を生成する（再現性のある乱数）
     ///
     /// シミュレーション・再現性のあるテストに使用する。
     pub fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_red_fives([1, 1, 1], Some(seed))
+    }
+
+    /// 赤ドラの枚数を指定して牌山を生成する
+    ///
+    /// `seed` を指定すると固定シードでシャッフルする。
+    pub fn new_with_red_fives(red_fives: [u8; 3], seed: Option<u64>) -> Self {
         use rand::SeedableRng;
-        let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
-        let mut tiles = Self::create_all_tiles();
-        tiles.shuffle(&mut rng);
+        let mut tiles = Self::create_tiles(red_fives);
+        match seed {
+            Some(seed) => tiles.shuffle(&mut rand::rngs::SmallRng::seed_from_u64(seed)),
+            None => tiles.shuffle(&mut rand::rng()),
+        }
         Self::from_shuffled(tiles)
     }
 
@@ -66,8 +84,28 @@ impl Wall {
         Self::from_shuffled(tiles)
     }
 
+    /// ツモ牌・残りの嶺上牌・ドラ表示牌（裏ドラ表示牌と交互に10枚）を指定して牌山を生成する
+    ///
+    /// 局面の再現（`ScenarioBuilder`）に使用する。ツモ牌は先頭からツモる。
+    /// 嶺上牌が4枚に満たない分は、カンで既にツモられたものとして扱う。
+    pub(crate) fn from_parts(tiles: Vec<Tile>, rinshan: Vec<Tile>, indicators: Vec<Tile>) -> Self {
+        debug_assert!(rinshan.len() <= 4);
+        debug_assert_eq!(indicators.len(), 10);
+        let rinshan_index = 4 - rinshan.len();
+        // ツモ済みの嶺上牌の位置は参照されないため、ドラ表示牌で埋めておく
+        let mut dead_wall = vec![indicators[0]; rinshan_index];
+        dead_wall.extend(rinshan);
+        dead_wall.extend(indicators);
+        Wall {
+            tiles: tiles.into(),
+            dead_wall,
+            rinshan_index,
+            dora_indicator_count: 1,
+        }
+    }
+
     /// 並び順確定済みの136枚から、末尾14枚を王牌として分離して牌山を作る
-    fn from_shuffled(mut tiles: Vec<Tile>) -> Self {
+    pub(crate) fn from_shuffled(mut tiles: Vec<Tile>) -> Self {
         let dead_wall: Vec<Tile> = tiles.split_off(tiles.len() - 14);
         Wall {
             tiles: tiles.into(),
@@ -93,10 +131,14 @@ impl Wall {
     }
 
     /// カン時にドラ表示牌を追加で公開する
-    pub fn add_dora_indicator(&mut self) {
-        if self.dora_indicator_count < 5 {
-            self.dora_indicator_count += 1;
+    ///
+    /// 新たに公開したドラ表示牌を返す。既に5枚公開済みなら何もせず `None` を返す。
+    pub fn add_dora_indicator(&mut self) -> Option<Tile> {
+        if self.dora_indicator_count >= 5 {
+            return None;
         }
+        self.dora_indicator_count += 1;
+        self.dora_indicators().last().copied()
     }
 
     /// 現在公開されているドラ表示牌を返す
@@ -126,6 +168,29 @@ impl Wall {
         result
     }
 
+    /// 王牌のうち、まだツモられていない嶺上牌とドラ表示牌・裏ドラ表示牌（10枚）を並び順のまま返す
+    ///
+    /// 局面の保存（`Round::snapshot`）に使用する。`from_parts` に渡せば同じ王牌を作り直せる。
+    pub(crate) fn remaining_dead_wall(&self) -> Vec<Tile> {
+        self.dead_wall[self.rinshan_index..].to_vec()
+    }
+
+    /// ツモ牌と王牌を並び順のまま返す
+    ///
+    /// ツモ・カンを行う前の牌山であれば、`from_shuffled` で同じ牌山を作り直せる（局の再生用）。
+    pub(crate) fn ordered_tiles(&self) -> Vec<Tile> {
+        self.tiles
+            .iter()
+            .chain(self.dead_wall.iter())
+            .copied()
+            .collect()
+    }
+
+    /// 残りのツモ牌をツモ順に返す
+    pub fn live_tiles(&self) -> Vec<Tile> {
+        self.tiles.iter().copied().collect()
+    }
+
     /// 山の残り枚数を返す
     pub fn remaining(&self) -> usize {
         self.tiles.len()
@@ -211,6 +276,19 @@ mod tests {
         assert_eq!(red_5s, 1);
     }
 
+    #[test]
+    fn test_create_tiles_red_fives() {
+        let tiles = Wall::create_tiles([0, 2, 4]);
+        assert_eq!(tiles.len(), 136);
+        let red = |tile_type| {
+            tiles
+                .iter()
+                .filter(|t| t.get() == tile_type && t.is_red_dora())
+                .count()
+        };
+        assert_eq!((red(Tile::M5), red(Tile::P5), red(Tile::S5)), (0, 2, 4));
+    }
+
     #[test]
     fn test_wall_new() {
         let wall = Wall::new();
@@ -269,7 +347,8 @@ mod tests {
         assert_eq!(wall.dora_indicators().len(), 1);
         assert_eq!(wall.uradora_indicators().len(), 1);
 
-        wall.add_dora_indicator();
+        let revealed = wall.add_dora_indicator();
+        assert_eq!(revealed, wall.dora_indicators().last().copied());
         assert_eq!(wall.dora_indicators().len(), 2);
         assert_eq!(wall.uradora_indicators().len(), 2);
 
@@ -279,6 +358,7 @@ mod tests {
         }
         assert_eq!(wall.dora_indicators().len(), 5);
         assert_eq!(wall.uradora_indicators().len(), 5);
+        assert_eq!(wall.add_dora_indicator(), None);
     }
 
     #[test]